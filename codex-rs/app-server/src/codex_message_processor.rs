@@ -293,6 +293,7 @@ impl CodexMessageProcessor {
 
     fn review_request_from_target(
         target: ApiReviewTarget,
+        test_output: Option<String>,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -332,6 +333,31 @@ impl CodexMessageProcessor {
                     instructions: trimmed,
                 }
             }
+            ApiReviewTarget::CommitRange { range } => {
+                let range = range.trim().to_string();
+                if !range.contains("..") {
+                    return Err(invalid_request(
+                        "range must look like A..B or A...B".to_string(),
+                    ));
+                }
+                ApiReviewTarget::CommitRange { range }
+            }
+            ApiReviewTarget::PatchFile { path } => {
+                if path.as_os_str().is_empty() {
+                    return Err(invalid_request("path must not be empty".to_string()));
+                }
+                ApiReviewTarget::PatchFile { path }
+            }
+            ApiReviewTarget::Paths { paths } => {
+                let paths: Vec<_> = paths
+                    .into_iter()
+                    .filter(|path| !path.as_os_str().is_empty())
+                    .collect();
+                if paths.is_empty() {
+                    return Err(invalid_request("paths must not be empty".to_string()));
+                }
+                ApiReviewTarget::Paths { paths }
+            }
         };
 
         let core_target = match cleaned_target {
@@ -339,12 +365,16 @@ impl CodexMessageProcessor {
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
+            ApiReviewTarget::CommitRange { range } => CoreReviewTarget::CommitRange { range },
+            ApiReviewTarget::PatchFile { path } => CoreReviewTarget::PatchFile { path },
+            ApiReviewTarget::Paths { paths } => CoreReviewTarget::Paths { paths },
         };
 
         let hint = codex_core::review_prompts::user_facing_hint(&core_target);
         let review_request = ReviewRequest {
             target: core_target,
             user_facing_hint: Some(hint.clone()),
+            test_output,
         };
 
         Ok((review_request, hint))
@@ -2945,6 +2975,7 @@ impl CodexMessageProcessor {
             thread_id,
             target,
             delivery,
+            test_output,
         } = params;
         let (parent_conversation_id, parent_conversation) =
             match self.conversation_from_thread_id(&thread_id).await {
@@ -2955,13 +2986,14 @@ impl CodexMessageProcessor {
                 }
             };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
-            Ok(value) => value,
-            Err(err) => {
-                self.outgoing.send_error(request_id, err).await;
-                return;
-            }
-        };
+        let (review_request, display_text) =
+            match Self::review_request_from_target(target, test_output) {
+                Ok(value) => value,
+                Err(err) => {
+                    self.outgoing.send_error(request_id, err).await;
+                    return;
+                }
+            };
 
         let delivery = delivery.unwrap_or(ApiReviewDelivery::Inline).to_core();
         match delivery {
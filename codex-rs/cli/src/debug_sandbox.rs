@@ -1,8 +1,12 @@
+mod inspect;
 #[cfg(target_os = "macos")]
 mod pid_tracker;
 #[cfg(target_os = "macos")]
 mod seatbelt;
 
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::path::Path;
 use std::path::PathBuf;
 
 use codex_common::CliConfigOverrides;
@@ -14,8 +18,11 @@ use codex_core::landlock::spawn_command_under_linux_sandbox;
 use codex_core::seatbelt::spawn_command_under_seatbelt;
 use codex_core::spawn::StdioPolicy;
 use codex_protocol::config_types::SandboxMode;
+use tokio::process::Child;
 
+use crate::ExplainCommand;
 use crate::LandlockCommand;
+use crate::ProbeCommand;
 use crate::SeatbeltCommand;
 use crate::WindowsCommand;
 use crate::exit_status::handle_exit_status;
@@ -109,15 +116,10 @@ async fn run_command_under_sandbox(
     log_denials: bool,
 ) -> anyhow::Result<()> {
     let sandbox_mode = create_sandbox_mode(full_auto);
-    let config = Config::load_with_cli_overrides_and_harness_overrides(
-        config_overrides
-            .parse_overrides()
-            .map_err(anyhow::Error::msg)?,
-        ConfigOverrides {
-            sandbox_mode: Some(sandbox_mode),
-            codex_linux_sandbox_exe,
-            ..Default::default()
-        },
+    let config = load_config(
+        Some(sandbox_mode),
+        config_overrides,
+        codex_linux_sandbox_exe,
     )
     .await?;
 
@@ -210,30 +212,92 @@ async fn run_command_under_sandbox(
     #[cfg(not(target_os = "macos"))]
     let _ = log_denials;
 
-    let mut child = match sandbox_type {
+    let mut child = spawn_under_sandbox(
+        &sandbox_type,
+        command,
+        cwd,
+        &config,
+        sandbox_policy_cwd.as_path(),
+        stdio_policy,
+        env,
+    )
+    .await?;
+
+    #[cfg(target_os = "macos")]
+    if let Some(denial_logger) = &mut denial_logger {
+        denial_logger.on_child_spawn(&child);
+    }
+
+    let status = child.wait().await?;
+
+    #[cfg(target_os = "macos")]
+    if let Some(denial_logger) = denial_logger {
+        let denials = denial_logger.finish().await;
+        eprintln!("\n=== Sandbox denials ===");
+        if denials.is_empty() {
+            eprintln!("None found.");
+        } else {
+            for seatbelt::SandboxDenial { name, capability } in denials {
+                eprintln!("({name}) {capability}");
+            }
+        }
+    }
+
+    handle_exit_status(status);
+}
+
+async fn load_config(
+    sandbox_mode: Option<SandboxMode>,
+    config_overrides: CliConfigOverrides,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<Config> {
+    Ok(Config::load_with_cli_overrides_and_harness_overrides(
+        config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?,
+        ConfigOverrides {
+            sandbox_mode,
+            codex_linux_sandbox_exe,
+            ..Default::default()
+        },
+    )
+    .await?)
+}
+
+async fn spawn_under_sandbox(
+    sandbox_type: &SandboxType,
+    command: Vec<String>,
+    cwd: PathBuf,
+    config: &Config,
+    sandbox_policy_cwd: &Path,
+    stdio_policy: StdioPolicy,
+    env: HashMap<String, String>,
+) -> anyhow::Result<Child> {
+    let child = match sandbox_type {
         #[cfg(target_os = "macos")]
         SandboxType::Seatbelt => {
             spawn_command_under_seatbelt(
                 command,
                 cwd,
                 config.sandbox_policy.get(),
-                sandbox_policy_cwd.as_path(),
+                sandbox_policy_cwd,
                 stdio_policy,
                 env,
             )
             .await?
         }
         SandboxType::Landlock => {
-            #[expect(clippy::expect_used)]
             let codex_linux_sandbox_exe = config
                 .codex_linux_sandbox_exe
-                .expect("codex-linux-sandbox executable not found");
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("codex-linux-sandbox executable not found"))?;
             spawn_command_under_linux_sandbox(
                 codex_linux_sandbox_exe,
                 command,
                 cwd,
                 config.sandbox_policy.get(),
-                sandbox_policy_cwd.as_path(),
+                sandbox_policy_cwd,
+                &config.sandbox_resource_limits,
                 stdio_policy,
                 env,
             )
@@ -243,28 +307,80 @@ async fn run_command_under_sandbox(
             unreachable!("Windows sandbox should have been handled above");
         }
     };
+    Ok(child)
+}
 
-    #[cfg(target_os = "macos")]
-    if let Some(denial_logger) = &mut denial_logger {
-        denial_logger.on_child_spawn(&child);
+/// Prints the effective sandbox policy: which folders commands can read and
+/// write, whether they can reach the network, and the environment they get.
+pub async fn explain_sandbox(
+    command: ExplainCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let ExplainCommand {
+        full_auto,
+        config_overrides,
+    } = command;
+    let sandbox_mode = full_auto.then_some(SandboxMode::WorkspaceWrite);
+    let config = load_config(sandbox_mode, config_overrides, codex_linux_sandbox_exe).await?;
+    let env = create_env(&config.shell_environment_policy);
+    print!("{}", inspect::explain(&config, &env));
+    Ok(())
+}
+
+/// Runs a battery of probe operations under the effective sandbox policy and
+/// reports which of them it allowed.
+pub async fn probe_sandbox(
+    command: ProbeCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let ProbeCommand {
+        full_auto,
+        config_overrides,
+    } = command;
+    if cfg!(target_os = "windows") {
+        anyhow::bail!("Sandbox probes are only available on macOS and Linux");
     }
+    #[cfg(target_os = "macos")]
+    let sandbox_type = SandboxType::Seatbelt;
+    #[cfg(not(target_os = "macos"))]
+    let sandbox_type = SandboxType::Landlock;
 
-    let status = child.wait().await?;
+    let sandbox_mode = full_auto.then_some(SandboxMode::WorkspaceWrite);
+    let config = load_config(sandbox_mode, config_overrides, codex_linux_sandbox_exe).await?;
+    let env = create_env(&config.shell_environment_policy);
+    println!("Sandbox policy: {}", config.sandbox_policy.get());
+    println!();
 
-    #[cfg(target_os = "macos")]
-    if let Some(denial_logger) = denial_logger {
-        let denials = denial_logger.finish().await;
-        eprintln!("\n=== Sandbox denials ===");
-        if denials.is_empty() {
-            eprintln!("None found.");
-        } else {
-            for seatbelt::SandboxDenial { name, capability } in denials {
-                eprintln!("({name}) {capability}");
+    // The socket probe connects here, so it never leaves the machine.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+
+    let mut any_denied = false;
+    for probe in inspect::probes(&config.cwd, &env, port) {
+        let outcome = match probe.command {
+            Ok(command) => {
+                let child = spawn_under_sandbox(
+                    &sandbox_type,
+                    command,
+                    config.cwd.clone(),
+                    &config,
+                    config.cwd.as_path(),
+                    StdioPolicy::RedirectForShellTool,
+                    env.clone(),
+                )
+                .await?;
+                inspect::ProbeOutcome::from_output(&child.wait_with_output().await?)
             }
-        }
+            Err(reason) => inspect::ProbeOutcome::Skipped(reason),
+        };
+        any_denied |= matches!(outcome, inspect::ProbeOutcome::Denied(_));
+        println!("{}", inspect::format_probe_result(probe.name, &outcome));
     }
-
-    handle_exit_status(status);
+    if any_denied {
+        println!();
+        println!("Run `codex sandbox explain` to see the policy behind these results.");
+    }
+    Ok(())
 }
 
 pub fn create_sandbox_mode(full_auto: bool) -> SandboxMode {
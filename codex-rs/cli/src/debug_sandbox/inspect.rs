@@ -0,0 +1,222 @@
+//! Reports behind `codex sandbox explain` and `codex sandbox probe`.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Output;
+
+use codex_core::config::Config;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SandboxResourceLimits;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+const MIB: u64 = 1024 * 1024;
+
+/// Describes the effective sandbox policy in plain text. Only the names of
+/// the environment variables are listed since their values may be secrets.
+pub(crate) fn explain(config: &Config, env: &HashMap<String, String>) -> String {
+    let policy = config.sandbox_policy.get();
+    let cwd = config.cwd.as_path();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "Sandbox policy: {policy}");
+    let _ = writeln!(out, "Enforced by: {}", mechanism(policy));
+    let _ = writeln!(out, "Working directory: {}", cwd.display());
+    out.push('\n');
+
+    if policy.has_full_disk_write_access() {
+        out.push_str("Writable: entire file system\n");
+    } else {
+        let roots = policy.get_writable_roots_with_cwd(cwd);
+        if roots.is_empty() {
+            out.push_str("Writable: nothing\n");
+        } else {
+            out.push_str("Writable roots:\n");
+            for root in roots {
+                let _ = write!(out, "  {}", root.root.display());
+                if !root.read_only_subpaths.is_empty() {
+                    let _ = write!(out, " (except {})", join_paths(&root.read_only_subpaths));
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    if policy.has_full_disk_read_access() {
+        out.push_str("Readable: entire file system\n");
+    } else {
+        out.push_str("Readable roots (plus the system folders needed to run programs):\n");
+        for root in policy.get_readable_roots_with_cwd(cwd) {
+            let _ = writeln!(out, "  {}", root.display());
+        }
+    }
+    let denied = policy.get_deny_read_paths();
+    if !denied.is_empty() {
+        out.push_str("Never readable or writable:\n");
+        for path in denied {
+            let _ = writeln!(out, "  {}", path.display());
+        }
+    }
+
+    let network = if policy.has_full_network_access() {
+        "allowed".to_string()
+    } else {
+        match &config.network_proxy {
+            Some(proxy) if !proxy.allowed_domains.is_empty() => format!(
+                "blocked, except {} through the egress proxy",
+                proxy.allowed_domains.join(", ")
+            ),
+            _ => "blocked".to_string(),
+        }
+    };
+    let _ = writeln!(out, "Network: {network}");
+    let _ = writeln!(
+        out,
+        "Resource limits: {}",
+        describe_limits(&config.sandbox_resource_limits)
+    );
+    out.push('\n');
+
+    let mut names: Vec<&str> = env.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    let _ = writeln!(
+        out,
+        "Environment ({} variables after shell_environment_policy):",
+        names.len()
+    );
+    for name in names {
+        let _ = writeln!(out, "  {name}");
+    }
+    out
+}
+
+fn mechanism(policy: &SandboxPolicy) -> &'static str {
+    match policy {
+        SandboxPolicy::DangerFullAccess => "nothing, commands run unsandboxed",
+        SandboxPolicy::ExternalSandbox { .. } => "the external sandbox Codex runs in",
+        SandboxPolicy::ReadOnly | SandboxPolicy::WorkspaceWrite { .. } => {
+            if cfg!(target_os = "macos") {
+                "Seatbelt (sandbox-exec)"
+            } else if cfg!(target_os = "linux") {
+                "Landlock and seccomp (codex-linux-sandbox)"
+            } else if cfg!(target_os = "windows") {
+                "a restricted token (when the Windows sandbox is enabled)"
+            } else {
+                "nothing, no sandbox is available on this platform"
+            }
+        }
+    }
+}
+
+fn join_paths(paths: &[AbsolutePathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn describe_limits(limits: &SandboxResourceLimits) -> String {
+    let mut parts = Vec::new();
+    if let Some(secs) = limits.cpu_time_secs {
+        parts.push(format!("{secs}s of CPU time"));
+    }
+    if let Some(bytes) = limits.memory_bytes {
+        parts.push(format!("{} MiB of memory", bytes / MIB));
+    }
+    if let Some(count) = limits.max_processes {
+        parts.push(format!("{count} processes"));
+    }
+    if let Some(bytes) = limits.max_file_size_bytes {
+        parts.push(format!("files up to {} MiB", bytes / MIB));
+    }
+    if parts.is_empty() {
+        "none".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub(crate) struct Probe {
+    pub(crate) name: &'static str,
+    /// Command to run under the sandbox, or why the probe does not apply.
+    pub(crate) command: Result<Vec<String>, &'static str>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ProbeOutcome {
+    Allowed,
+    Denied(String),
+    Skipped(&'static str),
+}
+
+impl ProbeOutcome {
+    pub(crate) fn from_output(output: &Output) -> Self {
+        if output.status.success() {
+            return ProbeOutcome::Allowed;
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .map(str::trim)
+            .rfind(|line| !line.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| output.status.to_string());
+        ProbeOutcome::Denied(reason)
+    }
+}
+
+/// The operations `codex sandbox probe` tries. The socket probe connects to
+/// `listener_port` on the loopback interface.
+pub(crate) fn probes(cwd: &Path, env: &HashMap<String, String>, listener_port: u16) -> Vec<Probe> {
+    let home = env.get("HOME").map(Path::new);
+    let write_probe = |dir: &Path| {
+        bash(
+            r#"f=$(mktemp "$1/.codex-probe.XXXXXX") && rm -f "$f""#,
+            &dir.to_string_lossy(),
+        )
+    };
+
+    vec![
+        Probe {
+            name: "write to cwd",
+            command: Ok(write_probe(cwd)),
+        },
+        Probe {
+            name: "write to $HOME",
+            command: home.map(write_probe).ok_or("HOME is not set"),
+        },
+        Probe {
+            name: "open a network socket",
+            command: Ok(bash(
+                r#"exec 3<>"/dev/tcp/127.0.0.1/$1""#,
+                &listener_port.to_string(),
+            )),
+        },
+        Probe {
+            name: "read ~/.ssh",
+            command: match home.map(|home| home.join(".ssh")) {
+                Some(ssh) if ssh.exists() => {
+                    Ok(bash(r#"ls -A "$1" > /dev/null"#, &ssh.to_string_lossy()))
+                }
+                Some(_) => Err("~/.ssh does not exist"),
+                None => Err("HOME is not set"),
+            },
+        },
+    ]
+}
+
+fn bash(script: &str, arg: &str) -> Vec<String> {
+    ["bash", "-c", script, "codex-probe", arg]
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) fn format_probe_result(name: &str, outcome: &ProbeOutcome) -> String {
+    match outcome {
+        ProbeOutcome::Allowed => format!("{name:<24} allowed"),
+        ProbeOutcome::Denied(reason) => format!("{name:<24} denied   {reason}"),
+        ProbeOutcome::Skipped(reason) => format!("{name:<24} skipped  {reason}"),
+    }
+}
@@ -50,3 +50,23 @@ pub struct WindowsCommand {
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
 }
+
+#[derive(Debug, Parser)]
+pub struct ExplainCommand {
+    /// Explain the workspace-write sandbox used by `--full-auto` instead of the configured one
+    #[arg(long = "full-auto", default_value_t = false)]
+    pub full_auto: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Parser)]
+pub struct ProbeCommand {
+    /// Probe the workspace-write sandbox used by `--full-auto` instead of the configured one
+    #[arg(long = "full-auto", default_value_t = false)]
+    pub full_auto: bool,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
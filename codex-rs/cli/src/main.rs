@@ -6,7 +6,9 @@ use clap_complete::generate;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
+use codex_cli::ExplainCommand;
 use codex_cli::LandlockCommand;
+use codex_cli::ProbeCommand;
 use codex_cli::SeatbeltCommand;
 use codex_cli::WindowsCommand;
 use codex_cli::login::read_api_key_from_stdin;
@@ -24,6 +26,8 @@ use codex_execpolicy::ExecPolicyCheckCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
 use codex_tui::Cli as TuiCli;
+use codex_tui::ExportFormat;
+use codex_tui::export_rollout;
 use codex_tui::update_action::UpdateAction;
 use codex_tui2 as tui2;
 use owo_colors::OwoColorize;
@@ -117,6 +121,9 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Render a recorded session as Markdown, HTML or JSON.
+    Export(ExportCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -159,6 +166,21 @@ struct ResumeCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct ExportCommand {
+    /// Conversation/session id (UUID) of the session to export.
+    #[arg(value_name = "SESSION_ID")]
+    session_id: String,
+
+    /// Output format.
+    #[arg(long = "format", short = 'f', value_enum, default_value_t = ExportFormat::Markdown)]
+    format: ExportFormat,
+
+    /// Write the document to this file instead of stdout.
+    #[arg(long = "output", short = 'o', value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
 #[derive(Debug, Parser)]
 struct SandboxArgs {
     #[command(subcommand)]
@@ -177,6 +199,12 @@ enum SandboxCommand {
 
     /// Run a command under Windows restricted token (Windows only).
     Windows(WindowsCommand),
+
+    /// Show what the sandbox lets commands read, write and reach.
+    Explain(ExplainCommand),
+
+    /// Try a few common operations under the sandbox and report which are denied.
+    Probe(ProbeCommand),
 }
 
 #[derive(Debug, Parser)]
@@ -361,6 +389,21 @@ fn run_execpolicycheck(cmd: ExecPolicyCheckCommand) -> anyhow::Result<()> {
     cmd.run()
 }
 
+async fn run_export(cmd: ExportCommand) -> anyhow::Result<()> {
+    let codex_home = find_codex_home()?;
+    let Some(path) =
+        codex_core::find_conversation_path_by_id_str(&codex_home, &cmd.session_id).await?
+    else {
+        anyhow::bail!("No session found with id {}", cmd.session_id);
+    };
+    let document = export_rollout(&path, cmd.format).await?;
+    match cmd.output {
+        Some(output) => std::fs::write(&output, document)?,
+        None => print!("{document}"),
+    }
+    Ok(())
+}
+
 #[derive(Debug, Default, Parser, Clone)]
 struct FeatureToggles {
     /// Enable a feature (repeatable). Equivalent to `-c features.<name>=true`.
@@ -509,6 +552,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Export(export_cli)) => run_export(export_cli).await?,
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -591,6 +635,21 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
                 )
                 .await?;
             }
+            SandboxCommand::Explain(mut explain_cli) => {
+                prepend_config_flags(
+                    &mut explain_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::explain_sandbox(explain_cli, codex_linux_sandbox_exe)
+                    .await?;
+            }
+            SandboxCommand::Probe(mut probe_cli) => {
+                prepend_config_flags(
+                    &mut probe_cli.config_overrides,
+                    root_config_overrides.clone(),
+                );
+                codex_cli::debug_sandbox::probe_sandbox(probe_cli, codex_linux_sandbox_exe).await?;
+            }
         },
         Some(Subcommand::Execpolicy(ExecpolicyCommand { sub })) => match sub {
             ExecpolicySubcommand::Check(cmd) => run_execpolicycheck(cmd)?,
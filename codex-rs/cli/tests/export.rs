@@ -0,0 +1,118 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use serde_json::json;
+use tempfile::TempDir;
+
+const SESSION_ID: &str = "0199a213-81c0-7800-8aa1-bbab2a035a53";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+/// Record a short session: one question, one command and one answer.
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let sessions = codex_home.join("sessions/2025/01/01");
+    std::fs::create_dir_all(&sessions)?;
+    let mut file = std::fs::File::create(
+        sessions.join(format!("rollout-2025-01-01T00-00-00-{SESSION_ID}.jsonl")),
+    )?;
+    let lines = [
+        json!({
+            "type": "session_meta",
+            "payload": {
+                "id": SESSION_ID,
+                "timestamp": "2025-01-01T00:00:00Z",
+                "instructions": null,
+                "cwd": "/work/repo",
+                "originator": "test",
+                "cli_version": "test",
+                "model_provider": "test-provider"
+            }
+        }),
+        json!({
+            "type": "event_msg",
+            "payload": { "type": "user_message", "message": "which tests fail?" }
+        }),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call",
+                "name": "shell_command",
+                "arguments": "{\"command\":\"cargo test\"}",
+                "call_id": "call-1"
+            }
+        }),
+        json!({
+            "type": "response_item",
+            "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "Exit code: 101\nWall time: 2 seconds\nOutput:\ntest parser::nested ... FAILED"
+            }
+        }),
+        json!({
+            "type": "event_msg",
+            "payload": { "type": "agent_message", "message": "Only `parser::nested` fails." }
+        }),
+    ];
+    for mut line in lines {
+        line["timestamp"] = json!("2025-01-01T00:00:00.000Z");
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+#[test]
+fn export_renders_session_as_markdown() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID, "--format", "md"])
+        .assert()
+        .success()
+        .stdout(contains(format!("# Codex session {SESSION_ID}")))
+        .stdout(contains("## You\n\nwhich tests fail?"))
+        .stdout(contains("cargo test"))
+        .stdout(contains("test parser::nested ... FAILED"))
+        .stdout(contains("Only parser::nested fails."));
+
+    Ok(())
+}
+
+#[test]
+fn export_writes_html_to_output_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+    let output = codex_home.path().join("session.html");
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID, "-f", "html", "-o"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    let html = std::fs::read_to_string(&output)?;
+    assert!(html.starts_with("<!DOCTYPE html>"), "{html}");
+    assert!(html.contains("which tests fail?"), "{html}");
+
+    Ok(())
+}
+
+#[test]
+fn export_reports_unknown_session() -> Result<()> {
+    let codex_home = TempDir::new()?;
+
+    codex_command(codex_home.path())?
+        .args(["export", SESSION_ID])
+        .assert()
+        .failure()
+        .stderr(contains(format!("No session found with id {SESSION_ID}")));
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use anyhow::Result;
+use predicates::str::contains;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path, cwd: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home).current_dir(cwd);
+    Ok(cmd)
+}
+
+#[test]
+fn explain_lists_writable_roots_and_network() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+    let cwd = workspace.path().canonicalize()?;
+    std::fs::create_dir(cwd.join(".git"))?;
+
+    codex_command(codex_home.path(), &cwd)?
+        .args([
+            "sandbox",
+            "explain",
+            "-c",
+            "sandbox_mode=\"workspace-write\"",
+            "-c",
+            "sandbox_workspace_write.exclude_slash_tmp=true",
+            "-c",
+            "sandbox_workspace_write.exclude_tmpdir_env_var=true",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Sandbox policy: workspace-write"))
+        .stdout(contains(format!(
+            "Writable roots:\n  {} (except {})\n",
+            cwd.display(),
+            cwd.join(".git").display()
+        )))
+        .stdout(contains("Network: blocked"));
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn probe_reports_denied_writes_under_read_only() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let workspace = TempDir::new()?;
+
+    codex_command(codex_home.path(), workspace.path())?
+        .args(["sandbox", "probe", "-c", "sandbox_mode=\"read-only\""])
+        .assert()
+        .success()
+        .stdout(contains("Sandbox policy: read-only"))
+        .stdout(contains("write to cwd             denied"))
+        .stdout(contains("open a network socket    denied"));
+
+    Ok(())
+}
@@ -0,0 +1,622 @@
+//! Record/replay support for model traffic.
+//!
+//! A cassette is a JSONL file where every line is one request/response
+//! interaction. In [`CassetteMode::Record`] the wrapped transport talks to the
+//! network as usual and every exchange (including SSE bodies and HTTP error
+//! responses) is appended to the file. In [`CassetteMode::Replay`] the network
+//! is never touched: each request is matched against the recorded interactions
+//! by method, path and normalized JSON body, and the recorded response is
+//! served back.
+//!
+//! Request headers are never written to the cassette so credentials do not end
+//! up in fixtures checked into a repository.
+
+use crate::error::TransportError;
+use crate::request::Request;
+use crate::request::Response;
+use crate::transport::ByteStream;
+use crate::transport::HttpTransport;
+use crate::transport::StreamResponse;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::Stream;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
+use http::StatusCode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::task::Context;
+use std::task::Poll;
+
+/// Request body fields that change between otherwise identical runs and are
+/// therefore ignored when matching.
+const VOLATILE_BODY_FIELDS: &[&str] = &["prompt_cache_key"];
+
+/// Response headers that are not worth persisting.
+const SKIPPED_RESPONSE_HEADERS: &[&str] = &["set-cookie", "date"];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    /// Forward requests to the network and append every exchange to the file.
+    Record,
+    /// Serve responses from the file without touching the network.
+    Replay,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct RecordedRequest {
+    method: String,
+    path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct RecordedResponse {
+    status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: String,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+type OpenCassettes = HashMap<(PathBuf, CassetteMode), Arc<Cassette>>;
+
+/// A cassette file shared by every transport pointed at the same path.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    state: Mutex<CassetteState>,
+}
+
+impl Cassette {
+    /// Opens the cassette at `path`.
+    ///
+    /// Cassettes are shared process-wide per path, so the transports that are
+    /// created for every request all append to (or consume from) the same
+    /// file. In record mode the file is truncated the first time it is opened
+    /// by this process.
+    pub fn open(path: &Path, mode: CassetteMode) -> io::Result<Arc<Self>> {
+        static OPEN: OnceLock<Mutex<OpenCassettes>> = OnceLock::new();
+        let mut open = OPEN
+            .get_or_init(|| Mutex::new(HashMap::new()))
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let key = (path.to_path_buf(), mode);
+        if let Some(cassette) = open.get(&key) {
+            return Ok(Arc::clone(cassette));
+        }
+        let cassette = Arc::new(Self::load(path, mode)?);
+        open.insert(key, Arc::clone(&cassette));
+        Ok(cassette)
+    }
+
+    fn load(path: &Path, mode: CassetteMode) -> io::Result<Self> {
+        let interactions = match mode {
+            CassetteMode::Record => {
+                if let Some(parent) = path.parent()
+                    && !parent.as_os_str().is_empty()
+                {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(path, "")?;
+                Vec::new()
+            }
+            CassetteMode::Replay => {
+                let contents = std::fs::read_to_string(path).map_err(|err| {
+                    io::Error::new(
+                        err.kind(),
+                        format!("failed to read cassette {}: {err}", path.display()),
+                    )
+                })?;
+                contents
+                    .lines()
+                    .enumerate()
+                    .filter(|(_, line)| !line.trim().is_empty())
+                    .map(|(idx, line)| {
+                        serde_json::from_str(line).map_err(|err| {
+                            io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!(
+                                    "invalid cassette entry at {}:{}: {err}",
+                                    path.display(),
+                                    idx + 1
+                                ),
+                            )
+                        })
+                    })
+                    .collect::<io::Result<Vec<Interaction>>>()?
+            }
+        };
+        let used = vec![false; interactions.len()];
+        Ok(Self {
+            path: path.to_path_buf(),
+            mode,
+            state: Mutex::new(CassetteState { interactions, used }),
+        })
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn record(&self, interaction: Interaction) {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let result = serde_json::to_string(&interaction)
+            .map_err(io::Error::other)
+            .and_then(|line| {
+                let mut file = OpenOptions::new().append(true).open(&self.path)?;
+                writeln!(file, "{line}")
+            });
+        if let Err(err) = result {
+            tracing::warn!("failed to write cassette {}: {err}", self.path.display());
+        }
+        state.interactions.push(interaction);
+        state.used.push(true);
+    }
+
+    /// Returns the first unused interaction matching `request`, marking it as
+    /// used so that repeated identical requests replay in recorded order.
+    fn take(&self, request: &RecordedRequest) -> Option<RecordedResponse> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let CassetteState { interactions, used } = &mut *state;
+        let idx = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| !used && interaction.request == *request)?;
+        used[idx] = true;
+        Some(interactions[idx].response.clone())
+    }
+}
+
+/// An [`HttpTransport`] that records traffic to, or replays it from, a
+/// [`Cassette`]. Without a cassette it simply delegates to `inner`.
+#[derive(Debug, Clone)]
+pub struct CassetteTransport<T> {
+    inner: T,
+    cassette: Option<Arc<Cassette>>,
+}
+
+impl<T> CassetteTransport<T> {
+    pub fn new(inner: T, cassette: Option<Arc<Cassette>>) -> Self {
+        Self { inner, cassette }
+    }
+
+    fn replay(cassette: &Cassette, req: &Request) -> Result<RecordedResponse, TransportError> {
+        let key = recorded_request(req);
+        cassette.take(&key).ok_or_else(|| {
+            TransportError::Network(format!(
+                "no unused interaction for {} {} in cassette {}",
+                key.method,
+                key.path,
+                cassette.path.display()
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl<T: HttpTransport> HttpTransport for CassetteTransport<T> {
+    async fn execute(&self, req: Request) -> Result<Response, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.inner.execute(req).await;
+        };
+        match cassette.mode {
+            CassetteMode::Replay => {
+                let recorded = Self::replay(cassette, &req)?;
+                let (status, headers) = recorded.status_and_headers()?;
+                Ok(Response {
+                    status,
+                    headers,
+                    body: Bytes::from(recorded.body),
+                })
+            }
+            CassetteMode::Record => {
+                let request = recorded_request(&req);
+                let result = self.inner.execute(req).await;
+                let response = match &result {
+                    Ok(resp) => Some(RecordedResponse::new(
+                        resp.status,
+                        &resp.headers,
+                        String::from_utf8_lossy(&resp.body).into_owned(),
+                    )),
+                    Err(err) => RecordedResponse::from_error(err),
+                };
+                if let Some(response) = response {
+                    cassette.record(Interaction { request, response });
+                }
+                result
+            }
+        }
+    }
+
+    async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+        let Some(cassette) = &self.cassette else {
+            return self.inner.stream(req).await;
+        };
+        match cassette.mode {
+            CassetteMode::Replay => {
+                let recorded = Self::replay(cassette, &req)?;
+                let (status, headers) = recorded.status_and_headers()?;
+                let body = Bytes::from(recorded.body);
+                Ok(StreamResponse {
+                    status,
+                    headers,
+                    bytes: Box::pin(futures::stream::once(async move { Ok(body) })),
+                })
+            }
+            CassetteMode::Record => {
+                let request = recorded_request(&req);
+                match self.inner.stream(req).await {
+                    Ok(resp) => {
+                        let response =
+                            RecordedResponse::new(resp.status, &resp.headers, String::new());
+                        let bytes = RecordingStream {
+                            inner: resp.bytes,
+                            body: Vec::new(),
+                            pending: Some((
+                                Arc::clone(cassette),
+                                Interaction { request, response },
+                            )),
+                        };
+                        Ok(StreamResponse {
+                            status: resp.status,
+                            headers: resp.headers,
+                            bytes: Box::pin(bytes),
+                        })
+                    }
+                    Err(err) => {
+                        if let Some(response) = RecordedResponse::from_error(&err) {
+                            cassette.record(Interaction { request, response });
+                        }
+                        Err(err)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Forwards a byte stream while buffering it, writing the interaction to the
+/// cassette once the stream ends or is dropped.
+struct RecordingStream {
+    inner: ByteStream,
+    body: Vec<u8>,
+    pending: Option<(Arc<Cassette>, Interaction)>,
+}
+
+impl RecordingStream {
+    fn finish(&mut self) {
+        if let Some((cassette, mut interaction)) = self.pending.take() {
+            interaction.response.body = String::from_utf8_lossy(&self.body).into_owned();
+            cassette.record(interaction);
+        }
+    }
+}
+
+impl Stream for RecordingStream {
+    type Item = Result<Bytes, TransportError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let poll = this.inner.as_mut().poll_next(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(chunk))) => this.body.extend_from_slice(chunk),
+            Poll::Ready(None) => this.finish(),
+            Poll::Ready(Some(Err(_))) | Poll::Pending => {}
+        }
+        poll
+    }
+}
+
+impl Drop for RecordingStream {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+impl RecordedResponse {
+    fn new(status: StatusCode, headers: &HeaderMap, body: String) -> Self {
+        let headers = headers
+            .iter()
+            .filter(|(name, _)| !SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()))
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.as_str().to_string(), value.to_string()))
+            })
+            .collect();
+        Self {
+            status: status.as_u16(),
+            headers,
+            body,
+        }
+    }
+
+    /// HTTP error responses are part of the conversation with the server and
+    /// are recorded; transport failures (timeouts, connection errors) are not.
+    fn from_error(err: &TransportError) -> Option<Self> {
+        match err {
+            TransportError::Http {
+                status,
+                headers,
+                body,
+            } => Some(Self::new(
+                *status,
+                headers.as_ref().unwrap_or(&HeaderMap::new()),
+                body.clone().unwrap_or_default(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn status_and_headers(&self) -> Result<(StatusCode, HeaderMap), TransportError> {
+        let status = StatusCode::from_u16(self.status)
+            .map_err(|err| TransportError::Build(format!("invalid status in cassette: {err}")))?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        if !status.is_success() {
+            return Err(TransportError::Http {
+                status,
+                headers: Some(headers),
+                body: Some(self.body.clone()),
+            });
+        }
+        Ok((status, headers))
+    }
+}
+
+fn recorded_request(req: &Request) -> RecordedRequest {
+    RecordedRequest {
+        method: req.method.to_string(),
+        path: request_path(&req.url),
+        body: req.body.as_ref().map(normalize_body),
+    }
+}
+
+/// Strips scheme and host so cassettes recorded against one base URL replay
+/// against another (for example a local mock server).
+fn request_path(url: &str) -> String {
+    match url.split_once("://") {
+        Some((_, rest)) => match rest.find('/') {
+            Some(idx) => rest[idx..].to_string(),
+            None => "/".to_string(),
+        },
+        None => url.to_string(),
+    }
+}
+
+fn normalize_body(body: &Value) -> Value {
+    let mut body = body.clone();
+    if let Value::Object(map) = &mut body {
+        for field in VOLATILE_BODY_FIELDS {
+            map.remove(*field);
+        }
+    }
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use http::Method;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    const SSE_BODY: &str = "event: response.completed\ndata: {}\n\n";
+
+    #[derive(Default)]
+    struct FakeTransport {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl HttpTransport for FakeTransport {
+        async fn execute(&self, _req: Request) -> Result<Response, TransportError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let mut headers = HeaderMap::new();
+            headers.insert("x-request-id", HeaderValue::from_static("abc"));
+            Ok(Response {
+                status: StatusCode::OK,
+                headers,
+                body: Bytes::from_static(b"{\"ok\":true}"),
+            })
+        }
+
+        async fn stream(&self, req: Request) -> Result<StreamResponse, TransportError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if req.url.ends_with("/fail") {
+                return Err(TransportError::Http {
+                    status: StatusCode::TOO_MANY_REQUESTS,
+                    headers: None,
+                    body: Some("slow down".to_string()),
+                });
+            }
+            let (first, second) = SSE_BODY.split_at(10);
+            let chunks = vec![
+                Ok(Bytes::from_static(first.as_bytes())),
+                Ok(Bytes::from_static(second.as_bytes())),
+            ];
+            Ok(StreamResponse {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                bytes: Box::pin(futures::stream::iter(chunks)),
+            })
+        }
+    }
+
+    fn request(url: &str, cache_key: &str) -> Request {
+        Request::new(Method::POST, url.to_string())
+            .with_json(&json!({"model": "m", "prompt_cache_key": cache_key}))
+    }
+
+    async fn collect(resp: StreamResponse) -> String {
+        let chunks: Vec<Bytes> = resp
+            .bytes
+            .map(|chunk| chunk.expect("chunk"))
+            .collect()
+            .await;
+        String::from_utf8(chunks.concat()).expect("utf8")
+    }
+
+    #[tokio::test]
+    async fn recorded_interactions_replay_without_network() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("nested").join("session.jsonl");
+
+        let recorder = CassetteTransport::new(
+            FakeTransport::default(),
+            Some(Cassette::open(&path, CassetteMode::Record).expect("record")),
+        );
+        let streamed = recorder
+            .stream(request("https://api.example.com/v1/responses", "one"))
+            .await
+            .expect("stream");
+        assert_eq!(collect(streamed).await, SSE_BODY);
+        recorder
+            .execute(request("https://api.example.com/v1/models", "one"))
+            .await
+            .expect("execute");
+        let err = recorder
+            .stream(request("https://api.example.com/v1/fail", "one"))
+            .await
+            .err()
+            .expect("http error");
+        assert!(matches!(err, TransportError::Http { .. }));
+
+        let contents = std::fs::read_to_string(&path).expect("read cassette");
+        assert_eq!(contents.lines().count(), 3);
+        assert!(!contents.contains("prompt_cache_key"));
+
+        let player = CassetteTransport::new(
+            FakeTransport::default(),
+            Some(Cassette::open(&path, CassetteMode::Replay).expect("replay")),
+        );
+        // Host and volatile body fields do not participate in matching.
+        let replayed = player
+            .stream(request("http://127.0.0.1:1234/v1/responses", "two"))
+            .await
+            .expect("replayed stream");
+        assert_eq!(collect(replayed).await, SSE_BODY);
+        let resp = player
+            .execute(request("http://127.0.0.1:1234/v1/models", "two"))
+            .await
+            .expect("replayed execute");
+        assert_eq!(resp.body, Bytes::from_static(b"{\"ok\":true}"));
+        assert_eq!(
+            resp.headers.get("x-request-id"),
+            Some(&HeaderValue::from_static("abc"))
+        );
+        let err = player
+            .stream(request("http://127.0.0.1:1234/v1/fail", "two"))
+            .await
+            .err()
+            .expect("replayed http error");
+        assert!(matches!(
+            err,
+            TransportError::Http { status, body: Some(body), .. }
+                if status == StatusCode::TOO_MANY_REQUESTS && body == "slow down"
+        ));
+        assert_eq!(player.inner.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn replay_reports_unmatched_and_exhausted_requests() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("session.jsonl");
+        let interaction = Interaction {
+            request: RecordedRequest {
+                method: "POST".to_string(),
+                path: "/v1/models".to_string(),
+                body: Some(json!({"model": "m"})),
+            },
+            response: RecordedResponse {
+                status: 200,
+                headers: BTreeMap::new(),
+                body: "{}".to_string(),
+            },
+        };
+        std::fs::write(
+            &path,
+            format!("{}\n", serde_json::to_string(&interaction).expect("json")),
+        )
+        .expect("write cassette");
+
+        let player = CassetteTransport::new(
+            FakeTransport::default(),
+            Some(Cassette::open(&path, CassetteMode::Replay).expect("replay")),
+        );
+        let other_body = Request::new(Method::POST, "https://x/v1/models".to_string())
+            .with_json(&json!({"model": "other"}));
+        assert!(matches!(
+            player.execute(other_body).await,
+            Err(TransportError::Network(_))
+        ));
+        player
+            .execute(request("https://x/v1/models", "a"))
+            .await
+            .expect("first replay");
+        assert!(matches!(
+            player.execute(request("https://x/v1/models", "a")).await,
+            Err(TransportError::Network(_))
+        ));
+    }
+
+    #[test]
+    fn request_path_strips_scheme_and_host() {
+        assert_eq!(
+            request_path("https://api.openai.com/v1/responses?x=1"),
+            "/v1/responses?x=1"
+        );
+        assert_eq!(request_path("http://localhost:8080"), "/");
+        assert_eq!(request_path("/v1/chat/completions"), "/v1/chat/completions");
+    }
+}
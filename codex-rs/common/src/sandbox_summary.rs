@@ -17,6 +17,8 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            readable_roots,
+            deny_read,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -38,6 +40,16 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             if *network_access {
                 summary.push_str(" (network access enabled)");
             }
+            if !readable_roots.is_empty() {
+                summary.push_str(" (reads restricted)");
+            }
+            if !deny_read.is_empty() {
+                let denied = deny_read
+                    .iter()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect::<Vec<_>>();
+                summary.push_str(&format!(" (read denied: {})", denied.join(", ")));
+            }
             summary
         }
     }
@@ -74,6 +86,8 @@ mod tests {
             network_access: true,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            readable_roots: vec![],
+            deny_read: vec![],
         });
         assert_eq!(
             summary,
@@ -83,4 +97,29 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn workspace_write_summary_lists_denied_paths() {
+        let root = if cfg!(windows) {
+            "C:\\secrets"
+        } else {
+            "/secrets"
+        };
+        let denied = AbsolutePathBuf::try_from(root).unwrap();
+        let summary = summarize_sandbox_policy(&SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![denied.clone()],
+            deny_read: vec![denied.clone()],
+        });
+        assert_eq!(
+            summary,
+            format!(
+                "workspace-write [workdir] (reads restricted) (read denied: {})",
+                denied.to_string_lossy()
+            )
+        );
+    }
 }
@@ -7,6 +7,7 @@ use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_apply_patch::MaybeApplyPatchVerified;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -31,6 +32,18 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Notes from a hunk-by-hunk review (rejected or edited hunks) that are
+    /// appended to the tool output so the model knows what was not applied.
+    pub(crate) user_feedback: Option<String>,
+}
+
+impl ApplyPatchExec {
+    pub(crate) fn with_user_feedback(&self, content: String) -> String {
+        match &self.user_feedback {
+            Some(feedback) if !feedback.is_empty() => format!("{content}\n\n{feedback}"),
+            _ => content,
+        }
+    }
 }
 
 pub(crate) async fn apply_patch(
@@ -51,6 +64,7 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            user_feedback: None,
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -67,6 +81,7 @@ pub(crate) async fn apply_patch(
                     convert_apply_patch_to_protocol(&action),
                     None,
                     None,
+                    false,
                 )
                 .await;
             match rx_approve.await.unwrap_or_default() {
@@ -76,8 +91,12 @@ pub(crate) async fn apply_patch(
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        user_feedback: None,
                     })
                 }
+                ReviewDecision::ApprovedHunks { patch, feedback } => {
+                    apply_approved_hunks(&action, patch, feedback)
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
                         "patch rejected by user".to_string(),
@@ -91,6 +110,48 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Replaces the proposed patch with the subset of hunks the user accepted.
+/// The reduced patch is verified again so edited hunks still have to apply
+/// cleanly, and it may only touch files that were part of the proposal.
+fn apply_approved_hunks(
+    proposed: &ApplyPatchAction,
+    patch: String,
+    feedback: String,
+) -> InternalApplyPatchInvocation {
+    if patch.trim().is_empty() {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            feedback,
+        )));
+    }
+    let command = vec!["apply_patch".to_string(), patch];
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &proposed.cwd) {
+        MaybeApplyPatchVerified::Body(action)
+            if action
+                .changes()
+                .keys()
+                .all(|path| proposed.changes().contains_key(path)) =>
+        {
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action,
+                user_explicitly_approved_this_action: true,
+                user_feedback: Some(feedback),
+            })
+        }
+        MaybeApplyPatchVerified::CorrectnessError(err) => {
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(format!(
+                "the hunks approved by the user could not be applied: {err}\n\n{feedback}"
+            ))))
+        }
+        MaybeApplyPatchVerified::Body(_)
+        | MaybeApplyPatchVerified::ShellParseError(_)
+        | MaybeApplyPatchVerified::NotApplyPatch => {
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(format!(
+                "the hunks approved by the user did not form a valid patch\n\n{feedback}"
+            ))))
+        }
+    }
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
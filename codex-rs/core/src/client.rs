@@ -3,6 +3,9 @@ use std::sync::Arc;
 use crate::api_bridge::auth_provider_from_auth;
 use crate::api_bridge::map_api_error;
 use codex_api::AggregateStreamExt;
+use codex_api::Cassette;
+use codex_api::CassetteMode;
+use codex_api::CassetteTransport;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
@@ -32,6 +35,7 @@ use http::HeaderValue;
 use http::StatusCode as HttpStatusCode;
 use reqwest::StatusCode;
 use serde_json::Value;
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tracing::warn;
@@ -42,10 +46,13 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
+use crate::config::types::ModelFallback;
 use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::features::FEATURES;
+use crate::flags::CODEX_RS_CASSETTE_RECORD;
+use crate::flags::CODEX_RS_CASSETTE_REPLAY;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
@@ -59,7 +66,10 @@ pub struct ModelClient {
     auth_manager: Option<Arc<AuthManager>>,
     model_family: ModelFamily,
     otel_manager: OtelManager,
+    provider_id: String,
     provider: ModelProviderInfo,
+    /// Remaining entries of the fallback chain, in the order they are tried.
+    fallbacks: Vec<ModelFallback>,
     conversation_id: ConversationId,
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
@@ -79,12 +89,16 @@ impl ModelClient {
         conversation_id: ConversationId,
         session_source: SessionSource,
     ) -> Self {
+        let provider_id = config.model_provider_id.clone();
+        let fallbacks = config.model_fallbacks.clone();
         Self {
             config,
             auth_manager,
             model_family,
             otel_manager,
+            provider_id,
             provider,
+            fallbacks,
             conversation_id,
             effort,
             summary,
@@ -170,7 +184,7 @@ impl ModelClient {
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = self.build_transport()?;
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -270,7 +284,7 @@ impl ModelClient {
                 .provider
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = self.build_transport()?;
             let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
@@ -312,6 +326,33 @@ impl ModelClient {
         self.provider.clone()
     }
 
+    /// Returns the key of the active provider in `model_providers`.
+    pub fn get_provider_id(&self) -> &str {
+        &self.provider_id
+    }
+
+    /// Returns the provider/model this client would fail over to next.
+    pub fn next_fallback(&self) -> Option<&ModelFallback> {
+        self.fallbacks.first()
+    }
+
+    /// Builds a client for [`Self::next_fallback`]; `model_family` must
+    /// describe its model. The returned client continues with the rest of
+    /// the chain, so repeated failures walk the list in order.
+    pub fn fallback_client(&self, model_family: ModelFamily) -> Option<Self> {
+        let (fallback, rest) = self.fallbacks.split_first()?;
+        Some(Self {
+            otel_manager: self
+                .otel_manager
+                .with_model(fallback.model.as_str(), model_family.get_model_slug()),
+            model_family,
+            provider_id: fallback.provider_id.clone(),
+            provider: fallback.provider.clone(),
+            fallbacks: rest.to_vec(),
+            ..self.clone()
+        })
+    }
+
     pub fn get_otel_manager(&self) -> OtelManager {
         self.otel_manager.clone()
     }
@@ -358,7 +399,7 @@ impl ModelClient {
             .provider
             .to_api_provider(auth.as_ref().map(|a| a.mode))?;
         let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = self.build_transport()?;
         let request_telemetry = self.build_request_telemetry();
         let client = ApiCompactClient::new(transport, api_provider, api_auth)
             .with_telemetry(Some(request_telemetry));
@@ -403,6 +444,28 @@ impl ModelClient {
         (request_telemetry, sse_telemetry)
     }
 
+    /// Builds the HTTP transport for a model request, recording to or replaying
+    /// from a cassette when one is configured.
+    fn build_transport(&self) -> Result<CassetteTransport<ReqwestTransport>> {
+        let selection = if let Some(path) = *CODEX_RS_CASSETTE_REPLAY {
+            Some((CassetteMode::Replay, PathBuf::from(path)))
+        } else if let Some(path) = *CODEX_RS_CASSETTE_RECORD {
+            Some((CassetteMode::Record, PathBuf::from(path)))
+        } else {
+            self.config
+                .cassette
+                .as_ref()
+                .map(|cassette| (cassette.mode, cassette.path.clone()))
+        };
+        let cassette = selection
+            .map(|(mode, path)| Cassette::open(&path, mode))
+            .transpose()?;
+        Ok(CassetteTransport::new(
+            ReqwestTransport::new(build_reqwest_client()),
+            cassette,
+        ))
+    }
+
     /// Builds request telemetry for unary API calls (e.g., Compact endpoint).
     fn build_request_telemetry(&self) -> Arc<dyn RequestTelemetry> {
         let telemetry = Arc::new(ApiTelemetry::new(self.otel_manager.clone()));
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -80,9 +81,13 @@ use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
+use crate::cost::BudgetStatus;
+use crate::cost::CostTracker;
 use crate::environment_context::EnvironmentContext;
+use crate::error::BudgetScope;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
@@ -91,6 +96,7 @@ use crate::exec_policy::ExecPolicyUpdateError;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::network_proxy::NetworkProxy;
 use crate::project_doc::get_user_instructions;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
@@ -108,6 +114,7 @@ use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 use crate::protocol::SessionConfiguredEvent;
 use crate::protocol::SkillErrorInfo;
 use crate::protocol::SkillMetadata as ProtocolSkillMetadata;
@@ -118,6 +125,7 @@ use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::redaction::SecretRedactor;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
@@ -157,6 +165,7 @@ use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::ReadinessFlag;
 
@@ -370,6 +379,7 @@ pub(crate) struct TurnContext {
     pub(crate) ghost_snapshot: GhostSnapshotConfig,
     pub(crate) final_output_json_schema: Option<Value>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
+    pub(crate) sandbox_resource_limits: SandboxResourceLimits,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) truncation_policy: TruncationPolicy,
 }
@@ -386,6 +396,39 @@ impl TurnContext {
             .as_deref()
             .unwrap_or(compact::SUMMARIZATION_PROMPT)
     }
+
+    /// Same turn settings, but talking to `client`. Tool specs and truncation
+    /// follow the new client's model family.
+    fn with_client(&self, client: ModelClient) -> Self {
+        let model_family = client.get_model_family();
+        let config = client.config();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &config.features,
+        });
+        Self {
+            sub_id: self.sub_id.clone(),
+            client,
+            cwd: self.cwd.clone(),
+            developer_instructions: self.developer_instructions.clone(),
+            base_instructions: self.base_instructions.clone(),
+            compact_prompt: self.compact_prompt.clone(),
+            user_instructions: self.user_instructions.clone(),
+            approval_policy: self.approval_policy,
+            sandbox_policy: self.sandbox_policy.clone(),
+            shell_environment_policy: self.shell_environment_policy.clone(),
+            tools_config,
+            ghost_snapshot: self.ghost_snapshot.clone(),
+            final_output_json_schema: self.final_output_json_schema.clone(),
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            sandbox_resource_limits: self.sandbox_resource_limits.clone(),
+            tool_call_gate: Arc::clone(&self.tool_call_gate),
+            truncation_policy: TruncationPolicy::new(
+                config.as_ref(),
+                model_family.truncation_policy,
+            ),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -528,6 +571,7 @@ impl Session {
             ghost_snapshot: per_turn_config.ghost_snapshot.clone(),
             final_output_json_schema: None,
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
+            sandbox_resource_limits: per_turn_config.sandbox_resource_limits.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             truncation_policy: TruncationPolicy::new(
                 per_turn_config.as_ref(),
@@ -654,6 +698,18 @@ impl Session {
         }
         let state = SessionState::new(session_configuration.clone());
 
+        let network_proxy = match config.network_proxy.as_ref() {
+            Some(proxy_config) => match NetworkProxy::start(proxy_config, &config.codex_home).await
+            {
+                Ok(proxy) => Some(proxy),
+                Err(err) => {
+                    error!("failed to start network proxy for sandboxed commands: {err}");
+                    None
+                }
+            },
+            None => None,
+        };
+
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
@@ -668,6 +724,9 @@ impl Session {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            cost_tracker: CostTracker::new(&config, conversation_id),
+            redactor: SecretRedactor::from_config(&config.redaction),
+            network_proxy,
         };
 
         let sess = Arc::new(Session {
@@ -1051,6 +1110,40 @@ impl Session {
         Ok(())
     }
 
+    /// Adds `path` to the writable roots of the session's workspace-write
+    /// sandbox and to the current project's entry in `config.toml`, so later
+    /// commands in this project can write there.
+    pub(crate) async fn add_sandbox_writable_root(&self, path: &Path) -> anyhow::Result<()> {
+        let root = AbsolutePathBuf::from_absolute_path(path)?;
+        let (mut sandbox_policy, cwd, codex_home) = {
+            let state = self.state.lock().await;
+            (
+                state.session_configuration.sandbox_policy.get().clone(),
+                state.session_configuration.cwd.clone(),
+                state
+                    .session_configuration
+                    .original_config_do_not_use
+                    .codex_home
+                    .clone(),
+            )
+        };
+        let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy else {
+            anyhow::bail!("writable roots only apply to the workspace-write sandbox");
+        };
+        if !writable_roots.contains(&root) {
+            writable_roots.push(root);
+        }
+        self.update_settings(SessionSettingsUpdate {
+            sandbox_policy: Some(sandbox_policy),
+            ..Default::default()
+        })
+        .await?;
+        ConfigEditsBuilder::new(&codex_home)
+            .add_sandbox_writable_root(cwd, path)
+            .apply()
+            .await
+    }
+
     /// Emit an exec approval request event and await the user's decision.
     ///
     /// The request is keyed by `sub_id`/`call_id` so matching responses are delivered
@@ -1105,6 +1198,7 @@ impl Session {
         changes: HashMap<PathBuf, FileChange>,
         reason: Option<String>,
         grant_root: Option<PathBuf>,
+        whole_patch_only: bool,
     ) -> oneshot::Receiver<ReviewDecision> {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -1130,6 +1224,7 @@ impl Session {
             changes,
             reason,
             grant_root,
+            whole_patch_only,
         });
         self.send_event(turn_context, event).await;
         rx_approve
@@ -1177,11 +1272,31 @@ impl Session {
         turn_context: &TurnContext,
         items: &[ResponseItem],
     ) {
+        let redacted = self.redact_secrets(turn_context, items).await;
+        let items = redacted.as_deref().unwrap_or(items);
         self.record_into_history(items, turn_context).await;
         self.persist_rollout_response_items(items).await;
         self.send_raw_response_items(turn_context, items).await;
     }
 
+    /// Scrubs secrets from tool outputs, warning the user when anything was
+    /// redacted. Returns `None` when `items` can be recorded as-is.
+    async fn redact_secrets(
+        &self,
+        turn_context: &TurnContext,
+        items: &[ResponseItem],
+    ) -> Option<Vec<ResponseItem>> {
+        let (redacted, report) = self.services.redactor.as_ref()?.redact_items(items)?;
+        self.send_event(
+            turn_context,
+            EventMsg::Warning(WarningEvent {
+                message: report.warning_message(),
+            }),
+        )
+        .await;
+        Some(redacted)
+    }
+
     fn reconstruct_history_from_rollout(
         &self,
         turn_context: &TurnContext,
@@ -1321,6 +1436,18 @@ impl Session {
         turn_context: &TurnContext,
         token_usage: Option<&TokenUsage>,
     ) {
+        let cost = match token_usage {
+            Some(token_usage) => {
+                let model = turn_context.client.get_model();
+                match self.services.cost_tracker.record(&model, token_usage).await {
+                    Some(last_usd) => {
+                        Some((last_usd, self.services.cost_tracker.daily_total_usd().await))
+                    }
+                    None => None,
+                }
+            }
+            None => None,
+        };
         {
             let mut state = self.state.lock().await;
             if let Some(token_usage) = token_usage {
@@ -1329,6 +1456,9 @@ impl Session {
                     turn_context.client.get_model_context_window(),
                 );
             }
+            if let Some((last_usd, daily_usd)) = cost {
+                state.record_cost(last_usd, daily_usd);
+            }
         }
         self.send_token_count_event(turn_context).await;
     }
@@ -1379,14 +1509,34 @@ impl Session {
     }
 
     async fn send_token_count_event(&self, turn_context: &TurnContext) {
-        let (info, rate_limits) = {
+        let ((info, rate_limits), cost) = {
             let state = self.state.lock().await;
-            state.token_info_and_rate_limits()
+            (state.token_info_and_rate_limits(), state.cost())
         };
-        let event = EventMsg::TokenCount(TokenCountEvent { info, rate_limits });
+        let event = EventMsg::TokenCount(TokenCountEvent {
+            info,
+            rate_limits,
+            cost,
+        });
         self.send_event(turn_context, event).await;
     }
 
+    /// Compare this session's and today's estimated spend against the
+    /// configured budget.
+    async fn check_budget(&self) -> BudgetStatus {
+        let tracker = &self.services.cost_tracker;
+        if !tracker.has_budget() {
+            return BudgetStatus::WithinBudget;
+        }
+        let session_usd = self.state.lock().await.session_cost_usd();
+        let daily_usd = tracker.daily_total_usd().await;
+        tracker.check(session_usd, daily_usd)
+    }
+
+    async fn mark_soft_budget_notified(&self, scope: BudgetScope) -> bool {
+        self.state.lock().await.mark_soft_budget_notified(scope)
+    }
+
     pub(crate) async fn set_total_tokens_full(&self, turn_context: &TurnContext) {
         let context_window = turn_context.client.get_model_context_window();
         if let Some(context_window) = context_window {
@@ -1674,6 +1824,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Review { review_request } => {
                 handlers::review(&sess, &config, sub.id.clone(), review_request).await;
             }
+            Op::ApplySandboxAmendment { amendment } => {
+                handlers::apply_sandbox_amendment(&sess, sub.id.clone(), amendment).await;
+            }
             _ => {} // Ignore unknown ops; enum is non_exhaustive to allow extensions.
         }
     }
@@ -1706,6 +1859,8 @@ mod handlers {
     use codex_protocol::protocol::Op;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
+    use codex_protocol::protocol::SandboxAmendment;
+    use codex_protocol::protocol::SandboxAmendmentAppliedEvent;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::WarningEvent;
@@ -1865,6 +2020,31 @@ mod handlers {
         }
     }
 
+    pub async fn apply_sandbox_amendment(
+        sess: &Arc<Session>,
+        sub_id: String,
+        amendment: SandboxAmendment,
+    ) {
+        let result = match &amendment {
+            SandboxAmendment::AddWritableRoot { path } => {
+                sess.add_sandbox_writable_root(path).await
+            }
+            SandboxAmendment::RunOutsideSandbox { prefix } => sess
+                .persist_execpolicy_amendment(prefix)
+                .await
+                .map_err(anyhow::Error::from),
+        };
+        let msg = match result {
+            Ok(()) => EventMsg::SandboxAmendmentApplied(SandboxAmendmentAppliedEvent { amendment }),
+            Err(err) => {
+                let message = format!("Failed to apply sandbox amendment: {err}");
+                warn!("{message}");
+                EventMsg::Warning(WarningEvent { message })
+            }
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
         match decision {
             ReviewDecision::Abort => {
@@ -2154,6 +2334,7 @@ async fn spawn_review_thread(
         cwd: parent_turn_context.cwd.clone(),
         final_output_json_schema: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
+        sandbox_resource_limits: parent_turn_context.sandbox_resource_limits.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         truncation_policy: TruncationPolicy::new(&per_turn_config, model_family.truncation_policy),
     };
@@ -2169,6 +2350,7 @@ async fn spawn_review_thread(
     let review_request = ReviewRequest {
         target: resolved.target,
         user_facing_hint: Some(resolved.user_facing_hint),
+        test_output: resolved.test_output,
     };
     sess.send_event(&tc, EventMsg::EnteredReviewMode(review_request))
         .await;
@@ -2206,7 +2388,7 @@ fn errors_to_info(errors: &[SkillError]) -> Vec<SkillErrorInfo> {
 /// - 如果模型只输出 assistant 消息且没有工具调用，则认为本次 task 完成，并返回最后一条 assistant 文本。
 pub(crate) async fn run_task(
     sess: Arc<Session>,
-    turn_context: Arc<TurnContext>,
+    mut turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     cancellation_token: CancellationToken,
 ) -> Option<String> {
@@ -2215,6 +2397,12 @@ pub(crate) async fn run_task(
         return None;
     }
 
+    // A new user message counts as approval to continue past a soft budget,
+    // so only hard limits stop the task here.
+    if enforce_budget(&sess, &turn_context, false).await {
+        return None;
+    }
+
     // 自动压缩：如果累计 token 已经逼近阈值，先做一次 compaction，避免后续 turn 直接撞上下文上限。
     let auto_compact_limit = turn_context
         .client
@@ -2338,6 +2526,9 @@ pub(crate) async fn run_task(
                         });
                     break;
                 }
+                if enforce_budget(&sess, &turn_context, true).await {
+                    break;
+                }
                 continue;
             }
             Err(CodexErr::TurnAborted) => {
@@ -2352,6 +2543,10 @@ pub(crate) async fn run_task(
                 state.history.replace_last_turn_images("Invalid image");
             }
             Err(e) => {
+                if let Some(fallback_turn_context) = fail_over(&sess, &turn_context, &e).await {
+                    turn_context = fallback_turn_context;
+                    continue;
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -2364,8 +2559,83 @@ pub(crate) async fn run_task(
     last_agent_message
 }
 
+/// Check the spend budget and report any limit that has been reached.
+/// Returns `true` when the task must stop before the next model request: a
+/// hard limit always stops it, while a soft limit only pauses it (when
+/// `pause_on_soft_limit` is set) the first time it is crossed.
+async fn enforce_budget(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    pause_on_soft_limit: bool,
+) -> bool {
+    match sess.check_budget().await {
+        BudgetStatus::WithinBudget => false,
+        BudgetStatus::SoftLimitReached {
+            scope,
+            limit_usd,
+            spent_usd,
+        } => {
+            if !sess.mark_soft_budget_notified(scope).await {
+                return false;
+            }
+            let mut message = format!(
+                "Reached the {} soft budget of ${limit_usd:.2} (estimated spend ${spent_usd:.2}).",
+                scope.label()
+            );
+            if pause_on_soft_limit {
+                message.push_str(" Paused; send a message to continue.");
+            }
+            sess.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+            pause_on_soft_limit
+        }
+        BudgetStatus::HardLimitReached(err) => {
+            let event = EventMsg::Error(CodexErr::BudgetExceeded(err).to_error_event(None));
+            sess.send_event(turn_context, event).await;
+            true
+        }
+    }
+}
+
+/// Switch to the next provider/model in the fallback chain after `err`.
+/// Returns the turn context to retry with, or `None` when the error is not
+/// worth failing over for or the chain is exhausted.
+async fn fail_over(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    err: &CodexErr,
+) -> Option<Arc<TurnContext>> {
+    if !err.should_fail_over() {
+        return None;
+    }
+    let fallback = turn_context.client.next_fallback()?.clone();
+    let model_family = sess
+        .services
+        .models_manager
+        .construct_model_family(&fallback.model, &turn_context.client.config())
+        .await;
+    let client = turn_context.client.fallback_client(model_family)?;
+
+    let message = format!(
+        "{} ({}) failed: {err}. Switching to {} ({}).",
+        turn_context.client.get_model(),
+        turn_context.client.get_provider_id(),
+        fallback.model,
+        fallback.provider_id,
+    );
+    warn!("{message}");
+    sess.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+        .await;
+
+    Some(Arc::new(turn_context.with_client(client)))
+}
+
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
+    if should_use_remote_compact_task(
+        sess.as_ref(),
+        turn_context.as_ref(),
+        &turn_context.client.get_provider(),
+    ) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
     } else {
         run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
@@ -2581,6 +2851,7 @@ async fn try_run_turn(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider_id: Some(turn_context.client.get_provider_id().to_string()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
@@ -3109,6 +3380,7 @@ mod tests {
             aggregated_output: StreamOutput::new("Command output".to_string()),
             duration: StdDuration::from_secs(1),
             timed_out: true,
+            blocked_syscalls: Vec::new(),
         };
         let (_, turn_context) = make_session_and_context().await;
 
@@ -3265,6 +3537,9 @@ mod tests {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            cost_tracker: CostTracker::new(&config, conversation_id),
+            redactor: SecretRedactor::from_config(&config.redaction),
+            network_proxy: None,
         };
 
         let turn_context = Session::make_turn_context(
@@ -3352,6 +3627,9 @@ mod tests {
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            cost_tracker: CostTracker::new(&config, conversation_id),
+            redactor: SecretRedactor::from_config(&config.redaction),
+            network_proxy: None,
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
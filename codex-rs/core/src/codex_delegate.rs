@@ -310,6 +310,7 @@ async fn handle_patch_approval(
             event.changes,
             event.reason,
             event.grant_root,
+            event.whole_patch_only,
         )
         .await;
     let decision = await_approval_with_cancel(
@@ -6,6 +6,7 @@ use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::config::types::CompactionStrategy;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
@@ -26,17 +27,23 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::user_input::UserInput;
 use futures::prelude::*;
+use strategy::MiddleCompaction;
 use tracing::error;
 
+mod strategy;
+
 pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt.md");
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
 
 pub(crate) fn should_use_remote_compact_task(
     session: &Session,
+    turn_context: &TurnContext,
     provider: &ModelProviderInfo,
 ) -> bool {
-    provider.is_openai() && session.enabled(Feature::RemoteCompaction)
+    provider.is_openai()
+        && session.enabled(Feature::RemoteCompaction)
+        && turn_context.client.config().compaction.strategy == CompactionStrategy::SummarizeAll
 }
 
 pub(crate) async fn run_inline_auto_compact_task(
@@ -69,6 +76,25 @@ async fn run_compact_task_inner(
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
 
     let mut history = sess.clone_history().await;
+    let compaction = turn_context.client.config().compaction;
+    let middle = match compaction.strategy {
+        CompactionStrategy::SummarizeAll => None,
+        CompactionStrategy::SummarizeMiddle => {
+            // Keep the verbatim tail to half of the budget so compaction
+            // always leaves room for the next turn.
+            let max_recent_tokens = turn_context
+                .client
+                .get_model_family()
+                .auto_compact_token_limit()
+                .or(turn_context.client.get_model_context_window())
+                .and_then(|tokens| usize::try_from(tokens / 2).ok())
+                .unwrap_or(usize::MAX);
+            let middle =
+                MiddleCompaction::plan(&history.get_history(), &compaction, max_recent_tokens);
+            history.replace(middle.to_summarize.clone());
+            Some(middle)
+        }
+    };
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
@@ -84,6 +110,7 @@ async fn run_compact_task_inner(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider_id: Some(turn_context.client.get_provider_id().to_string()),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
         base_instructions: turn_context.base_instructions.clone(),
@@ -159,22 +186,33 @@ async fn run_compact_task_inner(
     let summary_suffix =
         get_last_assistant_message_from_turn(&history_snapshot).unwrap_or_default();
     let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
-    let user_messages = collect_user_messages(&history_snapshot);
 
     let initial_context = sess.build_initial_context(turn_context.as_ref());
-    let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
-    let ghost_snapshots: Vec<ResponseItem> = history_snapshot
-        .iter()
-        .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
-        .cloned()
-        .collect();
-    new_history.extend(ghost_snapshots);
+    let (new_history, replacement_history) = match middle {
+        Some(middle) => {
+            let new_history = middle.into_history(initial_context, &summary_text);
+            // The kept turns cannot be rebuilt from the summary on resume.
+            (new_history.clone(), Some(new_history))
+        }
+        None => {
+            let user_messages = collect_user_messages(&history_snapshot);
+            let mut new_history =
+                build_compacted_history(initial_context, &user_messages, &summary_text);
+            let ghost_snapshots: Vec<ResponseItem> = history_snapshot
+                .iter()
+                .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
+                .cloned()
+                .collect();
+            new_history.extend(ghost_snapshots);
+            (new_history, None)
+        }
+    };
     sess.replace_history(new_history).await;
     sess.recompute_token_usage(&turn_context).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
-        replacement_history: None,
+        replacement_history,
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
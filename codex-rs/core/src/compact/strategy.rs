@@ -0,0 +1,296 @@
+//! Splits the history for [`CompactionStrategy::SummarizeMiddle`] into the
+//! part that is summarized and the parts that are kept verbatim.
+//!
+//! [`CompactionStrategy::SummarizeMiddle`]: crate::config::types::CompactionStrategy::SummarizeMiddle
+
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+
+use super::build_compacted_history;
+use super::collect_user_messages;
+use super::is_summary_message;
+use crate::config::types::CompactionConfig;
+use crate::truncate::approx_token_count;
+
+/// User messages starting with this marker survive compaction verbatim.
+pub(crate) const PIN_MARKER: &str = "[pin]";
+const OMITTED_TOOL_OUTPUT: &str = "[tool output omitted during compaction]";
+const UPDATE_PLAN_TOOL: &str = "update_plan";
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct MiddleCompaction {
+    /// Items handed to the summarizer, with stale tool outputs dropped.
+    pub(crate) to_summarize: Vec<ResponseItem>,
+    /// Ghost snapshots from the summarized part, kept so `/undo` still works.
+    ghost_snapshots: Vec<ResponseItem>,
+    /// Pinned user messages from the summarized part.
+    pinned: Vec<String>,
+    /// The latest `update_plan` call and its output, if it is being summarized.
+    plan: Vec<ResponseItem>,
+    /// The most recent turns.
+    recent: Vec<ResponseItem>,
+}
+
+impl MiddleCompaction {
+    /// Splits `items` keeping at most `config.keep_recent_turns` turns whose
+    /// combined size stays under `max_recent_tokens`, so that compaction
+    /// always frees room even when the latest turn is huge.
+    pub(crate) fn plan(
+        items: &[ResponseItem],
+        config: &CompactionConfig,
+        max_recent_tokens: usize,
+    ) -> Self {
+        let recent_start = recent_start(items, config.keep_recent_turns, max_recent_tokens);
+        let (older, recent) = items.split_at(recent_start);
+
+        let plan = if config.preserve_plan && !recent.iter().any(is_plan_call) {
+            latest_plan_update(older)
+        } else {
+            Vec::new()
+        };
+        let pinned = if config.preserve_pinned {
+            collect_user_messages(older)
+                .into_iter()
+                .filter(|message| is_pinned(message))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let ghost_snapshots = older
+            .iter()
+            .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
+            .cloned()
+            .collect();
+        let to_summarize = older
+            .iter()
+            .filter(|item| !plan.contains(item))
+            .map(|item| {
+                if config.drop_tool_outputs {
+                    drop_tool_output(item)
+                } else {
+                    item.clone()
+                }
+            })
+            .collect();
+
+        Self {
+            to_summarize,
+            ghost_snapshots,
+            pinned,
+            plan,
+            recent: recent.to_vec(),
+        }
+    }
+
+    /// The history that replaces the conversation once the summary is known.
+    pub(crate) fn into_history(
+        self,
+        initial_context: Vec<ResponseItem>,
+        summary_text: &str,
+    ) -> Vec<ResponseItem> {
+        let mut history = initial_context;
+        history.extend(self.pinned.iter().map(|message| user_message(message)));
+        let user_messages: Vec<String> = collect_user_messages(&self.to_summarize)
+            .into_iter()
+            .filter(|message| !self.pinned.contains(message))
+            .collect();
+        let mut history = build_compacted_history(history, &user_messages, summary_text);
+        history.extend(self.ghost_snapshots);
+        history.extend(self.plan);
+        history.extend(self.recent);
+        history
+    }
+}
+
+/// Index of the first kept item: the start of the `keep_turns`-th last user
+/// turn, moved forward a turn at a time while the kept part is too large.
+fn recent_start(items: &[ResponseItem], keep_turns: usize, max_tokens: usize) -> usize {
+    let turn_starts: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| is_user_turn_start(item))
+        .map(|(idx, _)| idx)
+        .collect();
+    let candidates = turn_starts.iter().rev().take(keep_turns).rev();
+    for &start in candidates {
+        let tokens: usize = items[start..].iter().map(item_tokens).sum();
+        if tokens <= max_tokens {
+            return start;
+        }
+    }
+    items.len()
+}
+
+fn is_user_turn_start(item: &ResponseItem) -> bool {
+    matches!(
+        crate::event_mapping::parse_turn_item(item),
+        Some(TurnItem::UserMessage(user)) if !is_summary_message(&user.message())
+    )
+}
+
+fn is_pinned(message: &str) -> bool {
+    message.trim_start().starts_with(PIN_MARKER)
+}
+
+fn item_tokens(item: &ResponseItem) -> usize {
+    match item {
+        ResponseItem::GhostSnapshot { .. } => 0,
+        item => approx_token_count(&serde_json::to_string(item).unwrap_or_default()),
+    }
+}
+
+fn is_plan_call(item: &ResponseItem) -> bool {
+    matches!(item, ResponseItem::FunctionCall { name, .. } if name == UPDATE_PLAN_TOOL)
+}
+
+fn latest_plan_update(items: &[ResponseItem]) -> Vec<ResponseItem> {
+    let Some(call) = items.iter().rev().find(|item| is_plan_call(item)) else {
+        return Vec::new();
+    };
+    let ResponseItem::FunctionCall { call_id, .. } = call else {
+        return Vec::new();
+    };
+    let output = items.iter().find(|item| {
+        matches!(item, ResponseItem::FunctionCallOutput { call_id: id, .. } if id == call_id)
+    });
+    match output {
+        Some(output) => vec![call.clone(), output.clone()],
+        None => Vec::new(),
+    }
+}
+
+fn drop_tool_output(item: &ResponseItem) -> ResponseItem {
+    match item {
+        ResponseItem::FunctionCallOutput { call_id, output } => ResponseItem::FunctionCallOutput {
+            call_id: call_id.clone(),
+            output: FunctionCallOutputPayload {
+                content: OMITTED_TOOL_OUTPUT.to_string(),
+                content_items: None,
+                success: output.success,
+            },
+        },
+        ResponseItem::CustomToolCallOutput { call_id, .. } => ResponseItem::CustomToolCallOutput {
+            call_id: call_id.clone(),
+            output: OMITTED_TOOL_OUTPUT.to_string(),
+        },
+        item => item.clone(),
+    }
+}
+
+fn user_message(text: &str) -> ResponseItem {
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: text.to_string(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn assistant(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn call(name: &str, call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str, content: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content: content.to_string(),
+                content_items: None,
+                success: Some(true),
+            },
+        }
+    }
+
+    fn history() -> Vec<ResponseItem> {
+        vec![
+            user_message("[pin] Never touch the migrations folder."),
+            call("update_plan", "plan-1"),
+            output("plan-1", "Plan updated"),
+            call("shell", "ls-1"),
+            output("ls-1", "src/\nmigrations/"),
+            assistant("Listed the files."),
+            user_message("Rename the config module."),
+            assistant("Renamed."),
+            user_message("Now update the docs."),
+            assistant("Updated."),
+        ]
+    }
+
+    #[test]
+    fn keeps_recent_turns_pinned_messages_and_plan() {
+        let config = CompactionConfig {
+            keep_recent_turns: 2,
+            ..CompactionConfig::default()
+        };
+        let compaction = MiddleCompaction::plan(&history(), &config, usize::MAX);
+
+        assert_eq!(
+            compaction.to_summarize,
+            vec![
+                user_message("[pin] Never touch the migrations folder."),
+                call("shell", "ls-1"),
+                output("ls-1", OMITTED_TOOL_OUTPUT),
+                assistant("Listed the files."),
+            ]
+        );
+
+        let rebuilt = compaction.into_history(Vec::new(), "SUMMARY");
+        assert_eq!(
+            rebuilt,
+            vec![
+                user_message("[pin] Never touch the migrations folder."),
+                user_message("SUMMARY"),
+                call("update_plan", "plan-1"),
+                output("plan-1", "Plan updated"),
+                user_message("Rename the config module."),
+                assistant("Renamed."),
+                user_message("Now update the docs."),
+                assistant("Updated."),
+            ]
+        );
+    }
+
+    #[test]
+    fn drops_recent_turns_that_do_not_fit() {
+        let config = CompactionConfig {
+            keep_recent_turns: 2,
+            preserve_plan: false,
+            preserve_pinned: false,
+            drop_tool_outputs: false,
+            ..CompactionConfig::default()
+        };
+        let items = history();
+        let last_turn: usize = items[8..].iter().map(item_tokens).sum();
+
+        let compaction = MiddleCompaction::plan(&items, &config, last_turn);
+        assert_eq!(compaction.recent, items[8..].to_vec());
+        assert_eq!(compaction.to_summarize, items[..8].to_vec());
+
+        let compaction = MiddleCompaction::plan(&items, &config, 0);
+        assert_eq!(compaction.recent, Vec::new());
+        assert_eq!(compaction.to_summarize, items);
+    }
+}
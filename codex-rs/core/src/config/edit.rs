@@ -1,6 +1,7 @@
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
+use crate::git_info::resolve_root_git_project_for_trust;
 use anyhow::Context;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::openai_models::ReasoningEffort;
@@ -46,6 +47,10 @@ pub enum ConfigEdit {
     },
     /// Remove the value stored at the exact dotted path.
     ClearPath { segments: Vec<String> },
+    /// Append a folder to `sandbox_workspace_write.writable_roots` of the
+    /// `[projects."<path>"]` entry that applies to `cwd`, unless it is already
+    /// listed.
+    AddSandboxWritableRoot { cwd: PathBuf, path: PathBuf },
 }
 
 // TODO(jif) move to a dedicated file
@@ -310,9 +315,59 @@ impl ConfigDocument {
                 )?;
                 Ok(true)
             }
+            ConfigEdit::AddSandboxWritableRoot { cwd, path } => {
+                Ok(self.add_sandbox_writable_root(cwd, path))
+            }
         }
     }
 
+    fn add_sandbox_writable_root(&mut self, cwd: &Path, path: &Path) -> bool {
+        let project = self.project_key(cwd);
+        let path = path.to_string_lossy();
+        let Some(table) = self.descend(
+            &[
+                "projects".to_string(),
+                project,
+                "sandbox_workspace_write".to_string(),
+            ],
+            TraversalMode::Create,
+        ) else {
+            return false;
+        };
+        let roots = table
+            .entry("writable_roots")
+            .or_insert_with(|| value(toml_edit::Array::new()));
+        let Some(roots) = roots.as_array_mut() else {
+            return false;
+        };
+        if roots
+            .iter()
+            .any(|root| root.as_str() == Some(path.as_ref()))
+        {
+            return false;
+        }
+        roots.push(path.as_ref());
+        true
+    }
+
+    /// Picks the `[projects."<path>"]` entry that applies to `cwd`, matching
+    /// the config loader: an existing entry for `cwd`, otherwise the root git
+    /// project (which is also what the trust prompt records), otherwise `cwd`.
+    fn project_key(&self, cwd: &Path) -> String {
+        let cwd_key = cwd.to_string_lossy().to_string();
+        let has_entry = self
+            .doc
+            .get("projects")
+            .and_then(|projects| projects.get(cwd_key.as_str()))
+            .is_some();
+        if has_entry {
+            return cwd_key;
+        }
+        resolve_root_git_project_for_trust(cwd)
+            .map(|root| root.to_string_lossy().to_string())
+            .unwrap_or(cwd_key)
+    }
+
     fn write_profile_value(&mut self, segments: &[&str], value: Option<TomlItem>) -> bool {
         match value {
             Some(item) => self.write_value(Scope::Profile, segments, item),
@@ -655,6 +710,18 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn add_sandbox_writable_root<C: Into<PathBuf>, P: Into<PathBuf>>(
+        mut self,
+        cwd: C,
+        path: P,
+    ) -> Self {
+        self.edits.push(ConfigEdit::AddSandboxWritableRoot {
+            cwd: cwd.into(),
+            path: path.into(),
+        });
+        self
+    }
+
     /// Enable or disable a feature flag by key under the `[features]` table.
     pub fn set_feature_enabled(mut self, key: &str, enabled: bool) -> Self {
         self.edits.push(ConfigEdit::SetPath {
@@ -1401,6 +1468,74 @@ foo = { command = "cmd" , enabled = false }
         assert_eq!(notifications, Some(false));
     }
 
+    #[test]
+    fn blocking_add_sandbox_writable_root_appends_once() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"[sandbox_workspace_write]
+network_access = true # keep me
+
+[projects."/work/app"]
+trust_level = "trusted"
+
+[projects."/work/app".sandbox_workspace_write]
+writable_roots = ["/srv/cache"]
+"#,
+        )
+        .expect("seed");
+
+        ConfigEditsBuilder::new(codex_home)
+            .add_sandbox_writable_root("/work/app", "/home/dev/.npm")
+            .add_sandbox_writable_root("/work/app", "/srv/cache")
+            .apply_blocking()
+            .expect("apply");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[sandbox_workspace_write]
+network_access = true # keep me
+
+[projects."/work/app"]
+trust_level = "trusted"
+
+[projects."/work/app".sandbox_workspace_write]
+writable_roots = ["/srv/cache", "/home/dev/.npm"]
+"#;
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn blocking_add_sandbox_writable_root_creates_project_entry() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        let project = tmp.path().join("project");
+        std::fs::create_dir(&project).expect("create project");
+
+        ConfigEditsBuilder::new(codex_home)
+            .add_sandbox_writable_root(&project, "/srv/cache")
+            .apply_blocking()
+            .expect("apply");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let config: TomlValue = toml::from_str(&contents).expect("parse config");
+        let roots = config
+            .get("projects")
+            .and_then(|projects| projects.get(project.to_string_lossy().as_ref()))
+            .and_then(|entry| entry.get("sandbox_workspace_write"))
+            .and_then(|table| table.get("writable_roots"))
+            .cloned();
+        assert_eq!(
+            roots,
+            Some(TomlValue::Array(vec![TomlValue::String(
+                "/srv/cache".to_string()
+            )]))
+        );
+        assert!(config.get("sandbox_workspace_write").is_none());
+    }
+
     #[tokio::test]
     async fn async_builder_set_model_persists() {
         let tmp = tempdir().expect("tmpdir");
@@ -1,17 +1,33 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::Budget;
+use crate::config::types::CassetteConfig;
+use crate::config::types::CompactionConfig;
+use crate::config::types::CompactionToml;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::InlineImages;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelFallback;
+use crate::config::types::ModelFallbackToml;
+use crate::config::types::ModelPricing;
+use crate::config::types::NetworkProxyConfig;
+use crate::config::types::NetworkProxyToml;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::RedactionConfig;
+use crate::config::types::RedactionToml;
+use crate::config::types::ReviewPersona;
+use crate::config::types::ReviewPersonaToml;
+use crate::config::types::SandboxResourceLimitsToml;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ScrollInputMode;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::Tui;
+use crate::config::types::TuiKeymap;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
@@ -30,6 +46,7 @@ use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
 use codex_protocol::config_types::ForcedLoginMethod;
@@ -104,6 +121,10 @@ pub struct Config {
     /// Model used specifically for review sessions. Defaults to "gpt-5.1-codex-max".
     pub review_model: String,
 
+    /// Enabled reviewer personas, sorted by name. Each runs as its own review
+    /// pass next to the default one.
+    pub review_personas: Vec<ReviewPersona>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -116,6 +137,31 @@ pub struct Config {
     /// Info needed to make an API request to the model.
     pub model_provider: ModelProviderInfo,
 
+    /// Per-model token pricing used to estimate spend, keyed by model slug.
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Session and daily spend limits enforced against `model_pricing`.
+    pub budget: Budget,
+
+    /// Ordered provider/model pairs to switch to when the active model fails.
+    pub model_fallbacks: Vec<ModelFallback>,
+
+    /// Strategy and options used to compact the conversation.
+    pub compaction: CompactionConfig,
+
+    /// Record/replay model traffic through a cassette file.
+    pub cassette: Option<CassetteConfig>,
+
+    /// Secret redaction applied to tool outputs.
+    pub redaction: RedactionConfig,
+
+    /// Domain allow-list enforced on network traffic from sandboxed
+    /// commands. `None` leaves sandboxed commands without network access.
+    pub network_proxy: Option<NetworkProxyConfig>,
+
+    /// CPU, memory, process and file-size caps for sandboxed commands.
+    pub sandbox_resource_limits: SandboxResourceLimits,
+
     /// Approval policy for executing commands.
     pub approval_policy: Constrained<AskForApproval>,
 
@@ -236,6 +282,15 @@ pub struct Config {
     /// consistently to both mouse wheels and trackpads.
     pub tui_scroll_invert: bool,
 
+    /// Key binding overrides from `[tui.keymap]` (see [`TuiKeymap`]).
+    pub tui_keymap: TuiKeymap,
+
+    /// Edit the composer with vi-style modes (`tui.vi_mode`).
+    pub tui_vi_mode: bool,
+
+    /// How the TUI draws images inline (`tui.inline_images`).
+    pub tui_inline_images: InlineImages,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -673,6 +728,9 @@ pub struct ConfigToml {
     /// Review model override used by the `/review` feature.
     pub review_model: Option<String>,
 
+    /// Extra reviewer personas that run alongside the default review pass.
+    pub review_personas: Option<BTreeMap<String, ReviewPersonaToml>>,
+
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
 
@@ -739,6 +797,33 @@ pub struct ConfigToml {
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Token pricing per model slug, used to estimate spend.
+    #[serde(default)]
+    pub model_pricing: HashMap<String, ModelPricing>,
+
+    /// Session and daily spend limits.
+    pub budget: Option<Budget>,
+
+    /// Ordered provider/model pairs to fail over to when the active model
+    /// returns a server error, hits a rate limit, or overflows its context.
+    pub model_fallbacks: Option<Vec<ModelFallbackToml>>,
+
+    /// How the conversation is compacted.
+    pub compaction: Option<CompactionToml>,
+
+    /// Record model traffic to, or replay it from, a cassette file.
+    pub cassette: Option<CassetteConfig>,
+
+    /// Secret redaction for tool outputs sent to the model.
+    pub redaction: Option<RedactionToml>,
+
+    /// Let sandboxed commands reach an allow-list of domains through a local
+    /// egress proxy.
+    pub network_proxy: Option<NetworkProxyToml>,
+
+    /// Caps on the resources a sandboxed command may use.
+    pub sandbox_resource_limits: Option<SandboxResourceLimitsToml>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
@@ -857,9 +942,33 @@ impl From<ConfigToml> for UserSavedConfig {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+/// Settings under `[projects."<path>"]`. Apart from `trust_level`, every
+/// field overrides the top-level setting of the same name while Codex runs in
+/// that project; see `config_loader` for how they are layered.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
+
+    /// Approval policy to use in this project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_policy: Option<AskForApproval>,
+
+    /// Sandbox mode to use in this project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<SandboxMode>,
+
+    /// Workspace-write settings (writable roots, network, ...) merged over
+    /// the top-level `sandbox_workspace_write` table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
+
+    /// MCP servers added to, or overriding, the top-level ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<HashMap<String, McpServerConfig>>,
+
+    /// Extra execpolicy `.rules` files loaded in this project.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<AbsolutePathBuf>>,
 }
 
 impl ProjectConfig {
@@ -940,11 +1049,15 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    readable_roots,
+                    deny_read,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    readable_roots: readable_roots.clone(),
+                    deny_read: deny_read.clone(),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -1154,14 +1267,28 @@ impl Config {
             .into_iter()
             .map(|path| AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd))
             .collect::<Result<Vec<_>, _>>()?;
-        let active_project = cfg
-            .get_active_project(&resolved_cwd)
-            .unwrap_or(ProjectConfig { trust_level: None });
+        let active_project = cfg.get_active_project(&resolved_cwd).unwrap_or_default();
 
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
         } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        // The read-only policy has no read restrictions of its own, so refuse
+        // to start rather than silently expose paths the user meant to hide.
+        if matches!(sandbox_policy, SandboxPolicy::ReadOnly)
+            && cfg
+                .sandbox_workspace_write
+                .as_ref()
+                .is_some_and(|settings| {
+                    !settings.deny_read.is_empty() || !settings.readable_roots.is_empty()
+                })
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "`sandbox_workspace_write.deny_read` and `sandbox_workspace_write.readable_roots` \
+                 only apply in workspace-write mode; remove them or use `sandbox_mode = \"workspace-write\"`",
+            ));
+        }
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -1211,8 +1338,98 @@ impl Config {
             })?
             .clone();
 
+        let compaction = config_profile
+            .compaction
+            .clone()
+            .or(cfg.compaction)
+            .map(CompactionConfig::from)
+            .unwrap_or_default();
+
+        let model_fallbacks = config_profile
+            .model_fallbacks
+            .or(cfg.model_fallbacks)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|fallback| {
+                let provider_id = fallback
+                    .provider
+                    .unwrap_or_else(|| model_provider_id.clone());
+                let provider = model_providers
+                    .get(&provider_id)
+                    .ok_or_else(|| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::NotFound,
+                            format!(
+                                "Model provider `{provider_id}` for fallback model `{}` not found",
+                                fallback.model
+                            ),
+                        )
+                    })?
+                    .clone();
+                Ok(ModelFallback {
+                    provider_id,
+                    provider,
+                    model: fallback.model,
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+
         let shell_environment_policy = cfg.shell_environment_policy.into();
 
+        let budget = {
+            let profile_budget = config_profile.budget.unwrap_or_default();
+            let global_budget = cfg.budget.unwrap_or_default();
+            Budget {
+                session_soft_limit_usd: profile_budget
+                    .session_soft_limit_usd
+                    .or(global_budget.session_soft_limit_usd),
+                session_hard_limit_usd: profile_budget
+                    .session_hard_limit_usd
+                    .or(global_budget.session_hard_limit_usd),
+                daily_soft_limit_usd: profile_budget
+                    .daily_soft_limit_usd
+                    .or(global_budget.daily_soft_limit_usd),
+                daily_hard_limit_usd: profile_budget
+                    .daily_hard_limit_usd
+                    .or(global_budget.daily_hard_limit_usd),
+            }
+        };
+
+        let redaction = {
+            let redaction = cfg.redaction.unwrap_or_default();
+            let patterns = redaction.patterns.unwrap_or_default();
+            for pattern in &patterns {
+                regex::Regex::new(pattern).map_err(|err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("invalid redaction pattern {pattern:?}: {err}"),
+                    )
+                })?;
+            }
+            RedactionConfig {
+                enabled: redaction.enabled.unwrap_or(true),
+                patterns,
+            }
+        };
+
+        let network_proxy = match cfg.network_proxy.and_then(|proxy| proxy.allowed_domains) {
+            Some(allowed_domains) if !allowed_domains.is_empty() => {
+                for domain in &allowed_domains {
+                    if !crate::network_proxy::is_valid_domain_pattern(domain) {
+                        return Err(std::io::Error::new(
+                            std::io::ErrorKind::InvalidInput,
+                            format!("invalid network_proxy.allowed_domains entry {domain:?}"),
+                        ));
+                    }
+                }
+                Some(NetworkProxyConfig { allowed_domains })
+            }
+            _ => None,
+        };
+
+        let sandbox_resource_limits =
+            resolve_sandbox_resource_limits(cfg.sandbox_resource_limits.unwrap_or_default())?;
+
         let history = cfg.history.unwrap_or_default();
 
         let ghost_snapshot = {
@@ -1295,6 +1512,7 @@ impl Config {
         let review_model = override_review_model
             .or(cfg.review_model)
             .unwrap_or_else(default_review_model);
+        let review_personas = resolve_review_personas(cfg.review_personas, &review_model)?;
 
         let check_for_update_on_startup = cfg.check_for_update_on_startup.unwrap_or(true);
 
@@ -1315,10 +1533,22 @@ impl Config {
         let config = Self {
             model,
             review_model,
+            review_personas,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
+            model_pricing: cfg.model_pricing,
+            budget,
+            model_fallbacks,
+            compaction,
+            cassette: cfg.cassette.map(|cassette| CassetteConfig {
+                path: resolved_cwd.join(cassette.path),
+                ..cassette
+            }),
+            redaction,
+            network_proxy,
+            sandbox_resource_limits,
             cwd: resolved_cwd,
             approval_policy: constrained_approval_policy,
             sandbox_policy: constrained_sandbox_policy,
@@ -1418,6 +1648,17 @@ impl Config {
                 .as_ref()
                 .and_then(|t| t.scroll_wheel_like_max_duration_ms),
             tui_scroll_invert: cfg.tui.as_ref().map(|t| t.scroll_invert).unwrap_or(false),
+            tui_keymap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keymap.clone())
+                .unwrap_or_default(),
+            tui_vi_mode: cfg.tui.as_ref().map(|t| t.vi_mode).unwrap_or(false),
+            tui_inline_images: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.inline_images)
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1492,6 +1733,60 @@ impl Config {
     }
 }
 
+/// Converts the `[sandbox_resource_limits]` table into the limits passed to the
+/// sandbox helper. Zero is rejected rather than silently treated as unlimited.
+fn resolve_sandbox_resource_limits(
+    limits: SandboxResourceLimitsToml,
+) -> std::io::Result<SandboxResourceLimits> {
+    const MIB: u64 = 1024 * 1024;
+    let checked = |name: &str, value: Option<u64>, scale: u64| match value {
+        Some(0) => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("sandbox_resource_limits.{name} must be greater than zero"),
+        )),
+        Some(value) => value.checked_mul(scale).map(Some).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("sandbox_resource_limits.{name} is too large"),
+            )
+        }),
+        None => Ok(None),
+    };
+    Ok(SandboxResourceLimits {
+        cpu_time_secs: checked("cpu_time_secs", limits.cpu_time_secs, 1)?,
+        memory_bytes: checked("memory_mb", limits.memory_mb, MIB)?,
+        max_processes: checked("max_processes", limits.max_processes, 1)?,
+        max_file_size_bytes: checked("max_file_size_mb", limits.max_file_size_mb, MIB)?,
+        cgroup_parent: limits.cgroup_parent.map(AbsolutePathBuf::into_path_buf),
+    })
+}
+
+fn resolve_review_personas(
+    personas: Option<BTreeMap<String, ReviewPersonaToml>>,
+    review_model: &str,
+) -> std::io::Result<Vec<ReviewPersona>> {
+    let mut resolved = Vec::new();
+    for (name, persona) in personas.unwrap_or_default() {
+        if !persona.enabled.unwrap_or(true) {
+            continue;
+        }
+        let instructions = persona.instructions.trim();
+        if instructions.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("review_personas.{name}.instructions must not be empty"),
+            ));
+        }
+        resolved.push(ReviewPersona {
+            model: persona.model.unwrap_or_else(|| review_model.to_string()),
+            instructions: instructions.to_string(),
+            severity_threshold: persona.severity_threshold,
+            name,
+        });
+    }
+    Ok(resolved)
+}
+
 fn default_review_model() -> String {
     OPENAI_DEFAULT_REVIEW_MODEL.to_string()
 }
@@ -1536,9 +1831,12 @@ mod tests {
     use crate::config::edit::ConfigEdit;
     use crate::config::edit::ConfigEditsBuilder;
     use crate::config::edit::apply_blocking;
+    use crate::config::types::CompactionStrategy;
     use crate::config::types::HistoryPersistence;
+    use crate::config::types::KeyChords;
     use crate::config::types::McpServerTransportConfig;
     use crate::config::types::Notifications;
+    use crate::config::types::ReviewSeverity;
     use crate::features::Feature;
 
     use super::*;
@@ -1605,10 +1903,54 @@ persistence = "none"
                 scroll_wheel_tick_detect_max_ms: None,
                 scroll_wheel_like_max_duration_ms: None,
                 scroll_invert: false,
+                keymap: TuiKeymap::default(),
+                vi_mode: false,
+                inline_images: InlineImages::Auto,
+            }
+        );
+    }
+
+    #[test]
+    fn tui_keymap_accepts_single_chords_and_lists() {
+        let cfg = r#"
+[tui]
+vi_mode = true
+
+[tui.keymap]
+transcript = "ctrl+o"
+external_editor = ["ctrl+g", "f4"]
+pager_close = []
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("keymap should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert!(tui.vi_mode);
+        assert_eq!(
+            tui.keymap,
+            TuiKeymap {
+                transcript: Some(KeyChords::One("ctrl+o".to_string())),
+                external_editor: Some(KeyChords::Many(vec![
+                    "ctrl+g".to_string(),
+                    "f4".to_string()
+                ])),
+                pager_close: Some(KeyChords::Many(Vec::new())),
+                ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn tui_keymap_rejects_unknown_actions() {
+        let cfg = r#"
+[tui.keymap]
+transcrpt = "ctrl+o"
+"#;
+
+        let err = toml::from_str::<ConfigToml>(cfg).expect_err("unknown action should fail");
+        assert!(err.to_string().contains("transcrpt"), "{err}");
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1696,6 +2038,8 @@ exclude_slash_tmp = true
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        readable_roots: vec![],
+                        deny_read: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1744,6 +2088,8 @@ trust_level = "trusted"
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        readable_roots: vec![],
+                        deny_read: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1923,6 +2269,284 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn read_only_mode_rejects_read_restrictions() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+sandbox_mode = "read-only"
+
+[sandbox_workspace_write]
+deny_read = ["/home/user/.ssh"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let err = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("deny_read cannot be enforced in read-only mode");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert!(
+            config
+                .sandbox_policy
+                .get()
+                .is_path_read_denied(Path::new("/home/user/.ssh/id_ed25519"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile_budget_overrides_base_field_by_field() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+profile = "work"
+
+[budget]
+session_soft_limit_usd = 5.0
+daily_hard_limit_usd = 50.0
+
+[profiles.work.budget]
+daily_hard_limit_usd = 20.0
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.budget,
+            Budget {
+                session_soft_limit_usd: Some(5.0),
+                session_hard_limit_usd: None,
+                daily_soft_limit_usd: None,
+                daily_hard_limit_usd: Some(20.0),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn redaction_defaults_on_and_rejects_invalid_patterns() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.redaction, RedactionConfig::default());
+        assert!(config.redaction.enabled);
+
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[redaction]
+patterns = ["(unclosed"]
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let err = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("invalid pattern should be rejected");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile_compaction_replaces_top_level_table() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[compaction]
+strategy = "summarize-middle"
+keep_recent_turns = 4
+
+[profiles.refactor.compaction]
+strategy = "summarize-middle"
+drop_tool_outputs = false
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let load = |profile: Option<&str>| {
+            Config::load_from_base_config_with_overrides(
+                cfg.clone(),
+                ConfigOverrides {
+                    config_profile: profile.map(str::to_string),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(
+            load(None)?.compaction,
+            CompactionConfig {
+                strategy: CompactionStrategy::SummarizeMiddle,
+                keep_recent_turns: 4,
+                ..CompactionConfig::default()
+            }
+        );
+        assert_eq!(
+            load(Some("refactor"))?.compaction,
+            CompactionConfig {
+                strategy: CompactionStrategy::SummarizeMiddle,
+                drop_tool_outputs: false,
+                ..CompactionConfig::default()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn review_personas_resolve_model_and_skip_disabled() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+review_model = "review-default"
+
+[review_personas.security]
+instructions = "Focus on injection and auth bypasses."
+model = "security-model"
+severity_threshold = "high"
+
+[review_personas.api-compat]
+instructions = "  Flag breaking public API changes.  "
+
+[review_personas.performance]
+instructions = "Look for hot-path allocations."
+enabled = false
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.review_personas,
+            vec![
+                ReviewPersona {
+                    name: "api-compat".to_string(),
+                    instructions: "Flag breaking public API changes.".to_string(),
+                    model: "review-default".to_string(),
+                    severity_threshold: None,
+                },
+                ReviewPersona {
+                    name: "security".to_string(),
+                    instructions: "Focus on injection and auth bypasses.".to_string(),
+                    model: "security-model".to_string(),
+                    severity_threshold: Some(ReviewSeverity::High),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn network_proxy_requires_allowed_domains() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| {
+            let cfg =
+                toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(load("")?.network_proxy, None);
+        assert_eq!(
+            load("[network_proxy]\nallowed_domains = []")?.network_proxy,
+            None
+        );
+        assert_eq!(
+            load(
+                r#"
+[network_proxy]
+allowed_domains = ["crates.io", "*.npmjs.org"]
+"#
+            )?
+            .network_proxy,
+            Some(NetworkProxyConfig {
+                allowed_domains: vec!["crates.io".to_string(), "*.npmjs.org".to_string()],
+            })
+        );
+        let err = load("[network_proxy]\nallowed_domains = [\"https://crates.io\"]")
+            .expect_err("URLs are not domains");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sandbox_resource_limits_are_converted_and_validated() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let load = |toml: &str| {
+            let cfg =
+                toml::from_str::<ConfigToml>(toml).expect("TOML deserialization should succeed");
+            Config::load_from_base_config_with_overrides(
+                cfg,
+                ConfigOverrides::default(),
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(
+            load("")?.sandbox_resource_limits,
+            SandboxResourceLimits::default()
+        );
+        assert_eq!(
+            load(
+                r#"
+[sandbox_resource_limits]
+cpu_time_secs = 600
+memory_mb = 2048
+max_processes = 256
+max_file_size_mb = 512
+"#
+            )?
+            .sandbox_resource_limits,
+            SandboxResourceLimits {
+                cpu_time_secs: Some(600),
+                memory_bytes: Some(2048 * 1024 * 1024),
+                max_processes: Some(256),
+                max_file_size_bytes: Some(512 * 1024 * 1024),
+                cgroup_parent: None,
+            }
+        );
+        let err = load("[sandbox_resource_limits]\nmax_processes = 0")
+            .expect_err("zero is not a valid limit");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+
+        Ok(())
+    }
+
     #[test]
     fn cli_override_takes_precedence_over_profile_sandbox_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -3152,10 +3776,19 @@ model_verbosity = "high"
             Config {
                 model: Some("o3".to_string()),
                 review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+                review_personas: Vec::new(),
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
+                model_pricing: HashMap::new(),
+                budget: Budget::default(),
+                cassette: None,
+                redaction: RedactionConfig::default(),
+                network_proxy: None,
+                sandbox_resource_limits: SandboxResourceLimits::default(),
+                model_fallbacks: Vec::new(),
+                compaction: CompactionConfig::default(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3194,7 +3827,7 @@ model_verbosity = "high"
                 ghost_snapshot: GhostSnapshotConfig::default(),
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
-                active_project: ProjectConfig { trust_level: None },
+                active_project: ProjectConfig::default(),
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
@@ -3211,6 +3844,9 @@ model_verbosity = "high"
                 tui_scroll_wheel_tick_detect_max_ms: None,
                 tui_scroll_wheel_like_max_duration_ms: None,
                 tui_scroll_invert: false,
+                tui_keymap: TuiKeymap::default(),
+                tui_vi_mode: false,
+                tui_inline_images: InlineImages::Auto,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3235,10 +3871,19 @@ model_verbosity = "high"
         let expected_gpt3_profile_config = Config {
             model: Some("gpt-3.5-turbo".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_personas: Vec::new(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
+            model_pricing: HashMap::new(),
+            budget: Budget::default(),
+            cassette: None,
+            redaction: RedactionConfig::default(),
+            network_proxy: None,
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            model_fallbacks: Vec::new(),
+            compaction: CompactionConfig::default(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3277,7 +3922,7 @@ model_verbosity = "high"
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -3294,6 +3939,9 @@ model_verbosity = "high"
             tui_scroll_wheel_tick_detect_max_ms: None,
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
+            tui_keymap: TuiKeymap::default(),
+            tui_vi_mode: false,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3333,10 +3981,19 @@ model_verbosity = "high"
         let expected_zdr_profile_config = Config {
             model: Some("o3".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_personas: Vec::new(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_pricing: HashMap::new(),
+            budget: Budget::default(),
+            cassette: None,
+            redaction: RedactionConfig::default(),
+            network_proxy: None,
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            model_fallbacks: Vec::new(),
+            compaction: CompactionConfig::default(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3375,7 +4032,7 @@ model_verbosity = "high"
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -3392,6 +4049,9 @@ model_verbosity = "high"
             tui_scroll_wheel_tick_detect_max_ms: None,
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
+            tui_keymap: TuiKeymap::default(),
+            tui_vi_mode: false,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3417,10 +4077,19 @@ model_verbosity = "high"
         let expected_gpt5_profile_config = Config {
             model: Some("gpt-5.1".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            review_personas: Vec::new(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
+            model_pricing: HashMap::new(),
+            budget: Budget::default(),
+            cassette: None,
+            redaction: RedactionConfig::default(),
+            network_proxy: None,
+            sandbox_resource_limits: SandboxResourceLimits::default(),
+            model_fallbacks: Vec::new(),
+            compaction: CompactionConfig::default(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
@@ -3459,7 +4128,7 @@ model_verbosity = "high"
             ghost_snapshot: GhostSnapshotConfig::default(),
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -3476,6 +4145,9 @@ model_verbosity = "high"
             tui_scroll_wheel_tick_detect_max_ms: None,
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
+            tui_keymap: TuiKeymap::default(),
+            tui_vi_mode: false,
+            tui_inline_images: InlineImages::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3754,6 +4426,7 @@ trust_level = "untrusted"
                     test_path.to_string_lossy().to_string(),
                     ProjectConfig {
                         trust_level: Some(TrustLevel::Untrusted),
+                        ..Default::default()
                     },
                 )])),
                 ..Default::default()
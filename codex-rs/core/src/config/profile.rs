@@ -33,6 +33,15 @@ pub struct ConfigProfile {
     #[serde(default)]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Spend limits scoped to this profile; unset fields fall back to the
+    /// top-level `budget` table.
+    pub budget: Option<crate::config::types::Budget>,
+    /// Ordered provider/model pairs to fail over to; replaces the top-level
+    /// `model_fallbacks` list when set.
+    pub model_fallbacks: Option<Vec<crate::config::types::ModelFallbackToml>>,
+    /// Compaction settings for this profile; replaces the top-level
+    /// `[compaction]` table when set.
+    pub compaction: Option<crate::config::types::CompactionToml>,
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
@@ -3,6 +3,8 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::model_provider_info::ModelProviderInfo;
+pub use codex_api::CassetteMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -385,6 +387,23 @@ impl Default for ScrollInputMode {
     }
 }
 
+/// Whether and how the TUI draws images inline in the transcript.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineImages {
+    /// Detect a supported graphics protocol from the terminal environment.
+    #[default]
+    Auto,
+    /// Use the kitty graphics protocol.
+    Kitty,
+    /// Use iTerm2's inline image escape sequence.
+    Iterm2,
+    /// Use sixel graphics.
+    Sixel,
+    /// Never draw images; show only their paths.
+    Off,
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct Tui {
@@ -505,6 +524,66 @@ pub struct Tui {
     /// wheel and trackpad input.
     #[serde(default)]
     pub scroll_invert: bool,
+
+    /// Overrides for TUI key bindings (`[tui.keymap]`).
+    #[serde(default)]
+    pub keymap: TuiKeymap,
+
+    /// Edit the composer with vi-style normal and insert modes.
+    /// Defaults to `false`.
+    #[serde(default)]
+    pub vi_mode: bool,
+
+    /// Draw images the agent views and images attached to prompts inline
+    /// using a terminal graphics protocol. Defaults to `auto`.
+    #[serde(default)]
+    pub inline_images: InlineImages,
+}
+
+/// One or more key chords bound to a TUI action, written either as a single
+/// string (`"ctrl+o"`) or as a list of alternatives (`["ctrl+o", "f2"]`).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum KeyChords {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyChords {
+    pub fn chords(&self) -> Vec<&str> {
+        match self {
+            KeyChords::One(chord) => vec![chord.as_str()],
+            KeyChords::Many(chords) => chords.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// The `[tui.keymap]` table. Unset actions keep their default chords; an
+/// empty list unbinds the action. Chords are validated by the TUI at startup.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct TuiKeymap {
+    /// Open (and close) the transcript overlay. Default: `ctrl+t`.
+    pub transcript: Option<KeyChords>,
+    /// Edit the composer in `$VISUAL`/`$EDITOR`. Default: `ctrl+g`.
+    pub external_editor: Option<KeyChords>,
+    /// Step back to edit a previous message when the composer is empty. Default: `esc`.
+    pub edit_previous: Option<KeyChords>,
+    /// Insert a newline in the composer. Default: `ctrl+j` (`shift+enter` also
+    /// works on terminals that report it).
+    pub newline: Option<KeyChords>,
+    /// Paste an image from the clipboard. Default: `ctrl+v`, `alt+v` and `ctrl+alt+v`.
+    pub paste_image: Option<KeyChords>,
+    /// Close full-screen overlays such as the transcript and diff viewer. Default: `q`.
+    pub pager_close: Option<KeyChords>,
+    /// Switch to the next conversation tab. Default: `alt+.`.
+    pub next_tab: Option<KeyChords>,
+    /// Switch to the previous conversation tab. Default: `alt+,`.
+    pub previous_tab: Option<KeyChords>,
+    /// Open the editor for messages queued during a turn. Default: `alt+q`.
+    pub queue: Option<KeyChords>,
+    /// Open the command palette when the composer is empty. Default: `ctrl+k`.
+    pub command_palette: Option<KeyChords>,
 }
 
 const fn default_true() -> bool {
@@ -547,6 +626,13 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// When non-empty, sandboxed commands can only read these folders, the
+    /// writable roots, and system locations.
+    #[serde(default)]
+    pub readable_roots: Vec<AbsolutePathBuf>,
+    /// Files and folders sandboxed commands can neither read nor write.
+    #[serde(default)]
+    pub deny_read: Vec<AbsolutePathBuf>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -560,6 +646,227 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
     }
 }
 
+/// Token pricing for a model, in USD per million tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_mtok: f64,
+    /// Rate for cached input tokens. Defaults to `input_per_mtok`.
+    pub cached_input_per_mtok: Option<f64>,
+    pub output_per_mtok: f64,
+    /// Rate for reasoning output tokens. Defaults to `output_per_mtok`.
+    pub reasoning_output_per_mtok: Option<f64>,
+}
+
+/// Spend limits, in USD. Soft limits pause the agent until the user sends
+/// another message; hard limits refuse to start new turns.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct Budget {
+    pub session_soft_limit_usd: Option<f64>,
+    pub session_hard_limit_usd: Option<f64>,
+    /// Daily limits cover every session sharing the same `CODEX_HOME`.
+    pub daily_soft_limit_usd: Option<f64>,
+    pub daily_hard_limit_usd: Option<f64>,
+}
+
+/// Secret redaction for tool output, as written in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RedactionToml {
+    /// Defaults to `true`.
+    pub enabled: Option<bool>,
+    /// Extra regular expressions to redact. A `(?P<secret>...)` group limits
+    /// redaction to that part of the match.
+    pub patterns: Option<Vec<String>>,
+}
+
+/// Redaction applied to tool outputs before they are recorded or sent to the
+/// model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedactionConfig {
+    pub enabled: bool,
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Egress proxy for sandboxed commands, as written in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NetworkProxyToml {
+    /// Hosts sandboxed commands may reach. `*.example.com` matches any
+    /// subdomain of `example.com` (but not `example.com` itself).
+    pub allowed_domains: Option<Vec<String>>,
+}
+
+/// Routes network traffic from sandboxed commands through a local proxy that
+/// only lets connections to `allowed_domains` through.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkProxyConfig {
+    pub allowed_domains: Vec<String>,
+}
+
+/// Resource limits for sandboxed commands, as written in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SandboxResourceLimitsToml {
+    pub cpu_time_secs: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub max_processes: Option<u64>,
+    pub max_file_size_mb: Option<u64>,
+    /// Delegated cgroup v2 directory used to enforce `memory_mb` and
+    /// `max_processes` across the whole process tree.
+    pub cgroup_parent: Option<AbsolutePathBuf>,
+}
+
+/// Records model traffic to, or replays it from, a JSONL cassette file.
+/// Overridden by the `CODEX_RS_CASSETTE_RECORD` and `CODEX_RS_CASSETTE_REPLAY`
+/// environment variables.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CassetteConfig {
+    pub mode: CassetteMode,
+    /// Relative paths are resolved against the session working directory.
+    pub path: PathBuf,
+}
+
+/// How the conversation is compacted when it nears the context window or on
+/// `/compact`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompactionStrategy {
+    /// Replace the whole conversation with a summary (remote compaction is
+    /// used when enabled).
+    #[default]
+    SummarizeAll,
+    /// Keep the most recent turns, pinned messages and the latest plan
+    /// verbatim and summarize only the rest.
+    SummarizeMiddle,
+}
+
+/// The `[compaction]` table in `config.toml` or a profile.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CompactionToml {
+    pub strategy: Option<CompactionStrategy>,
+    /// Defaults to 2.
+    pub keep_recent_turns: Option<usize>,
+    /// Defaults to `true`.
+    pub preserve_plan: Option<bool>,
+    /// Defaults to `true`.
+    pub preserve_pinned: Option<bool>,
+    /// Defaults to `true`.
+    pub drop_tool_outputs: Option<bool>,
+}
+
+/// Resolved compaction settings. Everything but `strategy` only applies to
+/// [`CompactionStrategy::SummarizeMiddle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionConfig {
+    pub strategy: CompactionStrategy,
+    /// Number of most recent user turns kept verbatim.
+    pub keep_recent_turns: usize,
+    /// Keep the latest `update_plan` call instead of summarizing it.
+    pub preserve_plan: bool,
+    /// Keep user messages starting with `[pin]` verbatim.
+    pub preserve_pinned: bool,
+    /// Replace the output of tool calls being summarized with a placeholder.
+    pub drop_tool_outputs: bool,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            strategy: CompactionStrategy::default(),
+            keep_recent_turns: 2,
+            preserve_plan: true,
+            preserve_pinned: true,
+            drop_tool_outputs: true,
+        }
+    }
+}
+
+impl From<CompactionToml> for CompactionConfig {
+    fn from(toml: CompactionToml) -> Self {
+        let defaults = Self::default();
+        Self {
+            strategy: toml.strategy.unwrap_or(defaults.strategy),
+            keep_recent_turns: toml.keep_recent_turns.unwrap_or(defaults.keep_recent_turns),
+            preserve_plan: toml.preserve_plan.unwrap_or(defaults.preserve_plan),
+            preserve_pinned: toml.preserve_pinned.unwrap_or(defaults.preserve_pinned),
+            drop_tool_outputs: toml.drop_tool_outputs.unwrap_or(defaults.drop_tool_outputs),
+        }
+    }
+}
+
+/// A reviewer persona under `[review_personas.<name>]` in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewPersonaToml {
+    /// What this pass should focus on; added to the standard review rubric.
+    pub instructions: String,
+    /// Defaults to `review_model`.
+    pub model: Option<String>,
+    /// Findings less severe than this are dropped from the pass.
+    pub severity_threshold: Option<ReviewSeverity>,
+    /// Defaults to `true`.
+    pub enabled: Option<bool>,
+}
+
+/// Severity of a review finding, from the `[P0]`…`[P3]` priority tags.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewSeverity {
+    /// P0
+    Critical,
+    /// P1
+    High,
+    /// P2
+    Medium,
+    /// P3
+    Low,
+}
+
+impl ReviewSeverity {
+    /// Whether a finding with `priority` (0 = P0) is at least this severe.
+    pub fn includes(self, priority: i32) -> bool {
+        let max_priority = match self {
+            ReviewSeverity::Critical => 0,
+            ReviewSeverity::High => 1,
+            ReviewSeverity::Medium => 2,
+            ReviewSeverity::Low => 3,
+        };
+        priority <= max_priority
+    }
+}
+
+/// An extra review pass that runs in parallel with the default one when
+/// `/review` starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewPersona {
+    pub name: String,
+    pub instructions: String,
+    pub model: String,
+    pub severity_threshold: Option<ReviewSeverity>,
+}
+
+/// An entry in `model_fallbacks` as written in `config.toml`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ModelFallbackToml {
+    /// Key in `model_providers`. Defaults to the primary `model_provider`.
+    pub provider: Option<String>,
+    pub model: String,
+}
+
+/// A provider/model pair to switch to when the active one fails with a
+/// server error, an exhausted rate limit, or a context window overflow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelFallback {
+    pub provider_id: String,
+    pub provider: ModelProviderInfo,
+    pub model: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(rename_all = "kebab-case")]
 pub enum ShellEnvironmentPolicyInherit {
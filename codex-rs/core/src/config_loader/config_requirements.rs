@@ -258,6 +258,8 @@ mod tests {
                     network_access: false,
                     exclude_tmpdir_env_var: false,
                     exclude_slash_tmp: false,
+                    readable_roots: vec![],
+                    deny_read: vec![],
                 })
                 .is_ok()
         );
@@ -14,6 +14,7 @@ use crate::config::CONFIG_TOML_FILE;
 use crate::config::ConfigToml;
 use crate::config_loader::config_requirements::ConfigRequirementsToml;
 use crate::config_loader::layer_io::LoadedConfigLayers;
+use crate::git_info::resolve_root_git_project_for_trust;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::protocol::AskForApproval;
@@ -60,6 +61,7 @@ const DEFAULT_PROJECT_ROOT_MARKERS: &[&str] = &[".git"];
 /// - cwd       `${PWD}/config.toml`
 /// - tree      parent directories up to root looking for `./.codex/config.toml`
 /// - repo      `$(git rev-parse --show-toplevel)/.codex/config.toml`
+/// - project   `[projects."<path>"]` settings from the system or user config
 /// - runtime   e.g., --config flags, model selector in UI
 ///
 /// (*) Only available on macOS via managed device profiles.
@@ -153,6 +155,12 @@ pub async fn load_config_layers_state(
         let project_root = find_project_root(&cwd, &project_root_markers).await?;
         let project_layers = load_project_layers(&cwd, &project_root).await?;
         layers.extend(project_layers);
+
+        // Only the system and user configs may declare per-project settings,
+        // so a checkout cannot loosen its own sandbox through `.codex/`.
+        if let Some(project_overrides) = project_overrides_layer(&merged_so_far, &cwd)? {
+            layers.push(project_overrides);
+        }
     }
 
     // Add a layer for runtime overrides from the CLI or UI, if any exist.
@@ -526,6 +534,47 @@ async fn load_project_layers(
     Ok(layers)
 }
 
+/// Builds the [ConfigLayerSource::ProjectOverrides] layer from the
+/// `[projects."<path>"]` entry for `cwd` in `config`, if it sets anything
+/// besides `trust_level`. The entry is found the same way as the project's
+/// trust level: by `cwd` itself, or else by the root of its git repository.
+fn project_overrides_layer(
+    config: &TomlValue,
+    cwd: &AbsolutePathBuf,
+) -> io::Result<Option<ConfigLayerEntry>> {
+    let Some(projects) = config.get("projects").and_then(TomlValue::as_table) else {
+        return Ok(None);
+    };
+    let Some((project, settings)) = std::iter::once(cwd.to_path_buf())
+        .chain(resolve_root_git_project_for_trust(cwd.as_path()))
+        .find_map(|path| {
+            projects
+                .get(path.to_string_lossy().as_ref())
+                .map(|settings| (path, settings))
+        })
+    else {
+        return Ok(None);
+    };
+
+    let Some(settings) = settings.as_table() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("projects.\"{}\" must be a table", project.display()),
+        ));
+    };
+    let mut overrides = settings.clone();
+    overrides.remove("trust_level");
+    if overrides.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ConfigLayerEntry::new(
+        ConfigLayerSource::ProjectOverrides {
+            project: AbsolutePathBuf::from_absolute_path(project)?,
+        },
+        TomlValue::Table(overrides),
+    )))
+}
+
 /// The legacy mechanism for specifying admin-enforced configuration is to read
 /// from a file like `/etc/codex/managed_config.toml` that has the same
 /// structure as `config.toml` where fields like `approval_policy` can specify
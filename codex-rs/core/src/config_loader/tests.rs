@@ -9,6 +9,7 @@ use crate::config_loader::config_requirements::ConfigRequirementsToml;
 use crate::config_loader::fingerprint::version_for_toml;
 use crate::config_loader::load_requirements_toml;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
@@ -434,3 +435,115 @@ project_root_markers = [".hg"]
 
     Ok(())
 }
+
+#[tokio::test]
+async fn project_settings_override_user_and_project_config() -> std::io::Result<()> {
+    let tmp = tempdir()?;
+    let root = tmp.path().canonicalize()?;
+    let vendored = root.join("vendored");
+    let monorepo = root.join("monorepo");
+    tokio::fs::create_dir_all(vendored.join(".codex")).await?;
+    tokio::fs::create_dir_all(vendored.join("src")).await?;
+    tokio::fs::create_dir_all(&monorepo).await?;
+    // Settings for a repository also apply in its subfolders.
+    let git_init = std::process::Command::new("git")
+        .args(["init", "--quiet"])
+        .current_dir(&vendored)
+        .status()?;
+    assert!(git_init.success());
+    // The checkout's own config cannot loosen what the user configured for it.
+    tokio::fs::write(
+        vendored.join(".codex").join(CONFIG_TOML_FILE),
+        "sandbox_mode = \"danger-full-access\"\n",
+    )
+    .await?;
+
+    let codex_home = root.join("home");
+    tokio::fs::create_dir_all(&codex_home).await?;
+    tokio::fs::write(
+        codex_home.join(CONFIG_TOML_FILE),
+        format!(
+            r#"
+approval_policy = "never"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+network_access = true
+
+[projects."{vendored}"]
+trust_level = "untrusted"
+approval_policy = "untrusted"
+sandbox_mode = "read-only"
+
+[projects."{monorepo}"]
+trust_level = "trusted"
+
+[projects."{monorepo}".sandbox_workspace_write]
+writable_roots = ["{cache}"]
+"#,
+            vendored = vendored.display(),
+            monorepo = monorepo.display(),
+            cache = root.join("cache").display(),
+        ),
+    )
+    .await?;
+
+    let load = |cwd: std::path::PathBuf| {
+        let codex_home = codex_home.clone();
+        async move {
+            ConfigBuilder::default()
+                .codex_home(codex_home)
+                .harness_overrides(ConfigOverrides {
+                    cwd: Some(cwd),
+                    ..ConfigOverrides::default()
+                })
+                .build()
+                .await
+        }
+    };
+
+    let config = load(vendored.join("src")).await?;
+    assert_eq!(
+        config.approval_policy.value(),
+        AskForApproval::UnlessTrusted
+    );
+    assert_eq!(
+        config.sandbox_policy.get(),
+        &SandboxPolicy::new_read_only_policy()
+    );
+    let layer = config
+        .config_layer_stack
+        .layers_high_to_low()
+        .into_iter()
+        .find(|layer| {
+            matches!(
+                layer.name,
+                super::ConfigLayerSource::ProjectOverrides { .. }
+            )
+        })
+        .expect("project overrides layer");
+    assert_eq!(
+        layer.name,
+        super::ConfigLayerSource::ProjectOverrides {
+            project: AbsolutePathBuf::from_absolute_path(&vendored)?,
+        }
+    );
+
+    let config = load(monorepo.clone()).await?;
+    assert_eq!(config.approval_policy.value(), AskForApproval::Never);
+    let SandboxPolicy::WorkspaceWrite {
+        writable_roots,
+        network_access,
+        ..
+    } = config.sandbox_policy.get()
+    else {
+        panic!("expected workspace-write, got {:?}", config.sandbox_policy);
+    };
+    assert_eq!(
+        writable_roots,
+        &vec![AbsolutePathBuf::from_absolute_path(root.join("cache"))?]
+    );
+    assert!(network_access);
+
+    Ok(())
+}
@@ -0,0 +1,355 @@
+//! Spend estimation and budget enforcement.
+//!
+//! Token usage reported by the model is converted to USD using the
+//! `model_pricing` table from `config.toml`. Each priced response is appended
+//! to a per-day ledger at `~/.codex/spend/<YYYY-MM-DD>.jsonl` so that daily
+//! budgets cover every session sharing the same `CODEX_HOME`:
+//!
+//! ````text
+//! {"conversation_id":"<uuid>","model":"<slug>","usd":0.0123}
+//! ````
+//!
+//! Lines are written with a single `write(2)` on an `O_APPEND` descriptor so
+//! concurrent sessions do not interleave records.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use chrono::Local;
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::TokenUsage;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::config::types::Budget;
+use crate::config::types::ModelPricing;
+use crate::error::BudgetExceededError;
+use crate::error::BudgetScope;
+
+/// Directory inside `~/.codex` that holds the daily spend ledgers.
+const SPEND_SUBDIR: &str = "spend";
+
+const TOKENS_PER_MTOK: f64 = 1_000_000.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct SpendEntry {
+    conversation_id: String,
+    model: String,
+    usd: f64,
+}
+
+/// Result of comparing the current spend against the configured [`Budget`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BudgetStatus {
+    WithinBudget,
+    SoftLimitReached {
+        scope: BudgetScope,
+        limit_usd: f64,
+        spent_usd: f64,
+    },
+    HardLimitReached(BudgetExceededError),
+}
+
+impl ModelPricing {
+    /// Estimated cost of `usage` in USD. Reasoning tokens are a subset of the
+    /// output tokens and cached tokens are a subset of the input tokens.
+    pub fn cost_usd(&self, usage: &TokenUsage) -> f64 {
+        let cached_input = usage.cached_input() as f64;
+        let non_cached_input = usage.non_cached_input() as f64;
+        let reasoning_output = usage.reasoning_output_tokens.max(0) as f64;
+        let output = (usage.output_tokens.max(0) as f64 - reasoning_output).max(0.0);
+
+        let cached_rate = self.cached_input_per_mtok.unwrap_or(self.input_per_mtok);
+        let reasoning_rate = self
+            .reasoning_output_per_mtok
+            .unwrap_or(self.output_per_mtok);
+
+        (non_cached_input * self.input_per_mtok
+            + cached_input * cached_rate
+            + output * self.output_per_mtok
+            + reasoning_output * reasoning_rate)
+            / TOKENS_PER_MTOK
+    }
+}
+
+/// Prices token usage for a session and persists it to the daily ledger.
+#[derive(Debug, Clone)]
+pub(crate) struct CostTracker {
+    pricing: HashMap<String, ModelPricing>,
+    budget: Budget,
+    ledger_dir: PathBuf,
+    conversation_id: ConversationId,
+}
+
+impl CostTracker {
+    pub(crate) fn new(config: &Config, conversation_id: ConversationId) -> Self {
+        Self {
+            pricing: config.model_pricing.clone(),
+            budget: config.budget,
+            ledger_dir: config.codex_home.join(SPEND_SUBDIR),
+            conversation_id,
+        }
+    }
+
+    /// Resolve pricing for `model`: an exact slug match wins, otherwise the
+    /// longest configured key that prefixes the slug (so `gpt-5.1` covers
+    /// `gpt-5.1-2025-11-13`).
+    pub(crate) fn pricing_for(&self, model: &str) -> Option<&ModelPricing> {
+        if let Some(pricing) = self.pricing.get(model) {
+            return Some(pricing);
+        }
+        self.pricing
+            .iter()
+            .filter(|(slug, _)| model.starts_with(slug.as_str()))
+            .max_by_key(|(slug, _)| slug.len())
+            .map(|(_, pricing)| pricing)
+    }
+
+    pub(crate) fn has_budget(&self) -> bool {
+        let Budget {
+            session_soft_limit_usd,
+            session_hard_limit_usd,
+            daily_soft_limit_usd,
+            daily_hard_limit_usd,
+        } = self.budget;
+        session_soft_limit_usd.is_some()
+            || session_hard_limit_usd.is_some()
+            || daily_soft_limit_usd.is_some()
+            || daily_hard_limit_usd.is_some()
+    }
+
+    /// Price `usage` for `model` and append it to today's ledger. Returns
+    /// `None` when no pricing is configured for the model.
+    pub(crate) async fn record(&self, model: &str, usage: &TokenUsage) -> Option<f64> {
+        let usd = self.pricing_for(model)?.cost_usd(usage);
+        if let Err(err) = self.append_entry(model, usd).await {
+            warn!("failed to record spend: {err}");
+        }
+        Some(usd)
+    }
+
+    /// Sum of today's ledger across all sessions.
+    pub(crate) async fn daily_total_usd(&self) -> f64 {
+        let contents = match tokio::fs::read_to_string(self.ledger_path()).await {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return 0.0,
+            Err(err) => {
+                warn!("failed to read spend ledger: {err}");
+                return 0.0;
+            }
+        };
+        contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<SpendEntry>(line).ok())
+            .map(|entry| entry.usd)
+            .sum()
+    }
+
+    /// Compare spend against the budget. Hard limits take precedence over soft
+    /// limits, and session limits over daily limits.
+    pub(crate) fn check(&self, session_usd: f64, daily_usd: f64) -> BudgetStatus {
+        let hard_limits = [
+            (
+                BudgetScope::Session,
+                self.budget.session_hard_limit_usd,
+                session_usd,
+            ),
+            (
+                BudgetScope::Daily,
+                self.budget.daily_hard_limit_usd,
+                daily_usd,
+            ),
+        ];
+        for (scope, limit, spent) in hard_limits {
+            if let Some(limit_usd) = limit
+                && spent >= limit_usd
+            {
+                return BudgetStatus::HardLimitReached(BudgetExceededError {
+                    scope,
+                    limit_usd,
+                    spent_usd: spent,
+                });
+            }
+        }
+
+        let soft_limits = [
+            (
+                BudgetScope::Session,
+                self.budget.session_soft_limit_usd,
+                session_usd,
+            ),
+            (
+                BudgetScope::Daily,
+                self.budget.daily_soft_limit_usd,
+                daily_usd,
+            ),
+        ];
+        for (scope, limit, spent) in soft_limits {
+            if let Some(limit_usd) = limit
+                && spent >= limit_usd
+            {
+                return BudgetStatus::SoftLimitReached {
+                    scope,
+                    limit_usd,
+                    spent_usd: spent,
+                };
+            }
+        }
+
+        BudgetStatus::WithinBudget
+    }
+
+    fn ledger_path(&self) -> PathBuf {
+        let today = Local::now().format("%Y-%m-%d");
+        self.ledger_dir.join(format!("{today}.jsonl"))
+    }
+
+    async fn append_entry(&self, model: &str, usd: f64) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.ledger_dir).await?;
+        let entry = SpendEntry {
+            conversation_id: self.conversation_id.to_string(),
+            model: model.to_string(),
+            usd,
+        };
+        let mut line = serde_json::to_string(&entry)
+            .map_err(|e| std::io::Error::other(format!("failed to serialise spend entry: {e}")))?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.ledger_path())
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn tracker(codex_home: &TempDir, budget: Budget) -> CostTracker {
+        CostTracker {
+            pricing: HashMap::from([
+                (
+                    "gpt-5.1".to_string(),
+                    ModelPricing {
+                        input_per_mtok: 1.0,
+                        cached_input_per_mtok: Some(0.25),
+                        output_per_mtok: 10.0,
+                        reasoning_output_per_mtok: None,
+                    },
+                ),
+                (
+                    "gpt-5.1-codex".to_string(),
+                    ModelPricing {
+                        input_per_mtok: 2.0,
+                        cached_input_per_mtok: None,
+                        output_per_mtok: 20.0,
+                        reasoning_output_per_mtok: Some(40.0),
+                    },
+                ),
+            ]),
+            budget,
+            ledger_dir: codex_home.path().join(SPEND_SUBDIR),
+            conversation_id: ConversationId::new(),
+        }
+    }
+
+    fn usage() -> TokenUsage {
+        TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 500_000,
+            output_tokens: 200_000,
+            reasoning_output_tokens: 100_000,
+            total_tokens: 1_200_000,
+        }
+    }
+
+    #[test]
+    fn cost_splits_cached_input_and_reasoning_output() {
+        let pricing = ModelPricing {
+            input_per_mtok: 2.0,
+            cached_input_per_mtok: Some(0.5),
+            output_per_mtok: 10.0,
+            reasoning_output_per_mtok: Some(30.0),
+        };
+
+        // 0.5M * 2 + 0.5M * 0.5 + 0.1M * 10 + 0.1M * 30
+        assert_eq!(pricing.cost_usd(&usage()), 5.25);
+    }
+
+    #[test]
+    fn pricing_prefers_exact_then_longest_prefix() {
+        let home = TempDir::new().expect("tempdir");
+        let tracker = tracker(&home, Budget::default());
+
+        let codex = tracker
+            .pricing_for("gpt-5.1-codex")
+            .map(|p| p.input_per_mtok);
+        let codex_max = tracker
+            .pricing_for("gpt-5.1-codex-max")
+            .map(|p| p.input_per_mtok);
+        let base = tracker
+            .pricing_for("gpt-5.1-2025-11-13")
+            .map(|p| p.input_per_mtok);
+
+        assert_eq!(codex, Some(2.0));
+        assert_eq!(codex_max, Some(2.0));
+        assert_eq!(base, Some(1.0));
+        assert!(tracker.pricing_for("o3").is_none());
+    }
+
+    #[tokio::test]
+    async fn record_appends_to_daily_ledger() {
+        let home = TempDir::new().expect("tempdir");
+        let tracker = tracker(&home, Budget::default());
+
+        let first = tracker.record("gpt-5.1", &usage()).await;
+        let second = tracker.record("gpt-5.1", &usage()).await;
+        let unpriced = tracker.record("o3", &usage()).await;
+
+        assert_eq!(first, Some(2.625));
+        assert_eq!(second, Some(2.625));
+        assert_eq!(unpriced, None);
+        assert_eq!(tracker.daily_total_usd().await, 5.25);
+    }
+
+    #[test]
+    fn hard_limits_win_over_soft_limits() {
+        let home = TempDir::new().expect("tempdir");
+        let tracker = tracker(
+            &home,
+            Budget {
+                session_soft_limit_usd: Some(1.0),
+                session_hard_limit_usd: Some(5.0),
+                daily_soft_limit_usd: None,
+                daily_hard_limit_usd: Some(20.0),
+            },
+        );
+
+        assert_eq!(tracker.check(0.5, 0.5), BudgetStatus::WithinBudget);
+        assert_eq!(
+            tracker.check(2.0, 2.0),
+            BudgetStatus::SoftLimitReached {
+                scope: BudgetScope::Session,
+                limit_usd: 1.0,
+                spent_usd: 2.0,
+            }
+        );
+        assert_eq!(
+            tracker.check(2.0, 25.0),
+            BudgetStatus::HardLimitReached(BudgetExceededError {
+                scope: BudgetScope::Daily,
+                limit_usd: 20.0,
+                spent_usd: 25.0,
+            })
+        );
+    }
+}
@@ -11,6 +11,7 @@ use codex_async_utils::CancelErr;
 use codex_protocol::ConversationId;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::ExecExitReason;
 use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
 use serde_json;
@@ -47,6 +48,13 @@ pub enum SandboxErr {
     #[error("command timed out")]
     Timeout { output: Box<ExecToolCallOutput> },
 
+    /// Command was killed for exceeding a sandbox resource limit
+    #[error("command exceeded the sandbox {reason}")]
+    ResourceLimit {
+        reason: ExecExitReason,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Command was killed by a signal
     #[error("command was killed by a signal")]
     Signal(i32),
@@ -110,6 +118,10 @@ pub enum CodexErr {
     #[error("{0}")]
     UsageLimitReached(UsageLimitReachedError),
 
+    /// A hard spend limit from the `budget` config table was reached.
+    #[error("{0}")]
+    BudgetExceeded(BudgetExceededError),
+
     #[error("{0}")]
     ResponseStreamFailed(ResponseStreamFailed),
 
@@ -305,6 +317,49 @@ impl std::fmt::Display for RetryLimitReachedError {
     }
 }
 
+/// Which spend limit a budget check applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BudgetScope {
+    Session,
+    Daily,
+}
+
+impl BudgetScope {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            BudgetScope::Session => "session",
+            BudgetScope::Daily => "daily",
+        }
+    }
+
+    pub(crate) fn hard_limit_key(self) -> &'static str {
+        match self {
+            BudgetScope::Session => "budget.session_hard_limit_usd",
+            BudgetScope::Daily => "budget.daily_hard_limit_usd",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetExceededError {
+    pub scope: BudgetScope,
+    pub limit_usd: f64,
+    pub spent_usd: f64,
+}
+
+impl std::fmt::Display for BudgetExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Reached the {} budget of ${:.2} (estimated spend ${:.2}). Raise `{}` to continue.",
+            self.scope.label(),
+            self.limit_usd,
+            self.spent_usd,
+            self.scope.hard_limit_key()
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct UsageLimitReachedError {
     pub(crate) plan_type: Option<PlanType>,
@@ -442,6 +497,7 @@ impl CodexErr {
             CodexErr::UsageLimitReached(_)
             | CodexErr::QuotaExceeded
             | CodexErr::UsageNotIncluded => CodexErrorInfo::UsageLimitExceeded,
+            CodexErr::BudgetExceeded(_) => CodexErrorInfo::BudgetExceeded,
             CodexErr::RetryLimit(_) => CodexErrorInfo::ResponseTooManyFailedAttempts {
                 http_status_code: self.http_status_code_value(),
             },
@@ -463,6 +519,19 @@ impl CodexErr {
         }
     }
 
+    /// Whether switching to a fallback provider/model may get past this
+    /// error: server failures, exhausted rate limits, and context overflows.
+    pub fn should_fail_over(&self) -> bool {
+        match self {
+            CodexErr::ContextWindowExceeded
+            | CodexErr::UsageLimitReached(_)
+            | CodexErr::InternalServerError => true,
+            _ => self
+                .http_status_code_value()
+                .is_some_and(|status| status == 429 || (500..600).contains(&status)),
+        }
+    }
+
     pub fn to_error_event(&self, message_prefix: Option<String>) -> ErrorEvent {
         let error_message = self.to_string();
         let message: String = match message_prefix {
@@ -507,6 +576,9 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 }
             }
         }
+        CodexErr::Sandbox(SandboxErr::ResourceLimit { reason, .. }) => {
+            format!("error: command exceeded the sandbox {reason}")
+        }
         // Timeouts are not sandbox errors from a UX perspective; present them plainly
         CodexErr::Sandbox(SandboxErr::Timeout { output }) => {
             format!(
@@ -585,6 +657,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_fail_over_on_server_rate_limit_and_context_errors() {
+        let retry_limit = |status| {
+            CodexErr::RetryLimit(RetryLimitReachedError {
+                status,
+                request_id: None,
+            })
+        };
+
+        assert!(CodexErr::ContextWindowExceeded.should_fail_over());
+        assert!(CodexErr::InternalServerError.should_fail_over());
+        assert!(retry_limit(StatusCode::BAD_GATEWAY).should_fail_over());
+        assert!(retry_limit(StatusCode::TOO_MANY_REQUESTS).should_fail_over());
+        assert!(!retry_limit(StatusCode::BAD_REQUEST).should_fail_over());
+        assert!(!CodexErr::QuotaExceeded.should_fail_over());
+        assert!(!CodexErr::TurnAborted.should_fail_over());
+    }
+
     #[test]
     fn sandbox_denied_uses_aggregated_output_when_stderr_empty() {
         let output = ExecToolCallOutput {
@@ -594,6 +684,7 @@ mod tests {
             aggregated_output: StreamOutput::new("aggregate detail".to_string()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -610,6 +701,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(10),
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -626,6 +718,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(8),
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -669,6 +762,7 @@ mod tests {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::from_millis(5),
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
@@ -10,6 +10,8 @@ use std::time::Duration;
 use std::time::Instant;
 
 use async_channel::Sender;
+use serde::Deserialize;
+use serde::Serialize;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
@@ -20,17 +22,25 @@ use crate::error::CodexErr;
 use crate::error::Result;
 use crate::error::SandboxErr;
 use crate::get_platform_sandbox;
+#[cfg(target_os = "linux")]
+use crate::landlock::SANDBOX_REPORT_FD;
+#[cfg(target_os = "linux")]
+use crate::landlock::SandboxReportPipe;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
+use crate::protocol::ExecExitReason;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
+#[cfg(target_os = "linux")]
+use crate::spawn::spawn_child_async_with_fd;
 use crate::text_encoding::bytes_to_string_smart;
 
 pub const DEFAULT_EXEC_COMMAND_TIMEOUT_MS: u64 = 10_000;
@@ -177,6 +187,9 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            None,
+            None,
+            None,
         )
         .map_err(CodexErr::from)?;
 
@@ -198,6 +211,7 @@ pub(crate) async fn execute_exec_env(
         sandbox_permissions,
         justification,
         arg0,
+        resource_limits,
     } = env;
 
     let params = ExecParams {
@@ -213,7 +227,12 @@ pub(crate) async fn execute_exec_env(
     let start = Instant::now();
     let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    finalize_exec_result(
+        raw_output_result,
+        sandbox,
+        duration,
+        resource_limits.as_ref(),
+    )
 }
 
 #[cfg(target_os = "windows")]
@@ -312,6 +331,7 @@ async fn exec_windows_sandbox(
         stderr,
         aggregated_output,
         timed_out: capture.timed_out,
+        sandbox_reports: Vec::new(),
     })
 }
 
@@ -319,6 +339,7 @@ fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
     duration: Duration,
+    resource_limits: Option<&SandboxResourceLimits>,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
@@ -326,14 +347,12 @@ fn finalize_exec_result(
             let mut timed_out = raw_output.timed_out;
 
             #[cfg(target_family = "unix")]
-            {
-                if let Some(signal) = raw_output.exit_status.signal() {
-                    if signal == TIMEOUT_CODE {
-                        timed_out = true;
-                    } else {
-                        return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
-                    }
-                }
+            let mut signal = raw_output.exit_status.signal();
+            #[cfg(not(target_family = "unix"))]
+            let mut signal: Option<i32> = None;
+            if signal == Some(TIMEOUT_CODE) {
+                timed_out = true;
+                signal = None;
             }
 
             let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
@@ -344,15 +363,45 @@ fn finalize_exec_result(
             let stdout = raw_output.stdout.from_utf8_lossy();
             let stderr = raw_output.stderr.from_utf8_lossy();
             let aggregated_output = raw_output.aggregated_output.from_utf8_lossy();
-            let exec_output = ExecToolCallOutput {
+            let mut reported_limit = None;
+            let mut blocked_syscalls = Vec::new();
+            for report in raw_output.sandbox_reports {
+                match report {
+                    SandboxReport::ResourceLimit { reason } => reported_limit = Some(reason),
+                    SandboxReport::BlockedSyscall { syscall } => blocked_syscalls.push(syscall),
+                }
+            }
+            let mut exec_output = ExecToolCallOutput {
                 exit_code,
                 stdout,
                 stderr,
                 aggregated_output,
                 duration,
                 timed_out,
+                blocked_syscalls,
             };
 
+            if !timed_out
+                && let Some(reason) = resource_limit_exit_reason(
+                    resource_limits,
+                    signal,
+                    &exec_output,
+                    reported_limit,
+                )
+            {
+                if let Some(signal) = signal {
+                    exec_output.exit_code = EXIT_CODE_SIGNAL_BASE + signal;
+                }
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                    reason,
+                    output: Box::new(exec_output),
+                }));
+            }
+
+            if let Some(signal) = signal {
+                return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
+            }
+
             if timed_out {
                 return Err(CodexErr::Sandbox(SandboxErr::Timeout {
                     output: Box::new(exec_output),
@@ -374,6 +423,73 @@ fn finalize_exec_result(
     }
 }
 
+/// Recognizes commands killed by one of the configured `resource_limits`:
+/// `SIGXCPU`/`SIGXFSZ` raised by rlimits (seen directly, or as the
+/// `128 + signal` exit code of a wrapping shell), or the cgroup limit
+/// `codex-linux-sandbox` reported out of band.
+fn resource_limit_exit_reason(
+    resource_limits: Option<&SandboxResourceLimits>,
+    signal: Option<i32>,
+    exec_output: &ExecToolCallOutput,
+    reported: Option<ExecExitReason>,
+) -> Option<ExecExitReason> {
+    let limits = resource_limits?;
+    if reported.is_some() {
+        return reported;
+    }
+
+    #[cfg(unix)]
+    {
+        let signal = signal.or_else(|| {
+            exec_output
+                .exit_code
+                .checked_sub(EXIT_CODE_SIGNAL_BASE)
+                .filter(|signal| *signal > 0)
+        });
+        match signal {
+            Some(libc::SIGXCPU) if limits.cpu_time_secs.is_some() => {
+                return Some(ExecExitReason::CpuTimeLimit);
+            }
+            Some(libc::SIGXFSZ) if limits.max_file_size_bytes.is_some() => {
+                return Some(ExecExitReason::FileSizeLimit);
+            }
+            _ => {}
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (limits, signal, exec_output);
+
+    None
+}
+
+/// One line of what `codex-linux-sandbox` writes to its report descriptor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SandboxReport {
+    /// A cgroup limit killed the command.
+    ResourceLimit { reason: ExecExitReason },
+    /// The seccomp filter refused a network system call, such as `connect`
+    /// or `socket(AF_INET)`.
+    BlockedSyscall { syscall: String },
+}
+
+impl SandboxReport {
+    /// The report as a line of JSON, newline included.
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
+/// Reports in the order they were written; lines that do not parse are
+/// skipped.
+pub(crate) fn parse_sandbox_reports(text: &str) -> Vec<SandboxReport> {
+    text.lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
 pub(crate) mod errors {
     use super::CodexErr;
     use crate::sandboxing::SandboxTransformError;
@@ -406,6 +522,11 @@ pub(crate) fn is_likely_sandbox_denied(
         return false;
     }
 
+    // The helper saw the seccomp filter refuse a call; no guessing needed.
+    if !exec_output.blocked_syscalls.is_empty() {
+        return true;
+    }
+
     // Quick rejects: well-known non-sandbox shell exit codes
     // 2: misuse of shell builtins
     // 126: permission denied
@@ -468,6 +589,8 @@ struct RawExecToolCallOutput {
     pub stderr: StreamOutput<Vec<u8>>,
     pub aggregated_output: StreamOutput<Vec<u8>>,
     pub timed_out: bool,
+    /// What `codex-linux-sandbox` reported out of band.
+    pub sandbox_reports: Vec<SandboxReport>,
 }
 
 impl StreamOutput<String> {
@@ -501,6 +624,9 @@ pub struct ExecToolCallOutput {
     pub aggregated_output: StreamOutput<String>,
     pub duration: Duration,
     pub timed_out: bool,
+    /// Network system calls the seccomp filter of `codex-linux-sandbox`
+    /// refused, as the helper reported them.
+    pub blocked_syscalls: Vec<String>,
 }
 
 impl Default for ExecToolCallOutput {
@@ -512,6 +638,7 @@ impl Default for ExecToolCallOutput {
             aggregated_output: StreamOutput::new(String::new()),
             duration: Duration::ZERO,
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         }
     }
 }
@@ -548,6 +675,33 @@ async fn exec(
         ))
     })?;
     let arg0_ref = arg0.as_deref();
+
+    // Cgroup kills and refused system calls are reported by
+    // `codex-linux-sandbox` over a pipe, where the command cannot forge them.
+    #[cfg(target_os = "linux")]
+    if sandbox == SandboxType::LinuxSeccomp {
+        let (report, report_fd) = SandboxReportPipe::new()?;
+        let args = ["--report-fd".to_string(), SANDBOX_REPORT_FD.to_string()]
+            .into_iter()
+            .chain(args.iter().cloned())
+            .collect();
+        let child = spawn_child_async_with_fd(
+            PathBuf::from(program),
+            args,
+            arg0_ref,
+            cwd,
+            sandbox_policy,
+            StdioPolicy::RedirectForShellTool,
+            env,
+            report_fd,
+            SANDBOX_REPORT_FD,
+        )
+        .await?;
+        let mut output = consume_truncated_output(child, expiration, stdout_stream).await?;
+        output.sandbox_reports = report.read();
+        return Ok(output);
+    }
+
     let child = spawn_child_async(
         PathBuf::from(program),
         args.into(),
@@ -679,6 +833,7 @@ async fn consume_truncated_output(
         stderr,
         aggregated_output,
         timed_out,
+        sandbox_reports: Vec::new(),
     })
 }
 
@@ -800,6 +955,7 @@ mod tests {
             aggregated_output: StreamOutput::new(aggregated.to_string()),
             duration: Duration::from_millis(1),
             timed_out: false,
+            blocked_syscalls: Vec::new(),
         }
     }
 
@@ -855,6 +1011,83 @@ mod tests {
         assert!(is_likely_sandbox_denied(SandboxType::LinuxSeccomp, &output));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn resource_limit_detection_only_reports_configured_limits() {
+        let cpu_and_files = SandboxResourceLimits {
+            cpu_time_secs: Some(10),
+            max_file_size_bytes: Some(1024),
+            ..Default::default()
+        };
+        let shell_wrapped = make_exec_output(EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU, "", "", "");
+        assert_eq!(
+            resource_limit_exit_reason(Some(&cpu_and_files), None, &shell_wrapped, None),
+            Some(ExecExitReason::CpuTimeLimit)
+        );
+        let killed = make_exec_output(-1, "", "", "");
+        assert_eq!(
+            resource_limit_exit_reason(Some(&cpu_and_files), Some(libc::SIGXFSZ), &killed, None),
+            Some(ExecExitReason::FileSizeLimit)
+        );
+
+        // Exit codes that merely look like a limit signal are not attributed
+        // to limits that were never set.
+        let memory_only = SandboxResourceLimits {
+            memory_bytes: Some(1 << 30),
+            ..Default::default()
+        };
+        assert_eq!(
+            resource_limit_exit_reason(Some(&memory_only), None, &shell_wrapped, None),
+            None
+        );
+        assert_eq!(
+            resource_limit_exit_reason(None, None, &shell_wrapped, None),
+            None
+        );
+
+        // Cgroup kills come only from the helper's report, never from output.
+        let oom = make_exec_output(137, "", r#""memory_limit""#, "");
+        assert_eq!(
+            resource_limit_exit_reason(Some(&memory_only), None, &oom, None),
+            None
+        );
+        let reported = Some(ExecExitReason::MemoryLimit);
+        assert_eq!(
+            resource_limit_exit_reason(Some(&memory_only), None, &oom, reported),
+            reported
+        );
+        assert_eq!(resource_limit_exit_reason(None, None, &oom, reported), None);
+    }
+
+    #[test]
+    fn sandbox_reports_round_trip_line_by_line() {
+        let reports = vec![
+            SandboxReport::BlockedSyscall {
+                syscall: "socket(AF_INET)".to_string(),
+            },
+            SandboxReport::ResourceLimit {
+                reason: ExecExitReason::ProcessLimit,
+            },
+        ];
+        let text: String = reports.iter().map(SandboxReport::to_line).collect();
+        assert_eq!(
+            text.lines().next(),
+            Some(r#"{"type":"blocked_syscall","syscall":"socket(AF_INET)"}"#)
+        );
+        assert_eq!(parse_sandbox_reports(&format!("{text}not json\n")), reports);
+    }
+
+    #[test]
+    fn blocked_syscalls_mark_a_failure_as_denied() {
+        let mut output = make_exec_output(1, "", "", "");
+        assert!(!is_likely_sandbox_denied(
+            SandboxType::LinuxSeccomp,
+            &output
+        ));
+        output.blocked_syscalls = vec!["connect".to_string()];
+        assert!(is_likely_sandbox_denied(SandboxType::LinuxSeccomp, &output));
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn kill_child_process_group_kills_grandchildren_on_timeout() -> Result<()> {
@@ -8,6 +8,7 @@ use arc_swap::ArcSwap;
 use crate::command_safety::is_dangerous_command::requires_initial_appoval;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigLayerStackOrdering;
+use codex_app_server_protocol::ConfigLayerSource;
 use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
@@ -22,6 +23,7 @@ use codex_protocol::protocol::SandboxPolicy;
 use thiserror::Error;
 use tokio::fs;
 use tokio::task::spawn_blocking;
+use toml::Value as TomlValue;
 
 use crate::bash::parse_shell_lc_plain_commands;
 use crate::features::Feature;
@@ -217,6 +219,9 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
             let layer_policy_paths = collect_policy_files(&policy_dir).await?;
             policy_paths.extend(layer_policy_paths);
         }
+        if let ConfigLayerSource::ProjectOverrides { .. } = &layer.name {
+            policy_paths.extend(project_policy_files(&layer.config));
+        }
     }
 
     let mut parser = PolicyParser::new();
@@ -243,6 +248,19 @@ pub async fn load_exec_policy(config_stack: &ConfigLayerStack) -> Result<Policy,
     Ok(policy)
 }
 
+/// The `.rules` files listed in a project's `rules` setting. Paths were
+/// already made absolute when the config was loaded.
+fn project_policy_files(config: &TomlValue) -> Vec<PathBuf> {
+    config
+        .get("rules")
+        .and_then(TomlValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(TomlValue::as_str)
+        .map(PathBuf::from)
+        .collect()
+}
+
 fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
@@ -483,6 +501,45 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn loads_rules_listed_in_project_settings() -> anyhow::Result<()> {
+        let project_dir = tempdir()?;
+        let rules_file = project_dir.path().join("vendored.rules");
+        fs::write(
+            &rules_file,
+            r#"prefix_rule(pattern=["curl"], decision="forbidden")"#,
+        )?;
+
+        let mut settings = toml::map::Map::new();
+        settings.insert(
+            "rules".to_string(),
+            TomlValue::Array(vec![TomlValue::String(
+                rules_file.to_string_lossy().to_string(),
+            )]),
+        );
+        let layer = ConfigLayerEntry::new(
+            ConfigLayerSource::ProjectOverrides {
+                project: AbsolutePathBuf::from_absolute_path(project_dir.path())?,
+            },
+            TomlValue::Table(settings),
+        );
+        let config_stack = ConfigLayerStack::new(vec![layer], ConfigRequirements::default())?;
+
+        let policy = load_exec_policy(&config_stack).await?;
+
+        assert_eq!(
+            Evaluation {
+                decision: Decision::Forbidden,
+                matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                    matched_prefix: vec!["curl".to_string()],
+                    decision: Decision::Forbidden
+                }],
+            },
+            policy.check_multiple([vec!["curl".to_string()]].iter(), &|_| Decision::Allow)
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn loads_policies_from_multiple_config_layers() -> anyhow::Result<()> {
         let user_dir = tempdir()?;
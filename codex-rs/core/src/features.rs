@@ -94,6 +94,13 @@ pub enum Feature {
     Skills,
     /// Enforce UTF8 output in Powershell.
     PowershellUtf8,
+    /// Give Linux sandboxed commands a private `/tmp` and a read-only view of
+    /// the host through a mount namespace.
+    LinuxMountNamespace,
+    /// Run Linux sandboxed shell commands on a copy-on-write overlay of the
+    /// workspace and ask for approval before keeping their changes. Implies
+    /// `LinuxMountNamespace`.
+    WorkspaceOverlay,
 }
 
 impl Feature {
@@ -401,4 +408,16 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::LinuxMountNamespace,
+        key: "linux_mount_namespace",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::WorkspaceOverlay,
+        key: "workspace_overlay",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
 ];
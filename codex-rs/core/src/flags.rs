@@ -3,4 +3,11 @@ use env_flags::env_flags;
 env_flags! {
     /// Fixture path for offline tests (see client.rs).
     pub CODEX_RS_SSE_FIXTURE: Option<&str> = None;
+
+    /// Cassette file to record model traffic to (see `[cassette]` in config).
+    pub CODEX_RS_CASSETTE_RECORD: Option<&str> = None;
+
+    /// Cassette file to replay model traffic from; takes precedence over
+    /// `CODEX_RS_CASSETTE_RECORD`.
+    pub CODEX_RS_CASSETTE_REPLAY: Option<&str> = None;
 }
@@ -1,4 +1,8 @@
+#[cfg(target_os = "linux")]
+use crate::exec::SandboxReport;
+use crate::network_proxy::NetworkProxy;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use std::collections::HashMap;
@@ -13,19 +17,28 @@ use tokio::process::Child;
 /// helper accepts a list of `--sandbox-permission`/`-s` flags mirroring the
 /// public CLI. We convert the internal [`SandboxPolicy`] representation into
 /// the equivalent CLI options.
+#[allow(clippy::too_many_arguments)]
 pub async fn spawn_command_under_linux_sandbox<P>(
     codex_linux_sandbox_exe: P,
     command: Vec<String>,
     command_cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    resource_limits: &SandboxResourceLimits,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child>
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_linux_sandbox_command_args(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        None,
+        Some(resource_limits),
+        None,
+    );
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
         codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -39,11 +52,85 @@ where
     .await
 }
 
+/// Mount-namespace isolation requested from `codex-linux-sandbox`: a private
+/// `/tmp` and a read-only view of everything outside the writable roots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct MountIsolation {
+    /// Folder for the upper and work directories of a copy-on-write overlay
+    /// over the workspace. `None` keeps the workspace writable in place.
+    pub(crate) workspace_overlay: Option<PathBuf>,
+}
+
+/// File descriptor on which `codex-linux-sandbox` reports which cgroup limit
+/// killed the command and which system calls its seccomp filter refused. The
+/// command itself never holds it, so unlike its output the reports cannot be
+/// forged.
+pub(crate) const SANDBOX_REPORT_FD: i32 = 3;
+
+/// Read end of the pipe behind [`SANDBOX_REPORT_FD`].
+#[cfg(target_os = "linux")]
+pub(crate) struct SandboxReportPipe {
+    reader: std::fs::File,
+}
+
+#[cfg(target_os = "linux")]
+impl SandboxReportPipe {
+    /// Creates the pipe, returning the report and the write end to hand to
+    /// the helper.
+    pub(crate) fn new() -> std::io::Result<(Self, std::os::fd::OwnedFd)> {
+        use std::os::fd::FromRawFd;
+        use std::os::fd::OwnedFd;
+
+        let mut fds = [0; 2];
+        // Non-blocking, so reading never waits on a helper that is somehow
+        // still alive.
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC | libc::O_NONBLOCK) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let (reader, writer) =
+            unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        Ok((
+            Self {
+                reader: reader.into(),
+            },
+            writer,
+        ))
+    }
+
+    /// What the helper reported, read once the command has exited. Processes
+    /// the command left running may still hold the write end, so this takes
+    /// whatever has been written instead of waiting for end of file.
+    pub(crate) fn read(mut self) -> Vec<SandboxReport> {
+        use std::io::Read;
+
+        let mut reports = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            match self.reader.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => reports.extend_from_slice(&chunk[..read]),
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+        crate::exec::parse_sandbox_reports(&String::from_utf8_lossy(&reports))
+    }
+}
+
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
+///
+/// With a `network_proxy`, the helper gives the command a private network
+/// namespace whose only route out is the proxy. `resource_limits` are applied
+/// by the helper right before it runs the command, and `mount_isolation`
+/// gives it a mount namespace of its own. Callers that can hand the helper a
+/// [`SANDBOX_REPORT_FD`] add `--report-fd` themselves.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy: Option<&NetworkProxy>,
+    resource_limits: Option<&SandboxResourceLimits>,
+    mount_isolation: Option<&MountIsolation>,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,11 +147,40 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
 
+    if let Some(proxy) = network_proxy
+        && let Some(socket_path) = proxy.socket_path()
+    {
+        linux_cmd.extend([
+            "--network-proxy-port".to_string(),
+            proxy.port().to_string(),
+            "--network-proxy-socket".to_string(),
+            socket_path.to_string_lossy().to_string(),
+        ]);
+    }
+
+    if let Some(limits) = resource_limits.filter(|limits| !limits.is_unlimited()) {
+        #[expect(clippy::expect_used)]
+        let limits_json = serde_json::to_string(limits)
+            .expect("Failed to serialize SandboxResourceLimits to JSON");
+        linux_cmd.extend(["--resource-limits".to_string(), limits_json]);
+    }
+
+    if let Some(isolation) = mount_isolation {
+        linux_cmd.push("--private-mounts".to_string());
+        if let Some(overlay) = &isolation.workspace_overlay {
+            linux_cmd.extend([
+                "--workspace-overlay".to_string(),
+                overlay.to_string_lossy().to_string(),
+            ]);
+        }
+    }
+
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
+
     // Append the original tool command.
     linux_cmd.extend(command);
 
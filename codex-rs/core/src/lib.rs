@@ -20,6 +20,7 @@ mod command_safety;
 pub mod config;
 pub mod config_loader;
 mod context_manager;
+mod cost;
 pub mod custom_prompts;
 pub mod env;
 mod environment_context;
@@ -70,7 +71,9 @@ pub use conversation_manager::NewConversation;
 pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
+mod network_proxy;
 pub mod project_doc;
+mod redaction;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
@@ -81,6 +84,8 @@ pub mod spawn;
 pub mod terminal;
 mod tools;
 pub mod turn_diff_tracker;
+#[cfg(target_os = "linux")]
+mod workspace_overlay;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
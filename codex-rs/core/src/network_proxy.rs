@@ -0,0 +1,648 @@
+//! Egress proxy for sandboxed commands.
+//!
+//! When `[network_proxy]` lists allowed domains, each session runs a small
+//! HTTP(S)/SOCKS5 proxy. Sandboxed commands get the standard proxy
+//! environment variables pointing at `127.0.0.1:<port>`, and the platform
+//! sandbox only lets them reach the proxy:
+//!
+//! - On Linux the proxy listens only on a Unix socket in a private (0700)
+//!   directory under `CODEX_HOME`. The helper moves the command into a
+//!   private network namespace whose loopback listener on the proxy port is
+//!   bridged to that socket, so nothing on the host can reach the proxy over
+//!   TCP.
+//! - On macOS the proxy listens on host loopback and the Seatbelt policy
+//!   allows outbound connections to the proxy port only.
+//!
+//! The proxy resolves and connects on behalf of the command, so only hosts on
+//! the allow-list are reachable. Denied hosts are logged.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+#[cfg(not(target_os = "linux"))]
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+use tracing::warn;
+
+use crate::config::types::NetworkProxyConfig;
+
+/// Upper bound on the size of an HTTP request head read from a client.
+const MAX_REQUEST_HEAD_BYTES: usize = 64 * 1024;
+
+const SOCKS5_VERSION: u8 = 0x05;
+const SOCKS5_NO_AUTH: u8 = 0x00;
+const SOCKS5_CMD_CONNECT: u8 = 0x01;
+const SOCKS5_ATYP_IPV4: u8 = 0x01;
+const SOCKS5_ATYP_DOMAIN: u8 = 0x03;
+const SOCKS5_ATYP_IPV6: u8 = 0x04;
+const SOCKS5_REPLY_SUCCEEDED: u8 = 0x00;
+const SOCKS5_REPLY_NOT_ALLOWED: u8 = 0x02;
+const SOCKS5_REPLY_HOST_UNREACHABLE: u8 = 0x04;
+const SOCKS5_REPLY_COMMAND_NOT_SUPPORTED: u8 = 0x07;
+const SOCKS5_REPLY_ADDRESS_NOT_SUPPORTED: u8 = 0x08;
+
+/// Returns whether `pattern` is a valid `network_proxy.allowed_domains` entry:
+/// a host name, an IP address, or `*.` followed by a host name.
+pub(crate) fn is_valid_domain_pattern(pattern: &str) -> bool {
+    let host = pattern.strip_prefix("*.").unwrap_or(pattern);
+    !host.is_empty()
+        && !host.contains('*')
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | ':'))
+}
+
+/// Hosts sandboxed commands may connect to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DomainAllowList {
+    patterns: Vec<String>,
+}
+
+impl DomainAllowList {
+    pub(crate) fn new(patterns: &[String]) -> Self {
+        Self {
+            patterns: patterns
+                .iter()
+                .map(|pattern| normalize_host(pattern))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn allows(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        self.patterns
+            .iter()
+            .any(|pattern| match pattern.strip_prefix("*.") {
+                Some(suffix) => host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.')),
+                None => host == *pattern,
+            })
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+}
+
+/// Port the proxy is reached on from inside the Linux sandbox's private
+/// network namespace, where nothing else is listening.
+#[cfg(target_os = "linux")]
+const NAMESPACE_PROXY_PORT: u16 = 3128;
+
+/// A running egress proxy. Stops accepting connections when dropped.
+#[derive(Debug)]
+pub(crate) struct NetworkProxy {
+    port: u16,
+    socket_path: Option<PathBuf>,
+    /// Private directory holding `socket_path`; removed on drop.
+    #[cfg(target_os = "linux")]
+    _socket_dir: tempfile::TempDir,
+    shutdown: CancellationToken,
+}
+
+impl NetworkProxy {
+    pub(crate) async fn start(config: &NetworkProxyConfig, codex_home: &Path) -> io::Result<Self> {
+        let allow_list = Arc::new(DomainAllowList::new(&config.allowed_domains));
+        let shutdown = CancellationToken::new();
+
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            // Only this user may reach the socket, so the directory is made
+            // private before the socket is bound inside it.
+            let parent = codex_home.join("tmp");
+            std::fs::create_dir_all(&parent)?;
+            let socket_dir = tempfile::Builder::new()
+                .prefix("network-proxy-")
+                .tempdir_in(&parent)?;
+            std::fs::set_permissions(socket_dir.path(), std::fs::Permissions::from_mode(0o700))?;
+            let path = socket_dir.path().join("proxy.sock");
+            let listener = tokio::net::UnixListener::bind(&path)?;
+            tokio::spawn(accept_unix(listener, allow_list, shutdown.clone()));
+            Ok(Self {
+                port: NAMESPACE_PROXY_PORT,
+                socket_path: Some(path),
+                _socket_dir: socket_dir,
+                shutdown,
+            })
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = codex_home;
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+            let port = listener.local_addr()?.port();
+            tokio::spawn(accept_tcp(listener, allow_list, shutdown.clone()));
+            Ok(Self {
+                port,
+                socket_path: None,
+                shutdown,
+            })
+        }
+    }
+
+    /// Loopback port commands reach the proxy on: the host port on macOS, or
+    /// the port bridged to the proxy socket inside the Linux sandbox's network
+    /// namespace.
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Unix socket the Linux sandbox helper bridges connections to.
+    pub(crate) fn socket_path(&self) -> Option<&Path> {
+        self.socket_path.as_deref()
+    }
+
+    /// Environment variables that point common tools at the proxy.
+    pub(crate) fn env(&self) -> HashMap<String, String> {
+        let http = format!("http://127.0.0.1:{}", self.port);
+        let socks = format!("socks5h://127.0.0.1:{}", self.port);
+        let no_proxy = "localhost,127.0.0.1,::1".to_string();
+        [
+            ("HTTP_PROXY", http.clone()),
+            ("HTTPS_PROXY", http.clone()),
+            ("ALL_PROXY", socks.clone()),
+            ("NO_PROXY", no_proxy.clone()),
+            ("http_proxy", http.clone()),
+            ("https_proxy", http),
+            ("all_proxy", socks),
+            ("no_proxy", no_proxy),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect()
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn accept_tcp(
+    listener: TcpListener,
+    allow_list: Arc<DomainAllowList>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let accepted = tokio::select! {
+            _ = shutdown.cancelled() => return,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((stream, _)) => spawn_client(stream, &allow_list),
+            Err(err) => warn!("network proxy failed to accept connection: {err}"),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn accept_unix(
+    listener: tokio::net::UnixListener,
+    allow_list: Arc<DomainAllowList>,
+    shutdown: CancellationToken,
+) {
+    loop {
+        let accepted = tokio::select! {
+            _ = shutdown.cancelled() => return,
+            accepted = listener.accept() => accepted,
+        };
+        match accepted {
+            Ok((stream, _)) => spawn_client(stream, &allow_list),
+            Err(err) => warn!("network proxy failed to accept connection: {err}"),
+        }
+    }
+}
+
+fn spawn_client<S>(stream: S, allow_list: &Arc<DomainAllowList>)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let allow_list = Arc::clone(allow_list);
+    tokio::spawn(async move {
+        if let Err(err) = handle_client(stream, &allow_list).await {
+            debug!("network proxy connection ended with error: {err}");
+        }
+    });
+}
+
+async fn handle_client<S>(mut client: S, allow_list: &DomainAllowList) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let first = client.read_u8().await?;
+    if first == SOCKS5_VERSION {
+        handle_socks5(client, allow_list).await
+    } else {
+        handle_http(client, first, allow_list).await
+    }
+}
+
+fn log_denied(host: &str, port: u16) {
+    warn!(
+        "network proxy denied connection to {host}:{port} (not in network_proxy.allowed_domains)"
+    );
+}
+
+async fn handle_http<S>(mut client: S, first: u8, allow_list: &DomainAllowList) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = vec![first];
+    let head_len = loop {
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return write_http_error(&mut client, "431 Request Header Fields Too Large", "").await;
+        }
+        let mut chunk = [0u8; 4096];
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    };
+    let leftover = buf.split_off(head_len);
+    let head = String::from_utf8_lossy(&buf).into_owned();
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return write_http_error(&mut client, "400 Bad Request", "").await;
+    };
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let Some((host, port)) = split_host_port(target, None) else {
+            return write_http_error(&mut client, "400 Bad Request", "").await;
+        };
+        if !allow_list.allows(&host) {
+            log_denied(&host, port);
+            return write_http_error(&mut client, "403 Forbidden", &denied_message(&host)).await;
+        }
+        let Ok(mut upstream) = TcpStream::connect((host.as_str(), port)).await else {
+            return write_http_error(&mut client, "502 Bad Gateway", "").await;
+        };
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+        upstream.write_all(&leftover).await?;
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+        return Ok(());
+    }
+
+    // Plain HTTP requests arrive in absolute form (`GET http://host/path`).
+    let Some(rest) = target.strip_prefix("http://") else {
+        return write_http_error(&mut client, "400 Bad Request", "").await;
+    };
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let Some((host, port)) = split_host_port(authority, Some(80)) else {
+        return write_http_error(&mut client, "400 Bad Request", "").await;
+    };
+    if !allow_list.allows(&host) {
+        log_denied(&host, port);
+        return write_http_error(&mut client, "403 Forbidden", &denied_message(&host)).await;
+    }
+    let Ok(mut upstream) = TcpStream::connect((host.as_str(), port)).await else {
+        return write_http_error(&mut client, "502 Bad Gateway", "").await;
+    };
+
+    // Forward a single request per connection so a client cannot reuse the
+    // upstream connection for a different host.
+    let mut forwarded = format!("{method} {path} {version}\r\n");
+    for header in lines.filter(|line| !line.is_empty()) {
+        let name = header.split(':').next().unwrap_or_default().trim();
+        if name.eq_ignore_ascii_case("connection")
+            || name.to_ascii_lowercase().starts_with("proxy-")
+        {
+            continue;
+        }
+        forwarded.push_str(header);
+        forwarded.push_str("\r\n");
+    }
+    forwarded.push_str("Connection: close\r\n\r\n");
+    upstream.write_all(forwarded.as_bytes()).await?;
+    upstream.write_all(&leftover).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+fn denied_message(host: &str) -> String {
+    format!("codex network proxy: {host} is not in network_proxy.allowed_domains\n")
+}
+
+async fn write_http_error<S>(client: &mut S, status: &str, body: &str) -> io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+/// Splits `host:port` (or `[v6]:port`), falling back to `default_port`.
+fn split_host_port(authority: &str, default_port: Option<u16>) -> Option<(String, u16)> {
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        let port = match rest.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None if rest.is_empty() => default_port?,
+            None => return None,
+        };
+        return Some((host.to_string(), port));
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() => Some((host.to_string(), port.parse().ok()?)),
+        Some(_) => None,
+        None if !authority.is_empty() => Some((authority.to_string(), default_port?)),
+        None => None,
+    }
+}
+
+async fn handle_socks5<S>(mut client: S, allow_list: &DomainAllowList) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let method_count = client.read_u8().await?;
+    let mut methods = vec![0u8; usize::from(method_count)];
+    client.read_exact(&mut methods).await?;
+    client.write_all(&[SOCKS5_VERSION, SOCKS5_NO_AUTH]).await?;
+
+    let mut request = [0u8; 4];
+    client.read_exact(&mut request).await?;
+    let [_, command, _, address_type] = request;
+    let host = match address_type {
+        SOCKS5_ATYP_IPV4 => {
+            let mut octets = [0u8; 4];
+            client.read_exact(&mut octets).await?;
+            Ipv4Addr::from(octets).to_string()
+        }
+        SOCKS5_ATYP_DOMAIN => {
+            let len = client.read_u8().await?;
+            let mut name = vec![0u8; usize::from(len)];
+            client.read_exact(&mut name).await?;
+            String::from_utf8_lossy(&name).into_owned()
+        }
+        SOCKS5_ATYP_IPV6 => {
+            let mut octets = [0u8; 16];
+            client.read_exact(&mut octets).await?;
+            Ipv6Addr::from(octets).to_string()
+        }
+        _ => return write_socks5_reply(&mut client, SOCKS5_REPLY_ADDRESS_NOT_SUPPORTED).await,
+    };
+    let port = client.read_u16().await?;
+
+    if command != SOCKS5_CMD_CONNECT {
+        return write_socks5_reply(&mut client, SOCKS5_REPLY_COMMAND_NOT_SUPPORTED).await;
+    }
+    if !allow_list.allows(&host) {
+        log_denied(&host, port);
+        return write_socks5_reply(&mut client, SOCKS5_REPLY_NOT_ALLOWED).await;
+    }
+    let Ok(mut upstream) = TcpStream::connect((host.as_str(), port)).await else {
+        return write_socks5_reply(&mut client, SOCKS5_REPLY_HOST_UNREACHABLE).await;
+    };
+    write_socks5_reply(&mut client, SOCKS5_REPLY_SUCCEEDED).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+async fn write_socks5_reply<S>(client: &mut S, reply: u8) -> io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    // The bound address is not meaningful to clients; report 0.0.0.0:0.
+    client
+        .write_all(&[
+            SOCKS5_VERSION,
+            reply,
+            0x00,
+            SOCKS5_ATYP_IPV4,
+            0,
+            0,
+            0,
+            0,
+            0,
+            0,
+        ])
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::net::TcpListener;
+
+    #[cfg(target_os = "linux")]
+    type ProxyStream = tokio::net::UnixStream;
+    #[cfg(not(target_os = "linux"))]
+    type ProxyStream = TcpStream;
+
+    async fn connect(proxy: &NetworkProxy) -> ProxyStream {
+        #[cfg(target_os = "linux")]
+        let stream = ProxyStream::connect(proxy.socket_path().expect("proxy socket")).await;
+        #[cfg(not(target_os = "linux"))]
+        let stream = ProxyStream::connect((Ipv4Addr::LOCALHOST, proxy.port())).await;
+        stream.expect("connect to proxy")
+    }
+
+    fn proxy_config(domains: &[&str]) -> NetworkProxyConfig {
+        NetworkProxyConfig {
+            allowed_domains: domains.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Echo server standing in for an allowed upstream host.
+    async fn start_upstream() -> u16 {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("bind upstream");
+        let port = listener.local_addr().expect("upstream addr").port();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = stream.split();
+                    let _ = tokio::io::copy(&mut reader, &mut writer).await;
+                });
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn allow_list_matches_exact_hosts_and_wildcard_subdomains() {
+        let allow_list = DomainAllowList::new(&[
+            "crates.io".to_string(),
+            "*.NPMJS.org".to_string(),
+            "127.0.0.1".to_string(),
+        ]);
+
+        assert!(allow_list.allows("crates.io"));
+        assert!(allow_list.allows("Crates.IO."));
+        assert!(!allow_list.allows("static.crates.io"));
+        assert!(allow_list.allows("registry.npmjs.org"));
+        assert!(!allow_list.allows("npmjs.org"));
+        assert!(!allow_list.allows("evilnpmjs.org"));
+        assert!(allow_list.allows("127.0.0.1"));
+        assert!(!allow_list.allows("example.com"));
+    }
+
+    #[test]
+    fn domain_patterns_are_validated() {
+        assert!(is_valid_domain_pattern("registry.npmjs.org"));
+        assert!(is_valid_domain_pattern("*.crates.io"));
+        assert!(is_valid_domain_pattern("::1"));
+        assert!(!is_valid_domain_pattern(""));
+        assert!(!is_valid_domain_pattern("*"));
+        assert!(!is_valid_domain_pattern("crates.*"));
+        assert!(!is_valid_domain_pattern("https://crates.io"));
+    }
+
+    #[test]
+    fn split_host_port_handles_defaults_and_ipv6() {
+        assert_eq!(
+            split_host_port("crates.io:443", None),
+            Some(("crates.io".to_string(), 443))
+        );
+        assert_eq!(
+            split_host_port("crates.io", Some(80)),
+            Some(("crates.io".to_string(), 80))
+        );
+        assert_eq!(split_host_port("crates.io", None), None);
+        assert_eq!(
+            split_host_port("[::1]:8080", None),
+            Some(("::1".to_string(), 8080))
+        );
+    }
+
+    #[tokio::test]
+    async fn http_connect_tunnels_allowed_hosts_and_rejects_others() {
+        let upstream_port = start_upstream().await;
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let proxy = NetworkProxy::start(&proxy_config(&["localhost"]), codex_home.path())
+            .await
+            .expect("start proxy");
+
+        let mut allowed = connect(&proxy).await;
+        allowed
+            .write_all(format!("CONNECT localhost:{upstream_port} HTTP/1.1\r\n\r\n").as_bytes())
+            .await
+            .expect("send CONNECT");
+        let established = b"HTTP/1.1 200 Connection Established\r\n\r\n";
+        let mut response = vec![0u8; established.len()];
+        allowed
+            .read_exact(&mut response)
+            .await
+            .expect("read response");
+        assert_eq!(response, established);
+        allowed.write_all(b"ping").await.expect("write tunnel");
+        let mut echoed = [0u8; 4];
+        allowed.read_exact(&mut echoed).await.expect("read tunnel");
+        assert_eq!(&echoed, b"ping");
+
+        let mut denied = connect(&proxy).await;
+        denied
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\n\r\n")
+            .await
+            .expect("send CONNECT");
+        let mut response = String::new();
+        denied
+            .read_to_string(&mut response)
+            .await
+            .expect("read response");
+        assert!(response.starts_with("HTTP/1.1 403 Forbidden"), "{response}");
+        assert!(response.contains("example.com is not in network_proxy.allowed_domains"));
+    }
+
+    #[tokio::test]
+    async fn socks5_connect_enforces_allow_list() {
+        let upstream_port = start_upstream().await;
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let proxy = NetworkProxy::start(&proxy_config(&["localhost"]), codex_home.path())
+            .await
+            .expect("start proxy");
+
+        async fn socks_connect(proxy: &NetworkProxy, host: &str, port: u16) -> (ProxyStream, u8) {
+            let mut stream = connect(proxy).await;
+            stream
+                .write_all(&[SOCKS5_VERSION, 1, SOCKS5_NO_AUTH])
+                .await
+                .expect("greeting");
+            let mut choice = [0u8; 2];
+            stream.read_exact(&mut choice).await.expect("method");
+            assert_eq!(choice, [SOCKS5_VERSION, SOCKS5_NO_AUTH]);
+            let mut request = vec![
+                SOCKS5_VERSION,
+                SOCKS5_CMD_CONNECT,
+                0,
+                SOCKS5_ATYP_DOMAIN,
+                host.len() as u8,
+            ];
+            request.extend_from_slice(host.as_bytes());
+            request.extend_from_slice(&port.to_be_bytes());
+            stream.write_all(&request).await.expect("request");
+            let mut reply = [0u8; 10];
+            stream.read_exact(&mut reply).await.expect("reply");
+            (stream, reply[1])
+        }
+
+        let (mut stream, reply) = socks_connect(&proxy, "localhost", upstream_port).await;
+        assert_eq!(reply, SOCKS5_REPLY_SUCCEEDED);
+        stream.write_all(b"pong").await.expect("write tunnel");
+        let mut echoed = [0u8; 4];
+        stream.read_exact(&mut echoed).await.expect("read tunnel");
+        assert_eq!(&echoed, b"pong");
+
+        let (_, reply) = socks_connect(&proxy, "example.com", 443).await;
+        assert_eq!(reply, SOCKS5_REPLY_NOT_ALLOWED);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn proxy_socket_lives_in_a_private_directory_under_codex_home() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let codex_home = tempfile::tempdir().expect("codex home");
+        let proxy = NetworkProxy::start(&proxy_config(&["localhost"]), codex_home.path())
+            .await
+            .expect("start proxy");
+
+        let socket_path = proxy.socket_path().expect("proxy socket").to_path_buf();
+        let socket_dir = socket_path.parent().expect("socket dir");
+        assert!(socket_dir.starts_with(codex_home.path()));
+        let mode = std::fs::metadata(socket_dir)
+            .expect("socket dir metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        drop(proxy);
+        assert!(!socket_dir.exists());
+    }
+}
@@ -1,3 +1,8 @@
+use std::path::Path;
+
+use serde_json::Value;
+use serde_json::json;
+
 use crate::protocol::ReviewFinding;
 use crate::protocol::ReviewOutputEvent;
 
@@ -80,3 +85,239 @@ pub fn render_review_output_text(output: &ReviewOutputEvent) -> String {
         sections.join("\n\n")
     }
 }
+
+/// Severity label for a finding's priority (0 = P0 … 3 = P3).
+fn severity(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=0 => "critical",
+        1 => "high",
+        2 => "medium",
+        _ => "low",
+    }
+}
+
+fn sarif_level(priority: i32) -> &'static str {
+    match priority {
+        i32::MIN..=1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+/// Path of the finding relative to `root` with `/` separators, or the
+/// absolute path when the file lies outside `root`.
+fn relative_path(item: &ReviewFinding, root: &Path) -> String {
+    let path = &item.code_location.absolute_file_path;
+    match path.strip_prefix(root) {
+        Ok(relative) => relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+        Err(_) => path.to_string_lossy().replace('\\', "/"),
+    }
+}
+
+/// Returns the contents of the first ```suggestion block in `body`, which the
+/// review prompt reserves for replacement code.
+pub fn extract_suggestion(body: &str) -> Option<String> {
+    let mut lines = body.lines();
+    lines.find(|line| line.trim_start().starts_with("```suggestion"))?;
+    let mut suggestion = Vec::new();
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            return Some(suggestion.join("\n"));
+        }
+        suggestion.push(line);
+    }
+    None
+}
+
+/// Render review findings as a SARIF 2.1.0 log. File paths are made relative
+/// to `root`, which is reported as the `%SRCROOT%` base. Findings that carry a
+/// ```suggestion block also get a SARIF fix replacing their line range.
+pub fn render_review_sarif(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|item| {
+            let uri = relative_path(item, root);
+            let range = &item.code_location.line_range;
+            let region = json!({ "startLine": range.start, "endLine": range.end });
+            let mut result = json!({
+                "ruleId": "codex-review",
+                "level": sarif_level(item.priority),
+                "message": { "text": format!("{}\n\n{}", item.title, item.body) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+                        "region": region,
+                    }
+                }],
+                "properties": {
+                    "title": item.title,
+                    "priority": item.priority,
+                    "severity": severity(item.priority),
+                    "confidence": item.confidence_score,
+                },
+            });
+            if let Some(suggestion) = extract_suggestion(&item.body) {
+                result["fixes"] = json!([{
+                    "description": { "text": "Apply the suggested change" },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+                        "replacements": [{
+                            "deletedRegion": region,
+                            "insertedContent": { "text": format!("{suggestion}\n") },
+                        }],
+                    }],
+                }]);
+            }
+            result
+        })
+        .collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "codex",
+                    "informationUri": "https://github.com/openai/codex",
+                    "rules": [{
+                        "id": "codex-review",
+                        "shortDescription": { "text": "Issue found by Codex code review" },
+                    }],
+                }
+            },
+            "originalUriBaseIds": {
+                "%SRCROOT%": { "uri": directory_uri(root) },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidence": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+fn directory_uri(root: &Path) -> String {
+    let path = root.to_string_lossy().replace('\\', "/");
+    let path = path.trim_end_matches('/');
+    if path.starts_with('/') {
+        format!("file://{path}/")
+    } else {
+        format!("file:///{path}/")
+    }
+}
+
+/// Render review findings as inline review comments that map directly onto the
+/// GitHub and GitLab review APIs: one entry per finding with a path relative to
+/// `root`, an inclusive line range and a Markdown body ready to post.
+pub fn render_review_comments(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let comments: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|item| {
+            let range = &item.code_location.line_range;
+            json!({
+                "path": relative_path(item, root),
+                "start_line": range.start,
+                "end_line": range.end,
+                "severity": severity(item.priority),
+                "priority": item.priority,
+                "confidence": item.confidence_score,
+                "title": item.title,
+                "body": format!("**{}**\n\n{}", item.title, item.body),
+                "suggestion": extract_suggestion(&item.body),
+            })
+        })
+        .collect();
+
+    json!({
+        "overall_correctness": output.overall_correctness,
+        "overall_explanation": output.overall_explanation,
+        "overall_confidence_score": output.overall_confidence_score,
+        "comments": comments,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn review_output(root: &Path) -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![ReviewFinding {
+                title: "[P1] Check the index before slicing".to_string(),
+                body: "Slicing panics when `idx` is past the end.\n\n```suggestion\n    let tail = buf.get(idx..)?;\n```".to_string(),
+                confidence_score: 0.8,
+                priority: 1,
+                code_location: ReviewCodeLocation {
+                    absolute_file_path: root.join("src").join("lib.rs"),
+                    line_range: ReviewLineRange { start: 10, end: 11 },
+                },
+            }],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "Slicing can panic.".to_string(),
+            overall_confidence_score: 0.7,
+        }
+    }
+
+    #[test]
+    fn extracts_first_suggestion_block() {
+        assert_eq!(
+            extract_suggestion("text\n```suggestion\na\n  b\n```\n```suggestion\nc\n```"),
+            Some("a\n  b".to_string())
+        );
+        assert_eq!(extract_suggestion("no fences here"), None);
+        assert_eq!(extract_suggestion("```suggestion\nunterminated"), None);
+    }
+
+    #[test]
+    fn sarif_reports_relative_locations_and_fixes() {
+        let root = PathBuf::from("/repo");
+        let sarif = render_review_sarif(&review_output(&root), &root);
+
+        assert_eq!(sarif["version"], "2.1.0");
+        let run = &sarif["runs"][0];
+        assert_eq!(
+            run["originalUriBaseIds"]["%SRCROOT%"]["uri"],
+            "file:///repo/"
+        );
+        let result = &run["results"][0];
+        assert_eq!(result["level"], "error");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "%SRCROOT%" },
+                "region": { "startLine": 10, "endLine": 11 },
+            })
+        );
+        assert_eq!(
+            result["fixes"][0]["artifactChanges"][0]["replacements"][0]["insertedContent"]["text"],
+            "    let tail = buf.get(idx..)?;\n"
+        );
+    }
+
+    #[test]
+    fn comments_carry_range_severity_and_suggestion() {
+        let root = PathBuf::from("/repo");
+        let comments = render_review_comments(&review_output(&root), &root);
+
+        assert_eq!(comments["overall_correctness"], "patch is incorrect");
+        let comment = &comments["comments"][0];
+        assert_eq!(comment["path"], "src/lib.rs");
+        assert_eq!(comment["start_line"], 10);
+        assert_eq!(comment["end_line"], 11);
+        assert_eq!(comment["severity"], "high");
+        assert_eq!(comment["suggestion"], "    let tail = buf.get(idx..)?;");
+    }
+}
@@ -1,13 +1,16 @@
+use anyhow::Context;
 use codex_git::merge_base_with_head;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use std::path::Path;
+use std::path::PathBuf;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
     pub prompt: String,
     pub user_facing_hint: String,
+    pub test_output: Option<String>,
 }
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
@@ -19,20 +22,39 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes in the commit range {range}. Run `git log {range}` to list the commits and `git diff {range}` to inspect the combined changes. Provide prioritized, actionable findings.";
+
+const PATCH_FILE_PROMPT: &str = "Review the code changes in the patch below, taken from {path}. The patch has not been applied, so the files in the working tree still show the code before the change. Provide prioritized, actionable findings.";
+
+const PATHS_PROMPT: &str = "Review the current code in the following files and directories rather than a diff: {paths}. Provide prioritized, actionable findings.";
+
+const TEST_OUTPUT_PROMPT: &str =
+    "The following output from a failing test run may help locate problems in these changes:";
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
 ) -> anyhow::Result<ResolvedReviewRequest> {
     let target = request.target;
-    let prompt = review_prompt(&target, cwd)?;
+    let mut prompt = review_prompt(&target, cwd)?;
     let user_facing_hint = request
         .user_facing_hint
         .unwrap_or_else(|| user_facing_hint(&target));
+    let test_output = request
+        .test_output
+        .filter(|output| !output.trim().is_empty());
+    if let Some(output) = &test_output {
+        prompt = format!(
+            "{prompt}\n\n{TEST_OUTPUT_PROMPT}\n\n```\n{}\n```",
+            output.trim_end()
+        );
+    }
 
     Ok(ResolvedReviewRequest {
         target,
         prompt,
         user_facing_hint,
+        test_output,
     })
 }
 
@@ -64,9 +86,39 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
             }
             Ok(prompt.to_string())
         }
+        ReviewTarget::CommitRange { range } => {
+            if !range.contains("..") {
+                anyhow::bail!("Commit range must look like A..B or A...B, got '{range}'");
+            }
+            Ok(COMMIT_RANGE_PROMPT.replace("{range}", range))
+        }
+        ReviewTarget::PatchFile { path } => {
+            let full_path = cwd.join(path);
+            let patch = std::fs::read_to_string(&full_path)
+                .with_context(|| format!("failed to read patch file {}", full_path.display()))?;
+            if patch.trim().is_empty() {
+                anyhow::bail!("Patch file {} is empty", full_path.display());
+            }
+            let prompt = PATCH_FILE_PROMPT.replace("{path}", &path.display().to_string());
+            Ok(format!("{prompt}\n\n```diff\n{}\n```", patch.trim_end()))
+        }
+        ReviewTarget::Paths { paths } => {
+            if paths.is_empty() {
+                anyhow::bail!("Specify at least one path to review");
+            }
+            Ok(PATHS_PROMPT.replace("{paths}", &join_paths(paths)))
+        }
     }
 }
 
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
     match target {
         ReviewTarget::UncommittedChanges => "current changes".to_string(),
@@ -80,6 +132,12 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
             }
         }
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
+        ReviewTarget::CommitRange { range } => format!("commits {range}"),
+        ReviewTarget::PatchFile { path } => {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            format!("patch {}", name.to_string_lossy())
+        }
+        ReviewTarget::Paths { paths } => join_paths(paths),
     }
 }
 
@@ -88,6 +146,78 @@ impl From<ResolvedReviewRequest> for ReviewRequest {
         ReviewRequest {
             target: resolved.target,
             user_facing_hint: Some(resolved.user_facing_hint),
+            test_output: resolved.test_output,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn patch_file_prompt_embeds_patch_and_test_output() {
+        let dir = TempDir::new().expect("tempdir");
+        let patch = "--- a/lib.rs\n+++ b/lib.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        std::fs::write(dir.path().join("change.patch"), patch).expect("write patch");
+
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: ReviewTarget::PatchFile {
+                    path: PathBuf::from("change.patch"),
+                },
+                user_facing_hint: None,
+                test_output: Some("test lib::works ... FAILED\n".to_string()),
+            },
+            dir.path(),
+        )
+        .expect("resolve patch review");
+
+        assert_eq!(resolved.user_facing_hint, "patch change.patch");
+        assert_eq!(
+            resolved.prompt,
+            format!(
+                "{}\n\n```diff\n{}\n```\n\n{TEST_OUTPUT_PROMPT}\n\n```\ntest lib::works ... FAILED\n```",
+                PATCH_FILE_PROMPT.replace("{path}", "change.patch"),
+                patch.trim_end()
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_ranges_and_missing_patches() {
+        let dir = TempDir::new().expect("tempdir");
+
+        assert!(
+            review_prompt(
+                &ReviewTarget::CommitRange {
+                    range: "abc123".to_string()
+                },
+                dir.path()
+            )
+            .is_err()
+        );
+        assert!(
+            review_prompt(
+                &ReviewTarget::PatchFile {
+                    path: PathBuf::from("missing.patch")
+                },
+                dir.path()
+            )
+            .is_err()
+        );
+        assert!(review_prompt(&ReviewTarget::Paths { paths: Vec::new() }, dir.path()).is_err());
+        assert_eq!(
+            review_prompt(
+                &ReviewTarget::CommitRange {
+                    range: "main..feature".to_string()
+                },
+                dir.path()
+            )
+            .expect("range prompt"),
+            COMMIT_RANGE_PROMPT.replace("{range}", "main..feature")
+        );
+    }
+}
@@ -237,6 +237,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -258,6 +260,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            readable_roots: vec![],
+            deny_read: vec![],
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
@@ -0,0 +1,514 @@
+/*
+Module: sandboxing::denials
+
+Explains why a sandboxed command most likely failed. On Linux,
+`codex-linux-sandbox` reports the network calls its seccomp filter refused;
+those are certain. Landlock does not say what it refused, and Seatbelt is not
+asked, so the command's own error output is also matched against the usual
+EPERM/EACCES/EROFS and resolver messages and checked against the policy. Both
+are turned into [`SandboxDenial`]s and the amendments that would have let the
+command through.
+*/
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::exec::ExecToolCallOutput;
+use crate::protocol::ExecPolicyAmendment;
+use crate::protocol::SandboxAmendment;
+use crate::protocol::SandboxDenial;
+use crate::protocol::SandboxDenialKind;
+use crate::protocol::SandboxPolicy;
+
+const FILE_SYSTEM_ERRORS: [&str; 3] = [
+    "permission denied",
+    "read-only file system",
+    "operation not permitted",
+];
+
+const NETWORK_ERRORS: [&str; 9] = [
+    "could not resolve host",
+    "temporary failure in name resolution",
+    "name or service not known",
+    "nodename nor servname provided",
+    "network is unreachable",
+    "failed to establish a new connection",
+    "getaddrinfo",
+    "socket: operation not permitted",
+    "socket: permission denied",
+];
+
+/// Folders that are never suggested as writable roots, nor is anything
+/// inside them.
+const SYSTEM_DIRS: [&str; 17] = [
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/libx32",
+    "/proc",
+    "/run",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/Applications",
+    "/Library",
+    "/System",
+    "/private/etc",
+];
+
+/// Longest output line kept as evidence.
+const MAX_EVIDENCE_CHARS: usize = 200;
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct SandboxDiagnosis {
+    pub(crate) denials: Vec<SandboxDenial>,
+    pub(crate) suggested_amendments: Vec<SandboxAmendment>,
+}
+
+impl SandboxDiagnosis {
+    /// One-line summary for the model, e.g. `access to /srv/cache; network access`.
+    pub(crate) fn summary(&self) -> String {
+        self.denials
+            .iter()
+            .map(|denial| match (denial.kind, &denial.target) {
+                (SandboxDenialKind::FileSystem, Some(target)) => format!("access to {target}"),
+                (SandboxDenialKind::FileSystem, None) => "file system access".to_string(),
+                (SandboxDenialKind::Network, Some(target)) => {
+                    format!("network access to {target}")
+                }
+                (SandboxDenialKind::Network, None) => "network access".to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Recognizes the sandbox restrictions a failed command ran into. Relative
+/// paths in its output are resolved against `command_cwd`;
+/// `allow_command_amendments` controls whether execpolicy rules may be
+/// suggested (they need the `exec_policy` feature). Folders that would open
+/// up `codex_home` are not suggested as writable roots.
+pub(crate) fn diagnose_sandbox_denial(
+    output: &ExecToolCallOutput,
+    policy: &SandboxPolicy,
+    policy_cwd: &Path,
+    command: &[String],
+    command_cwd: &Path,
+    codex_home: &Path,
+    allow_command_amendments: bool,
+) -> SandboxDiagnosis {
+    let text = if output.stderr.text.trim().is_empty() {
+        &output.aggregated_output.text
+    } else {
+        &output.stderr.text
+    };
+
+    let writable_roots = policy.get_writable_roots_with_cwd(policy_cwd);
+    let mut diagnosis = SandboxDiagnosis::default();
+    for syscall in &output.blocked_syscalls {
+        record_denial(
+            &mut diagnosis,
+            SandboxDenial {
+                kind: SandboxDenialKind::Network,
+                target: None,
+                evidence: format!("seccomp refused {syscall}"),
+            },
+            command,
+            allow_command_amendments,
+        );
+    }
+    for line in text.lines() {
+        let lower = line.to_lowercase();
+        let denial = if NETWORK_ERRORS.iter().any(|needle| lower.contains(needle)) {
+            if policy.has_full_network_access() {
+                continue;
+            }
+            SandboxDenial {
+                kind: SandboxDenialKind::Network,
+                target: host_in_line(line),
+                evidence: evidence(line),
+            }
+        } else if FILE_SYSTEM_ERRORS
+            .iter()
+            .any(|needle| lower.contains(needle))
+        {
+            if policy.has_full_disk_write_access() {
+                continue;
+            }
+            let path = path_in_line(line).map(|path| resolve(&path, command_cwd));
+            if let Some(path) = &path {
+                if writable_roots
+                    .iter()
+                    .any(|root| root.is_path_writable(path))
+                {
+                    // Writable under the policy: the failure is not ours.
+                    continue;
+                }
+                // Inside a writable root but not writable: one of the paths,
+                // such as `.git`, that are kept read-only on purpose.
+                let protected = writable_roots
+                    .iter()
+                    .any(|root| path.starts_with(root.root.as_path()));
+                if !protected && matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
+                    let root = if path.is_dir() {
+                        path.clone()
+                    } else {
+                        path.parent()
+                            .map_or_else(|| path.clone(), Path::to_path_buf)
+                    };
+                    if is_suggestable_writable_root(&root, codex_home) {
+                        push_unique(
+                            &mut diagnosis.suggested_amendments,
+                            SandboxAmendment::AddWritableRoot { path: root },
+                        );
+                    }
+                }
+            }
+            SandboxDenial {
+                kind: SandboxDenialKind::FileSystem,
+                target: path.map(|path| path.display().to_string()),
+                evidence: evidence(line),
+            }
+        } else {
+            continue;
+        };
+        record_denial(&mut diagnosis, denial, command, allow_command_amendments);
+    }
+    diagnosis
+}
+
+/// Adds `denial` unless one for the same target is already listed, along with
+/// the execpolicy rule that would have let a network call through.
+fn record_denial(
+    diagnosis: &mut SandboxDiagnosis,
+    denial: SandboxDenial,
+    command: &[String],
+    allow_command_amendments: bool,
+) {
+    if denial.kind == SandboxDenialKind::Network
+        && allow_command_amendments
+        && let Some(prefix) = command_prefix(command)
+    {
+        push_unique(
+            &mut diagnosis.suggested_amendments,
+            SandboxAmendment::RunOutsideSandbox { prefix },
+        );
+    }
+    if !diagnosis
+        .denials
+        .iter()
+        .any(|seen| seen.kind == denial.kind && seen.target == denial.target)
+    {
+        diagnosis.denials.push(denial);
+    }
+}
+
+/// Whether `root` is narrow enough to offer as a writable root. Top-level
+/// folders such as `/` or `/var`, system folders, the home directory and
+/// the folders above it, and anything that would cover `codex_home` (the
+/// config that holds the sandbox settings) are left to the user to add by
+/// hand.
+fn is_suggestable_writable_root(root: &Path, codex_home: &Path) -> bool {
+    if root.components().count() < 3 {
+        return false;
+    }
+    if SYSTEM_DIRS.iter().any(|dir| root.starts_with(dir)) {
+        return false;
+    }
+    if dirs::home_dir().is_some_and(|home| home.starts_with(root)) {
+        return false;
+    }
+    !(codex_home.starts_with(root) || root.starts_with(codex_home))
+}
+
+fn push_unique(amendments: &mut Vec<SandboxAmendment>, amendment: SandboxAmendment) {
+    if !amendments.contains(&amendment) {
+        amendments.push(amendment);
+    }
+}
+
+fn evidence(line: &str) -> String {
+    line.trim().chars().take(MAX_EVIDENCE_CHARS).collect()
+}
+
+/// The command an execpolicy rule would match: the command itself, or the
+/// single command inside a `bash -lc` wrapper. Scripts with several commands
+/// get no suggestion.
+fn command_prefix(command: &[String]) -> Option<ExecPolicyAmendment> {
+    let command = match parse_shell_lc_plain_commands(command) {
+        Some(commands) if commands.len() == 1 => commands.into_iter().next()?,
+        Some(_) => return None,
+        None => command.to_vec(),
+    };
+    (!command.is_empty()).then(|| ExecPolicyAmendment::new(command))
+}
+
+/// The path an error line complains about: a quoted string (`touch: cannot
+/// touch 'x'`, `PermissionError: ... '/x'`), or else the first token that
+/// looks like a path (`bash: /x: Read-only file system`).
+fn path_in_line(line: &str) -> Option<String> {
+    for (open, close) in [('\'', '\''), ('"', '"'), ('‘', '’'), ('`', '\'')] {
+        if let Some(start) = line.find(open) {
+            let rest = &line[start + open.len_utf8()..];
+            if let Some(end) = rest.find(close) {
+                let quoted = &rest[..end];
+                if !quoted.is_empty() && !quoted.contains(char::is_whitespace) {
+                    return Some(quoted.to_string());
+                }
+            }
+        }
+    }
+    line.split_whitespace()
+        .map(|token| token.trim_matches(|c: char| matches!(c, ':' | ',' | '(' | ')' | '[' | ']')))
+        .find(|token| {
+            (token.starts_with('/') || token.starts_with("./") || token.starts_with("~/"))
+                && token.len() > 1
+        })
+        .map(str::to_string)
+}
+
+fn resolve(path: &str, cwd: &Path) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = dirs::home_dir()
+    {
+        return home.join(rest);
+    }
+    cwd.join(path)
+}
+
+/// Host name from messages such as `curl: (6) Could not resolve host: example.com`.
+fn host_in_line(line: &str) -> Option<String> {
+    let lower = line.to_lowercase();
+    let start = lower.find("resolve host:")? + "resolve host:".len();
+    line[start..]
+        .split_whitespace()
+        .next()
+        .map(|host| {
+            host.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string()
+        })
+        .filter(|host| !host.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::StreamOutput;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+
+    fn failed_output(stderr: &str) -> ExecToolCallOutput {
+        ExecToolCallOutput {
+            exit_code: 1,
+            stderr: StreamOutput::new(stderr.to_string()),
+            aggregated_output: StreamOutput::new(stderr.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn workspace_write(cwd: &Path) -> SandboxPolicy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![AbsolutePathBuf::try_from(cwd.join("extra")).unwrap()],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![],
+            deny_read: vec![],
+        }
+    }
+
+    #[test]
+    fn write_outside_writable_roots_suggests_adding_the_folder() {
+        let cwd = tempfile::tempdir().unwrap();
+        let output = failed_output(
+            "touch: cannot touch '/srv/cache/build.lock': Read-only file system\n\
+             bash: ./out.txt: Permission denied\n",
+        );
+
+        let diagnosis = diagnose_sandbox_denial(
+            &output,
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &["touch".to_string()],
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            true,
+        );
+
+        assert_eq!(
+            diagnosis,
+            SandboxDiagnosis {
+                denials: vec![SandboxDenial {
+                    kind: SandboxDenialKind::FileSystem,
+                    target: Some("/srv/cache/build.lock".to_string()),
+                    evidence: "touch: cannot touch '/srv/cache/build.lock': Read-only file system"
+                        .to_string(),
+                }],
+                suggested_amendments: vec![SandboxAmendment::AddWritableRoot {
+                    path: PathBuf::from("/srv/cache"),
+                }],
+            }
+        );
+        assert_eq!(diagnosis.summary(), "access to /srv/cache/build.lock");
+    }
+
+    #[test]
+    fn network_failure_suggests_allowing_the_command() {
+        let cwd = tempfile::tempdir().unwrap();
+        let output = failed_output("curl: (6) Could not resolve host: example.com\n");
+        let command = vec![
+            "bash".to_string(),
+            "-lc".to_string(),
+            "curl -sS https://example.com".to_string(),
+        ];
+
+        let diagnosis = diagnose_sandbox_denial(
+            &output,
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &command,
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            true,
+        );
+
+        assert_eq!(
+            diagnosis.denials,
+            vec![SandboxDenial {
+                kind: SandboxDenialKind::Network,
+                target: Some("example.com".to_string()),
+                evidence: "curl: (6) Could not resolve host: example.com".to_string(),
+            }]
+        );
+        assert_eq!(
+            diagnosis.suggested_amendments,
+            vec![SandboxAmendment::RunOutsideSandbox {
+                prefix: ExecPolicyAmendment::new(vec![
+                    "curl".to_string(),
+                    "-sS".to_string(),
+                    "https://example.com".to_string(),
+                ]),
+            }]
+        );
+
+        let without_execpolicy = diagnose_sandbox_denial(
+            &output,
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &command,
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            false,
+        );
+        assert_eq!(without_execpolicy.suggested_amendments, vec![]);
+    }
+
+    #[test]
+    fn refused_syscalls_reported_by_the_helper_are_network_denials() {
+        let cwd = tempfile::tempdir().unwrap();
+        let output = ExecToolCallOutput {
+            exit_code: 1,
+            blocked_syscalls: vec!["socket(AF_INET)".to_string(), "connect".to_string()],
+            ..Default::default()
+        };
+
+        let diagnosis = diagnose_sandbox_denial(
+            &output,
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &["python3".to_string(), "fetch.py".to_string()],
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            true,
+        );
+
+        assert_eq!(
+            diagnosis,
+            SandboxDiagnosis {
+                denials: vec![SandboxDenial {
+                    kind: SandboxDenialKind::Network,
+                    target: None,
+                    evidence: "seccomp refused socket(AF_INET)".to_string(),
+                }],
+                suggested_amendments: vec![SandboxAmendment::RunOutsideSandbox {
+                    prefix: ExecPolicyAmendment::new(vec![
+                        "python3".to_string(),
+                        "fetch.py".to_string(),
+                    ]),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn protected_subpaths_and_allowed_access_are_not_amended() {
+        let cwd = tempfile::tempdir().unwrap();
+        std::fs::create_dir(cwd.path().join(".git")).unwrap();
+        let output = failed_output(&format!(
+            "error: could not lock config file {}/.git/config: Permission denied\n",
+            cwd.path().display()
+        ));
+
+        let diagnosis = diagnose_sandbox_denial(
+            &output,
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &["git".to_string()],
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            true,
+        );
+        assert_eq!(diagnosis.denials.len(), 1);
+        assert_eq!(diagnosis.suggested_amendments, vec![]);
+
+        let diagnosis = diagnose_sandbox_denial(
+            &failed_output("curl: (6) Could not resolve host: example.com\n"),
+            &SandboxPolicy::DangerFullAccess,
+            cwd.path(),
+            &["curl".to_string()],
+            cwd.path(),
+            Path::new("/nonexistent/codex-home"),
+            true,
+        );
+        assert_eq!(diagnosis, SandboxDiagnosis::default());
+    }
+
+    #[test]
+    fn broad_and_sensitive_folders_are_not_suggested() {
+        let cwd = tempfile::tempdir().unwrap();
+        let codex_home = tempfile::tempdir().unwrap();
+        let mut stderr = format!(
+            "touch: cannot touch '/out.txt': Read-only file system\n\
+             touch: cannot touch '/var/out.txt': Read-only file system\n\
+             touch: cannot touch '/etc/ssl/certs/x.pem': Read-only file system\n\
+             touch: cannot touch '/usr/local/bin/tool': Read-only file system\n\
+             touch: cannot touch '{home}/config.toml': Read-only file system\n\
+             touch: cannot touch '{home}/sessions/x.jsonl': Read-only file system\n",
+            home = codex_home.path().display()
+        );
+        if let Some(home) = dirs::home_dir() {
+            stderr.push_str(&format!(
+                "touch: cannot touch '{}': Read-only file system\n",
+                home.join(".bashrc").display()
+            ));
+        }
+
+        let diagnosis = diagnose_sandbox_denial(
+            &failed_output(&stderr),
+            &workspace_write(cwd.path()),
+            cwd.path(),
+            &["touch".to_string()],
+            cwd.path(),
+            codex_home.path(),
+            true,
+        );
+
+        assert_eq!(diagnosis.denials.len(), stderr.lines().count());
+        assert_eq!(diagnosis.suggested_amendments, vec![]);
+    }
+}
@@ -6,13 +6,18 @@ sandbox placement and transformation of portable CommandSpec into a
 ready‑to‑spawn environment.
 */
 
+pub(crate) mod denials;
+
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
+use crate::landlock::MountIsolation;
 use crate::landlock::create_linux_sandbox_command_args;
+use crate::network_proxy::NetworkProxy;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
@@ -47,6 +52,9 @@ pub struct ExecEnv {
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    /// Limits `codex-linux-sandbox` applies to the command, used to tell
+    /// whether one of them is what stopped it.
+    pub resource_limits: Option<SandboxResourceLimits>,
 }
 
 pub enum SandboxPreference {
@@ -93,6 +101,7 @@ impl SandboxManager {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn transform(
         &self,
         mut spec: CommandSpec,
@@ -100,6 +109,9 @@ impl SandboxManager {
         sandbox: SandboxType,
         sandbox_policy_cwd: &Path,
         codex_linux_sandbox_exe: Option<&PathBuf>,
+        network_proxy: Option<&NetworkProxy>,
+        resource_limits: Option<&SandboxResourceLimits>,
+        mount_isolation: Option<&MountIsolation>,
     ) -> Result<ExecEnv, SandboxTransformError> {
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -109,6 +121,14 @@ impl SandboxManager {
             );
         }
 
+        // Only sandboxed commands without full network access are confined to
+        // the egress proxy; everything else reaches the network directly.
+        let network_proxy = network_proxy
+            .filter(|_| sandbox != SandboxType::None && !policy.has_full_network_access());
+        if let Some(proxy) = network_proxy {
+            env.extend(proxy.env());
+        }
+
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
@@ -119,8 +139,12 @@ impl SandboxManager {
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy.map(NetworkProxy::port),
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -131,8 +155,14 @@ impl SandboxManager {
             SandboxType::LinuxSeccomp => {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
-                let mut args =
-                    create_linux_sandbox_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_linux_sandbox_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy,
+                    resource_limits,
+                    mount_isolation,
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
                 full_command.append(&mut args);
@@ -163,6 +193,9 @@ impl SandboxManager {
             sandbox_permissions: spec.sandbox_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits: resource_limits
+                .filter(|limits| sandbox == SandboxType::LinuxSeccomp && !limits.is_unlimited())
+                .cloned(),
         })
     }
 
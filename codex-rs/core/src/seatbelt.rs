@@ -14,6 +14,21 @@ use crate::spawn::spawn_child_async;
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
 
+/// Locations that stay readable when a policy restricts reads, so that
+/// programs, frameworks and system configuration can still be loaded.
+const MACOS_SYSTEM_READ_ROOTS: &[&str] = &[
+    "/System",
+    "/Library",
+    "/usr",
+    "/bin",
+    "/sbin",
+    "/dev",
+    "/opt",
+    "/private/etc",
+    "/private/var/db",
+    "/Applications/Xcode.app",
+];
+
 /// When working with `sandbox-exec`, only consider `sandbox-exec` in `/usr/bin`
 /// to defend against an attacker trying to inject a malicious version on the
 /// PATH. If /usr/bin/sandbox-exec has been tampered with, then the attacker
@@ -28,7 +43,7 @@ pub async fn spawn_command_under_seatbelt(
     stdio_policy: StdioPolicy,
     mut env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
-    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_seatbelt_command_args(command, sandbox_policy, sandbox_policy_cwd, None);
     let arg0 = None;
     env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
     spawn_child_async(
@@ -43,10 +58,13 @@ pub async fn spawn_command_under_seatbelt(
     .await
 }
 
+/// `network_proxy_port` allows outbound connections to the egress proxy on
+/// localhost when the policy otherwise has no network access.
 pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy_port: Option<u16>,
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -105,24 +123,84 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
-    let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
-        "; allow read-only file operations\n(allow file-read*)"
+    let (file_read_policy, file_read_dir_params) = if sandbox_policy.has_full_disk_read_access() {
+        (
+            "; allow read-only file operations\n(allow file-read*)".to_string(),
+            Vec::new(),
+        )
+    } else {
+        let mut readable_policies: Vec<String> = MACOS_SYSTEM_READ_ROOTS
+            .iter()
+            .map(|root| format!("(subpath \"{root}\")"))
+            .collect();
+        let mut readable_params = Vec::new();
+        for (index, root) in sandbox_policy
+            .get_readable_roots_with_cwd(sandbox_policy_cwd)
+            .iter()
+            .enumerate()
+        {
+            let canonical_root = root
+                .as_path()
+                .canonicalize()
+                .unwrap_or_else(|_| root.to_path_buf());
+            let root_param = format!("READABLE_ROOT_{index}");
+            readable_policies.push(format!("(subpath (param \"{root_param}\"))"));
+            readable_params.push((root_param, canonical_root));
+        }
+        (
+            format!(
+                "; reads are limited to the readable roots\n(allow file-read-metadata)\n(allow file-read*\n{}\n)",
+                readable_policies.join(" ")
+            ),
+            readable_params,
+        )
+    };
+
+    // Denials come last so they take precedence over the allow rules above.
+    let mut deny_read_params = Vec::new();
+    let deny_read_policy = if sandbox_policy.get_deny_read_paths().is_empty() {
+        String::new()
     } else {
-        ""
+        let mut denied_policies = Vec::new();
+        for (index, path) in sandbox_policy.get_deny_read_paths().iter().enumerate() {
+            let canonical_path = path
+                .as_path()
+                .canonicalize()
+                .unwrap_or_else(|_| path.to_path_buf());
+            let deny_param = format!("DENY_READ_{index}");
+            denied_policies.push(format!("(subpath (param \"{deny_param}\"))"));
+            deny_read_params.push((deny_param, canonical_path));
+        }
+        format!(
+            "(deny file-read* file-write*\n{}\n)",
+            denied_policies.join(" ")
+        )
     };
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        MACOS_SEATBELT_NETWORK_POLICY
+        MACOS_SEATBELT_NETWORK_POLICY.to_string()
+    } else if let Some(port) = network_proxy_port {
+        format!("(allow network-outbound (remote ip \"localhost:{port}\"))")
     } else {
-        ""
+        String::new()
     };
 
-    let full_policy = format!(
+    let mut full_policy = format!(
         "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
     );
+    if !deny_read_policy.is_empty() {
+        full_policy.push('\n');
+        full_policy.push_str(&deny_read_policy);
+    }
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [
+        file_read_dir_params,
+        file_write_dir_params,
+        deny_read_params,
+        macos_dir_params(),
+    ]
+    .concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -200,6 +278,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         // Create the Seatbelt command to wrap a shell command that tries to
@@ -217,7 +297,7 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd);
+        let args = create_seatbelt_command_args(shell_command.clone(), &policy, &cwd, None);
 
         // Build the expected policy text using a raw string for readability.
         // Note that the policy includes:
@@ -308,7 +388,8 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let write_hooks_file_args = create_seatbelt_command_args(shell_command_git, &policy, &cwd);
+        let write_hooks_file_args =
+            create_seatbelt_command_args(shell_command_git, &policy, &cwd, None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_hooks_file_args)
             .current_dir(&cwd)
@@ -345,7 +426,7 @@ mod tests {
         .map(std::string::ToString::to_string)
         .collect();
         let write_allowed_file_args =
-            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd);
+            create_seatbelt_command_args(shell_command_allowed, &policy, &cwd, None);
         let output = Command::new(MACOS_PATH_TO_SEATBELT_EXECUTABLE)
             .args(&write_allowed_file_args)
             .current_dir(&cwd)
@@ -385,6 +466,8 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            readable_roots: vec![],
+            deny_read: vec![],
         };
 
         let shell_command: Vec<String> = [
@@ -400,8 +483,12 @@ mod tests {
         .iter()
         .map(std::string::ToString::to_string)
         .collect();
-        let args =
-            create_seatbelt_command_args(shell_command.clone(), &policy, vulnerable_root.as_path());
+        let args = create_seatbelt_command_args(
+            shell_command.clone(),
+            &policy,
+            vulnerable_root.as_path(),
+            None,
+        );
 
         let tmpdir_env_var = std::env::var("TMPDIR")
             .ok()
@@ -470,6 +557,45 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn create_seatbelt_args_with_readable_roots_and_deny_read() {
+        let tmp = TempDir::new().expect("tempdir");
+        let cwd = tmp.path().join("cwd");
+        let docs = tmp.path().join("docs");
+        let secrets = cwd.join("secrets");
+        fs::create_dir_all(&secrets).expect("create secrets");
+        fs::create_dir_all(&docs).expect("create docs");
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![docs.as_path().try_into().unwrap()],
+            deny_read: vec![secrets.as_path().try_into().unwrap()],
+        };
+        let args = create_seatbelt_command_args(vec!["true".to_string()], &policy, &cwd, None);
+
+        let full_policy = &args[1];
+        assert!(!full_policy.contains("\n(allow file-read*)"));
+        assert!(full_policy.contains("(allow file-read-metadata)"));
+        assert!(full_policy.contains(r#"(subpath (param "READABLE_ROOT_0"))"#));
+        assert!(
+            full_policy
+                .ends_with("(deny file-read* file-write*\n(subpath (param \"DENY_READ_0\"))\n)")
+        );
+        let docs_canonical = docs.canonicalize().expect("canonicalize docs");
+        let secrets_canonical = secrets.canonicalize().expect("canonicalize secrets");
+        assert!(args.contains(&format!(
+            "-DREADABLE_ROOT_0={}",
+            docs_canonical.to_string_lossy()
+        )));
+        assert!(args.contains(&format!(
+            "-DDENY_READ_0={}",
+            secrets_canonical.to_string_lossy()
+        )));
+    }
+
     struct PopulatedTmp {
         /// Path containing a .git and .codex subfolder.
         /// For the purposes of this test, we consider this a "vulnerable" root
@@ -38,12 +38,65 @@ pub enum StdioPolicy {
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
-    #[cfg_attr(not(unix), allow(unused_variables))] arg0: Option<&str>,
+    arg0: Option<&str>,
     cwd: PathBuf,
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
+    build_child_command(program, args, arg0, cwd, sandbox_policy, stdio_policy, env)
+        .kill_on_drop(true)
+        .spawn()
+}
+
+/// Like [`spawn_child_async`], but also hands `fd` to the child as file
+/// descriptor `target_fd`.
+#[cfg(target_os = "linux")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn spawn_child_async_with_fd(
+    program: PathBuf,
+    args: Vec<String>,
+    arg0: Option<&str>,
+    cwd: PathBuf,
+    sandbox_policy: &SandboxPolicy,
+    stdio_policy: StdioPolicy,
+    env: HashMap<String, String>,
+    fd: std::os::fd::OwnedFd,
+    target_fd: std::os::fd::RawFd,
+) -> std::io::Result<Child> {
+    use std::os::fd::AsRawFd;
+
+    let mut cmd = build_child_command(program, args, arg0, cwd, sandbox_policy, stdio_policy, env);
+    let source_fd = fd.as_raw_fd();
+    unsafe {
+        cmd.pre_exec(move || {
+            // dup2 clears close-on-exec on the copy; when the descriptor
+            // already has the right number, clear it by hand.
+            if source_fd == target_fd {
+                if libc::fcntl(target_fd, libc::F_SETFD, 0) == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            } else if libc::dup2(source_fd, target_fd) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let child = cmd.kill_on_drop(true).spawn();
+    // The child has its own copy now.
+    drop(fd);
+    child
+}
+
+fn build_child_command(
+    program: PathBuf,
+    args: Vec<String>,
+    #[cfg_attr(not(unix), allow(unused_variables))] arg0: Option<&str>,
+    cwd: PathBuf,
+    sandbox_policy: &SandboxPolicy,
+    stdio_policy: StdioPolicy,
+    env: HashMap<String, String>,
+) -> Command {
     trace!(
         "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
     );
@@ -113,5 +166,5 @@ pub(crate) async fn spawn_child_async(
         }
     }
 
-    cmd.kill_on_drop(true).spawn()
+    cmd
 }
@@ -1,9 +1,13 @@
 //! Session-wide mutable state.
 
+use std::collections::HashSet;
+
 use codex_protocol::models::ResponseItem;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::error::BudgetScope;
+use crate::protocol::CostSummary;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -14,6 +18,10 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated spend; `None` until a response for a priced model completes.
+    cost: Option<CostSummary>,
+    /// Soft budget scopes the user has already been warned about.
+    soft_budgets_notified: HashSet<BudgetScope>,
 }
 
 impl SessionState {
@@ -24,6 +32,8 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            cost: None,
+            soft_budgets_notified: HashSet::new(),
         }
     }
 
@@ -74,6 +84,29 @@ impl SessionState {
         (self.token_info(), self.latest_rate_limits.clone())
     }
 
+    // Spend helpers
+    pub(crate) fn record_cost(&mut self, last_usd: f64, daily_usd: f64) {
+        let session_usd = self.session_cost_usd() + last_usd;
+        self.cost = Some(CostSummary {
+            last_usd,
+            session_usd,
+            daily_usd,
+        });
+    }
+
+    pub(crate) fn cost(&self) -> Option<CostSummary> {
+        self.cost
+    }
+
+    pub(crate) fn session_cost_usd(&self) -> f64 {
+        self.cost.map_or(0.0, |cost| cost.session_usd)
+    }
+
+    /// Returns `true` the first time a soft budget for `scope` is reported.
+    pub(crate) fn mark_soft_budget_notified(&mut self, scope: BudgetScope) -> bool {
+        self.soft_budgets_notified.insert(scope)
+    }
+
     pub(crate) fn set_token_usage_full(&mut self, context_window: i64) {
         self.history.set_token_usage_full(context_window);
     }
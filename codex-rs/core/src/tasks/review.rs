@@ -6,16 +6,21 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::AgentMessageContentDeltaEvent;
 use codex_protocol::protocol::AgentMessageDeltaEvent;
+use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::protocol::StreamErrorEvent;
+use codex_protocol::protocol::WarningEvent;
+use futures::future::join_all;
 use tokio_util::sync::CancellationToken;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex_delegate::run_codex_conversation_one_shot;
+use crate::config::types::ReviewPersona;
 use crate::review_format::format_review_findings_block;
 use crate::review_format::render_review_output_text;
 use crate::state::TaskKind;
@@ -23,6 +28,10 @@ use codex_protocol::user_input::UserInput;
 
 use super::SessionTask;
 use super::SessionTaskContext;
+use merge::PassOutput;
+use merge::merge_review_outputs;
+
+mod merge;
 
 #[derive(Clone, Copy)]
 pub(crate) struct ReviewTask;
@@ -46,18 +55,36 @@ impl SessionTask for ReviewTask {
         input: Vec<UserInput>,
         cancellation_token: CancellationToken,
     ) -> Option<String> {
-        // Start sub-codex conversation and get the receiver for events.
-        let output = match start_review_conversation(
-            session.clone(),
-            ctx.clone(),
-            input,
-            cancellation_token.clone(),
-        )
-        .await
-        {
-            Some(receiver) => process_review_events(session.clone(), ctx.clone(), receiver).await,
-            None => None,
-        };
+        // Run the default pass and every configured persona in parallel, each
+        // in its own sub-codex conversation. Only the default pass streams its
+        // progress; see `process_review_events`.
+        let config = ctx.client.config();
+        let personas = std::iter::once(None).chain(config.review_personas.iter().map(Some));
+        let passes = personas.map(|persona| {
+            let session = session.clone();
+            let ctx = ctx.clone();
+            let input = input.clone();
+            let cancellation_token = cancellation_token.clone();
+            async move {
+                let receiver = start_review_conversation(
+                    session.clone(),
+                    ctx.clone(),
+                    input,
+                    persona,
+                    cancellation_token,
+                )
+                .await?;
+                let persona_name = persona.map(|persona| persona.name.as_str());
+                let output = process_review_events(session, ctx, receiver, persona_name).await?;
+                Some(PassOutput {
+                    persona: persona.map(|persona| persona.name.clone()),
+                    severity_threshold: persona.and_then(|persona| persona.severity_threshold),
+                    output,
+                })
+            }
+        });
+        let outputs: Vec<PassOutput> = join_all(passes).await.into_iter().flatten().collect();
+        let output = merge_review_outputs(outputs);
         if !cancellation_token.is_cancelled() {
             exit_review_mode(session.clone_session(), output.clone(), ctx.clone()).await;
         }
@@ -73,6 +100,7 @@ async fn start_review_conversation(
     session: Arc<SessionTaskContext>,
     ctx: Arc<TurnContext>,
     input: Vec<UserInput>,
+    persona: Option<&ReviewPersona>,
     cancellation_token: CancellationToken,
 ) -> Option<async_channel::Receiver<Event>> {
     let config = ctx.client.config();
@@ -88,10 +116,20 @@ async fn start_review_conversation(
         .disable(crate::features::Feature::WebSearchRequest)
         .disable(crate::features::Feature::ViewImageTool);
 
-    // Set explicit review rubric for the sub-agent
-    sub_agent_config.base_instructions = Some(crate::REVIEW_PROMPT.to_string());
+    // Set explicit review rubric for the sub-agent, plus the persona's focus.
+    let (base_instructions, model) = match persona {
+        Some(persona) => (
+            format!("{}\n\n{}", crate::REVIEW_PROMPT, persona.instructions),
+            persona.model.clone(),
+        ),
+        None => (
+            crate::REVIEW_PROMPT.to_string(),
+            config.review_model.clone(),
+        ),
+    };
+    sub_agent_config.base_instructions = Some(base_instructions);
 
-    sub_agent_config.model = Some(config.review_model.clone());
+    sub_agent_config.model = Some(model);
     (run_codex_conversation_one_shot(
         sub_agent_config,
         session.auth_manager(),
@@ -107,13 +145,35 @@ async fn start_review_conversation(
         .map(|io| io.rx_event)
 }
 
+/// Forwards a review pass's events to the session and returns its output.
+///
+/// Passes run in parallel, and clients expect a single stream of turn
+/// events: forwarded messages and token counts from several passes would
+/// interleave and overwrite each other. So only the default pass (`persona`
+/// is `None`) forwards everything; a persona pass forwards just its errors
+/// and warnings, as warnings labelled with the persona. Clients end the turn
+/// on an error, and a failed persona pass must not end the review while the
+/// default pass is still running. Approvals do not go through here at all;
+/// the delegate routes them to the parent session for every pass.
 async fn process_review_events(
     session: Arc<SessionTaskContext>,
     ctx: Arc<TurnContext>,
     receiver: async_channel::Receiver<Event>,
+    persona: Option<&str>,
 ) -> Option<ReviewOutputEvent> {
     let mut prev_agent_message: Option<Event> = None;
     while let Ok(event) = receiver.recv().await {
+        if let Some(persona) = persona
+            && !matches!(
+                event.msg,
+                EventMsg::TaskComplete(_) | EventMsg::TurnAborted(_)
+            )
+        {
+            if let Some(msg) = persona_notice(persona, event.msg) {
+                session.clone_session().send_event(ctx.as_ref(), msg).await;
+            }
+            continue;
+        }
         match event.clone().msg {
             EventMsg::AgentMessage(_) => {
                 if let Some(prev) = prev_agent_message.take() {
@@ -157,6 +217,20 @@ async fn process_review_events(
     None
 }
 
+/// The errors and warnings of a persona pass as a warning labelled with the
+/// persona; `None` for everything else.
+fn persona_notice(persona: &str, msg: EventMsg) -> Option<EventMsg> {
+    let message = match msg {
+        EventMsg::Error(ErrorEvent { message, .. })
+        | EventMsg::StreamError(StreamErrorEvent { message, .. })
+        | EventMsg::Warning(WarningEvent { message }) => message,
+        _ => return None,
+    };
+    Some(EventMsg::Warning(WarningEvent {
+        message: format!("{persona} reviewer: {message}"),
+    }))
+}
+
 /// Parse a ReviewOutputEvent from a text blob returned by the reviewer model.
 /// If the text is valid JSON matching ReviewOutputEvent, deserialize it.
 /// Otherwise, attempt to extract the first JSON object substring and parse it.
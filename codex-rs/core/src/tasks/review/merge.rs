@@ -0,0 +1,204 @@
+//! Combines the results of parallel review passes into one review.
+
+use codex_protocol::protocol::ReviewFinding;
+use codex_protocol::protocol::ReviewOutputEvent;
+
+use crate::config::types::ReviewSeverity;
+
+const INCORRECT_VERDICT: &str = "patch is incorrect";
+
+/// The result of one review pass. `persona` is `None` for the default pass.
+pub(super) struct PassOutput {
+    pub(super) persona: Option<String>,
+    pub(super) severity_threshold: Option<ReviewSeverity>,
+    pub(super) output: ReviewOutputEvent,
+}
+
+/// Merges the passes in order. Findings below a pass's severity threshold are
+/// dropped, findings from personas are tagged with the persona name, and the
+/// same issue reported by several passes (same file, overlapping lines and
+/// title) is kept once at its highest priority. The patch is incorrect if any
+/// pass says so.
+pub(super) fn merge_review_outputs(mut passes: Vec<PassOutput>) -> Option<ReviewOutputEvent> {
+    if passes.len() <= 1 {
+        return passes.pop().map(|pass| filter_pass(pass).1);
+    }
+
+    let mut findings: Vec<(String, ReviewFinding)> = Vec::new();
+    let mut verdicts = Vec::new();
+    let mut explanations = Vec::new();
+    let mut confidence: Option<f32> = None;
+    for pass in passes {
+        let (label, output) = filter_pass(pass);
+        for finding in output.findings {
+            let key = normalized_title(&finding.title);
+            let finding = match &label {
+                Some(persona) => ReviewFinding {
+                    title: format!("{} ({persona})", finding.title),
+                    ..finding
+                },
+                None => finding,
+            };
+            match findings.iter_mut().find(|(existing_key, existing)| {
+                *existing_key == key && overlaps(existing, &finding)
+            }) {
+                Some((_, existing)) => {
+                    if (finding.priority, -finding.confidence_score)
+                        < (existing.priority, -existing.confidence_score)
+                    {
+                        *existing = finding;
+                    }
+                }
+                None => findings.push((key, finding)),
+            }
+        }
+
+        let verdict = output.overall_correctness.trim();
+        if !verdict.is_empty() {
+            verdicts.push(verdict.to_string());
+        }
+        let explanation = output.overall_explanation.trim();
+        if !explanation.is_empty() {
+            let label = label.as_deref().unwrap_or("general");
+            explanations.push(format!("{label}: {explanation}"));
+        }
+        confidence = Some(match confidence {
+            Some(current) => current.min(output.overall_confidence_score),
+            None => output.overall_confidence_score,
+        });
+    }
+
+    let overall_correctness = verdicts
+        .iter()
+        .find(|verdict| verdict.as_str() == INCORRECT_VERDICT)
+        .or(verdicts.first())
+        .cloned()
+        .unwrap_or_default();
+    Some(ReviewOutputEvent {
+        findings: findings.into_iter().map(|(_, finding)| finding).collect(),
+        overall_correctness,
+        overall_explanation: explanations.join("\n\n"),
+        overall_confidence_score: confidence.unwrap_or_default(),
+    })
+}
+
+fn filter_pass(pass: PassOutput) -> (Option<String>, ReviewOutputEvent) {
+    let PassOutput {
+        persona,
+        severity_threshold,
+        mut output,
+    } = pass;
+    if let Some(threshold) = severity_threshold {
+        output
+            .findings
+            .retain(|finding| threshold.includes(finding.priority));
+    }
+    (persona, output)
+}
+
+/// Lowercased title without the leading `[P1]`-style priority tag.
+fn normalized_title(title: &str) -> String {
+    let title = title.trim();
+    let title = match title
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((tag, rest)) if tag.starts_with('P') => rest,
+        _ => title,
+    };
+    title.trim().to_lowercase()
+}
+
+fn overlaps(a: &ReviewFinding, b: &ReviewFinding) -> bool {
+    let a_location = &a.code_location;
+    let b_location = &b.code_location;
+    a_location.absolute_file_path == b_location.absolute_file_path
+        && a_location.line_range.start <= b_location.line_range.end
+        && b_location.line_range.start <= a_location.line_range.end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ReviewCodeLocation;
+    use codex_protocol::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, priority: i32, start: u32, end: u32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: String::new(),
+            confidence_score: 0.5,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    fn pass(
+        persona: Option<&str>,
+        severity_threshold: Option<ReviewSeverity>,
+        correctness: &str,
+        findings: Vec<ReviewFinding>,
+    ) -> PassOutput {
+        PassOutput {
+            persona: persona.map(str::to_string),
+            severity_threshold,
+            output: ReviewOutputEvent {
+                findings,
+                overall_correctness: correctness.to_string(),
+                overall_explanation: format!("{} pass", persona.unwrap_or("default")),
+                overall_confidence_score: 0.9,
+            },
+        }
+    }
+
+    #[test]
+    fn single_pass_is_returned_unchanged() {
+        let output = merge_review_outputs(vec![pass(
+            None,
+            None,
+            "patch is correct",
+            vec![finding("[P3] Nit", 3, 1, 1)],
+        )])
+        .expect("output");
+
+        assert_eq!(output.findings, vec![finding("[P3] Nit", 3, 1, 1)]);
+        assert_eq!(output.overall_explanation, "default pass");
+    }
+
+    #[test]
+    fn merges_passes_dropping_duplicates_and_low_severity() {
+        let output = merge_review_outputs(vec![
+            pass(
+                None,
+                None,
+                "patch is correct",
+                vec![finding("[P2] Unescaped SQL input", 2, 10, 12)],
+            ),
+            pass(
+                Some("security"),
+                Some(ReviewSeverity::High),
+                "patch is incorrect",
+                vec![
+                    finding("[P0] Unescaped SQL input", 0, 11, 11),
+                    finding("[P3] Log the caller", 3, 40, 40),
+                ],
+            ),
+        ])
+        .expect("output");
+
+        assert_eq!(
+            output,
+            ReviewOutputEvent {
+                findings: vec![finding("[P0] Unescaped SQL input (security)", 0, 11, 11)],
+                overall_correctness: "patch is incorrect".to_string(),
+                overall_explanation: "general: default pass\n\nsecurity: security pass".to_string(),
+                overall_confidence_score: 0.9,
+            }
+        );
+    }
+}
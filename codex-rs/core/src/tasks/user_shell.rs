@@ -104,6 +104,7 @@ impl SessionTask for UserShellCommandTask {
             sandbox_permissions: SandboxPermissions::UseDefault,
             justification: None,
             arg0: None,
+            resource_limits: None,
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -127,6 +128,7 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(aborted_message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    blocked_syscalls: Vec::new(),
                 };
                 let output_items = [user_shell_command_record_item(
                     &raw_command,
@@ -154,6 +156,7 @@ impl SessionTask for UserShellCommandTask {
                             exit_code: -1,
                             duration: Duration::ZERO,
                             formatted_output: aborted_message,
+                            exit_reason: None,
                         }),
                     )
                     .await;
@@ -180,6 +183,7 @@ impl SessionTask for UserShellCommandTask {
                                 &output,
                                 turn_context.truncation_policy,
                             ),
+                            exit_reason: None,
                         }),
                     )
                     .await;
@@ -203,6 +207,7 @@ impl SessionTask for UserShellCommandTask {
                     aggregated_output: StreamOutput::new(message.clone()),
                     duration: Duration::ZERO,
                     timed_out: false,
+                    blocked_syscalls: Vec::new(),
                 };
                 session
                     .send_event(
@@ -225,6 +230,7 @@ impl SessionTask for UserShellCommandTask {
                                 &exec_output,
                                 turn_context.truncation_policy,
                             ),
+                            exit_reason: None,
                         }),
                     )
                     .await;
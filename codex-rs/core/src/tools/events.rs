@@ -3,16 +3,20 @@ use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandBeginEvent;
 use crate::protocol::ExecCommandEndEvent;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::ExecExitReason;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
+use crate::protocol::SandboxDenialEvent;
 use crate::protocol::TurnDiffEvent;
+use crate::sandboxing::denials::diagnose_sandbox_denial;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
@@ -55,6 +59,7 @@ pub(crate) enum ToolEventStage {
 
 pub(crate) enum ToolEventFailure {
     Output(ExecToolCallOutput),
+    ResourceLimit(ExecToolCallOutput, ExecExitReason),
     Message(String),
 }
 
@@ -202,7 +207,9 @@ impl ToolEmitter {
             }
             (
                 Self::ApplyPatch { changes, .. },
-                ToolEventStage::Failure(ToolEventFailure::Output(output)),
+                ToolEventStage::Failure(
+                    ToolEventFailure::Output(output) | ToolEventFailure::ResourceLimit(output, _),
+                ),
             ) => {
                 emit_patch_end(
                     ctx,
@@ -276,6 +283,7 @@ impl ToolEmitter {
         ctx: ToolEventCtx<'_>,
         out: Result<ExecToolCallOutput, ToolError>,
     ) -> Result<String, FunctionCallError> {
+        let mut denial_event = None;
         let (event, result) = match out {
             Ok(output) => {
                 let content = self.format_exec_output_for_model(&output, ctx);
@@ -288,13 +296,35 @@ impl ToolEmitter {
                 };
                 (event, result)
             }
-            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output }))) => {
                 let response = self.format_exec_output_for_model(&output, ctx);
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
             }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+                let mut response = self.format_exec_output_for_model(&output, ctx);
+                if let Some((summary, event)) = self.diagnose_denial(&output, ctx) {
+                    response.push_str(&format!("\n\nthe sandbox likely blocked {summary}"));
+                    denial_event = Some(event);
+                }
+                let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                reason,
+                output,
+            }))) => {
+                let response = format!(
+                    "{}\n\ncommand was killed for exceeding the sandbox {reason}",
+                    self.format_exec_output_for_model(&output, ctx)
+                );
+                let event =
+                    ToolEventStage::Failure(ToolEventFailure::ResourceLimit(*output, reason));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
             Err(ToolError::Codex(err)) => {
                 let message = format!("execution error: {err:?}");
                 let event = ToolEventStage::Failure(ToolEventFailure::Message(message.clone()));
@@ -315,8 +345,51 @@ impl ToolEmitter {
             }
         };
         self.emit(ctx, event).await;
+        if let Some(denial_event) = denial_event {
+            ctx.session
+                .send_event(ctx.turn, EventMsg::SandboxDenial(denial_event))
+                .await;
+        }
         result
     }
+
+    /// Summary for the model and event for the user of what the sandbox most
+    /// likely refused a failed command, when its output says so.
+    fn diagnose_denial(
+        &self,
+        output: &ExecToolCallOutput,
+        ctx: ToolEventCtx<'_>,
+    ) -> Option<(String, SandboxDenialEvent)> {
+        let (command, cwd) = match self {
+            Self::Shell { command, cwd, .. } | Self::UnifiedExec { command, cwd, .. } => {
+                (command, cwd)
+            }
+            Self::ApplyPatch { .. } => return None,
+        };
+        let diagnosis = diagnose_sandbox_denial(
+            output,
+            &ctx.turn.sandbox_policy,
+            &ctx.turn.cwd,
+            command,
+            cwd,
+            &ctx.turn.client.config().codex_home,
+            ctx.session.features().enabled(Feature::ExecPolicy),
+        );
+        if diagnosis.denials.is_empty() {
+            return None;
+        }
+        let summary = diagnosis.summary();
+        Some((
+            summary,
+            SandboxDenialEvent {
+                call_id: ctx.call_id.to_string(),
+                command: command.clone(),
+                cwd: cwd.clone(),
+                denials: diagnosis.denials,
+                suggested_amendments: diagnosis.suggested_amendments,
+            },
+        ))
+    }
 }
 
 struct ExecCommandInput<'a> {
@@ -355,6 +428,25 @@ struct ExecCommandResult {
     exit_code: i32,
     duration: Duration,
     formatted_output: String,
+    exit_reason: Option<ExecExitReason>,
+}
+
+impl ExecCommandResult {
+    fn from_output(
+        output: &ExecToolCallOutput,
+        ctx: ToolEventCtx<'_>,
+        exit_reason: Option<ExecExitReason>,
+    ) -> Self {
+        Self {
+            stdout: output.stdout.text.clone(),
+            stderr: output.stderr.text.clone(),
+            aggregated_output: output.aggregated_output.text.clone(),
+            exit_code: output.exit_code,
+            duration: output.duration,
+            formatted_output: format_exec_output_str(output, ctx.turn.truncation_policy),
+            exit_reason,
+        }
+    }
 }
 
 async fn emit_exec_stage(
@@ -377,14 +469,11 @@ async fn emit_exec_stage(
         }
         ToolEventStage::Success(output)
         | ToolEventStage::Failure(ToolEventFailure::Output(output)) => {
-            let exec_result = ExecCommandResult {
-                stdout: output.stdout.text.clone(),
-                stderr: output.stderr.text.clone(),
-                aggregated_output: output.aggregated_output.text.clone(),
-                exit_code: output.exit_code,
-                duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
-            };
+            let exec_result = ExecCommandResult::from_output(&output, ctx, None);
+            emit_exec_end(ctx, exec_input, exec_result).await;
+        }
+        ToolEventStage::Failure(ToolEventFailure::ResourceLimit(output, reason)) => {
+            let exec_result = ExecCommandResult::from_output(&output, ctx, Some(reason));
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
         ToolEventStage::Failure(ToolEventFailure::Message(message)) => {
@@ -396,6 +485,7 @@ async fn emit_exec_stage(
                 exit_code: -1,
                 duration: Duration::ZERO,
                 formatted_output: text,
+                exit_reason: None,
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -425,6 +515,7 @@ async fn emit_exec_end(
                 exit_code: exec_result.exit_code,
                 duration: exec_result.duration,
                 formatted_output: exec_result.formatted_output,
+                exit_reason: exec_result.exit_reason,
             }),
         )
         .await;
@@ -130,7 +130,8 @@ impl ToolHandler for ApplyPatchHandler {
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let content =
+                            apply.with_user_feedback(emitter.finish(event_ctx, out).await?);
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -217,7 +218,7 @@ pub(crate) async fn intercept_apply_patch(
                         .await;
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    let content = emitter.finish(event_ctx, out).await?;
+                    let content = apply.with_user_feedback(emitter.finish(event_ctx, out).await?);
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
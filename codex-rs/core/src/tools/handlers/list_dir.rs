@@ -13,6 +13,7 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
@@ -51,7 +52,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -100,7 +101,12 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        ensure_path_readable(&turn, &path)?;
+
+        let entries = list_dir_slice(&path, offset, limit, depth, &|dir| {
+            turn.sandbox_policy.is_path_readable(dir, &turn.cwd)
+        })
+        .await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -117,9 +123,10 @@ async fn list_dir_slice(
     offset: usize,
     limit: usize,
     depth: usize,
+    is_readable: &(dyn Fn(&Path) -> bool + Sync),
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(path, Path::new(""), depth, is_readable, &mut entries).await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -154,6 +161,7 @@ async fn collect_entries(
     dir_path: &Path,
     relative_prefix: &Path,
     depth: usize,
+    is_readable: &(dyn Fn(&Path) -> bool + Sync),
     entries: &mut Vec<DirEntry>,
 ) -> Result<(), FunctionCallError> {
     let mut queue = VecDeque::new();
@@ -200,7 +208,8 @@ async fn collect_entries(
         dir_entries.sort_unstable_by(|a, b| a.3.name.cmp(&b.3.name));
 
         for (entry_path, relative_path, kind, dir_entry) in dir_entries {
-            if kind == DirEntryKind::Directory && remaining_depth > 1 {
+            // Unreadable folders are still listed, but not descended into.
+            if kind == DirEntryKind::Directory && remaining_depth > 1 && is_readable(&entry_path) {
                 queue.push_back((entry_path, relative_path, remaining_depth - 1));
             }
             entries.push(dir_entry);
@@ -307,7 +316,7 @@ mod tests {
             symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 20, 3)
+        let entries = list_dir_slice(dir_path, 1, 20, 3, &|_| true)
             .await
             .expect("list directory");
 
@@ -341,7 +350,7 @@ mod tests {
             .await
             .expect("create sub dir");
 
-        let err = list_dir_slice(dir_path, 10, 1, 2)
+        let err = list_dir_slice(dir_path, 10, 1, 2, &|_| true)
             .await
             .expect_err("offset exceeds entries");
         assert_eq!(
@@ -368,7 +377,7 @@ mod tests {
             .await
             .expect("write deeper");
 
-        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
+        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1, &|_| true)
             .await
             .expect("list depth 1");
         assert_eq!(
@@ -376,7 +385,7 @@ mod tests {
             vec!["nested/".to_string(), "root.txt".to_string(),]
         );
 
-        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
+        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2, &|_| true)
             .await
             .expect("list depth 2");
         assert_eq!(
@@ -389,7 +398,7 @@ mod tests {
             ]
         );
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
+        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3, &|_| true)
             .await
             .expect("list depth 3");
         assert_eq!(
@@ -404,6 +413,25 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn does_not_descend_into_unreadable_directories() {
+        let temp = tempdir().expect("create tempdir");
+        let dir_path = temp.path();
+        let secret = dir_path.join("secret");
+        tokio::fs::create_dir(&secret).await.expect("create secret");
+        tokio::fs::write(secret.join("key.pem"), b"key")
+            .await
+            .expect("write key");
+        tokio::fs::write(dir_path.join("root.txt"), b"root")
+            .await
+            .expect("write root");
+
+        let entries = list_dir_slice(dir_path, 1, 10, 2, &|path| path != secret)
+            .await
+            .expect("list directory");
+        assert_eq!(entries, vec!["root.txt".to_string(), "secret/".to_string()]);
+    }
+
     #[tokio::test]
     async fn handles_large_limit_without_overflow() {
         let temp = tempdir().expect("create tempdir");
@@ -418,7 +446,7 @@ mod tests {
             .await
             .expect("write gamma");
 
-        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
+        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1, &|_| true)
             .await
             .expect("list without overflow");
         assert_eq!(
@@ -439,7 +467,7 @@ mod tests {
                 .expect("write file");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 25, 1)
+        let entries = list_dir_slice(dir_path, 1, 25, 1, &|_| true)
             .await
             .expect("list directory");
         assert_eq!(entries.len(), 26);
@@ -461,7 +489,7 @@ mod tests {
         tokio::fs::write(nested.join("child.txt"), b"child").await?;
         tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3, &|_| true).await?;
         assert_eq!(
             entries_depth_three,
             vec![
@@ -24,3 +24,20 @@ pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
+
+use std::path::Path;
+
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+
+/// Refuses to read `path` when the sandbox policy would not let a sandboxed
+/// command read it either.
+fn ensure_path_readable(turn: &TurnContext, path: &Path) -> Result<(), FunctionCallError> {
+    if turn.sandbox_policy.is_path_readable(path, &turn.cwd) {
+        return Ok(());
+    }
+    Err(FunctionCallError::RespondToModel(format!(
+        "{} is outside the sandbox's readable roots or in its deny_read list and cannot be read",
+        path.display()
+    )))
+}
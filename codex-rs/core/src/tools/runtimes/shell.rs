@@ -163,6 +163,18 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.sandbox_permissions,
             req.justification.clone(),
         )?;
+
+        #[cfg(target_os = "linux")]
+        if attempt.sandbox == crate::exec::SandboxType::LinuxSeccomp
+            && ctx.session.features().enabled(Feature::WorkspaceOverlay)
+            && matches!(
+                attempt.policy,
+                crate::protocol::SandboxPolicy::WorkspaceWrite { .. }
+            )
+        {
+            return run_on_workspace_overlay(spec, attempt, ctx).await;
+        }
+
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
@@ -172,3 +184,75 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
         Ok(out)
     }
 }
+
+/// Runs the command on a copy-on-write overlay of the workspace and keeps its
+/// changes only once they are approved like a patch. Commands that time out
+/// or are denied by the sandbox leave the workspace untouched; a command that
+/// exits with an error still has its changes offered for approval.
+#[cfg(target_os = "linux")]
+async fn run_on_workspace_overlay(
+    spec: crate::sandboxing::CommandSpec,
+    attempt: &SandboxAttempt<'_>,
+    ctx: &ToolCtx<'_>,
+) -> Result<ExecToolCallOutput, ToolError> {
+    use crate::workspace_overlay::WorkspaceOverlay;
+    use codex_protocol::protocol::AskForApproval;
+
+    let overlay =
+        WorkspaceOverlay::new(attempt.sandbox_cwd).map_err(|err| ToolError::Codex(err.into()))?;
+    let env = attempt
+        .env_with_workspace_overlay(spec, overlay.dir())
+        .map_err(|err| ToolError::Codex(err.into()))?;
+    let mut out = execute_env(env, attempt.policy, ShellRuntime::stdout_stream(ctx))
+        .await
+        .map_err(ToolError::Codex)?;
+
+    let changes = overlay
+        .changes()
+        .map_err(|err| ToolError::Codex(err.into()))?;
+    if changes.is_empty() {
+        return Ok(out);
+    }
+    let reason = match changes.omissions() {
+        Some(omissions) => {
+            format!("Keep the changes this command made to the workspace? ({omissions})")
+        }
+        None => "Keep the changes this command made to the workspace?".to_string(),
+    };
+    let approved = if ctx.turn.approval_policy == AskForApproval::Never {
+        true
+    } else {
+        let rx_approve = ctx
+            .session
+            .request_patch_approval(
+                ctx.turn,
+                ctx.call_id.clone(),
+                changes.changes,
+                Some(reason),
+                None,
+                true,
+            )
+            .await;
+        // The request is whole-patch-only, so a partial approval can only
+        // come from a misbehaving client; it keeps nothing.
+        match rx_approve.await.unwrap_or_default() {
+            ReviewDecision::Approved
+            | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+            | ReviewDecision::ApprovedForSession => true,
+            ReviewDecision::ApprovedHunks { .. }
+            | ReviewDecision::Denied
+            | ReviewDecision::Abort => false,
+        }
+    };
+
+    if approved {
+        overlay
+            .commit()
+            .map_err(|err| ToolError::Codex(err.into()))?;
+    } else {
+        out.aggregated_output
+            .text
+            .push_str("\n\nthe user rejected the changes this command made to the workspace; they were discarded");
+    }
+    Ok(out)
+}
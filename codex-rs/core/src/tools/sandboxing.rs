@@ -7,7 +7,10 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
+use crate::landlock::MountIsolation;
+use crate::network_proxy::NetworkProxy;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::SandboxResourceLimits;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxTransformError;
@@ -240,12 +243,40 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    pub(crate) network_proxy: Option<&'a NetworkProxy>,
+    pub(crate) resource_limits: Option<&'a SandboxResourceLimits>,
+    /// Run Linux sandboxed commands in a mount namespace of their own.
+    pub(crate) private_mounts: bool,
 }
 
 impl<'a> SandboxAttempt<'a> {
     pub fn env_for(
         &self,
         spec: CommandSpec,
+    ) -> Result<crate::sandboxing::ExecEnv, SandboxTransformError> {
+        let mount_isolation = self.private_mounts.then(MountIsolation::default);
+        self.transform(spec, mount_isolation.as_ref())
+    }
+
+    /// Like [`Self::env_for`], but runs the command on a copy-on-write overlay
+    /// of the workspace whose upper and work directories live in
+    /// `overlay_dir`.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn env_with_workspace_overlay(
+        &self,
+        spec: CommandSpec,
+        overlay_dir: &Path,
+    ) -> Result<crate::sandboxing::ExecEnv, SandboxTransformError> {
+        let mount_isolation = MountIsolation {
+            workspace_overlay: Some(overlay_dir.to_path_buf()),
+        };
+        self.transform(spec, Some(&mount_isolation))
+    }
+
+    fn transform(
+        &self,
+        spec: CommandSpec,
+        mount_isolation: Option<&MountIsolation>,
     ) -> Result<crate::sandboxing::ExecEnv, SandboxTransformError> {
         self.manager.transform(
             spec,
@@ -253,6 +284,9 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.network_proxy,
+            self.resource_limits,
+            mount_isolation,
         )
     }
 }
@@ -0,0 +1,507 @@
+//! Copy-on-write overlay of the workspace for Linux sandboxed commands.
+//!
+//! With the `workspace_overlay` feature, `codex-linux-sandbox` mounts an
+//! overlay over the workspace so that everything a command writes there lands
+//! in the overlay's upper directory instead. The upper directory is then
+//! turned into [`FileChange`]s for approval and only copied back into the
+//! real workspace once they are approved. Binary and large files are listed
+//! without their content, and the size of the approval request is capped, so
+//! that build output such as `target/` or `node_modules/` stays reviewable.
+
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::fs;
+use std::fs::Metadata;
+use std::io;
+use std::io::Read;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use similar::TextDiff;
+use tempfile::TempDir;
+
+use crate::protocol::FileChange;
+
+/// Overlay metadata lives in `user.*` attributes when the overlay is mounted
+/// from a user namespace and in `trusted.*` ones otherwise.
+const OPAQUE_XATTRS: [&CStr; 2] = [c"user.overlay.opaque", c"trusted.overlay.opaque"];
+
+/// Files larger than this are listed without their content.
+const MAX_FILE_CONTENT_BYTES: u64 = 256 * 1024;
+
+/// File content, in total, that one approval request may carry.
+const MAX_CONTENT_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Changed paths listed in one approval request; the rest are only counted.
+const MAX_LISTED_CHANGES: usize = 1_000;
+
+/// What a command changed in the workspace, trimmed to fit an approval
+/// request.
+#[derive(Debug, Default)]
+pub(crate) struct OverlayChanges {
+    pub(crate) changes: HashMap<PathBuf, FileChange>,
+    /// Listed files shown without content: binary, too large, or past the
+    /// request's content budget.
+    pub(crate) without_content: usize,
+    /// Changed files that are not listed at all.
+    pub(crate) unlisted: usize,
+    content_bytes: u64,
+}
+
+impl OverlayChanges {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.unlisted == 0
+    }
+
+    /// Tells the user what the approval request leaves out, if anything.
+    pub(crate) fn omissions(&self) -> Option<String> {
+        let mut notes = Vec::new();
+        if self.without_content > 0 {
+            notes.push(format!(
+                "{} binary or large file(s) are listed without their content",
+                self.without_content
+            ));
+        }
+        if self.unlisted > 0 {
+            notes.push(format!(
+                "{} more changed file(s) are not listed but are kept too",
+                self.unlisted
+            ));
+        }
+        (!notes.is_empty()).then(|| notes.join("; "))
+    }
+
+    fn is_full(&self) -> bool {
+        self.changes.len() >= MAX_LISTED_CHANGES
+    }
+
+    /// Lists `change` for `path`, or only counts it once the list is full.
+    fn insert(&mut self, path: PathBuf, change: FileChange) {
+        if self.is_full() {
+            self.unlisted += 1;
+        } else {
+            self.changes.insert(path, change);
+        }
+    }
+
+    /// Reads text files that fit the per-file limit and the remaining
+    /// budget; `None` for everything else.
+    fn read_text(&mut self, paths: &[&Path]) -> io::Result<Option<Vec<String>>> {
+        let mut size = 0;
+        for path in paths {
+            let len = fs::metadata(path)?.len();
+            if len > MAX_FILE_CONTENT_BYTES {
+                return Ok(None);
+            }
+            size += len;
+        }
+        if self.content_bytes + size > MAX_CONTENT_BYTES {
+            return Ok(None);
+        }
+        let mut texts = Vec::with_capacity(paths.len());
+        for path in paths {
+            match read_text(path)? {
+                Some(text) => texts.push(text),
+                None => return Ok(None),
+            }
+        }
+        self.content_bytes += size;
+        Ok(Some(texts))
+    }
+}
+
+pub(crate) struct WorkspaceOverlay {
+    dir: TempDir,
+    workspace: PathBuf,
+}
+
+impl WorkspaceOverlay {
+    pub(crate) fn new(workspace: &Path) -> io::Result<Self> {
+        let dir = tempfile::Builder::new()
+            .prefix("codex-overlay-")
+            .tempdir()?;
+        Ok(Self {
+            dir,
+            workspace: workspace.to_path_buf(),
+        })
+    }
+
+    /// Folder to pass to `codex-linux-sandbox --workspace-overlay`.
+    pub(crate) fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    fn upper(&self) -> PathBuf {
+        self.dir.path().join("upper")
+    }
+
+    /// Changes the command made to the workspace, keyed by absolute path.
+    /// Files whose content did not change (e.g. after a `chmod`) are left out.
+    pub(crate) fn changes(&self) -> io::Result<OverlayChanges> {
+        let mut changes = OverlayChanges::default();
+        let upper = self.upper();
+        if upper.is_dir() {
+            collect_changes(&upper, &self.workspace, &mut changes)?;
+        }
+        Ok(changes)
+    }
+
+    /// Copies the command's changes into the real workspace.
+    pub(crate) fn commit(&self) -> io::Result<()> {
+        let upper = self.upper();
+        if upper.is_dir() {
+            commit_dir(&upper, &self.workspace)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for WorkspaceOverlay {
+    fn drop(&mut self) {
+        // The kernel leaves an inaccessible `work/work` behind, which would
+        // keep the temporary folder from being removed.
+        let _ = fs::set_permissions(
+            self.dir.path().join("work").join("work"),
+            fs::Permissions::from_mode(0o700),
+        );
+    }
+}
+
+fn collect_changes(
+    upper_dir: &Path,
+    lower_dir: &Path,
+    changes: &mut OverlayChanges,
+) -> io::Result<()> {
+    if is_opaque(upper_dir)
+        && let Ok(entries) = fs::read_dir(lower_dir)
+    {
+        // The folder was removed and recreated: whatever the command did not
+        // write again is gone.
+        for entry in entries {
+            let entry = entry?;
+            if fs::symlink_metadata(upper_dir.join(entry.file_name())).is_err() {
+                collect_deleted(&entry.path(), changes)?;
+            }
+        }
+    }
+
+    for entry in fs::read_dir(upper_dir)? {
+        let entry = entry?;
+        let upper = entry.path();
+        let lower = lower_dir.join(entry.file_name());
+        let metadata = entry.metadata()?;
+        let lower_metadata = fs::symlink_metadata(&lower).ok();
+        if is_whiteout(&metadata) {
+            collect_deleted(&lower, changes)?;
+        } else if metadata.is_dir() {
+            if lower_metadata.is_some_and(|lower| !lower.is_dir()) {
+                collect_deleted(&lower, changes)?;
+            }
+            collect_changes(&upper, &lower, changes)?;
+        } else if metadata.is_symlink() {
+            let target = fs::read_link(&upper)?;
+            if fs::read_link(&lower).ok().as_ref() == Some(&target) {
+                continue;
+            }
+            if lower_metadata.is_some_and(|lower| lower.is_dir()) {
+                collect_deleted(&lower, changes)?;
+            }
+            changes.insert(
+                lower,
+                FileChange::Add {
+                    content: target.display().to_string(),
+                },
+            );
+        } else if lower_metadata
+            .as_ref()
+            .is_some_and(|lower_metadata| lower_metadata.is_file())
+        {
+            if files_are_equal(&upper, &lower)? {
+                continue;
+            }
+            if changes.is_full() {
+                changes.unlisted += 1;
+                continue;
+            }
+            let unified_diff = match changes
+                .read_text(&[lower.as_path(), upper.as_path()])?
+                .as_deref()
+            {
+                Some([old_content, new_content]) => TextDiff::from_lines(old_content, new_content)
+                    .unified_diff()
+                    .context_radius(1)
+                    .to_string(),
+                _ => {
+                    changes.without_content += 1;
+                    String::new()
+                }
+            };
+            changes.insert(
+                lower,
+                FileChange::Update {
+                    unified_diff,
+                    move_path: None,
+                },
+            );
+        } else {
+            if lower_metadata.is_some_and(|lower_metadata| lower_metadata.is_dir()) {
+                collect_deleted(&lower, changes)?;
+            }
+            let content = file_content(&upper, changes)?;
+            changes.insert(lower, FileChange::Add { content });
+        }
+    }
+    Ok(())
+}
+
+/// Records `path`, and everything below it, as deleted.
+fn collect_deleted(path: &Path, changes: &mut OverlayChanges) -> io::Result<()> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            collect_deleted(&entry?.path(), changes)?;
+        }
+        return Ok(());
+    }
+    let content = if metadata.is_symlink() {
+        fs::read_link(path)?.display().to_string()
+    } else {
+        file_content(path, changes)?
+    };
+    changes.insert(path.to_path_buf(), FileChange::Delete { content });
+    Ok(())
+}
+
+/// Content to show for a listed file: empty when it is left out.
+fn file_content(path: &Path, changes: &mut OverlayChanges) -> io::Result<String> {
+    if changes.is_full() {
+        return Ok(String::new());
+    }
+    match changes.read_text(&[path])? {
+        Some(mut texts) => Ok(texts.pop().unwrap_or_default()),
+        None => {
+            changes.without_content += 1;
+            Ok(String::new())
+        }
+    }
+}
+
+fn commit_dir(upper_dir: &Path, target_dir: &Path) -> io::Result<()> {
+    if is_opaque(upper_dir)
+        && let Ok(entries) = fs::read_dir(target_dir)
+    {
+        for entry in entries {
+            let entry = entry?;
+            if fs::symlink_metadata(upper_dir.join(entry.file_name())).is_err() {
+                remove_path(&entry.path())?;
+            }
+        }
+    }
+
+    for entry in fs::read_dir(upper_dir)? {
+        let entry = entry?;
+        let upper = entry.path();
+        let target = target_dir.join(entry.file_name());
+        let metadata = entry.metadata()?;
+        let target_metadata = fs::symlink_metadata(&target).ok();
+        if is_whiteout(&metadata) {
+            remove_path(&target)?;
+        } else if metadata.is_dir() {
+            if !target_metadata.is_some_and(|target| target.is_dir()) {
+                remove_path(&target)?;
+                fs::create_dir(&target)?;
+            }
+            commit_dir(&upper, &target)?;
+            fs::set_permissions(&target, metadata.permissions())?;
+        } else if metadata.is_symlink() {
+            remove_path(&target)?;
+            std::os::unix::fs::symlink(fs::read_link(&upper)?, &target)?;
+        } else {
+            // `fs::copy` would follow a symlink at the destination.
+            if !target_metadata.is_some_and(|target| target.is_file()) {
+                remove_path(&target)?;
+            }
+            fs::copy(&upper, &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err),
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path),
+        Ok(_) => fs::remove_file(path),
+    }
+}
+
+/// Overlayfs records deletions as 0/0 character devices.
+fn is_whiteout(metadata: &Metadata) -> bool {
+    metadata.file_type().is_char_device() && metadata.rdev() == 0
+}
+
+/// Whether `dir` hides the lower folder of the same name entirely.
+fn is_opaque(dir: &Path) -> bool {
+    let Ok(path) = CString::new(dir.as_os_str().as_bytes()) else {
+        return false;
+    };
+    OPAQUE_XATTRS.iter().any(|name| {
+        let mut value = [0u8; 1];
+        let len = unsafe {
+            libc::lgetxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_mut_ptr().cast(),
+                value.len(),
+            )
+        };
+        len == 1 && value[0] == b'y'
+    })
+}
+
+/// The file's content if it is UTF-8 text without NUL bytes.
+fn read_text(path: &Path) -> io::Result<Option<String>> {
+    Ok(String::from_utf8(fs::read(path)?)
+        .ok()
+        .filter(|text| !text.contains('\0')))
+}
+
+/// Compares two files without reading either of them whole.
+fn files_are_equal(a: &Path, b: &Path) -> io::Result<bool> {
+    if fs::metadata(a)?.len() != fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut a = io::BufReader::new(fs::File::open(a)?);
+    let mut b = io::BufReader::new(fs::File::open(b)?);
+    let mut buf_a = [0u8; 8192];
+    let mut buf_b = [0u8; 8192];
+    loop {
+        let read = a.read(&mut buf_a)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        b.read_exact(&mut buf_b[..read])?;
+        if buf_a[..read] != buf_b[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Lays out an upper directory the way overlayfs would after a command
+    /// edited and created files.
+    fn overlay_with_changes(workspace: &Path) -> WorkspaceOverlay {
+        fs::write(workspace.join("edited.txt"), "one\ntwo\nthree\n").unwrap();
+        fs::write(workspace.join("untouched.txt"), "same\n").unwrap();
+
+        let overlay = WorkspaceOverlay::new(workspace).unwrap();
+        let upper = overlay.upper();
+        fs::create_dir_all(upper.join("nested")).unwrap();
+        fs::write(upper.join("edited.txt"), "one\n2\nthree\n").unwrap();
+        fs::write(upper.join("untouched.txt"), "same\n").unwrap();
+        fs::write(upper.join("nested").join("added.txt"), "hello\n").unwrap();
+        overlay
+    }
+
+    #[test]
+    fn changes_describe_edits_and_additions() {
+        let workspace = tempfile::tempdir().unwrap();
+        let overlay = overlay_with_changes(workspace.path());
+
+        let mut changes: Vec<_> = overlay.changes().unwrap().changes.into_iter().collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    workspace.path().join("edited.txt"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n".to_string(),
+                        move_path: None,
+                    },
+                ),
+                (
+                    workspace.path().join("nested").join("added.txt"),
+                    FileChange::Add {
+                        content: "hello\n".to_string(),
+                    },
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn binary_and_large_files_are_listed_without_content() {
+        let workspace = tempfile::tempdir().unwrap();
+        let overlay = WorkspaceOverlay::new(workspace.path()).unwrap();
+        let upper = overlay.upper();
+        fs::create_dir_all(upper.join("target")).unwrap();
+        fs::write(upper.join("target").join("app"), b"\x7fELF\0\0\x01").unwrap();
+        let large = "x".repeat(MAX_FILE_CONTENT_BYTES as usize + 1);
+        fs::write(upper.join("target").join("log.txt"), &large).unwrap();
+
+        let changes = overlay.changes().unwrap();
+
+        assert_eq!(changes.without_content, 2);
+        assert_eq!(
+            changes
+                .changes
+                .get(&workspace.path().join("target").join("app")),
+            Some(&FileChange::Add {
+                content: String::new(),
+            })
+        );
+        assert_eq!(
+            changes
+                .changes
+                .get(&workspace.path().join("target").join("log.txt")),
+            Some(&FileChange::Add {
+                content: String::new(),
+            })
+        );
+        assert!(changes.omissions().is_some());
+    }
+
+    #[test]
+    fn approval_lists_a_bounded_number_of_changes() {
+        let workspace = tempfile::tempdir().unwrap();
+        let overlay = WorkspaceOverlay::new(workspace.path()).unwrap();
+        let upper = overlay.upper().join("node_modules");
+        fs::create_dir_all(&upper).unwrap();
+        for i in 0..MAX_LISTED_CHANGES + 5 {
+            fs::write(upper.join(format!("{i}.js")), "module.exports = 1;\n").unwrap();
+        }
+
+        let changes = overlay.changes().unwrap();
+
+        assert_eq!(changes.changes.len(), MAX_LISTED_CHANGES);
+        assert_eq!(changes.unlisted, 5);
+        assert!(!changes.is_empty());
+    }
+
+    #[test]
+    fn commit_copies_changes_into_the_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let overlay = overlay_with_changes(workspace.path());
+
+        overlay.commit().unwrap();
+
+        let read = |path: &str| fs::read_to_string(workspace.path().join(path)).unwrap();
+        assert_eq!(read("edited.txt"), "one\n2\nthree\n");
+        assert_eq!(read("nested/added.txt"), "hello\n");
+        assert_eq!(read("untouched.txt"), "same\n");
+    }
+}
@@ -628,6 +628,8 @@ fn scenarios() -> Vec<ScenarioSpec> {
         network_access,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     vec![
@@ -1719,3 +1721,73 @@ async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts
 
     Ok(())
 }
+
+#[tokio::test(flavor = "current_thread")]
+async fn approving_selected_hunks_applies_subset_and_reports_feedback() -> Result<()> {
+    let server = start_mock_server().await;
+    let approval_policy = AskForApproval::UnlessTrusted;
+    let sandbox_policy = SandboxPolicy::ReadOnly;
+    let sandbox_policy_for_config = sandbox_policy.clone();
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| {
+            config.approval_policy = Constrained::allow_any(approval_policy);
+            config.sandbox_policy = Constrained::allow_any(sandbox_policy_for_config);
+        });
+    let test = builder.build(&server).await?;
+    let target = test.cwd.path().join("hunks.txt");
+    fs::write(&target, "one\ntwo\nthree\nfour\nfive\nsix\nseven\n")?;
+
+    let call_id = "apply-selected-hunks";
+    let patch = "*** Begin Patch\n*** Update File: hunks.txt\n@@\n one\n-two\n+TWO\n@@\n six\n-seven\n+SEVEN\n*** End Patch\n";
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-hunks-1"),
+            ev_apply_patch_function_call(call_id, patch),
+            ev_completed("resp-hunks-1"),
+        ]),
+    )
+    .await;
+    let results_mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-hunks", "done"),
+            ev_completed("resp-hunks-2"),
+        ]),
+    )
+    .await;
+
+    submit_turn(&test, call_id, approval_policy, sandbox_policy).await?;
+    expect_patch_approval(&test, call_id).await;
+
+    let approved = format!(
+        "*** Begin Patch\n*** Update File: {}\n@@\n one\n-two\n+TWO\n@@\n six\n seven\n*** End Patch\n",
+        target.display()
+    );
+    test.codex
+        .submit(Op::PatchApproval {
+            id: "0".into(),
+            decision: ReviewDecision::ApprovedHunks {
+                patch: approved,
+                feedback: "The user rejected the change to line 7.".to_string(),
+            },
+        })
+        .await?;
+    wait_for_completion(&test).await;
+
+    assert_eq!(
+        fs::read_to_string(&target)?,
+        "one\nTWO\nthree\nfour\nfive\nsix\nseven\n"
+    );
+    let output = results_mock
+        .single_request()
+        .function_call_output_text(call_id)
+        .expect("apply_patch output should be sent back to the model");
+    assert!(
+        output.contains("The user rejected the change to line 7."),
+        "feedback missing from tool output: {output}"
+    );
+
+    Ok(())
+}
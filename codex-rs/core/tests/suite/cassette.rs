@@ -0,0 +1,111 @@
+use anyhow::Result;
+use codex_core::config::Config;
+use codex_core::config::types::CassetteConfig;
+use codex_core::config::types::CassetteMode;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+async fn run_turn(test: &TestCodex) -> Result<Option<String>> {
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+    Ok(wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::TaskComplete(complete) => Some(complete.last_agent_message.clone()),
+        EventMsg::Error(err) => panic!("unexpected error: {}", err.message),
+        _ => None,
+    })
+    .await)
+}
+
+fn cassette_config(
+    mode: CassetteMode,
+    shared: &Path,
+    path: PathBuf,
+) -> impl FnOnce(&mut Config) + Send + 'static {
+    let shared = shared.to_path_buf();
+    move |config| {
+        // Request bodies include the working directory and the skills found
+        // under CODEX_HOME, so both sessions must share them for the replayed
+        // requests to match.
+        config.cwd = shared.join("cwd");
+        config.codex_home = shared.join("home");
+        config.cassette = Some(CassetteConfig { mode, path });
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn recorded_session_replays_without_server() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let shared = TempDir::new()?;
+    std::fs::create_dir_all(shared.path().join("cwd"))?;
+    std::fs::create_dir_all(shared.path().join("home"))?;
+    let cassette_dir = TempDir::new()?;
+    let cassette = cassette_dir.path().join("session.jsonl");
+
+    let server = start_mock_server().await;
+    let mock = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "recorded reply"),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let recorder = test_codex()
+        .with_config(cassette_config(
+            CassetteMode::Record,
+            shared.path(),
+            cassette.clone(),
+        ))
+        .build(&server)
+        .await?;
+    assert_eq!(
+        run_turn(&recorder).await?.as_deref(),
+        Some("recorded reply")
+    );
+    assert_eq!(mock.requests().len(), 1);
+    assert_eq!(std::fs::read_to_string(&cassette)?.lines().count(), 1);
+
+    // Nothing is mounted on this server: any request reaching it would fail.
+    let empty_server = start_mock_server().await;
+    let player = test_codex()
+        .with_config(cassette_config(
+            CassetteMode::Replay,
+            shared.path(),
+            cassette,
+        ))
+        .build(&empty_server)
+        .await?;
+    assert_eq!(run_turn(&player).await?.as_deref(), Some("recorded reply"));
+    assert_eq!(
+        empty_server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .len(),
+        0
+    );
+
+    Ok(())
+}
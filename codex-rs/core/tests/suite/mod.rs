@@ -19,6 +19,7 @@ mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
 mod auth_refresh;
+mod cassette;
 mod cli_stream;
 mod client;
 mod codex_delegate;
@@ -35,6 +36,7 @@ mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod model_fallback;
 mod model_overrides;
 mod model_tools;
 mod models_etag_responses;
@@ -42,6 +44,7 @@ mod otel;
 mod prompt_caching;
 mod quota_exceeded;
 mod read_file;
+mod redaction;
 mod remote_models;
 mod resume;
 mod resume_warning;
@@ -0,0 +1,148 @@
+use anyhow::Result;
+use codex_core::config::types::ModelFallback;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once_match;
+use core_test_support::responses::sse;
+use core_test_support::responses::sse_failed;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
+use pretty_assertions::assert_eq;
+use wiremock::matchers::body_string_contains;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn context_window_error_fails_over_to_next_model() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let primary = mount_sse_once_match(
+        &server,
+        body_string_contains(r#""model":"gpt-5.1""#),
+        sse_failed(
+            "resp-primary",
+            "context_length_exceeded",
+            "Your input exceeds the context window of this model. Please adjust your input and try again.",
+        ),
+    )
+    .await;
+    let fallback = mount_sse_once_match(
+        &server,
+        body_string_contains(r#""model":"gpt-5.1-codex-max""#),
+        sse(vec![
+            ev_response_created("resp-fallback"),
+            ev_assistant_message("msg-1", "handled by the fallback"),
+            ev_completed("resp-fallback"),
+        ]),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.model = Some("gpt-5.1".to_string());
+            let mut provider = config.model_provider.clone();
+            provider.name = "backup".to_string();
+            config.model_fallbacks = vec![ModelFallback {
+                provider_id: "backup".to_string(),
+                provider,
+                model: "gpt-5.1-codex-max".to_string(),
+            }];
+        })
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+
+    let warning = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::Warning(warning) => Some(warning.message.clone()),
+        _ => None,
+    })
+    .await;
+    assert!(
+        warning.starts_with("gpt-5.1 (openai) failed:")
+            && warning.ends_with("Switching to gpt-5.1-codex-max (backup)."),
+        "unexpected warning: {warning}"
+    );
+
+    let last_message = wait_for_event_match(&test.codex, |event| match event {
+        EventMsg::TaskComplete(complete) => Some(complete.last_agent_message.clone()),
+        EventMsg::Error(err) => panic!("unexpected error: {}", err.message),
+        _ => None,
+    })
+    .await;
+    assert_eq!(last_message.as_deref(), Some("handled by the fallback"));
+    assert_eq!(primary.requests().len(), 1);
+    assert_eq!(fallback.requests().len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exhausted_fallback_chain_reports_last_error() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    mount_sse_once_match(
+        &server,
+        body_string_contains(r#""model":"gpt-5.1""#),
+        sse_failed(
+            "resp-primary",
+            "context_length_exceeded",
+            "Your input exceeds the context window of this model. Please adjust your input and try again.",
+        ),
+    )
+    .await;
+    let fallback = mount_sse_once_match(
+        &server,
+        body_string_contains(r#""model":"gpt-5.1-codex-max""#),
+        sse_failed(
+            "resp-fallback",
+            "context_length_exceeded",
+            "Your input exceeds the context window of this model. Please adjust your input and try again.",
+        ),
+    )
+    .await;
+
+    let test = test_codex()
+        .with_config(|config| {
+            config.model = Some("gpt-5.1".to_string());
+            config.model_fallbacks = vec![ModelFallback {
+                provider_id: "openai".to_string(),
+                provider: config.model_provider.clone(),
+                model: "gpt-5.1-codex-max".to_string(),
+            }];
+        })
+        .build(&server)
+        .await?;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "hello".into(),
+            }],
+        })
+        .await?;
+
+    wait_for_event(&test.codex, |event| matches!(event, EventMsg::Error(_))).await;
+    wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::TaskComplete(_))
+    })
+    .await;
+    assert_eq!(fallback.requests().len(), 1);
+
+    Ok(())
+}
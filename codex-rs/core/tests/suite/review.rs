@@ -7,6 +7,8 @@ use codex_core::REVIEW_PROMPT;
 use codex_core::ResponseItem;
 use codex_core::built_in_model_providers;
 use codex_core::config::Config;
+use codex_core::config::types::ReviewPersona;
+use codex_core::config::types::ReviewSeverity;
 use codex_core::protocol::ENVIRONMENT_CONTEXT_OPEN_TAG;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExitedReviewModeEvent;
@@ -87,6 +89,7 @@ async fn review_op_emits_lifecycle_and_review_output() {
                     instructions: "Please review my changes".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -207,6 +210,7 @@ async fn review_op_with_plain_text_emits_review_fallback() {
                     instructions: "Plain text review".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -267,6 +271,7 @@ async fn review_filters_agent_message_related_events() {
                     instructions: "Filter streaming events".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -348,6 +353,7 @@ async fn review_does_not_emit_agent_message_on_structured_output() {
                     instructions: "check structured".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -407,6 +413,7 @@ async fn review_uses_custom_review_model_from_config() {
                     instructions: "use custom model".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -436,6 +443,123 @@ async fn review_uses_custom_review_model_from_config() {
     server.verify().await;
 }
 
+/// Reviewer personas run as extra passes next to the default one, each with
+/// its own model and instructions, and their results are merged.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn review_runs_personas_alongside_default_pass() {
+    skip_if_no_network!();
+
+    let review_json = serde_json::json!({
+        "findings": [
+            {
+                "title": "[P2] Missing bounds check",
+                "body": "Index can exceed the buffer.",
+                "confidence_score": 0.7,
+                "priority": 2,
+                "code_location": {
+                    "absolute_file_path": "/tmp/file.rs",
+                    "line_range": {"start": 3, "end": 4}
+                }
+            }
+        ],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "Bounds check missing.",
+        "overall_confidence_score": 0.6
+    })
+    .to_string();
+    let sse_raw = r#"[
+            {"type":"response.output_item.done", "item":{
+                "type":"message", "role":"assistant",
+                "content":[{"type":"output_text","text":__REVIEW__}]
+            }},
+            {"type":"response.completed", "response": {"id": "__ID__"}}
+        ]"#
+    .replace("__REVIEW__", &serde_json::to_string(&review_json).unwrap());
+    let server = start_responses_server_with_sse(&sse_raw, 2).await;
+    let codex_home = TempDir::new().unwrap();
+    let codex = new_conversation_for_server(&server, &codex_home, |cfg| {
+        cfg.review_model = "gpt-5.1".to_string();
+        cfg.review_personas = vec![ReviewPersona {
+            name: "security".to_string(),
+            instructions: "Only report security issues.".to_string(),
+            model: "gpt-5.1-codex".to_string(),
+            severity_threshold: Some(ReviewSeverity::High),
+        }];
+    })
+    .await;
+
+    codex
+        .submit(Op::Review {
+            review_request: ReviewRequest {
+                target: ReviewTarget::Custom {
+                    instructions: "Please review my changes".to_string(),
+                },
+                user_facing_hint: None,
+                test_output: None,
+            },
+        })
+        .await
+        .unwrap();
+
+    // Only the default pass streams its turn events; the persona pass runs
+    // silently next to it and never ends the turn with an error.
+    let mut passes_started = 0;
+    let mut errors = 0;
+    let closed = wait_for_event(&codex, |ev| {
+        match ev {
+            EventMsg::TaskStarted(_) => passes_started += 1,
+            EventMsg::Error(_) => errors += 1,
+            _ => {}
+        }
+        matches!(ev, EventMsg::ExitedReviewMode(_))
+    })
+    .await;
+    assert_eq!(passes_started, 1);
+    assert_eq!(errors, 0);
+    let EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+        review_output: Some(review),
+    }) = closed
+    else {
+        panic!("expected ExitedReviewMode with a review, got {closed:?}");
+    };
+    let _complete = wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    // The persona only keeps high-severity findings, so the P2 finding comes
+    // from the default pass alone.
+    assert_eq!(
+        review
+            .findings
+            .iter()
+            .map(|f| f.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["[P2] Missing bounds check"]
+    );
+    assert_eq!(review.overall_correctness, "patch is incorrect");
+    assert_eq!(
+        review.overall_explanation,
+        "general: Bounds check missing.\n\nsecurity: Bounds check missing."
+    );
+
+    let requests = get_responses_requests(&server).await;
+    let mut models: Vec<String> = requests
+        .iter()
+        .map(|request| {
+            let body = request.body_json::<serde_json::Value>().unwrap();
+            let instructions = body["instructions"].as_str().unwrap_or_default();
+            let model = body["model"].as_str().unwrap().to_string();
+            assert_eq!(
+                instructions.contains("Only report security issues."),
+                model == "gpt-5.1-codex"
+            );
+            model
+        })
+        .collect();
+    models.sort();
+    assert_eq!(models, vec!["gpt-5.1", "gpt-5.1-codex"]);
+
+    server.verify().await;
+}
+
 /// When a review session begins, it must not prepend prior chat history from
 /// the parent session. The request `input` should contain only the review
 /// prompt from the user.
@@ -529,6 +653,7 @@ async fn review_input_isolated_from_parent_history() {
                     instructions: review_prompt.clone(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
@@ -642,6 +767,7 @@ async fn review_history_surfaces_in_parent_session() {
                     instructions: "Start a review".to_string(),
                 },
                 user_facing_hint: None,
+                test_output: None,
             },
         })
         .await
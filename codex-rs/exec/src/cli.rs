@@ -118,7 +118,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "commit", "range", "patch", "paths", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -126,7 +126,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "commit", "range", "patch", "paths", "prompt"]
     )]
     pub base: Option<String>,
 
@@ -134,7 +134,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "range", "patch", "paths", "prompt"]
     )]
     pub commit: Option<String>,
 
@@ -142,9 +142,57 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review the changes in a commit range, e.g. `main..feature`.
+    #[arg(
+        long = "range",
+        value_name = "A..B",
+        conflicts_with_all = ["uncommitted", "base", "commit", "patch", "paths", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Review the changes in a patch or diff file without applying it. A
+    /// relative path is read from the current directory, not from `-C`.
+    #[arg(
+        long = "patch",
+        value_name = "FILE",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "paths", "prompt"]
+    )]
+    pub patch: Option<PathBuf>,
+
+    /// Review the current contents of these files or directories.
+    #[arg(
+        long = "path",
+        value_name = "PATH",
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "patch", "prompt"]
+    )]
+    pub paths: Vec<PathBuf>,
+
+    /// File with the output of a failing test run to give the reviewer as
+    /// context.
+    #[arg(long = "test-output", value_name = "FILE")]
+    pub test_output: Option<PathBuf>,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Format of the final review printed to stdout (and written to
+    /// `--output-last-message`).
+    #[arg(long = "format", value_enum, default_value_t = ReviewFormat::Text)]
+    pub format: ReviewFormat,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// Plain text summary.
+    #[default]
+    Text,
+    /// SARIF 2.1.0 log, for code scanning tools.
+    Sarif,
+    /// JSON list of inline comments with file, line range, severity and
+    /// suggested replacement.
+    Comments,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
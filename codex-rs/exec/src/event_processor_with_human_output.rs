@@ -16,6 +16,7 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::SandboxDenialEvent;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
@@ -62,6 +63,7 @@ pub(crate) struct EventProcessorWithHumanOutput {
     show_raw_agent_reasoning: bool,
     last_message_path: Option<PathBuf>,
     last_total_token_usage: Option<codex_core::protocol::TokenUsageInfo>,
+    last_cost: Option<codex_core::protocol::CostSummary>,
     final_message: Option<String>,
 }
 
@@ -88,6 +90,7 @@ impl EventProcessorWithHumanOutput {
                 show_raw_agent_reasoning: config.show_raw_agent_reasoning,
                 last_message_path,
                 last_total_token_usage: None,
+                last_cost: None,
                 final_message: None,
             }
         } else {
@@ -105,6 +108,7 @@ impl EventProcessorWithHumanOutput {
                 show_raw_agent_reasoning: config.show_raw_agent_reasoning,
                 last_message_path,
                 last_total_token_usage: None,
+                last_cost: None,
                 final_message: None,
             }
         }
@@ -173,6 +177,23 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::SandboxDenial(SandboxDenialEvent {
+                denials,
+                suggested_amendments,
+                ..
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} the command was likely blocked by the sandbox",
+                    "sandbox:".style(self.yellow).style(self.bold)
+                );
+                for denial in denials {
+                    ts_msg!(self, "  {}", denial.evidence.style(self.dimmed));
+                }
+                for amendment in suggested_amendments {
+                    ts_msg!(self, "  suggestion: {amendment}");
+                }
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -261,6 +282,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::TokenCount(ev) => {
                 self.last_total_token_usage = ev.info;
+                if ev.cost.is_some() {
+                    self.last_cost = ev.cost;
+                }
             }
 
             EventMsg::AgentReasoningSectionBreak(_) => {
@@ -299,6 +323,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 aggregated_output,
                 duration,
                 exit_code,
+                exit_reason,
                 ..
             }) => {
                 let duration = format!(" in {}", format_duration(duration));
@@ -314,7 +339,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         ts_msg!(self, "{}", title.style(self.green));
                     }
                     _ => {
-                        let title = format!(" exited {exit_code}{duration}:");
+                        let reason = exit_reason
+                            .map(|reason| format!(" (exceeded the sandbox {reason})"))
+                            .unwrap_or_default();
+                        let title = format!(" exited {exit_code}{duration}{reason}:");
                         ts_msg!(self, "{}", title.style(self.red));
                     }
                 }
@@ -594,6 +622,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
             | EventMsg::SkillsUpdateAvailable
+            | EventMsg::SandboxAmendmentApplied(_)
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_) => {}
         }
@@ -608,6 +637,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 format_with_separators(usage_info.total_token_usage.blended_total())
             );
         }
+        if let Some(cost) = &self.last_cost {
+            eprintln!(
+                "{}\n${:.4}",
+                "estimated cost".style(self.magenta).style(self.italic),
+                cost.session_usd
+            );
+        }
 
         // If the user has not piped the final message to a file, they will see
         // it twice: once written to stderr as part of the normal event
@@ -10,9 +10,11 @@ mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
 
+use anyhow::Context;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::ReviewFormat;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
 use codex_core::AuthManager;
@@ -31,6 +33,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
@@ -43,6 +46,7 @@ use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 use supports_color::Stream;
 use tracing::debug;
@@ -314,6 +318,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             .new_conversation(config.clone())
             .await?
     };
+    let review_format = match &command {
+        Some(ExecCommand::Review(review_cli)) => review_cli.format,
+        _ => ReviewFormat::Text,
+    };
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
             let review_request = build_review_request(review_cli)?;
@@ -445,7 +453,9 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
-    while let Some(event) = rx.recv().await {
+    let review_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.to_path_buf());
+    let mut review_output: Option<ReviewOutputEvent> = None;
+    while let Some(mut event) = rx.recv().await {
         if let EventMsg::ElicitationRequest(ev) = &event.msg {
             // Automatically cancel elicitation requests in exec mode.
             conversation
@@ -459,6 +469,20 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         if matches!(event.msg, EventMsg::Error(_)) {
             error_seen = true;
         }
+        // Structured review formats replace the final message so that it is
+        // what lands on stdout and in --output-last-message.
+        match &mut event.msg {
+            EventMsg::ExitedReviewMode(ev) => review_output = ev.review_output.clone(),
+            EventMsg::TaskComplete(ev) => {
+                if let Some(output) = review_output.take()
+                    && let Some(rendered) =
+                        render_structured_review(review_format, &output, &review_root)
+                {
+                    ev.last_agent_message = Some(rendered);
+                }
+            }
+            _ => {}
+        }
         let shutdown: CodexStatus = event_processor.process_event(event);
         match shutdown {
             CodexStatus::Running => continue,
@@ -563,6 +587,19 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
+fn render_structured_review(
+    format: ReviewFormat,
+    output: &ReviewOutputEvent,
+    root: &Path,
+) -> Option<String> {
+    let value = match format {
+        ReviewFormat::Text => return None,
+        ReviewFormat::Sarif => codex_core::review_format::render_review_sarif(output, root),
+        ReviewFormat::Comments => codex_core::review_format::render_review_comments(output, root),
+    };
+    Some(format!("{value:#}"))
+}
+
 fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
     let target = if args.uncommitted {
         ReviewTarget::UncommittedChanges
@@ -573,6 +610,17 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        ReviewTarget::CommitRange { range }
+    } else if let Some(path) = args.patch {
+        // Resolve against the shell's directory, like `--test-output`; core
+        // would otherwise read it relative to the session cwd (`-C`).
+        let path = std::env::current_dir()
+            .context("Failed to resolve the current directory")?
+            .join(path);
+        ReviewTarget::PatchFile { path }
+    } else if !args.paths.is_empty() {
+        ReviewTarget::Paths { paths: args.paths }
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -583,13 +631,21 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --range, --patch, --path, or provide custom review instructions"
         );
     };
+    let test_output = args
+        .test_output
+        .map(|path| {
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read test output file {}", path.display()))
+        })
+        .transpose()?;
 
     Ok(ReviewRequest {
         target,
         user_facing_hint: None,
+        test_output,
     })
 }
 
@@ -605,13 +661,19 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            test_output: None,
             prompt: None,
+            format: ReviewFormat::Text,
         })
         .expect("builds uncommitted review request");
 
         let expected = ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            test_output: None,
         };
 
         assert_eq!(request, expected);
@@ -624,7 +686,12 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            test_output: None,
             prompt: None,
+            format: ReviewFormat::Text,
         })
         .expect("builds commit review request");
 
@@ -634,6 +701,7 @@ mod tests {
                 title: Some("Add review command".to_string()),
             },
             user_facing_hint: None,
+            test_output: None,
         };
 
         assert_eq!(request, expected);
@@ -646,7 +714,12 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            range: None,
+            patch: None,
+            paths: Vec::new(),
+            test_output: None,
             prompt: Some("  custom review instructions  ".to_string()),
+            format: ReviewFormat::Text,
         })
         .expect("builds custom review request");
 
@@ -655,6 +728,40 @@ mod tests {
                 instructions: "custom review instructions".to_string(),
             },
             user_facing_hint: None,
+            test_output: None,
+        };
+
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn builds_patch_review_request_with_test_output() {
+        let dir = tempfile::TempDir::new().expect("tempdir");
+        let test_output = dir.path().join("test.log");
+        std::fs::write(&test_output, "1 test failed\n").expect("write test output");
+
+        let request = build_review_request(ReviewArgs {
+            uncommitted: false,
+            base: None,
+            commit: None,
+            commit_title: None,
+            range: None,
+            patch: Some(PathBuf::from("incoming.patch")),
+            paths: Vec::new(),
+            test_output: Some(test_output),
+            prompt: None,
+            format: ReviewFormat::Text,
+        })
+        .expect("builds patch review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::PatchFile {
+                path: std::env::current_dir()
+                    .expect("current dir")
+                    .join("incoming.patch"),
+            },
+            user_facing_hint: None,
+            test_output: Some("1 test failed\n".to_string()),
         };
 
         assert_eq!(request, expected);
@@ -683,6 +683,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             exit_code: 0,
             duration: Duration::from_millis(5),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     );
     let out_ok = ep.collect_thread_events(&end_ok);
@@ -770,6 +771,7 @@ fn command_execution_output_delta_updates_item_progress() {
             exit_code: 0,
             duration: Duration::from_millis(3),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     );
     let out_end = ep.collect_thread_events(&end);
@@ -843,6 +845,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             exit_code: 1,
             duration: Duration::from_millis(2),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     );
     let out_fail = ep.collect_thread_events(&end_fail);
@@ -884,6 +887,7 @@ fn exec_command_end_without_begin_is_ignored() {
             exit_code: 0,
             duration: Duration::from_millis(1),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     );
     let out = ep.collect_thread_events(&end_only);
@@ -1054,6 +1058,7 @@ fn task_complete_produces_turn_completed_with_usage() {
         EventMsg::TokenCount(codex_core::protocol::TokenCountEvent {
             info: Some(info),
             rate_limits: None,
+            cost: None,
         }),
     );
     assert!(ep.collect_thread_events(&token_count_event).is_empty());
@@ -1073,6 +1078,7 @@ fn task_complete_produces_turn_completed_with_usage() {
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 345,
+                cost_usd: None,
             },
         })]
     );
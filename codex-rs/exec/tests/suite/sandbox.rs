@@ -42,6 +42,7 @@ async fn spawn_command_under_sandbox(
     env: HashMap<String, String>,
 ) -> std::io::Result<Child> {
     use codex_core::landlock::spawn_command_under_linux_sandbox;
+    use codex_core::protocol::SandboxResourceLimits;
     let codex_linux_sandbox_exe = codex_utils_cargo_bin::cargo_bin("codex-exec")
         .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?;
     spawn_command_under_linux_sandbox(
@@ -50,6 +51,7 @@ async fn spawn_command_under_sandbox(
         command_cwd,
         sandbox_policy,
         sandbox_cwd,
+        &SandboxResourceLimits::default(),
         stdio_policy,
         env,
     )
@@ -74,6 +76,8 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     let python_code = r#"import multiprocessing
@@ -173,6 +177,8 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
@@ -1,4 +1,6 @@
 use std::collections::BTreeMap;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
 use std::path::Path;
 
 use codex_core::error::CodexErr;
@@ -27,47 +29,78 @@ use seccompiler::apply_filter;
 
 /// Apply sandbox policies inside this thread so only the child inherits
 /// them, not the entire CLI process.
+///
+/// `proxied_network` means the thread already lives in a network namespace
+/// whose only way out is the egress proxy, so IP sockets are left usable and
+/// Unix sockets are blocked instead.
+///
+/// With `notify_denials`, refused system calls are handed to the returned
+/// seccomp listener instead of failing on their own; see
+/// [`crate::seccomp_notify`]. `None` means no filter was needed or the kernel
+/// lacks user notification, in which case calls fail with `EPERM` directly.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
-) -> Result<()> {
-    if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
-    }
+    proxied_network: bool,
+    notify_denials: bool,
+) -> Result<Option<OwnedFd>> {
+    let listener = if sandbox_policy.has_full_network_access() {
+        None
+    } else {
+        install_network_seccomp_filter_on_current_thread(proxied_network, notify_denials)?
+    };
 
-    if !sandbox_policy.has_full_disk_write_access() {
+    if !sandbox_policy.has_full_disk_write_access() || !sandbox_policy.has_full_disk_read_access() {
         let writable_roots = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        let readable_roots = (!sandbox_policy.has_full_disk_read_access())
+            .then(|| sandbox_policy.get_readable_roots_with_cwd(cwd));
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, readable_roots)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
-    Ok(())
+    Ok(listener)
 }
 
-/// Installs Landlock file-system rules on the current thread allowing read
-/// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// Locations that stay readable when a policy restricts reads, so that
+/// programs, shared libraries and system configuration can still be loaded.
+const SYSTEM_READ_ROOTS: &[&str] = &[
+    "/bin", "/sbin", "/usr", "/lib", "/lib32", "/lib64", "/etc", "/opt", "/dev", "/proc", "/sys",
+    "/run", "/nix",
+];
+
+/// Installs Landlock file-system rules on the current thread restricting write
+/// access to `/dev/null` and the provided list of `writable_roots`. Reads are
+/// allowed everywhere unless `readable_roots` is set, in which case they are
+/// limited to those roots and [`SYSTEM_READ_ROOTS`].
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    readable_roots: Option<Vec<AbsolutePathBuf>>,
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
     let access_ro = AccessFs::from_read(abi);
 
+    let read_roots: Vec<&Path> = match &readable_roots {
+        None => vec![Path::new("/")],
+        Some(readable_roots) => readable_roots
+            .iter()
+            .map(AbsolutePathBuf::as_path)
+            .chain(SYSTEM_READ_ROOTS.iter().map(Path::new))
+            .filter(|root| root.exists())
+            .collect(),
+    };
+
     let mut ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
         .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .add_rules(landlock::path_beneath_rules(&read_roots, access_ro))?
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
@@ -85,8 +118,19 @@ fn install_filesystem_landlock_rules_on_current_thread(
 }
 
 /// Installs a seccomp filter that blocks outbound network access except for
-/// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+/// AF_UNIX domain sockets. With `allow_ip_sockets`, IP sockets stay usable
+/// because the network namespace confines them to the proxy bridge, but
+/// creating AF_UNIX sockets is denied instead: the network namespace does not
+/// cover Unix sockets bound on the host filesystem (e.g. the Docker daemon,
+/// ssh-agent or D-Bus). `socketpair` stays allowed since its sockets are
+/// connected only to each other.
+///
+/// With `notify`, matching calls raise a user notification on the returned
+/// listener, which is expected to answer them with `EPERM`.
+fn install_network_seccomp_filter_on_current_thread(
+    allow_ip_sockets: bool,
+    notify: bool,
+) -> std::result::Result<Option<OwnedFd>, SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -95,34 +139,45 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
-    deny_syscall(libc::SYS_accept);
-    deny_syscall(libc::SYS_accept4);
-    deny_syscall(libc::SYS_bind);
-    deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmmsg);
-    // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
-    // with their socketpair + child processes for sub-proc management
-    // deny_syscall(libc::SYS_recvfrom);
-    deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
 
-    // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
-    let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
-        0, // first argument (domain)
-        SeccompCmpArgLen::Dword,
-        SeccompCmpOp::Ne,
-        libc::AF_UNIX as u64,
-    )?])?;
-
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
-    rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
+    if allow_ip_sockets {
+        let unix_rule = SeccompRule::new(vec![SeccompCondition::new(
+            0, // first argument (domain)
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Eq,
+            libc::AF_UNIX as u64,
+        )?])?;
+        rules.insert(libc::SYS_socket, vec![unix_rule]);
+    } else {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_accept);
+        deny_syscall(libc::SYS_accept4);
+        deny_syscall(libc::SYS_bind);
+        deny_syscall(libc::SYS_listen);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmmsg);
+        // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
+        // with their socketpair + child processes for sub-proc management
+        // deny_syscall(libc::SYS_recvfrom);
+        deny_syscall(libc::SYS_recvmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+
+        // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
+        let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
+            0, // first argument (domain)
+            SeccompCmpArgLen::Dword,
+            SeccompCmpOp::Ne,
+            libc::AF_UNIX as u64,
+        )?])?;
+
+        rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+        rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
+    }
 
     let filter = SeccompFilter::new(
         rules,
@@ -139,7 +194,38 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     let prog: BpfProgram = filter.try_into()?;
 
+    if notify && let Some(listener) = install_filter_with_listener(prog.clone()) {
+        return Ok(Some(listener));
+    }
     apply_filter(&prog)?;
 
-    Ok(())
+    Ok(None)
+}
+
+/// Installs `prog` with its `EPERM` returns turned into user notifications,
+/// returning the listener. `None` when the kernel does not support them
+/// (before Linux 5.0); nothing is installed then.
+fn install_filter_with_listener(mut prog: BpfProgram) -> Option<OwnedFd> {
+    let deny = libc::SECCOMP_RET_ERRNO | libc::EPERM as u32;
+    for instruction in &mut prog {
+        if instruction.code == (libc::BPF_RET | libc::BPF_K) as u16 && instruction.k == deny {
+            instruction.k = libc::SECCOMP_RET_USER_NOTIF;
+        }
+    }
+    let fprog = libc::sock_fprog {
+        len: u16::try_from(prog.len()).ok()?,
+        filter: prog.as_mut_ptr().cast(),
+    };
+    unsafe {
+        if libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) != 0 {
+            return None;
+        }
+        let fd = libc::syscall(
+            libc::SYS_seccomp,
+            libc::SECCOMP_SET_MODE_FILTER,
+            libc::SECCOMP_FILTER_FLAG_NEW_LISTENER,
+            &fprog,
+        );
+        (fd >= 0).then(|| OwnedFd::from_raw_fd(fd as libc::c_int))
+    }
 }
@@ -2,6 +2,16 @@
 mod landlock;
 #[cfg(target_os = "linux")]
 mod linux_run_main;
+#[cfg(target_os = "linux")]
+mod namespace;
+#[cfg(target_os = "linux")]
+mod network_proxy;
+#[cfg(target_os = "linux")]
+mod resource_limits;
+#[cfg(target_os = "linux")]
+mod seccomp_notify;
+#[cfg(target_os = "linux")]
+mod supervise;
 
 #[cfg(target_os = "linux")]
 pub fn run_main() -> ! {
@@ -3,6 +3,11 @@ use std::ffi::CString;
 use std::path::PathBuf;
 
 use crate::landlock::apply_sandbox_policy_to_current_thread;
+use crate::namespace::isolate_mounts;
+use crate::namespace::mask_denied_paths;
+use crate::network_proxy::enter_proxied_network_namespace;
+use crate::resource_limits::apply_resource_limits;
+use crate::seccomp_notify::supervise_refused_syscalls;
 
 #[derive(Debug, Parser)]
 pub struct LandlockCommand {
@@ -14,6 +19,35 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Loopback port, inside the command's private network namespace, that is
+    /// bridged to the egress proxy listening on `--network-proxy-socket`.
+    #[arg(long = "network-proxy-port", requires = "network_proxy_socket")]
+    pub network_proxy_port: Option<u16>,
+
+    /// Unix socket of the egress proxy that enforces the domain allow-list.
+    #[arg(long = "network-proxy-socket", requires = "network_proxy_port")]
+    pub network_proxy_socket: Option<PathBuf>,
+
+    /// CPU, memory, process and file-size caps for the command.
+    #[arg(long = "resource-limits")]
+    pub resource_limits: Option<codex_core::protocol::SandboxResourceLimits>,
+
+    /// Descriptor on which to report which cgroup limit killed the command
+    /// and which network system calls the seccomp filter refused. It is
+    /// closed before the command runs.
+    #[arg(long = "report-fd")]
+    pub report_fd: Option<i32>,
+
+    /// Run the command in a private mount namespace: a read-only view of the
+    /// host outside the writable roots and an empty `/tmp` of its own.
+    #[arg(long = "private-mounts")]
+    pub private_mounts: bool,
+
+    /// Cover the workspace with a copy-on-write overlay whose upper and work
+    /// directories live in this folder, leaving the real workspace untouched.
+    #[arg(long = "workspace-overlay", requires = "private_mounts")]
+    pub workspace_overlay: Option<PathBuf>,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,11 +57,77 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        network_proxy_port,
+        network_proxy_socket,
+        resource_limits,
+        report_fd,
+        private_mounts,
+        workspace_overlay,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
-        panic!("error running landlock: {e:?}");
+    // Limits are set up first so the supervising process that owns the
+    // command's cgroup stays outside the namespaces entered below.
+    if let Some(limits) = resource_limits
+        && let Err(e) = apply_resource_limits(&limits, report_fd)
+    {
+        panic!("error applying resource limits: {e}");
+    }
+
+    // Without a working namespace the seccomp filter keeps the network fully
+    // blocked, so failing here only costs the command its proxied access.
+    let proxied_network = match (network_proxy_port, network_proxy_socket) {
+        (Some(port), Some(socket)) if !sandbox_policy.has_full_network_access() => {
+            match enter_proxied_network_namespace(port, &socket) {
+                Ok(()) => true,
+                Err(err) => {
+                    eprintln!("codex-linux-sandbox: network proxy unavailable: {err}");
+                    false
+                }
+            }
+        }
+        _ => false,
+    };
+
+    // Writes the command expects to keep (or have reviewed, with an overlay)
+    // must not silently go to a different place, so this fails closed too.
+    if private_mounts
+        && !sandbox_policy.has_full_disk_write_access()
+        && let Err(e) = isolate_mounts(
+            &sandbox_policy,
+            &sandbox_policy_cwd,
+            workspace_overlay.as_deref(),
+        )
+    {
+        panic!("error setting up private mounts: {e}");
+    }
+
+    // Fail closed: running the command with denied paths visible would
+    // silently weaken the policy.
+    let deny_read = sandbox_policy.get_deny_read_paths();
+    if !deny_read.is_empty()
+        && let Err(e) = mask_denied_paths(deny_read)
+    {
+        panic!("error hiding deny_read paths: {e}");
+    }
+
+    let listener = match apply_sandbox_policy_to_current_thread(
+        &sandbox_policy,
+        &sandbox_policy_cwd,
+        proxied_network,
+        report_fd.is_some(),
+    ) {
+        Ok(listener) => listener,
+        Err(e) => panic!("error running landlock: {e:?}"),
+    };
+
+    if let Some(fd) = report_fd {
+        if let Some(listener) = listener {
+            supervise_refused_syscalls(listener, fd);
+        }
+        unsafe {
+            libc::close(fd);
+        }
     }
 
     if command.is_empty() {
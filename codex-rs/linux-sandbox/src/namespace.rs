@@ -0,0 +1,345 @@
+use std::ffi::CString;
+use std::fs::File;
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::WritableRoot;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+const TMP: &str = "/tmp";
+
+/// Kernel and device file systems keep their mount flags: commands still need
+/// to write to `/dev/null`, `/dev/shm` and friends.
+const SPECIAL_MOUNT_ROOTS: &[&str] = &["/dev", "/proc", "/sys"];
+
+/// Unshares the namespaces in `flags`. Callers without `CAP_SYS_ADMIN` get a
+/// user namespace as well, with the current uid/gid mapped to themselves so
+/// file ownership is unchanged.
+///
+/// The process must still be single-threaded.
+pub(crate) fn unshare_namespaces(flags: libc::c_int) -> io::Result<()> {
+    if unsafe { libc::unshare(flags) } == 0 {
+        return Ok(());
+    }
+
+    let uid = unsafe { libc::getuid() };
+    let gid = unsafe { libc::getgid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | flags) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    match std::fs::write("/proc/self/setgroups", "deny") {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+    }
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+    Ok(())
+}
+
+/// Hides `paths` from the command by mounting over them in a private mount
+/// namespace: folders get an empty read-only tmpfs and files are replaced by
+/// `/dev/null`. Paths that do not exist are skipped.
+///
+/// Landlock cannot carve a denied path out of a readable parent, hence the
+/// mounts. They must be in place before Landlock is applied, which in turn
+/// prevents the command from unmounting them.
+pub(crate) fn mask_denied_paths(paths: &[AbsolutePathBuf]) -> io::Result<()> {
+    unshare_namespaces(libc::CLONE_NEWNS)?;
+    // Keep the masks from propagating back to the parent mount namespace.
+    mount(
+        None,
+        Path::new("/"),
+        None,
+        libc::MS_REC | libc::MS_PRIVATE,
+        None,
+    )?;
+
+    for path in paths {
+        let Ok(metadata) = std::fs::metadata(path) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            mount(
+                Some("tmpfs"),
+                path.as_path(),
+                Some("tmpfs"),
+                libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                Some("size=0,mode=000"),
+            )?;
+        } else {
+            mount(Some("/dev/null"), path.as_path(), None, libc::MS_BIND, None)?;
+        }
+    }
+    Ok(())
+}
+
+/// Gives the command a view of the file system of its own, on top of
+/// Landlock: every mount outside the writable roots becomes read-only, `/tmp`
+/// is a fresh tmpfs, and with `workspace_overlay` the workspace is covered by
+/// a copy-on-write overlay whose changes land in `<workspace_overlay>/upper`
+/// instead of the real folder.
+///
+/// Must run before Landlock is applied.
+pub(crate) fn isolate_mounts(
+    sandbox_policy: &SandboxPolicy,
+    sandbox_policy_cwd: &Path,
+    workspace_overlay: Option<&Path>,
+) -> io::Result<()> {
+    let cwd = std::env::current_dir()?;
+    let writable_roots = sandbox_policy.get_writable_roots_with_cwd(sandbox_policy_cwd);
+    let workspace = sandbox_policy_cwd;
+    let tmp = Path::new(TMP);
+
+    unshare_namespaces(libc::CLONE_NEWNS)?;
+    mount(
+        None,
+        Path::new("/"),
+        None,
+        libc::MS_REC | libc::MS_PRIVATE,
+        None,
+    )?;
+    // Keeps the host `/tmp` reachable once the private tmpfs covers it.
+    let host_tmp = File::open(tmp)?;
+
+    if let Some(overlay) = workspace_overlay {
+        mount_workspace_overlay(workspace, overlay)?;
+    }
+
+    for mount_point in mount_points()? {
+        if SPECIAL_MOUNT_ROOTS
+            .iter()
+            .any(|root| mount_point.starts_with(root))
+            || (workspace_overlay.is_some() && mount_point == workspace)
+        {
+            continue;
+        }
+        // Mounts the command cannot reach anyway may refuse the remount;
+        // only the root file system has to succeed.
+        if let Err(err) = remount(&mount_point, true)
+            && mount_point == Path::new("/")
+        {
+            return Err(err);
+        }
+    }
+
+    for writable_root in &writable_roots {
+        let root = writable_root.root.as_path();
+        if root.starts_with(tmp) || !root.exists() {
+            continue;
+        }
+        if !(workspace_overlay.is_some() && root == workspace) {
+            bind_writable(&root.to_string_lossy(), root)?;
+        }
+        protect_read_only_subpaths(writable_root)?;
+    }
+
+    mount(
+        Some("tmpfs"),
+        tmp,
+        Some("tmpfs"),
+        libc::MS_NOSUID | libc::MS_NODEV,
+        Some("mode=1777"),
+    )?;
+    for writable_root in &writable_roots {
+        let root = writable_root.root.as_path();
+        let Ok(relative) = root.strip_prefix(tmp) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let source =
+            PathBuf::from(format!("/proc/self/fd/{}", host_tmp.as_raw_fd())).join(relative);
+        let Ok(metadata) = std::fs::metadata(&source) else {
+            continue;
+        };
+        if metadata.is_dir() {
+            std::fs::create_dir_all(root)?;
+        } else {
+            if let Some(parent) = root.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            File::create(root)?;
+        }
+        bind_writable(&source.to_string_lossy(), root)?;
+        protect_read_only_subpaths(writable_root)?;
+    }
+
+    // Pick up the overlay (or re-bound folder) in place of the directory the
+    // process started in. Commands started from a folder that is no longer
+    // visible keep their original working directory.
+    let _ = std::env::set_current_dir(&cwd);
+    Ok(())
+}
+
+fn mount_workspace_overlay(workspace: &Path, overlay: &Path) -> io::Result<()> {
+    let upper = overlay.join("upper");
+    let work = overlay.join("work");
+    std::fs::create_dir_all(&upper)?;
+    std::fs::create_dir_all(&work)?;
+    let option_path = |path: &Path| {
+        path.to_str()
+            .filter(|path| !path.contains([',', ':', '\\']))
+            .map(str::to_string)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} cannot be used in an overlay mount", path.display()),
+                )
+            })
+    };
+    let options = format!(
+        "lowerdir={},upperdir={},workdir={}",
+        option_path(workspace)?,
+        option_path(&upper)?,
+        option_path(&work)?
+    );
+    let flags = libc::MS_NOSUID | libc::MS_NODEV;
+    // Overlays mounted from a user namespace keep their metadata in `user.*`
+    // extended attributes; older kernels do not know the option.
+    mount(
+        Some("overlay"),
+        workspace,
+        Some("overlay"),
+        flags,
+        Some(&format!("{options},userxattr")),
+    )
+    .or_else(|_| {
+        mount(
+            Some("overlay"),
+            workspace,
+            Some("overlay"),
+            flags,
+            Some(&options),
+        )
+    })
+}
+
+/// Bind-mounts `source` over `target` and makes the new mount writable.
+fn bind_writable(source: &str, target: &Path) -> io::Result<()> {
+    mount(
+        Some(source),
+        target,
+        None,
+        libc::MS_BIND | libc::MS_REC,
+        None,
+    )?;
+    remount(target, false)
+}
+
+/// Keeps `.git` and friends read-only inside a writable root.
+fn protect_read_only_subpaths(writable_root: &WritableRoot) -> io::Result<()> {
+    for subpath in &writable_root.read_only_subpaths {
+        let subpath = subpath.as_path();
+        if !subpath.exists() {
+            continue;
+        }
+        mount(
+            Some(&subpath.to_string_lossy()),
+            subpath,
+            None,
+            libc::MS_BIND | libc::MS_REC,
+            None,
+        )?;
+        remount(subpath, true)?;
+    }
+    Ok(())
+}
+
+/// Changes whether the mount at `path` is read-only. The other flags have to
+/// be carried over: a user namespace may not clear flags such as `nosuid` on
+/// mounts it inherited.
+fn remount(path: &Path, read_only: bool) -> io::Result<()> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut flags = libc::MS_REMOUNT | libc::MS_BIND;
+    for (st_flag, ms_flag) in [
+        (libc::ST_NOSUID, libc::MS_NOSUID),
+        (libc::ST_NODEV, libc::MS_NODEV),
+        (libc::ST_NOEXEC, libc::MS_NOEXEC),
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (libc::ST_RELATIME, libc::MS_RELATIME),
+    ] {
+        if stat.f_flag & st_flag != 0 {
+            flags |= ms_flag;
+        }
+    }
+    if read_only {
+        flags |= libc::MS_RDONLY;
+    }
+    mount(None, path, None, flags, None)
+}
+
+/// Mount points of the current mount namespace, parents before children.
+fn mount_points() -> io::Result<Vec<PathBuf>> {
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+    Ok(mountinfo
+        .lines()
+        .filter_map(|line| line.split(' ').nth(4))
+        .map(|mount_point| PathBuf::from(unescape_mountinfo(mount_point)))
+        .collect())
+}
+
+/// Undoes the octal escaping (`\040` for a space, ...) used in mountinfo.
+fn unescape_mountinfo(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && let Some(code) = field
+                .get(i + 1..i + 4)
+                .and_then(|digits| u8::from_str_radix(digits, 8).ok())
+        {
+            out.push(code);
+            i += 4;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn mount(
+    source: Option<&str>,
+    target: &Path,
+    fstype: Option<&str>,
+    flags: libc::c_ulong,
+    data: Option<&str>,
+) -> io::Result<()> {
+    let to_cstring = |value: &[u8]| {
+        CString::new(value).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+    };
+    let source = source.map(|s| to_cstring(s.as_bytes())).transpose()?;
+    let target = to_cstring(target.as_os_str().as_bytes())?;
+    let fstype = fstype.map(|s| to_cstring(s.as_bytes())).transpose()?;
+    let data = data.map(|s| to_cstring(s.as_bytes())).transpose()?;
+    let result = unsafe {
+        libc::mount(
+            source.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            target.as_ptr(),
+            fstype.as_ref().map_or(std::ptr::null(), |s| s.as_ptr()),
+            flags,
+            data.as_ref()
+                .map_or(std::ptr::null(), |s| s.as_ptr().cast::<libc::c_void>()),
+        )
+    };
+    if result != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!("mounting over {}: {err}", target.to_string_lossy()),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+
+use crate::namespace::unshare_namespaces;
+
+/// Moves the current (single-threaded) process into a private network
+/// namespace with only a loopback interface, and forks a bridge that forwards
+/// connections to `127.0.0.1:port` to the egress proxy's Unix socket.
+///
+/// Must run before seccomp and Landlock are applied: the bridge keeps running
+/// outside those restrictions and exits together with the sandboxed command.
+pub(crate) fn enter_proxied_network_namespace(port: u16, proxy_socket: &Path) -> io::Result<()> {
+    unshare_namespaces(libc::CLONE_NEWNET)?;
+    bring_up_loopback()?;
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    spawn_bridge(listener, proxy_socket.to_path_buf())
+}
+
+/// New network namespaces start with `lo` down.
+fn bring_up_loopback() -> io::Result<()> {
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let result = (|| {
+        let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+        for (dst, src) in request.ifr_name.iter_mut().zip(b"lo") {
+            *dst = *src as libc::c_char;
+        }
+        if unsafe { libc::ioctl(fd, libc::SIOCGIFFLAGS as _, &mut request) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        unsafe { request.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short };
+        if unsafe { libc::ioctl(fd, libc::SIOCSIFFLAGS as _, &request) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    })();
+    unsafe { libc::close(fd) };
+    result
+}
+
+fn spawn_bridge(listener: TcpListener, proxy_socket: PathBuf) -> io::Result<()> {
+    let parent = unsafe { libc::getpid() };
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            // Die with the sandboxed command, and do not hold its stdio open.
+            unsafe {
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+                if libc::getppid() != parent {
+                    libc::_exit(0);
+                }
+                let dev_null = libc::open(c"/dev/null".as_ptr(), libc::O_RDWR);
+                if dev_null >= 0 {
+                    for fd in 0..3 {
+                        libc::dup2(dev_null, fd);
+                    }
+                }
+            }
+            run_bridge(listener, &proxy_socket);
+            unsafe { libc::_exit(0) }
+        }
+        _ => Ok(()),
+    }
+}
+
+fn run_bridge(listener: TcpListener, proxy_socket: &Path) {
+    for client in listener.incoming() {
+        let Ok(client) = client else {
+            continue;
+        };
+        let proxy_socket = proxy_socket.to_path_buf();
+        thread::spawn(move || {
+            if let Ok(upstream) = UnixStream::connect(&proxy_socket) {
+                let _ = pipe(client, upstream);
+            }
+        });
+    }
+}
+
+fn pipe(client: TcpStream, upstream: UnixStream) -> io::Result<()> {
+    let mut client_reader = client.try_clone()?;
+    let mut upstream_writer = upstream.try_clone()?;
+    let outbound = thread::spawn(move || {
+        let _ = io::copy(&mut client_reader, &mut upstream_writer);
+        let _ = upstream_writer.shutdown(Shutdown::Write);
+    });
+    let (mut upstream_reader, mut client_writer) = (upstream, client);
+    let _ = io::copy(&mut upstream_reader, &mut client_writer);
+    let _ = client_writer.shutdown(Shutdown::Write);
+    let _ = outbound.join();
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use codex_core::exec::SandboxReport;
+use codex_core::protocol::ExecExitReason;
+use codex_core::protocol::SandboxResourceLimits;
+
+use crate::supervise::exit_with_status;
+use crate::supervise::wait_for;
+use crate::supervise::write_report;
+
+#[cfg(target_env = "musl")]
+type RlimitResource = libc::c_int;
+#[cfg(not(target_env = "musl"))]
+type RlimitResource = libc::__rlimit_resource_t;
+
+/// Applies `limits` to the command about to be exec'd.
+///
+/// Memory and process limits are enforced by a per-command cgroup v2 when one
+/// can be created: the helper then forks, the child continues into the
+/// sandbox inside the cgroup, and this process stays behind to report which
+/// limit killed the command on `report_fd` and to remove the cgroup.
+///
+/// Without a cgroup, a memory limit falls back to `RLIMIT_AS`, which only
+/// caps each process's address space, with a warning on stderr. A process
+/// limit is refused instead: `RLIMIT_NPROC` counts every process of the user,
+/// not the command's.
+pub(crate) fn apply_resource_limits(
+    limits: &SandboxResourceLimits,
+    report_fd: Option<libc::c_int>,
+) -> io::Result<()> {
+    let in_cgroup = if limits.has_cgroup_limits() {
+        match Cgroup::create(limits).and_then(|cgroup| supervise_in_cgroup(cgroup, report_fd)) {
+            Ok(()) => true,
+            Err(err) if limits.max_processes.is_some() => {
+                return Err(io::Error::new(
+                    err.kind(),
+                    format!(
+                        "max_processes needs a per-command cgroup, which could not be set up \
+                         ({err}); point cgroup_parent at a delegated cgroup v2 with no \
+                         processes of its own"
+                    ),
+                ));
+            }
+            Err(err) => {
+                eprintln!(
+                    "codex-linux-sandbox: no cgroup for the memory limit ({err}); \
+                     falling back to RLIMIT_AS, which caps each process separately"
+                );
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    if let Some(secs) = limits.cpu_time_secs {
+        // The soft limit delivers SIGXCPU; the hard limit a second later
+        // SIGKILLs commands that ignore it.
+        set_rlimit(libc::RLIMIT_CPU, secs, secs.saturating_add(1))?;
+    }
+    if let Some(bytes) = limits.max_file_size_bytes {
+        set_rlimit(libc::RLIMIT_FSIZE, bytes, bytes)?;
+    }
+    if !in_cgroup && let Some(bytes) = limits.memory_bytes {
+        set_rlimit(libc::RLIMIT_AS, bytes, bytes)?;
+    }
+    Ok(())
+}
+
+/// Lowers a resource limit, never raising it above the current hard limit.
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    if unsafe { libc::getrlimit(resource, &mut current) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let cap = |value: u64| {
+        let value = value as libc::rlim_t;
+        if current.rlim_max == libc::RLIM_INFINITY {
+            value
+        } else {
+            value.min(current.rlim_max)
+        }
+    };
+    let limit = libc::rlimit {
+        rlim_cur: cap(soft),
+        rlim_max: cap(hard),
+    };
+    if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Forks so the command runs inside `cgroup`. Returns in the child only, with
+/// the result of joining the cgroup; the parent waits for the command,
+/// reports an exceeded limit on `report_fd` and exits with its status.
+fn supervise_in_cgroup(cgroup: Cgroup, report_fd: Option<libc::c_int>) -> io::Result<()> {
+    let parent = unsafe { libc::getpid() };
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            unsafe {
+                libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+                if libc::getppid() != parent {
+                    libc::_exit(1);
+                }
+            }
+            let joined = cgroup.join();
+            // The parent owns the cgroup and removes it once the command ends.
+            std::mem::forget(cgroup);
+            joined
+        }
+        child => {
+            let status = wait_for(child);
+            let failed = !libc::WIFEXITED(status) || libc::WEXITSTATUS(status) != 0;
+            if failed
+                && let Some(fd) = report_fd
+                && let Some(reason) = cgroup.exceeded_limit()
+            {
+                write_report(fd, &SandboxReport::ResourceLimit { reason });
+            }
+            drop(cgroup);
+            exit_with_status(status)
+        }
+    }
+}
+
+/// A cgroup v2 created for a single command.
+struct Cgroup {
+    path: PathBuf,
+}
+
+impl Cgroup {
+    fn create(limits: &SandboxResourceLimits) -> io::Result<Self> {
+        let parent = match &limits.cgroup_parent {
+            Some(parent) => parent.clone(),
+            None => current_cgroup_dir()?,
+        };
+        let mut controllers = Vec::new();
+        if limits.memory_bytes.is_some() {
+            controllers.push("memory");
+        }
+        if limits.max_processes.is_some() {
+            controllers.push("pids");
+        }
+        enable_controllers(&parent, &controllers)?;
+
+        let path = parent.join(format!("codex-sandbox-{}", std::process::id()));
+        fs::create_dir(&path)?;
+        let cgroup = Self { path };
+        if let Some(bytes) = limits.memory_bytes {
+            cgroup.write("memory.max", &bytes.to_string())?;
+            // Without swap accounting this file is absent; the limit still
+            // applies to RAM.
+            let _ = cgroup.write("memory.swap.max", "0");
+        }
+        if let Some(count) = limits.max_processes {
+            cgroup.write("pids.max", &count.to_string())?;
+        }
+        Ok(cgroup)
+    }
+
+    fn join(&self) -> io::Result<()> {
+        self.write("cgroup.procs", "0")
+    }
+
+    fn write(&self, file: &str, value: &str) -> io::Result<()> {
+        fs::write(self.path.join(file), value)
+    }
+
+    /// The limit that was hit, according to the cgroup's event counters.
+    fn exceeded_limit(&self) -> Option<ExecExitReason> {
+        let counter = |file: &str, key: &str| {
+            fs::read_to_string(self.path.join(file))
+                .ok()
+                .and_then(|events| event_count(&events, key))
+                .is_some_and(|count| count > 0)
+        };
+        if counter("memory.events", "oom_kill") {
+            Some(ExecExitReason::MemoryLimit)
+        } else if counter("pids.events", "max") {
+            Some(ExecExitReason::ProcessLimit)
+        } else {
+            None
+        }
+    }
+}
+
+impl Drop for Cgroup {
+    fn drop(&mut self) {
+        // Background processes the command left behind keep the cgroup busy;
+        // kill them (Linux 5.14+) and give them a moment to exit.
+        let _ = self.write("cgroup.kill", "1");
+        for _ in 0..50 {
+            if fs::remove_dir(&self.path).is_ok() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+/// Directory of the cgroup v2 this process belongs to.
+fn current_cgroup_dir() -> io::Result<PathBuf> {
+    let unavailable = || io::Error::new(io::ErrorKind::NotFound, "cgroup v2 is not available");
+    let cgroups = fs::read_to_string("/proc/self/cgroup")?;
+    let relative = cgroups
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .ok_or_else(unavailable)?;
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo")?;
+    let mount_point = cgroup2_mount_point(&mountinfo).ok_or_else(unavailable)?;
+    Ok(mount_point.join(relative.trim_start_matches('/')))
+}
+
+/// Mount point of the cgroup2 file system, from `/proc/self/mountinfo`.
+fn cgroup2_mount_point(mountinfo: &str) -> Option<PathBuf> {
+    mountinfo.lines().find_map(|line| {
+        let (mount, fs_info) = line.split_once(" - ")?;
+        if fs_info.split_whitespace().next()? != "cgroup2" {
+            return None;
+        }
+        mount.split_whitespace().nth(4).map(PathBuf::from)
+    })
+}
+
+/// Makes `controllers` available to child cgroups of `parent`.
+fn enable_controllers(parent: &Path, controllers: &[&str]) -> io::Result<()> {
+    let enabled = fs::read_to_string(parent.join("cgroup.subtree_control"))?;
+    for controller in controllers {
+        if !enabled.split_whitespace().any(|name| name == *controller) {
+            fs::write(
+                parent.join("cgroup.subtree_control"),
+                format!("+{controller}"),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Value of `key` in a flat-keyed cgroup file such as `memory.events`.
+fn event_count(events: &str, key: &str) -> Option<u64> {
+    events.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        (name == key).then(|| value.trim().parse().ok()).flatten()
+    })
+}
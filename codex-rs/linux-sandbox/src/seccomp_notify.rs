@@ -0,0 +1,157 @@
+//! Reports the network system calls the seccomp filter refuses.
+//!
+//! When core passes a report descriptor, the filter raises a user
+//! notification for each call it matches instead of failing it on its own.
+//! The helper then forks: the child goes on to run the command, and this
+//! process stays behind, answers every notification with `EPERM` and, once
+//! the command exits, writes the calls it refused to the report descriptor.
+//! Should the supervisor die first, pending calls fail with `ENOSYS`, so the
+//! filter never lets anything through.
+
+use std::io;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+
+use codex_core::exec::SandboxReport;
+
+use crate::supervise::exit_with_status;
+use crate::supervise::wait_for;
+use crate::supervise::write_report;
+
+// `_IOWR('!', 0, struct seccomp_notif)` and `_IOWR('!', 1, struct
+// seccomp_notif_resp)`; libc does not export them.
+const SECCOMP_IOCTL_NOTIF_RECV: u64 = 0xc050_2100;
+const SECCOMP_IOCTL_NOTIF_SEND: u64 = 0xc018_2101;
+
+/// Most distinct calls reported for one command.
+const MAX_REPORTED_SYSCALLS: usize = 32;
+
+/// How often to check on the command when the kernel has no pidfds.
+const CHILD_POLL_INTERVAL_MS: libc::c_int = 50;
+
+/// Forks so the command runs under the filter behind `listener`. Returns in
+/// the child only; the parent answers the filter's notifications, reports
+/// the refused calls on `report_fd` and exits with the command's status.
+pub(crate) fn supervise_refused_syscalls(listener: OwnedFd, report_fd: libc::c_int) {
+    let parent = unsafe { libc::getpid() };
+    match unsafe { libc::fork() } {
+        // Without a supervisor every filtered call fails with `ENOSYS`, which
+        // still keeps the command off the network.
+        -1 => {}
+        0 => unsafe {
+            libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+            if libc::getppid() != parent {
+                libc::_exit(1);
+            }
+        },
+        child => {
+            let (status, syscalls) = answer_until_exit(&listener, child);
+            for syscall in syscalls {
+                write_report(report_fd, &SandboxReport::BlockedSyscall { syscall });
+            }
+            exit_with_status(status)
+        }
+    }
+}
+
+/// Answers notifications until `child` exits, returning its wait status and
+/// the distinct network calls that were refused.
+fn answer_until_exit(listener: &OwnedFd, child: libc::pid_t) -> (libc::c_int, Vec<String>) {
+    let pidfd = pidfd_open(child);
+    let timeout = if pidfd.is_some() {
+        -1
+    } else {
+        CHILD_POLL_INTERVAL_MS
+    };
+    let mut syscalls: Vec<String> = Vec::new();
+    loop {
+        let mut fds = [
+            libc::pollfd {
+                fd: listener.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                // Negative descriptors are ignored by poll(2).
+                fd: pidfd.as_ref().map_or(-1, AsRawFd::as_raw_fd),
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout) };
+        if ready < 0 && io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            return (wait_for(child), syscalls);
+        }
+        if fds[0].revents & libc::POLLIN != 0
+            && let Some(syscall) = answer(listener)
+            && syscalls.len() < MAX_REPORTED_SYSCALLS
+            && !syscalls.contains(&syscall)
+        {
+            syscalls.push(syscall);
+        }
+        let mut status = 0;
+        if unsafe { libc::waitpid(child, &mut status, libc::WNOHANG) } == child {
+            return (status, syscalls);
+        }
+    }
+}
+
+/// Refuses the pending call with `EPERM`, returning its description when it
+/// is a network call.
+fn answer(listener: &OwnedFd) -> Option<String> {
+    // The kernel rejects requests that are not zeroed.
+    let mut request: libc::seccomp_notif = unsafe { std::mem::zeroed() };
+    let fd = listener.as_raw_fd();
+    if unsafe { libc::ioctl(fd, SECCOMP_IOCTL_NOTIF_RECV as _, &mut request) } != 0 {
+        // The caller was killed before we got to it.
+        return None;
+    }
+    let response = libc::seccomp_notif_resp {
+        id: request.id,
+        val: 0,
+        error: -libc::EPERM,
+        flags: 0,
+    };
+    unsafe {
+        libc::ioctl(fd, SECCOMP_IOCTL_NOTIF_SEND as _, &response);
+    }
+    describe_syscall(request.data.nr, request.data.args[0])
+}
+
+/// `socket(AF_INET)`, `connect`, ...; `None` for calls that are not about the
+/// network, such as `ptrace`, and for queries such as `getpeername` that
+/// programs (bash among them) make on any descriptor just to see whether it
+/// is a socket.
+fn describe_syscall(nr: libc::c_int, first_arg: u64) -> Option<String> {
+    let name = match i64::from(nr) {
+        libc::SYS_socket => return Some(format!("socket({})", family_name(first_arg))),
+        libc::SYS_socketpair => return Some(format!("socketpair({})", family_name(first_arg))),
+        libc::SYS_connect => "connect",
+        libc::SYS_accept => "accept",
+        libc::SYS_accept4 => "accept4",
+        libc::SYS_bind => "bind",
+        libc::SYS_listen => "listen",
+        libc::SYS_sendto => "sendto",
+        libc::SYS_sendmmsg => "sendmmsg",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+fn family_name(domain: u64) -> String {
+    match domain as libc::c_int {
+        libc::AF_UNIX => "AF_UNIX".to_string(),
+        libc::AF_INET => "AF_INET".to_string(),
+        libc::AF_INET6 => "AF_INET6".to_string(),
+        libc::AF_NETLINK => "AF_NETLINK".to_string(),
+        libc::AF_PACKET => "AF_PACKET".to_string(),
+        other => format!("family {other}"),
+    }
+}
+
+/// A descriptor that becomes readable when `child` exits (Linux 5.3+).
+fn pidfd_open(child: libc::pid_t) -> Option<OwnedFd> {
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, child, 0) };
+    (fd >= 0).then(|| unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) })
+}
@@ -0,0 +1,56 @@
+//! Shared by the helper processes that stay behind while the command runs:
+//! the cgroup owner in `resource_limits` and the seccomp supervisor in
+//! `seccomp_notify`.
+
+use std::io;
+
+use codex_core::exec::SandboxReport;
+
+/// Best effort: a caller that did not pass the descriptor simply gets no
+/// report.
+pub(crate) fn write_report(fd: libc::c_int, report: &SandboxReport) {
+    let line = report.to_line();
+    let mut remaining = line.as_bytes();
+    while !remaining.is_empty() {
+        let written = unsafe { libc::write(fd, remaining.as_ptr().cast(), remaining.len()) };
+        if written < 0 {
+            if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return;
+        }
+        remaining = &remaining[written as usize..];
+    }
+}
+
+pub(crate) fn wait_for(child: libc::pid_t) -> libc::c_int {
+    let mut status = 0;
+    loop {
+        if unsafe { libc::waitpid(child, &mut status, 0) } == child {
+            return status;
+        }
+        if io::Error::last_os_error().kind() != io::ErrorKind::Interrupted {
+            // Report the command as killed rather than successful.
+            return libc::SIGKILL;
+        }
+    }
+}
+
+/// Mirrors the command's exit status so callers see the same code or signal.
+pub(crate) fn exit_with_status(status: libc::c_int) -> ! {
+    if libc::WIFSIGNALED(status) {
+        let signal = libc::WTERMSIG(status);
+        unsafe {
+            // Do not dump the helper's core for signals such as SIGXCPU.
+            let no_core = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0,
+            };
+            libc::setrlimit(libc::RLIMIT_CORE, &no_core);
+            libc::signal(signal, libc::SIG_DFL);
+            libc::kill(libc::getpid(), signal);
+        }
+        std::process::exit(128 + signal);
+    }
+    std::process::exit(libc::WEXITSTATUS(status));
+}
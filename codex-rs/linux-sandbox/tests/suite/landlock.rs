@@ -59,6 +59,8 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         // writing to in the sandbox.
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
@@ -189,6 +191,41 @@ async fn assert_network_blocked(cmd: &[&str]) {
     }
 }
 
+#[tokio::test]
+#[expect(clippy::expect_used)]
+async fn sandbox_reports_refused_network_calls() {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let params = ExecParams {
+        command: vec![
+            "bash".to_string(),
+            "-c".to_string(),
+            // The report descriptor must not leak into the command.
+            "test ! -e /proc/self/fd/3 && echo hi > /dev/tcp/127.0.0.1/9".to_string(),
+        ],
+        cwd: cwd.clone(),
+        expiration: NETWORK_TIMEOUT_MS.into(),
+        env: create_env_from_core_vars(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        arg0: None,
+    };
+    let codex_linux_sandbox_exe = Some(PathBuf::from(env!("CARGO_BIN_EXE_codex-linux-sandbox")));
+
+    let result = process_exec_tool_call(
+        params,
+        &SandboxPolicy::new_read_only_policy(),
+        cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        None,
+    )
+    .await;
+
+    let Err(CodexErr::Sandbox(SandboxErr::Denied { output })) = result else {
+        panic!("expected sandbox denied error, got: {result:?}");
+    };
+    assert_eq!(output.blocked_syscalls, vec!["socket(AF_INET)".to_string()]);
+}
+
 #[tokio::test]
 async fn sandbox_blocks_curl() {
     assert_network_blocked(&["curl", "-I", "http://openai.com"]).await;
@@ -238,3 +275,336 @@ async fn sandbox_blocks_dev_tcp_redirection() {
     // all images ship bash, so we guard against 127 as well.
     assert_network_blocked(&["bash", "-c", "echo hi > /dev/tcp/127.0.0.1/80"]).await;
 }
+
+#[tokio::test]
+async fn sandbox_routes_proxy_port_to_network_proxy_socket() {
+    use std::io::BufRead;
+    use std::io::Write;
+
+    let tmpdir = tempfile::tempdir().expect("tempdir");
+    let socket_path = tmpdir.path().join("proxy.sock");
+    let listener = std::os::unix::net::UnixListener::bind(&socket_path).expect("bind proxy socket");
+    let proxy = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept bridged connection");
+        let mut line = String::new();
+        std::io::BufReader::new(&mut stream)
+            .read_line(&mut line)
+            .expect("read from sandbox");
+        stream.write_all(b"pong\n").expect("write to sandbox");
+        line
+    });
+
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .args(["--sandbox-policy", r#"{"type":"read-only"}"#])
+        .args(["--network-proxy-port", "3128"])
+        .arg("--network-proxy-socket")
+        .arg(&socket_path)
+        .args([
+            "--",
+            "bash",
+            "-c",
+            // Direct connections must still fail; only the proxy port works.
+            "! (exec 3<>/dev/tcp/1.1.1.1/80) 2>/dev/null \
+             && exec 3<>/dev/tcp/127.0.0.1/3128 && echo ping >&3 && head -n1 <&3",
+        ])
+        .output()
+        .await
+        .expect("run codex-linux-sandbox");
+
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "pong\n");
+    assert_eq!(proxy.join().expect("proxy thread"), "ping\n");
+}
+
+/// Runs `script` with `bash -c` under a workspace-write policy customized by
+/// `readable_roots` and `deny_read`, returning the combined output.
+#[expect(clippy::expect_used)]
+async fn run_script_with_read_policy(
+    script: &str,
+    readable_roots: Vec<AbsolutePathBuf>,
+    deny_read: Vec<AbsolutePathBuf>,
+) -> String {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let params = ExecParams {
+        command: vec!["bash".to_string(), "-c".to_string(), script.to_string()],
+        cwd: cwd.clone(),
+        expiration: NETWORK_TIMEOUT_MS.into(),
+        env: create_env_from_core_vars(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        arg0: None,
+    };
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+        readable_roots,
+        deny_read,
+    };
+    let codex_linux_sandbox_exe = Some(PathBuf::from(env!("CARGO_BIN_EXE_codex-linux-sandbox")));
+    let output = match process_exec_tool_call(
+        params,
+        &sandbox_policy,
+        cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        None,
+    )
+    .await
+    {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(err) => panic!("unexpected error: {err:?}"),
+    };
+    output.aggregated_output.text
+}
+
+#[tokio::test]
+async fn sandbox_hides_deny_read_paths() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let secret_dir = tmpdir.path().join(".ssh");
+    std::fs::create_dir(&secret_dir).unwrap();
+    std::fs::write(secret_dir.join("id_ed25519"), "top-secret-key").unwrap();
+    let secret_file = tmpdir.path().join("auth.json");
+    std::fs::write(&secret_file, "top-secret-token").unwrap();
+    let public_file = tmpdir.path().join("README");
+    std::fs::write(&public_file, "public").unwrap();
+
+    let output = run_script_with_read_policy(
+        &format!(
+            "cat {dir}/id_ed25519 {file} {public}; ls -A {dir}; echo done",
+            dir = secret_dir.display(),
+            file = secret_file.display(),
+            public = public_file.display(),
+        ),
+        vec![],
+        vec![
+            AbsolutePathBuf::try_from(secret_dir.as_path()).unwrap(),
+            AbsolutePathBuf::try_from(secret_file.as_path()).unwrap(),
+        ],
+    )
+    .await;
+
+    assert!(!output.contains("top-secret"), "{output}");
+    assert!(output.contains("public"), "{output}");
+    assert!(output.contains("done"), "{output}");
+}
+
+#[tokio::test]
+async fn sandbox_limits_reads_to_readable_roots() {
+    let readable = tempfile::tempdir().unwrap();
+    let unreadable = tempfile::tempdir().unwrap();
+    std::fs::write(readable.path().join("notes"), "readable-contents").unwrap();
+    std::fs::write(unreadable.path().join("notes"), "hidden-contents").unwrap();
+
+    let output = run_script_with_read_policy(
+        &format!(
+            "cat {}/notes; cat {}/notes; echo done",
+            readable.path().display(),
+            unreadable.path().display(),
+        ),
+        vec![AbsolutePathBuf::try_from(readable.path()).unwrap()],
+        vec![],
+    )
+    .await;
+
+    assert!(output.contains("readable-contents"), "{output}");
+    assert!(!output.contains("hidden-contents"), "{output}");
+    assert!(output.contains("done"), "{output}");
+}
+
+/// Runs `script` with `bash -c` through the sandbox helper with the given
+/// `--resource-limits` JSON.
+#[expect(clippy::expect_used)]
+async fn run_script_with_resource_limits(script: &str, limits: &str) -> std::process::Output {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .args(["--sandbox-policy", r#"{"type":"workspace-write"}"#])
+        .args(["--resource-limits", limits])
+        .args(["--", "bash", "-c", script])
+        .output()
+        .await
+        .expect("run codex-linux-sandbox")
+}
+
+fn terminating_signal(status: std::process::ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+
+    status
+        .signal()
+        .or_else(|| status.code().and_then(|code| code.checked_sub(128)))
+}
+
+#[tokio::test]
+async fn sandbox_enforces_cpu_time_limit() {
+    let output =
+        run_script_with_resource_limits("while :; do :; done", r#"{"cpu_time_secs":1}"#).await;
+
+    assert_eq!(terminating_signal(output.status), Some(libc::SIGXCPU));
+}
+
+#[tokio::test]
+async fn sandbox_enforces_file_size_limit() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let file = tmpdir.path().join("big");
+
+    let output = run_script_with_resource_limits(
+        &format!("head -c 4096 /dev/zero > {}", file.display()),
+        r#"{"max_file_size_bytes":1024}"#,
+    )
+    .await;
+
+    assert_eq!(terminating_signal(output.status), Some(libc::SIGXFSZ));
+    assert_eq!(std::fs::metadata(&file).unwrap().len(), 1024);
+}
+
+#[tokio::test]
+async fn sandbox_refuses_process_limit_without_a_cgroup() {
+    let output = run_script_with_resource_limits(
+        "echo ran",
+        r#"{"max_processes":64,"cgroup_parent":"/nonexistent/cgroup"}"#,
+    )
+    .await;
+
+    assert!(!output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("ran"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("max_processes"));
+
+    let output = run_script_with_resource_limits(
+        "echo ran",
+        r#"{"memory_bytes":1073741824,"cgroup_parent":"/nonexistent/cgroup"}"#,
+    )
+    .await;
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("falling back to RLIMIT_AS"));
+}
+
+/// Runs `script` with `bash -c` from `workspace` through the sandbox helper
+/// with `--private-mounts` and any `extra_args`.
+#[expect(clippy::expect_used)]
+async fn run_script_with_private_mounts(
+    script: &str,
+    workspace: &std::path::Path,
+    extra_args: &[&std::ffi::OsStr],
+) -> std::process::Output {
+    tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .current_dir(workspace)
+        .arg("--sandbox-policy-cwd")
+        .arg(workspace)
+        .args(["--sandbox-policy", r#"{"type":"workspace-write"}"#])
+        .arg("--private-mounts")
+        .args(extra_args)
+        .args(["--", "bash", "-c", script])
+        .output()
+        .await
+        .expect("run codex-linux-sandbox")
+}
+
+#[tokio::test]
+async fn sandbox_gives_commands_a_private_tmp() {
+    let workspace = tempfile::tempdir().unwrap();
+    let host_file = tempfile::Builder::new()
+        .prefix("codex-host-")
+        .tempfile_in("/tmp")
+        .unwrap();
+    let scratch = PathBuf::from(format!("/tmp/codex-scratch-{}", std::process::id()));
+
+    let output = run_script_with_private_mounts(
+        &format!(
+            "test -e {host} && echo host-visible; echo scratch > {scratch} && cat {scratch}; \
+             echo kept > kept.txt; echo denied > /usr/codex-denied; echo done",
+            host = host_file.path().display(),
+            scratch = scratch.display(),
+        ),
+        workspace.path(),
+        &[],
+    )
+    .await;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!stdout.contains("host-visible"), "{stdout}");
+    assert!(stdout.contains("scratch"), "{stdout}");
+    assert!(stdout.contains("done"), "{stdout}");
+    assert!(!scratch.exists());
+    assert!(!std::path::Path::new("/usr/codex-denied").exists());
+    assert_eq!(
+        std::fs::read_to_string(workspace.path().join("kept.txt")).unwrap(),
+        "kept\n"
+    );
+}
+
+#[tokio::test]
+async fn sandbox_workspace_overlay_keeps_writes_out_of_workspace() {
+    use std::os::unix::fs::FileTypeExt;
+
+    let workspace = tempfile::tempdir().unwrap();
+    std::fs::write(workspace.path().join("edited.txt"), "old\n").unwrap();
+    std::fs::write(workspace.path().join("removed.txt"), "gone\n").unwrap();
+    let overlay = tempfile::tempdir().unwrap();
+
+    let output = run_script_with_private_mounts(
+        "echo new > edited.txt && echo hi > added.txt && rm removed.txt && cat edited.txt",
+        workspace.path(),
+        &["--workspace-overlay".as_ref(), overlay.path().as_os_str()],
+    )
+    .await;
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "new\n");
+    let read = |path: &std::path::Path| std::fs::read_to_string(path).unwrap();
+    assert_eq!(read(&workspace.path().join("edited.txt")), "old\n");
+    assert_eq!(read(&workspace.path().join("removed.txt")), "gone\n");
+    assert!(!workspace.path().join("added.txt").exists());
+
+    let upper = overlay.path().join("upper");
+    assert_eq!(read(&upper.join("edited.txt")), "new\n");
+    assert_eq!(read(&upper.join("added.txt")), "hi\n");
+    let whiteout = std::fs::symlink_metadata(upper.join("removed.txt")).unwrap();
+    assert!(whiteout.file_type().is_char_device());
+}
+
+#[tokio::test]
+async fn proxied_network_blocks_host_unix_sockets() {
+    // Stands in for a host service such as the Docker daemon or ssh-agent.
+    let tmpdir = tempfile::tempdir().expect("tempdir");
+    let host_socket = tmpdir.path().join("host.sock");
+    let _listener = std::os::unix::net::UnixListener::bind(&host_socket).expect("bind host socket");
+    let proxy_socket = tmpdir.path().join("proxy.sock");
+    let _proxy = std::os::unix::net::UnixListener::bind(&proxy_socket).expect("bind proxy socket");
+
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let output = tokio::process::Command::new(env!("CARGO_BIN_EXE_codex-linux-sandbox"))
+        .arg("--sandbox-policy-cwd")
+        .arg(&cwd)
+        .args(["--sandbox-policy", r#"{"type":"read-only"}"#])
+        .args(["--network-proxy-port", "3128"])
+        .arg("--network-proxy-socket")
+        .arg(&proxy_socket)
+        .args([
+            "--",
+            "python3",
+            "-c",
+            "import socket, sys; socket.socket(socket.AF_UNIX).connect(sys.argv[1])",
+        ])
+        .arg(&host_socket)
+        .output()
+        .await
+        .expect("run codex-linux-sandbox");
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "connected to a host unix socket");
+    assert!(
+        stderr.contains("Operation not permitted"),
+        "stderr: {stderr}"
+    );
+}
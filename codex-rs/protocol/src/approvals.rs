@@ -92,4 +92,9 @@ pub struct ApplyPatchApprovalRequestEvent {
     /// When set, the agent is asking the user to allow writes under this root for the remainder of the session.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
+    /// When set, the changes can only be kept or discarded as a whole (e.g.
+    /// what a command wrote to the workspace overlay), so clients must not
+    /// offer to approve individual hunks.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub whole_patch_only: bool,
 }
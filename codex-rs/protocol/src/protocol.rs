@@ -225,6 +225,10 @@ pub enum Op {
 
     /// Request the list of available models.
     ListModels,
+
+    /// Apply one of the amendments suggested by a
+    /// [`EventMsg::SandboxDenial`] to the running session and persist it.
+    ApplySandboxAmendment { amendment: SandboxAmendment },
 }
 
 /// Determines the conditions under which the user is consulted to approve
@@ -332,6 +336,17 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// When non-empty, reads are limited to these folders, the writable
+        /// roots, and the system locations needed to run programs. Empty
+        /// means the entire file-system is readable.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        readable_roots: Vec<AbsolutePathBuf>,
+
+        /// Files and folders that can be neither read nor written, even when
+        /// they sit inside a readable or writable root (e.g. `~/.ssh`).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_read: Vec<AbsolutePathBuf>,
     },
 }
 
@@ -374,6 +389,111 @@ impl FromStr for SandboxPolicy {
     }
 }
 
+/// Resource caps for commands run under the sandbox. `None` leaves the
+/// corresponding resource unlimited.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct SandboxResourceLimits {
+    /// CPU time, in seconds, before the command receives `SIGXCPU`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_time_secs: Option<u64>,
+
+    /// Memory the command (and its children, when a cgroup is available) may
+    /// use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+
+    /// Number of processes the command may have running at once. Needs a
+    /// cgroup; commands are refused when none can be created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_processes: Option<u64>,
+
+    /// Largest file the command may write.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_file_size_bytes: Option<u64>,
+
+    /// Delegated cgroup v2 directory under which a per-command cgroup is
+    /// created to enforce the memory and process limits. Defaults to the
+    /// cgroup of the sandbox helper itself, which only works when that is the
+    /// root cgroup (as in many containers): elsewhere it also holds other
+    /// processes, and cgroup v2 does not hand out controllers from such a
+    /// cgroup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup_parent: Option<PathBuf>,
+}
+
+impl SandboxResourceLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.cpu_time_secs.is_none()
+            && self.memory_bytes.is_none()
+            && self.max_processes.is_none()
+            && self.max_file_size_bytes.is_none()
+    }
+
+    /// Whether a memory or process limit is set. Those are enforced with a
+    /// cgroup, whereas the others are plain rlimits.
+    pub fn has_cgroup_limits(&self) -> bool {
+        self.memory_bytes.is_some() || self.max_processes.is_some()
+    }
+}
+
+impl FromStr for SandboxResourceLimits {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Whether `path`, or the path it resolves to, is inside one of `roots`.
+/// Matching either spelling suits deny lists, where a hit on any of them must
+/// count.
+fn path_is_under_any(path: &Path, roots: &[AbsolutePathBuf]) -> bool {
+    let canonical = path.canonicalize().ok();
+    roots.iter().any(|root| {
+        path.starts_with(root)
+            || canonical
+                .as_deref()
+                .is_some_and(|canonical| canonical.starts_with(root))
+    })
+}
+
+/// Whether the path `path` resolves to is inside the resolved form of one of
+/// `roots`. Unlike [`path_is_under_any`], neither `..` nor a symlink pointing
+/// out of a root lets the raw spelling of `path` pass.
+fn resolved_path_is_under_any(path: &Path, roots: &[AbsolutePathBuf]) -> bool {
+    let resolved = resolve_path(path);
+    roots
+        .iter()
+        .any(|root| resolved.starts_with(resolve_path(root.as_path())))
+}
+
+/// Canonicalizes the longest existing prefix of `path` and lexically applies
+/// the remaining components, so paths that do not exist yet still resolve.
+fn resolve_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    let mut components = path.components();
+    let Some(last) = components.next_back() else {
+        return path.to_path_buf();
+    };
+    let parent = components.as_path();
+    if parent.as_os_str().is_empty() {
+        return path.to_path_buf();
+    }
+    let mut resolved = resolve_path(parent);
+    match last {
+        std::path::Component::Normal(name) => resolved.push(name),
+        std::path::Component::ParentDir => {
+            resolved.pop();
+        }
+        std::path::Component::CurDir
+        | std::path::Component::RootDir
+        | std::path::Component::Prefix(_) => {}
+    }
+    resolved
+}
+
 impl SandboxPolicy {
     /// Returns a policy with read-only disk access and no network.
     pub fn new_read_only_policy() -> Self {
@@ -389,12 +509,66 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            readable_roots: vec![],
+            deny_read: vec![],
         }
     }
 
-    /// Always returns `true`; restricting read access is not supported.
+    /// Returns `false` when reads are limited to an explicit set of readable
+    /// roots. Paths in `deny_read` are not taken into account.
     pub fn has_full_disk_read_access(&self) -> bool {
-        true
+        match self {
+            SandboxPolicy::WorkspaceWrite { readable_roots, .. } => readable_roots.is_empty(),
+            SandboxPolicy::DangerFullAccess
+            | SandboxPolicy::ExternalSandbox { .. }
+            | SandboxPolicy::ReadOnly => true,
+        }
+    }
+
+    /// Returns the folders that may be read when the policy does not have full
+    /// disk read access: the configured readable roots plus every writable
+    /// root. Empty when the whole disk is readable.
+    pub fn get_readable_roots_with_cwd(&self, cwd: &Path) -> Vec<AbsolutePathBuf> {
+        match self {
+            SandboxPolicy::WorkspaceWrite { readable_roots, .. } if !readable_roots.is_empty() => {
+                let mut roots = readable_roots.clone();
+                roots.extend(
+                    self.get_writable_roots_with_cwd(cwd)
+                        .into_iter()
+                        .map(|writable_root| writable_root.root),
+                );
+                roots
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Paths that must stay unreadable (and unwritable) under this policy.
+    pub fn get_deny_read_paths(&self) -> &[AbsolutePathBuf] {
+        match self {
+            SandboxPolicy::WorkspaceWrite { deny_read, .. } => deny_read,
+            SandboxPolicy::DangerFullAccess
+            | SandboxPolicy::ExternalSandbox { .. }
+            | SandboxPolicy::ReadOnly => &[],
+        }
+    }
+
+    /// Whether `path` is, or is inside, one of the `deny_read` paths.
+    pub fn is_path_read_denied(&self, path: &Path) -> bool {
+        path_is_under_any(path, self.get_deny_read_paths())
+    }
+
+    /// Whether Codex's own tools (as opposed to sandboxed commands) may read
+    /// `path`: it must not be read-denied and, when reads are limited to
+    /// readable roots, it must be inside one of them or a writable root.
+    /// System locations that the OS sandbox leaves readable for commands are
+    /// not included.
+    pub fn is_path_readable(&self, path: &Path, cwd: &Path) -> bool {
+        if self.is_path_read_denied(path) {
+            return false;
+        }
+        self.has_full_disk_read_access()
+            || resolved_path_is_under_any(path, &self.get_readable_roots_with_cwd(cwd))
     }
 
     pub fn has_full_disk_write_access(&self) -> bool {
@@ -428,6 +602,8 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                readable_roots: _,
+                deny_read: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<AbsolutePathBuf> = writable_roots.clone();
@@ -608,6 +784,13 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// A sandboxed command failed and its output points at the sandbox
+    /// restrictions it most likely ran into.
+    SandboxDenial(SandboxDenialEvent),
+
+    /// An [`Op::ApplySandboxAmendment`] was applied and persisted.
+    SandboxAmendmentApplied(SandboxAmendmentAppliedEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -676,6 +859,8 @@ pub enum EventMsg {
 pub enum CodexErrorInfo {
     ContextWindowExceeded,
     UsageLimitExceeded,
+    /// A hard spend limit from the `budget` config was reached.
+    BudgetExceeded,
     HttpConnectionFailed {
         http_status_code: Option<u16>,
     },
@@ -925,6 +1110,24 @@ impl TokenUsageInfo {
 pub struct TokenCountEvent {
     pub info: Option<TokenUsageInfo>,
     pub rate_limits: Option<RateLimitSnapshot>,
+    /// Estimated spend derived from `model_pricing`. `None` when the active
+    /// model has no configured price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cost: Option<CostSummary>,
+}
+
+/// Estimated spend, in USD, computed from token usage and the configured
+/// per-model pricing table.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CostSummary {
+    /// Cost of the most recent model response.
+    pub last_usd: f64,
+    /// Running cost of the current session.
+    pub session_usd: f64,
+    /// Running cost of all sessions started under this `CODEX_HOME` today
+    /// (local time).
+    pub daily_usd: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
@@ -1321,6 +1524,11 @@ pub struct TurnContextItem {
     pub approval_policy: AskForApproval,
     pub sandbox_policy: SandboxPolicy,
     pub model: String,
+    /// Provider that served `model`; differs from the session's provider
+    /// after a failover.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model_provider_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
     pub summary: ReasoningSummaryConfig,
@@ -1395,6 +1603,23 @@ pub enum ReviewTarget {
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
     Custom { instructions: String },
+
+    /// Review the changes in a commit range such as `main..feature` or
+    /// `v1.0...HEAD`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { range: String },
+
+    /// Review the changes in a patch or diff file that has not been applied.
+    /// Relative paths are resolved against the session's working directory.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    PatchFile { path: PathBuf },
+
+    /// Review the current contents of the given files and directories.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Paths { paths: Vec<PathBuf> },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -1404,6 +1629,10 @@ pub struct ReviewRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub user_facing_hint: Option<String>,
+    /// Output of a failing test run, given to the reviewer as extra context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub test_output: Option<String>,
 }
 
 /// Structured review result produced by a child review session.
@@ -1526,6 +1755,98 @@ pub struct ExecCommandEndEvent {
     pub duration: Duration,
     /// Formatted output from the command, as seen by the model.
     pub formatted_output: String,
+    /// Set when the command was killed for exceeding a sandbox resource limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub exit_reason: Option<ExecExitReason>,
+}
+
+/// Sandbox resource limit that terminated a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecExitReason {
+    CpuTimeLimit,
+    MemoryLimit,
+    ProcessLimit,
+    FileSizeLimit,
+}
+
+impl fmt::Display for ExecExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ExecExitReason::CpuTimeLimit => "CPU time limit",
+            ExecExitReason::MemoryLimit => "memory limit",
+            ExecExitReason::ProcessLimit => "process limit",
+            ExecExitReason::FileSizeLimit => "file size limit",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SandboxDenialEvent {
+    /// Identifier for the tool call whose command was denied.
+    pub call_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    /// Restrictions the command ran into, as recognized in its output.
+    pub denials: Vec<SandboxDenial>,
+    /// Changes that would let the command through, for the user to pick from
+    /// with [`Op::ApplySandboxAmendment`].
+    pub suggested_amendments: Vec<SandboxAmendment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SandboxDenial {
+    pub kind: SandboxDenialKind,
+    /// The path or host that was denied, when the output names it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub target: Option<String>,
+    /// Output line the denial was recognized in.
+    pub evidence: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SandboxDenialKind {
+    /// Access to a path outside the writable (or readable) roots.
+    FileSystem,
+    /// Outbound network access.
+    Network,
+}
+
+/// A change to the sandbox setup that would have let a denied command run.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum SandboxAmendment {
+    /// Add a folder to the current project's
+    /// `sandbox_workspace_write.writable_roots`.
+    AddWritableRoot { path: PathBuf },
+    /// Let commands starting with this prefix run outside the sandbox
+    /// entirely, through an execpolicy `allow` rule. Suggested when such a
+    /// command was denied network access.
+    RunOutsideSandbox { prefix: ExecPolicyAmendment },
+}
+
+impl fmt::Display for SandboxAmendment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SandboxAmendment::AddWritableRoot { path } => {
+                write!(f, "add {} to the writable roots", path.display())
+            }
+            SandboxAmendment::RunOutsideSandbox { prefix } => write!(
+                f,
+                "run `{}` commands outside the sandbox",
+                prefix.command().join(" ")
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SandboxAmendmentAppliedEvent {
+    pub amendment: SandboxAmendment,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1826,6 +2147,12 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has reviewed a patch hunk by hunk. `patch` holds the accepted (and
+    /// possibly edited) hunks to apply instead of the proposed patch, and
+    /// `feedback` describes the rejected and edited hunks for the agent. Only
+    /// meaningful in response to an ApplyPatchApprovalRequest.
+    ApprovedHunks { patch: String, feedback: String },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -1898,6 +2225,89 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn workspace_write_read_restrictions() -> Result<()> {
+        let legacy: SandboxPolicy = serde_json::from_value(json!({"type": "workspace-write"}))?;
+        assert_eq!(legacy, SandboxPolicy::new_workspace_write_policy());
+        assert!(legacy.has_full_disk_read_access());
+        assert!(legacy.get_deny_read_paths().is_empty());
+
+        let tmp = tempfile::tempdir()?;
+        let docs = AbsolutePathBuf::try_from(tmp.path().join("docs"))?;
+        let ssh = AbsolutePathBuf::try_from(tmp.path().join(".ssh"))?;
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![docs.clone()],
+            deny_read: vec![ssh],
+        };
+        assert!(!policy.has_full_disk_read_access());
+        assert_eq!(
+            policy.get_readable_roots_with_cwd(tmp.path()),
+            vec![docs, AbsolutePathBuf::try_from(tmp.path())?]
+        );
+        assert!(policy.is_path_read_denied(&tmp.path().join(".ssh/id_ed25519")));
+        assert!(!policy.is_path_read_denied(&tmp.path().join("docs/README")));
+
+        assert!(policy.is_path_readable(&tmp.path().join("docs/README"), tmp.path()));
+        assert!(policy.is_path_readable(&tmp.path().join("src/main.rs"), tmp.path()));
+        assert!(!policy.is_path_readable(&tmp.path().join(".ssh/id_ed25519"), tmp.path()));
+        assert!(!policy.is_path_readable(Path::new("/elsewhere/notes"), tmp.path()));
+        assert!(legacy.is_path_readable(Path::new("/elsewhere/notes"), tmp.path()));
+        Ok(())
+    }
+
+    #[test]
+    fn readable_roots_reject_dot_dot_escapes() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let workspace = tmp.path().join("ws");
+        std::fs::create_dir_all(&workspace)?;
+        std::fs::create_dir_all(tmp.path().join("home"))?;
+        std::fs::write(tmp.path().join("home/credentials"), "secret")?;
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![AbsolutePathBuf::try_from(workspace.join("docs"))?],
+            deny_read: vec![],
+        };
+
+        assert!(!policy.is_path_readable(&workspace.join("../home/credentials"), &workspace));
+        assert!(!policy.is_path_readable(&workspace.join("../missing/file"), &workspace));
+        assert!(policy.is_path_readable(&workspace.join("src/../docs/README"), &workspace));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn readable_roots_reject_symlinks_pointing_outside() -> Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let workspace = tmp.path().join("ws");
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&workspace)?;
+        std::fs::create_dir_all(&outside)?;
+        std::fs::write(outside.join("credentials"), "secret")?;
+        std::os::unix::fs::symlink(&outside, workspace.join("link"))?;
+        std::fs::create_dir_all(workspace.join("src"))?;
+        std::os::unix::fs::symlink(workspace.join("src"), workspace.join("src-link"))?;
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            readable_roots: vec![AbsolutePathBuf::try_from(workspace.join("docs"))?],
+            deny_read: vec![],
+        };
+
+        assert!(!policy.is_path_readable(&workspace.join("link/credentials"), &workspace));
+        assert!(!policy.is_path_readable(&workspace.join("link"), &workspace));
+        assert!(policy.is_path_readable(&workspace.join("src-link/main.rs"), &workspace));
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
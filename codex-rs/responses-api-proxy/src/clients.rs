@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+
+/// Client names keyed by the token each client authenticates with. Loaded
+/// from the `--client-tokens` file, a JSON object mapping names to tokens.
+pub(crate) struct ClientTokens {
+    clients: HashMap<String, String>,
+}
+
+impl ClientTokens {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("reading client tokens from {}", path.display()))?;
+        Self::parse(&contents).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let by_name: BTreeMap<String, String> = serde_json::from_str(contents)?;
+        let mut clients = HashMap::with_capacity(by_name.len());
+        for (name, token) in by_name {
+            if name.is_empty() || token.is_empty() {
+                bail!("client names and tokens must not be empty");
+            }
+            if let Some(other) = clients.insert(token, name.clone()) {
+                bail!("clients `{other}` and `{name}` share a token");
+            }
+        }
+        Ok(Self { clients })
+    }
+
+    /// The client that `authorization` (an `Authorization` header value)
+    /// authenticates, if any.
+    pub(crate) fn identify(&self, authorization: Option<&str>) -> Option<&str> {
+        let value = authorization?.trim();
+        let (scheme, token) = value.split_once(' ')?;
+        if !scheme.eq_ignore_ascii_case("bearer") {
+            return None;
+        }
+        self.clients.get(token.trim()).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn identifies_clients_by_bearer_token() {
+        let clients =
+            ClientTokens::parse(r#"{"alice": "tok-alice", "bob": "tok-bob"}"#).expect("parse");
+        assert_eq!(clients.identify(Some("Bearer tok-alice")), Some("alice"));
+        assert_eq!(clients.identify(Some("bearer tok-bob")), Some("bob"));
+        assert_eq!(clients.identify(Some("Bearer tok-mallory")), None);
+        assert_eq!(clients.identify(Some("tok-alice")), None);
+        assert_eq!(clients.identify(None), None);
+    }
+
+    #[test]
+    fn rejects_shared_or_empty_tokens() {
+        assert!(ClientTokens::parse(r#"{"alice": "same", "bob": "same"}"#).is_err());
+        assert!(ClientTokens::parse(r#"{"alice": ""}"#).is_err());
+    }
+}
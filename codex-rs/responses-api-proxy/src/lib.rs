@@ -20,6 +20,8 @@ use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
 use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
 use tiny_http::Header;
 use tiny_http::Method;
 use tiny_http::Request;
@@ -27,8 +29,26 @@ use tiny_http::Response;
 use tiny_http::Server;
 use tiny_http::StatusCode;
 
+mod audit;
+mod clients;
+mod quota;
 mod read_api_key;
+mod redact;
+use audit::AuditLog;
+use audit::AuditRecord;
+use audit::Exchange;
+use audit::ObservedBody;
+use audit::Outcome;
+use clients::ClientTokens;
+use quota::QuotaTracker;
 use read_api_key::read_auth_header_from_stdin;
+use redact::Redactor;
+
+/// Client id used for logging and quotas when `--client-tokens` is not set.
+const ANONYMOUS_CLIENT: &str = "anonymous";
+
+/// Client id logged for requests rejected for lacking a known client token.
+const UNKNOWN_CLIENT: &str = "unknown";
 
 /// CLI arguments for the proxy.
 #[derive(Debug, Clone, Parser)]
@@ -49,6 +69,33 @@ pub struct Args {
     /// Absolute URL the proxy should forward requests to (defaults to OpenAI).
     #[arg(long, default_value = "https://api.openai.com/v1/responses")]
     pub upstream_url: String,
+
+    /// Append one JSON line per request to this file, with secrets redacted.
+    #[arg(long, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Also record (redacted) response bodies in the log file.
+    #[arg(long, requires = "log_file")]
+    pub log_response_bodies: bool,
+
+    /// Extra regular expression to redact from logged bodies. Repeatable.
+    #[arg(long = "redact-pattern", value_name = "REGEX")]
+    pub redact_patterns: Vec<String>,
+
+    /// Only forward requests for this model. Repeatable; all models are
+    /// allowed when omitted.
+    #[arg(long = "allow-model", value_name = "MODEL")]
+    pub allowed_models: Vec<String>,
+
+    /// Maximum total tokens each client may use per UTC day.
+    #[arg(long, value_name = "TOKENS")]
+    pub client_token_quota: Option<u64>,
+
+    /// JSON file mapping client names to the tokens they send as
+    /// `Authorization: Bearer <token>`. When set, requests without a known
+    /// token are rejected; otherwise every request counts as `anonymous`.
+    #[arg(long, value_name = "FILE")]
+    pub client_tokens: Option<PathBuf>,
 }
 
 #[derive(Serialize)]
@@ -60,6 +107,12 @@ struct ServerInfo {
 struct ForwardConfig {
     upstream_url: Url,
     host_header: HeaderValue,
+    allowed_models: Vec<String>,
+    clients: Option<ClientTokens>,
+    redactor: Arc<Redactor>,
+    log: Option<Arc<AuditLog>>,
+    log_response_bodies: bool,
+    quota: Option<Arc<QuotaTracker>>,
 }
 
 /// Entry point for the library main, for parity with other crates.
@@ -75,9 +128,31 @@ pub fn run_main(args: Args) -> Result<()> {
     let host_header =
         HeaderValue::from_str(&host).context("constructing Host header from upstream URL")?;
 
+    let api_key = auth_header.strip_prefix("Bearer ").unwrap_or(auth_header);
+    let redactor = Redactor::new(vec![api_key], &args.redact_patterns)?;
+    let log = args
+        .log_file
+        .as_deref()
+        .map(AuditLog::open)
+        .transpose()?
+        .map(Arc::new);
+    let clients = args
+        .client_tokens
+        .as_deref()
+        .map(ClientTokens::load)
+        .transpose()?;
+
     let forward_config = Arc::new(ForwardConfig {
         upstream_url,
         host_header,
+        allowed_models: args.allowed_models,
+        clients,
+        redactor: Arc::new(redactor),
+        log,
+        log_response_bodies: args.log_response_bodies,
+        quota: args
+            .client_token_quota
+            .map(|limit| Arc::new(QuotaTracker::new(limit))),
     });
 
     let (listener, bound_addr) = bind_listener(args.port)?;
@@ -106,6 +181,11 @@ pub fn run_main(args: Args) -> Result<()> {
                 std::process::exit(0);
             }
 
+            if request.method() == &Method::Get && request.url() == "/health" {
+                let _ = request.respond(json_response(200, &json!({ "status": "ok" })));
+                return;
+            }
+
             if let Err(e) = forward_request(&client, auth_header, &forward_config, request) {
                 eprintln!("forwarding error: {e}");
             }
@@ -162,6 +242,84 @@ fn forward_request(
     let mut reader = req.as_reader();
     std::io::Read::read_to_end(&mut reader, &mut body)?;
 
+    // Clients are identified by the token they authenticate with, never by
+    // anything else they declare about themselves.
+    let client_id = match config.clients.as_ref() {
+        Some(clients) => {
+            let authorization = req
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("authorization"))
+                .map(|header| header.value.as_str());
+            clients.identify(authorization).map(str::to_string)
+        }
+        None => Some(ANONYMOUS_CLIENT.to_string()),
+    };
+    let authenticated = client_id.is_some();
+    let client_id = client_id.unwrap_or_else(|| UNKNOWN_CLIENT.to_string());
+    let parsed_body: Option<Value> = serde_json::from_slice(&body).ok();
+    let model = parsed_body
+        .as_ref()
+        .and_then(|body| body.get("model"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let audit_record = |outcome| {
+        config.log.as_ref().map(|_| {
+            AuditRecord::new(
+                &client_id,
+                model.as_deref(),
+                outcome,
+                audit::redacted_request(&config.redactor, &body, parsed_body.as_ref()),
+            )
+        })
+    };
+    let reject = |req: Request, outcome, status: u16, message: String| {
+        if let (Some(log), Some(mut record)) = (config.log.as_ref(), audit_record(outcome)) {
+            record.status = Some(status);
+            log.write(&record);
+        }
+        let code = match outcome {
+            Outcome::Unauthorized => "invalid_api_key",
+            Outcome::QuotaExceeded => "insufficient_quota",
+            _ => "model_not_allowed",
+        };
+        let error = json!({ "error": { "type": code, "code": code, "message": message } });
+        let _ = req.respond(json_response(status, &error));
+    };
+
+    if !authenticated {
+        let message = "missing or unknown client token".to_string();
+        reject(req, Outcome::Unauthorized, 401, message);
+        return Ok(());
+    }
+
+    if !is_model_allowed(&config.allowed_models, model.as_deref()) {
+        let message = format!(
+            "model `{}` is not allowed by this proxy",
+            model.as_deref().unwrap_or_default()
+        );
+        reject(req, Outcome::ModelNotAllowed, 403, message);
+        return Ok(());
+    }
+
+    let reservation = match config.quota.as_ref() {
+        Some(quota) => {
+            let estimate = quota::estimate_request_tokens(body.len(), parsed_body.as_ref());
+            match quota.reserve(&client_id, estimate) {
+                Some(reservation) => Some(reservation),
+                None => {
+                    let message = format!(
+                        "request would take client `{client_id}` past its daily quota of {} tokens",
+                        quota.limit()
+                    );
+                    reject(req, Outcome::QuotaExceeded, 429, message);
+                    return Ok(());
+                }
+            }
+        }
+        None => None,
+    };
+
     // Build headers for upstream, forwarding everything from the incoming
     // request except Authorization (we replace it below).
     let mut headers = HeaderMap::new();
@@ -189,18 +347,31 @@ fn forward_request(
 
     headers.insert(HOST, config.host_header.clone());
 
-    let upstream_resp = client
+    let mut record = audit_record(Outcome::Forwarded);
+    let upstream_resp = match client
         .post(config.upstream_url.clone())
         .headers(headers)
         .body(body)
         .send()
-        .context("forwarding request to upstream")?;
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            if let (Some(log), Some(mut record)) = (config.log.as_ref(), record) {
+                record.outcome = Outcome::UpstreamError;
+                log.write(&record);
+            }
+            return Err(err).context("forwarding request to upstream");
+        }
+    };
 
     // We have to create an adapter between a `reqwest::blocking::Response`
     // and a `tiny_http::Response`. Fortunately, `reqwest::blocking::Response`
     // implements `Read`, so we can use it directly as the body of the
     // `tiny_http::Response`.
     let status = upstream_resp.status();
+    if let Some(record) = record.as_mut() {
+        record.status = Some(status.as_u16());
+    }
     let mut response_headers = Vec::new();
     for (name, value) in upstream_resp.headers().iter() {
         // Skip headers that tiny_http manages itself.
@@ -224,10 +395,17 @@ fn forward_request(
         }
     });
 
+    let exchange = Exchange {
+        record,
+        log: config.log.clone(),
+        redactor: Arc::clone(&config.redactor),
+        reservation,
+        capture_body: config.log_response_bodies,
+    };
     let response = Response::new(
         StatusCode(status.as_u16()),
         response_headers,
-        upstream_resp,
+        ObservedBody::new(upstream_resp, exchange),
         content_length,
         None,
     );
@@ -235,3 +413,31 @@ fn forward_request(
     let _ = req.respond(response);
     Ok(())
 }
+
+fn is_model_allowed(allowed_models: &[String], model: Option<&str>) -> bool {
+    allowed_models.is_empty()
+        || model.is_some_and(|model| allowed_models.iter().any(|allowed| allowed == model))
+}
+
+fn json_response(status: u16, body: &Value) -> Response<std::io::Cursor<Vec<u8>>> {
+    let mut response = Response::from_data(body.to_string()).with_status_code(StatusCode(status));
+    if let Ok(header) = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]) {
+        response.add_header(header);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_allow_list_is_exact_and_optional() {
+        let allowed = vec!["gpt-5.1".to_string(), "gpt-5.1-codex-max".to_string()];
+        assert!(is_model_allowed(&allowed, Some("gpt-5.1")));
+        assert!(!is_model_allowed(&allowed, Some("gpt-5.1-mini")));
+        assert!(!is_model_allowed(&allowed, None));
+        assert!(is_model_allowed(&[], None));
+        assert!(is_model_allowed(&[], Some("anything")));
+    }
+}
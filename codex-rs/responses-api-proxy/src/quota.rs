@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use chrono::NaiveDate;
+use chrono::Utc;
+use serde_json::Value;
+
+/// Per-client token allowance that resets at midnight UTC.
+///
+/// Each request reserves an estimate of its cost before it is forwarded, so
+/// concurrent requests cannot all slip under the limit, and the reservation
+/// is replaced by the usage the upstream reports once the response completes.
+pub(crate) struct QuotaTracker {
+    limit: u64,
+    usage: Mutex<HashMap<String, DailyUsage>>,
+}
+
+#[derive(Clone, Copy)]
+struct DailyUsage {
+    day: NaiveDate,
+    /// Tokens charged for completed responses.
+    tokens: u64,
+    /// Estimates held by requests that are still in flight.
+    reserved: u64,
+}
+
+/// Tokens set aside for one in-flight request. Dropping it without calling
+/// [`Reservation::settle`] releases the estimate and charges nothing.
+pub(crate) struct Reservation {
+    tracker: Arc<QuotaTracker>,
+    client: String,
+    day: NaiveDate,
+    estimate: u64,
+    settled: bool,
+}
+
+impl QuotaTracker {
+    pub(crate) fn new(limit: u64) -> Self {
+        Self {
+            limit,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Tokens `client` has used today, not counting in-flight requests.
+    #[cfg(test)]
+    pub(crate) fn used(&self, client: &str) -> u64 {
+        self.used_on(client, Utc::now().date_naive())
+    }
+
+    /// Reserves `estimate` tokens for a request from `client`, or returns
+    /// `None` when that would take the client past its daily limit.
+    pub(crate) fn reserve(self: &Arc<Self>, client: &str, estimate: u64) -> Option<Reservation> {
+        self.reserve_on(client, estimate, Utc::now().date_naive())
+    }
+
+    fn reserve_on(
+        self: &Arc<Self>,
+        client: &str,
+        estimate: u64,
+        today: NaiveDate,
+    ) -> Option<Reservation> {
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = today_entry(&mut usage, client, today);
+        let committed = entry.tokens.saturating_add(entry.reserved);
+        if committed.saturating_add(estimate) > self.limit {
+            return None;
+        }
+        entry.reserved = entry.reserved.saturating_add(estimate);
+        Some(Reservation {
+            tracker: Arc::clone(self),
+            client: client.to_string(),
+            day: today,
+            estimate,
+            settled: false,
+        })
+    }
+
+    #[cfg(test)]
+    fn used_on(&self, client: &str, today: NaiveDate) -> u64 {
+        let usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        usage
+            .get(client)
+            .filter(|usage| usage.day == today)
+            .map_or(0, |usage| usage.tokens)
+    }
+
+    /// Drops a reservation made on `day` and charges `used` tokens instead.
+    /// A request that straddles midnight is charged to the new day; the old
+    /// day's reservation went away when the entry was reset.
+    fn release(&self, client: &str, day: NaiveDate, estimate: u64, used: u64, today: NaiveDate) {
+        let mut usage = self
+            .usage
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let entry = today_entry(&mut usage, client, today.max(day));
+        if entry.day == day {
+            entry.reserved = entry.reserved.saturating_sub(estimate);
+        }
+        entry.tokens = entry.tokens.saturating_add(used);
+    }
+}
+
+impl Reservation {
+    /// Replaces the estimate with the tokens the request actually used.
+    pub(crate) fn settle(self, used: u64) {
+        self.settle_on(used, Utc::now().date_naive());
+    }
+
+    fn settle_on(mut self, used: u64, today: NaiveDate) {
+        self.settled = true;
+        self.tracker
+            .release(&self.client, self.day, self.estimate, used, today);
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.tracker
+                .release(&self.client, self.day, self.estimate, 0, self.day);
+        }
+    }
+}
+
+/// Upper-bound guess at what a request will cost, reserved before it is
+/// forwarded: roughly one token per four bytes of request body for the input,
+/// plus the request's `max_output_tokens`. A request without
+/// `max_output_tokens` can end up costing more than its estimate.
+pub(crate) fn estimate_request_tokens(body_len: usize, body: Option<&Value>) -> u64 {
+    let input = (body_len as u64).div_ceil(4);
+    let output = body
+        .and_then(|body| body.get("max_output_tokens"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    input.saturating_add(output)
+}
+
+/// The client's usage entry, reset when it belongs to an earlier day.
+fn today_entry<'a>(
+    usage: &'a mut HashMap<String, DailyUsage>,
+    client: &str,
+    today: NaiveDate,
+) -> &'a mut DailyUsage {
+    let fresh = DailyUsage {
+        day: today,
+        tokens: 0,
+        reserved: 0,
+    };
+    let entry = usage.entry(client.to_string()).or_insert(fresh);
+    if entry.day < today {
+        *entry = fresh;
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn charge(tracker: &Arc<QuotaTracker>, client: &str, tokens: u64, day: NaiveDate) {
+        tracker
+            .reserve_on(client, 0, day)
+            .expect("reserve")
+            .settle_on(tokens, day);
+    }
+
+    #[test]
+    fn usage_is_tracked_per_client_and_resets_daily() {
+        let tracker = Arc::new(QuotaTracker::new(100));
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 6).expect("date");
+        let tuesday = NaiveDate::from_ymd_opt(2025, 1, 7).expect("date");
+
+        charge(&tracker, "alice", 60, monday);
+        charge(&tracker, "alice", 50, monday);
+        charge(&tracker, "bob", 10, monday);
+        assert_eq!(tracker.used_on("alice", monday), 110);
+        assert_eq!(tracker.used_on("bob", monday), 10);
+        assert!(tracker.reserve_on("alice", 0, monday).is_none());
+
+        assert_eq!(tracker.used_on("alice", tuesday), 0);
+        assert!(tracker.reserve_on("alice", 0, tuesday).is_some());
+    }
+
+    #[test]
+    fn estimate_covers_input_and_max_output_tokens() {
+        let body = serde_json::json!({"model": "gpt-5.1", "max_output_tokens": 100});
+        assert_eq!(estimate_request_tokens(400, Some(&body)), 200);
+        assert_eq!(estimate_request_tokens(401, None), 101);
+    }
+
+    #[test]
+    fn in_flight_reservations_count_against_the_limit() {
+        let tracker = Arc::new(QuotaTracker::new(100));
+        let monday = NaiveDate::from_ymd_opt(2025, 1, 6).expect("date");
+
+        let first = tracker.reserve_on("alice", 60, monday).expect("first");
+        assert!(tracker.reserve_on("alice", 60, monday).is_none());
+        assert!(tracker.reserve_on("bob", 60, monday).is_some());
+
+        // Settling with less than the estimate frees the difference.
+        first.settle_on(20, monday);
+        assert_eq!(tracker.used_on("alice", monday), 20);
+        let second = tracker.reserve_on("alice", 80, monday).expect("second");
+
+        // A request that fails without reporting usage charges nothing.
+        drop(second);
+        assert_eq!(tracker.used_on("alice", monday), 20);
+        assert!(tracker.reserve_on("alice", 80, monday).is_some());
+    }
+}
@@ -6,10 +6,15 @@ use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::export::ExportFormat;
+use crate::export::ExportHeader;
+use crate::export::render_export;
 use crate::external_editor;
 use crate::file_search::FileSearchManager;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
+use crate::inline_image::InlineImage;
+use crate::keymap;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
@@ -17,10 +22,12 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
+use crate::tabs::ParkedTab;
+use crate::tabs::TabId;
+use crate::tabs::Tabs;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
-use codex_ansi_escape::ansi_escape_line;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
@@ -29,6 +36,7 @@ use codex_core::config::edit::ConfigEditsBuilder;
 #[cfg(target_os = "windows")]
 use codex_core::features::Feature;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::models_manager::model_family::ModelFamily;
 use codex_core::models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
 use codex_core::protocol::EventMsg;
@@ -47,11 +55,14 @@ use color_eyre::eyre::WrapErr;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use ratatui::layout::Rect;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
 use ratatui::widgets::Wrap;
 use std::collections::BTreeMap;
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -67,6 +78,12 @@ use crate::history_cell::UpdateAvailableHistoryCell;
 
 const EXTERNAL_EDITOR_HINT: &str = "Save and close external editor to continue.";
 
+/// Recent sessions offered in the command palette.
+const PALETTE_SESSIONS: usize = 5;
+
+/// Command palette entries remembered for recent-usage ranking.
+const PALETTE_RECENT_LIMIT: usize = 50;
+
 #[derive(Debug, Clone)]
 pub struct AppExitInfo {
     pub token_usage: TokenUsage,
@@ -302,12 +319,23 @@ pub(crate) struct App {
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
     pub(crate) deferred_history_lines: Vec<Line<'static>>,
+    /// Images to draw over rows of `deferred_history_lines`.
+    pub(crate) deferred_history_images: Vec<InlineImage>,
     has_emitted_history_lines: bool,
 
     pub(crate) enhanced_keys_supported: bool,
 
     /// Controls the animation thread that sends CommitTick events.
     pub(crate) commit_anim_running: Arc<AtomicBool>,
+    /// Tabs whose streams still need commit ticks; the animation thread stops
+    /// once every tab has drained.
+    commit_anim_tabs: HashSet<TabId>,
+
+    /// Conversations open in other tabs, plus the strip bookkeeping.
+    pub(crate) tabs: Tabs,
+
+    /// Command palette entry ids, most recently used first.
+    palette_recent: Vec<String>,
 
     // Esc-backtracking state grouped
     pub(crate) backtrack: crate::app_backtrack::BacktrackState,
@@ -332,6 +360,269 @@ impl App {
         }
     }
 
+    /// Start a fresh conversation in a new tab next to the active one and
+    /// switch to it. The current conversation keeps running in the background.
+    fn open_new_tab(&mut self, tui: &mut tui::Tui, model_family: ModelFamily) {
+        let tab = self.tabs.allocate();
+        let init = crate::chatwidget::ChatWidgetInit {
+            config: self.config.clone(),
+            frame_requester: tui.frame_requester(),
+            app_event_tx: self.app_event_tx.for_tab(tab),
+            initial_prompt: None,
+            initial_images: Vec::new(),
+            enhanced_keys_supported: self.enhanced_keys_supported,
+            auth_manager: self.auth_manager.clone(),
+            models_manager: self.server.get_models_manager(),
+            feedback: self.feedback.clone(),
+            is_first_run: false,
+            model_family: model_family.clone(),
+        };
+        self.tabs.park(
+            tab,
+            ParkedTab {
+                widget: ChatWidget::new(init, self.server.clone()),
+                transcript_cells: Vec::new(),
+                unseen_cells: Vec::new(),
+                current_model: model_family.get_model_slug().to_string(),
+            },
+        );
+        self.switch_to_tab(tui, tab);
+    }
+
+    /// Shut down the active tab's conversation and show its neighbour.
+    async fn close_active_tab(&mut self, tui: &mut tui::Tui) {
+        let closing = self.tabs.active();
+        let Some(next) = self.tabs.neighbor(-1) else {
+            self.chat_widget.add_info_message(
+                "This is the only open tab.".to_string(),
+                Some("Use /quit to exit Codex.".to_string()),
+            );
+            return;
+        };
+        // The ShutdownComplete for this conversation arrives after the tab is
+        // gone and is dropped by the router, so no suppression is needed.
+        if let Some(conversation_id) = self.chat_widget.conversation_id() {
+            self.chat_widget.submit_op(Op::Shutdown);
+            self.server.remove_conversation(&conversation_id).await;
+        }
+        self.switch_to_tab(tui, next);
+        self.tabs.remove(closing);
+        self.stop_commit_animation(closing);
+    }
+
+    /// Write the active conversation's transcript to `$CODEX_HOME/exports`.
+    /// Replace the current conversation with the one recorded at `path`,
+    /// leaving the usual "to continue this session" note for the old one.
+    async fn resume_session(
+        &mut self,
+        tui: &mut tui::Tui,
+        path: PathBuf,
+        model_family: ModelFamily,
+    ) {
+        let summary = session_summary(
+            self.chat_widget.token_usage(),
+            self.chat_widget.conversation_id(),
+        );
+        match self
+            .server
+            .resume_conversation_from_rollout(
+                self.config.clone(),
+                path.clone(),
+                self.auth_manager.clone(),
+            )
+            .await
+        {
+            Ok(resumed) => {
+                self.shutdown_current_conversation().await;
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: self.config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: self.app_event_tx.for_tab(self.tabs.active()),
+                    initial_prompt: None,
+                    initial_images: Vec::new(),
+                    enhanced_keys_supported: self.enhanced_keys_supported,
+                    auth_manager: self.auth_manager.clone(),
+                    models_manager: self.server.get_models_manager(),
+                    feedback: self.feedback.clone(),
+                    is_first_run: false,
+                    model_family: model_family.clone(),
+                };
+                self.chat_widget = ChatWidget::new_from_existing(
+                    init,
+                    resumed.conversation,
+                    resumed.session_configured,
+                );
+                self.current_model = model_family.get_model_slug().to_string();
+                if let Some(summary) = summary {
+                    let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
+                    if let Some(command) = summary.resume_command {
+                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        lines.push(spans.into());
+                    }
+                    self.chat_widget.add_plain_history_lines(lines);
+                }
+            }
+            Err(err) => {
+                self.chat_widget.add_error_message(format!(
+                    "Failed to resume session from {}: {err}",
+                    path.display()
+                ));
+            }
+        }
+    }
+
+    fn export_transcript(&mut self, format: ExportFormat) {
+        let conversation_id = self.chat_widget.conversation_id();
+        let header = ExportHeader {
+            session_id: conversation_id.map(|id| id.to_string()),
+            started_at: None,
+            cwd: Some(self.config.cwd.clone()),
+        };
+        let document = render_export(&header, &self.transcript_cells, format);
+        let file_name = format!(
+            "{}.{}",
+            header.session_id.as_deref().unwrap_or("session"),
+            format.extension()
+        );
+        let dir = self.config.codex_home.join("exports");
+        let path = dir.join(file_name);
+        match std::fs::create_dir_all(&dir).and_then(|()| std::fs::write(&path, document)) {
+            Ok(()) => self
+                .chat_widget
+                .add_info_message(format!("Exported transcript to {}", path.display()), None),
+            Err(err) => self.chat_widget.add_error_message(format!(
+                "Failed to export transcript to {}: {err}",
+                path.display()
+            )),
+        }
+    }
+
+    /// Switch the displayed conversation to `tab` and write whatever it
+    /// produced in the background to the scrollback.
+    fn switch_to_tab(&mut self, tui: &mut tui::Tui, tab: TabId) {
+        let Some(unseen_cells) = self.activate_tab(tab) else {
+            return;
+        };
+        let width = tui.terminal.last_known_screen_size.width;
+        let title = self.tabs.title(tab, &self.transcript_cells);
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        if self.has_emitted_history_lines {
+            lines.push(Line::from(""));
+        }
+        lines.push(
+            format!("── tab {} · {title} ──", self.tabs.number(tab))
+                .dim()
+                .into(),
+        );
+        let mut images = Vec::new();
+        for cell in unseen_cells {
+            let display = cell.display_lines(width);
+            if display.is_empty() {
+                continue;
+            }
+            if !cell.is_stream_continuation() {
+                lines.push(Line::from(""));
+            }
+            let offset = lines.len();
+            images.extend(
+                cell.inline_images(width)
+                    .into_iter()
+                    .map(|image| image.offset(offset)),
+            );
+            lines.extend(display);
+        }
+        self.has_emitted_history_lines = true;
+        tui.insert_history_lines_with_images(lines, images);
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Swap the parked conversation `tab` in for the active one. Returns the
+    /// cells it produced while parked, or `None` if there is no such tab.
+    pub(crate) fn activate_tab(&mut self, tab: TabId) -> Option<Vec<Arc<dyn HistoryCell>>> {
+        if tab == self.tabs.active() {
+            return None;
+        }
+        let ParkedTab {
+            widget,
+            transcript_cells,
+            unseen_cells,
+            current_model,
+        } = self.tabs.take_parked(tab)?;
+        let previous = ParkedTab {
+            widget: std::mem::replace(&mut self.chat_widget, widget),
+            transcript_cells: std::mem::replace(&mut self.transcript_cells, transcript_cells),
+            unseen_cells: Vec::new(),
+            current_model: std::mem::replace(&mut self.current_model, current_model),
+        };
+        self.tabs.park(self.tabs.active(), previous);
+        self.tabs.set_active(tab);
+        self.backtrack.pending = None;
+        self.reset_backtrack_state();
+        Some(unseen_cells)
+    }
+
+    /// Route an event sent by a parked conversation. Only events that keep the
+    /// conversation running are applied; UI requests are dropped because the
+    /// user is looking at another tab.
+    pub(crate) fn handle_background_tab_event(&mut self, tab: TabId, event: AppEvent) {
+        match event {
+            AppEvent::StartCommitAnimation => return self.start_commit_animation(tab),
+            AppEvent::StopCommitAnimation => return self.stop_commit_animation(tab),
+            AppEvent::CodexEvent(ref event) => {
+                self.tabs
+                    .update_activity(tab, |activity| activity.on_event(&event.msg, false));
+            }
+            AppEvent::CodexOp(ref op) => {
+                self.tabs
+                    .update_activity(tab, |activity| activity.on_op(op));
+            }
+            _ => {}
+        }
+        let Some(parked) = self.tabs.parked_mut(tab) else {
+            tracing::debug!("dropping event for closed tab {tab}");
+            return;
+        };
+        match event {
+            AppEvent::CodexEvent(event) => parked.widget.handle_codex_event(event),
+            AppEvent::CodexOp(op) => parked.widget.submit_op(op),
+            AppEvent::InsertHistoryCell(cell) => {
+                let cell: Arc<dyn HistoryCell> = cell.into();
+                parked.transcript_cells.push(cell.clone());
+                parked.unseen_cells.push(cell);
+            }
+            AppEvent::RateLimitSnapshotFetched(snapshot) => {
+                parked.widget.on_rate_limit_snapshot(Some(snapshot));
+            }
+            AppEvent::DiffResult(_) => parked.widget.on_diff_complete(),
+            _ => tracing::debug!("dropping UI event from background tab {tab}"),
+        }
+    }
+
+    fn start_commit_animation(&mut self, tab: TabId) {
+        self.commit_anim_tabs.insert(tab);
+        if self
+            .commit_anim_running
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
+            let tx = self.app_event_tx.clone();
+            let running = self.commit_anim_running.clone();
+            thread::spawn(move || {
+                while running.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(50));
+                    tx.send(AppEvent::CommitTick);
+                }
+            });
+        }
+    }
+
+    fn stop_commit_animation(&mut self, tab: TabId) {
+        self.commit_anim_tabs.remove(&tab);
+        if self.commit_anim_tabs.is_empty() {
+            self.commit_anim_running.store(false, Ordering::Release);
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn run(
         tui: &mut tui::Tui,
@@ -381,7 +672,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: app_event_tx.for_tab(0),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -407,7 +698,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: app_event_tx.clone(),
+                    app_event_tx: app_event_tx.for_tab(0),
                     initial_prompt: initial_prompt.clone(),
                     initial_images: initial_images.clone(),
                     enhanced_keys_supported,
@@ -444,8 +735,12 @@ impl App {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            deferred_history_images: Vec::new(),
             has_emitted_history_lines: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
+            commit_anim_tabs: HashSet::new(),
+            tabs: Tabs::default(),
+            palette_recent: Vec::new(),
             backtrack: BacktrackState::default(),
             feedback: feedback.clone(),
             pending_update_action: None,
@@ -532,17 +827,36 @@ impl App {
                 }
                 TuiEvent::Draw => {
                     self.chat_widget.maybe_post_pending_notification(tui);
+                    for widget in self.tabs.parked_widgets_mut() {
+                        widget.maybe_post_pending_notification(tui);
+                    }
                     if self
                         .chat_widget
                         .handle_paste_burst_tick(tui.frame_requester())
                     {
                         return Ok(true);
                     }
+                    // With more than one conversation open, a one-line tab
+                    // strip sits above the chat widget.
+                    let strip = (self.tabs.len() > 1).then(|| {
+                        self.tabs
+                            .strip_line(&self.transcript_cells, keymap::current())
+                    });
+                    let strip_height = u16::from(strip.is_some());
                     tui.draw(
-                        self.chat_widget.desired_height(tui.terminal.size()?.width),
+                        self.chat_widget.desired_height(tui.terminal.size()?.width) + strip_height,
                         |frame| {
-                            self.chat_widget.render(frame.area(), frame.buffer);
-                            if let Some((x, y)) = self.chat_widget.cursor_pos(frame.area()) {
+                            let mut area = frame.area();
+                            if let Some(strip) = strip {
+                                strip.render(Rect { height: 1, ..area }, frame.buffer);
+                                area = Rect {
+                                    y: area.y + strip_height,
+                                    height: area.height.saturating_sub(strip_height),
+                                    ..area
+                                };
+                            }
+                            self.chat_widget.render(area, frame.buffer);
+                            if let Some((x, y)) = self.chat_widget.cursor_pos(area) {
                                 frame.set_cursor_position((x, y));
                             }
                         },
@@ -559,6 +873,16 @@ impl App {
     }
 
     async fn handle_event(&mut self, tui: &mut tui::Tui, event: AppEvent) -> Result<bool> {
+        // Events from the active tab are handled as before; everything a
+        // parked conversation sends is routed to that conversation.
+        let event = match event {
+            AppEvent::Tab { tab, event } if tab == self.tabs.active() => *event,
+            AppEvent::Tab { tab, event } => {
+                self.handle_background_tab_event(tab, *event);
+                return Ok(true);
+            }
+            event => event,
+        };
         let model_family = self
             .server
             .get_models_manager()
@@ -574,7 +898,7 @@ impl App {
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: self.config.clone(),
                     frame_requester: tui.frame_requester(),
-                    app_event_tx: self.app_event_tx.clone(),
+                    app_event_tx: self.app_event_tx.for_tab(self.tabs.active()),
                     initial_prompt: None,
                     initial_images: Vec::new(),
                     enhanced_keys_supported: self.enhanced_keys_supported,
@@ -596,6 +920,47 @@ impl App {
                 }
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::NewTab => {
+                self.open_new_tab(tui, model_family);
+            }
+            AppEvent::CloseTab => {
+                self.close_active_tab(tui).await;
+            }
+            AppEvent::ExportTranscript(format) => {
+                self.export_transcript(format);
+            }
+            AppEvent::EditQueuedMessage { index, text, edit } => {
+                self.chat_widget.edit_queued_message(index, &text, edit);
+            }
+            AppEvent::Tab { .. } => {
+                // Tab wrappers are unwrapped above and never nested.
+            }
+            AppEvent::OpenCommandPalette => {
+                let sessions = crate::resume_picker::recent_sessions(
+                    &self.config.codex_home,
+                    &self.config.model_provider_id,
+                    &self.config.cwd,
+                    PALETTE_SESSIONS,
+                )
+                .await;
+                self.chat_widget
+                    .open_command_palette(sessions, &self.palette_recent);
+            }
+            AppEvent::CommandPaletteUsed(id) => {
+                self.palette_recent.retain(|recent| *recent != id);
+                self.palette_recent.insert(0, id);
+                self.palette_recent.truncate(PALETTE_RECENT_LIMIT);
+            }
+            AppEvent::RunSlashCommand(cmd) => {
+                self.chat_widget.dispatch_command(cmd);
+            }
+            AppEvent::InsertInComposer(text) => {
+                self.chat_widget.insert_str(&text);
+            }
+            AppEvent::ResumeSession(path) => {
+                self.resume_session(tui, path, model_family).await;
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::OpenResumePicker => {
                 match crate::resume_picker::run_resume_picker(
                     tui,
@@ -606,60 +971,7 @@ impl App {
                 .await?
                 {
                     ResumeSelection::Resume(path) => {
-                        let summary = session_summary(
-                            self.chat_widget.token_usage(),
-                            self.chat_widget.conversation_id(),
-                        );
-                        match self
-                            .server
-                            .resume_conversation_from_rollout(
-                                self.config.clone(),
-                                path.clone(),
-                                self.auth_manager.clone(),
-                            )
-                            .await
-                        {
-                            Ok(resumed) => {
-                                self.shutdown_current_conversation().await;
-                                let init = crate::chatwidget::ChatWidgetInit {
-                                    config: self.config.clone(),
-                                    frame_requester: tui.frame_requester(),
-                                    app_event_tx: self.app_event_tx.clone(),
-                                    initial_prompt: None,
-                                    initial_images: Vec::new(),
-                                    enhanced_keys_supported: self.enhanced_keys_supported,
-                                    auth_manager: self.auth_manager.clone(),
-                                    models_manager: self.server.get_models_manager(),
-                                    feedback: self.feedback.clone(),
-                                    is_first_run: false,
-                                    model_family: model_family.clone(),
-                                };
-                                self.chat_widget = ChatWidget::new_from_existing(
-                                    init,
-                                    resumed.conversation,
-                                    resumed.session_configured,
-                                );
-                                self.current_model = model_family.get_model_slug().to_string();
-                                if let Some(summary) = summary {
-                                    let mut lines: Vec<Line<'static>> =
-                                        vec![summary.usage_line.clone().into()];
-                                    if let Some(command) = summary.resume_command {
-                                        let spans = vec![
-                                            "To continue this session, run ".into(),
-                                            command.cyan(),
-                                        ];
-                                        lines.push(spans.into());
-                                    }
-                                    self.chat_widget.add_plain_history_lines(lines);
-                                }
-                            }
-                            Err(err) => {
-                                self.chat_widget.add_error_message(format!(
-                                    "Failed to resume session from {}: {err}",
-                                    path.display()
-                                ));
-                            }
-                        }
+                        self.resume_session(tui, path, model_family).await;
                     }
                     ResumeSelection::Exit | ResumeSelection::StartFresh => {}
                 }
@@ -674,7 +986,9 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let width = tui.terminal.last_known_screen_size.width;
+                let mut display = cell.display_lines(width);
+                let mut images = cell.inline_images(width);
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -682,38 +996,34 @@ impl App {
                     if !cell.is_stream_continuation() {
                         if self.has_emitted_history_lines {
                             display.insert(0, Line::from(""));
+                            images = images.into_iter().map(|image| image.offset(1)).collect();
                         } else {
                             self.has_emitted_history_lines = true;
                         }
                     }
                     if self.overlay.is_some() {
+                        let offset = self.deferred_history_lines.len();
+                        self.deferred_history_images
+                            .extend(images.into_iter().map(|image| image.offset(offset)));
                         self.deferred_history_lines.extend(display);
                     } else {
-                        tui.insert_history_lines(display);
+                        tui.insert_history_lines_with_images(display, images);
                     }
                 }
             }
             AppEvent::StartCommitAnimation => {
-                if self
-                    .commit_anim_running
-                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
-                    .is_ok()
-                {
-                    let tx = self.app_event_tx.clone();
-                    let running = self.commit_anim_running.clone();
-                    thread::spawn(move || {
-                        while running.load(Ordering::Relaxed) {
-                            thread::sleep(Duration::from_millis(50));
-                            tx.send(AppEvent::CommitTick);
-                        }
-                    });
-                }
+                self.start_commit_animation(self.tabs.active());
             }
             AppEvent::StopCommitAnimation => {
-                self.commit_anim_running.store(false, Ordering::Release);
+                self.stop_commit_animation(self.tabs.active());
             }
             AppEvent::CommitTick => {
+                // The animation thread is shared, so every streaming tab
+                // advances on each tick.
                 self.chat_widget.on_commit_tick();
+                for widget in self.tabs.parked_widgets_mut() {
+                    widget.on_commit_tick();
+                }
             }
             AppEvent::CodexEvent(event) => {
                 if self.suppress_shutdown_complete
@@ -722,6 +1032,9 @@ impl App {
                     self.suppress_shutdown_complete = false;
                     return Ok(true);
                 }
+                let active = self.tabs.active();
+                self.tabs
+                    .update_activity(active, |activity| activity.on_event(&event.msg, true));
                 if let EventMsg::ListSkillsResponse(response) = &event.msg {
                     let cwd = self.chat_widget.config_ref().cwd.clone();
                     let errors = errors_for_cwd(&cwd, response);
@@ -735,21 +1048,19 @@ impl App {
             AppEvent::ExitRequest => {
                 return Ok(false);
             }
-            AppEvent::CodexOp(op) => self.chat_widget.submit_op(op),
+            AppEvent::CodexOp(op) => {
+                let active = self.tabs.active();
+                self.tabs
+                    .update_activity(active, |activity| activity.on_op(&op));
+                self.chat_widget.submit_op(op);
+            }
             AppEvent::DiffResult(text) => {
                 // Clear the in-progress state in the bottom pane
                 self.chat_widget.on_diff_complete();
-                // Enter alternate screen using TUI helper and build pager lines
+                // Enter alternate screen and open the diff browser on the
+                // working tree diff plus the diffs Codex produced this session.
                 let _ = tui.enter_alt_screen();
-                let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
-                    vec!["No changes detected.".italic().into()]
-                } else {
-                    text.lines().map(ansi_escape_line).collect()
-                };
-                self.overlay = Some(Overlay::new_static_with_lines(
-                    pager_lines,
-                    "D I F F".to_string(),
-                ));
+                self.overlay = Some(Overlay::new_diff(text, self.chat_widget.turn_diffs()));
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::StartFileSearch(query) => {
@@ -1226,24 +1537,25 @@ impl App {
     }
 
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        let keymap = keymap::current();
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            e if e.kind == KeyEventKind::Press && keymap.transcript.is_press(e) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            e if e.kind == KeyEventKind::Press && keymap.next_tab.is_press(e) => {
+                if let Some(tab) = self.tabs.neighbor(1) {
+                    self.switch_to_tab(tui, tab);
+                }
+            }
+            e if e.kind == KeyEventKind::Press && keymap.previous_tab.is_press(e) => {
+                if let Some(tab) = self.tabs.neighbor(-1) {
+                    self.switch_to_tab(tui, tab);
+                }
+            }
+            e if e.kind == KeyEventKind::Press && keymap.external_editor.is_press(e) => {
                 // Only launch the external editor if there is no overlay and the bottom pane is not in use.
                 // Note that it can be launched while a task is running to enable editing while the previous turn is ongoing.
                 if self.overlay.is_none()
@@ -1253,22 +1565,16 @@ impl App {
                     self.request_external_editor_launch(tui);
                 }
             }
-            // Esc primes/advances backtracking only in normal (not working) mode
-            // with the composer focused and empty. In any other state, forward
-            // Esc so the active UI (e.g. status indicator, modals, popups)
-            // handles it.
-            KeyEvent {
-                code: KeyCode::Esc,
-                kind: KeyEventKind::Press | KeyEventKind::Repeat,
-                ..
-            } => {
-                if self.chat_widget.is_normal_backtrack_mode()
-                    && self.chat_widget.composer_is_empty()
-                {
-                    self.handle_backtrack_esc_key(tui);
-                } else {
-                    self.chat_widget.handle_key_event(key_event);
-                }
+            // The edit-previous key (Esc by default) primes/advances backtracking
+            // only in normal (not working) mode with the composer focused and
+            // empty. In any other state, forward it so the active UI (e.g.
+            // status indicator, modals, popups, vi insert mode) handles it.
+            e if keymap.edit_previous.is_press(e)
+                && self.chat_widget.is_normal_backtrack_mode()
+                && self.chat_widget.composer_is_empty()
+                && !(e.code == KeyCode::Esc && self.chat_widget.composer_in_vi_insert_mode()) =>
+            {
+                self.handle_backtrack_esc_key(tui);
             }
             // Enter confirms backtrack when primed + count > 0. Otherwise pass to widget.
             KeyEvent {
@@ -1286,10 +1592,10 @@ impl App {
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
-                // Any non-Esc key press should cancel a primed backtrack.
+                // Any other key press should cancel a primed backtrack.
                 // This avoids stale "Esc-primed" state after the user starts typing
                 // (even if they later backspace to empty).
-                if key_event.code != KeyCode::Esc && self.backtrack.primed {
+                if !keymap.edit_previous.is_press(key_event) && self.backtrack.primed {
                     self.reset_backtrack_state();
                 }
                 self.chat_widget.handle_key_event(key_event);
@@ -1340,12 +1646,16 @@ mod tests {
     use crate::history_cell::HistoryCell;
     use crate::history_cell::UserHistoryCell;
     use crate::history_cell::new_session_info;
+    use crate::tabs::TabActivity;
+    use crate::tabs::tab_title;
     use codex_core::AuthManager;
     use codex_core::CodexAuth;
     use codex_core::ConversationManager;
     use codex_core::protocol::AskForApproval;
     use codex_core::protocol::Event;
     use codex_core::protocol::EventMsg;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use codex_core::protocol::ReviewDecision;
     use codex_core::protocol::SandboxPolicy;
     use codex_core::protocol::SessionConfiguredEvent;
     use codex_protocol::ConversationId;
@@ -1378,9 +1688,13 @@ mod tests {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            deferred_history_images: Vec::new(),
             has_emitted_history_lines: false,
             enhanced_keys_supported: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
+            commit_anim_tabs: HashSet::new(),
+            tabs: Tabs::default(),
+            palette_recent: Vec::new(),
             backtrack: BacktrackState::default(),
             feedback: codex_feedback::CodexFeedback::new(),
             pending_update_action: None,
@@ -1418,9 +1732,13 @@ mod tests {
                 transcript_cells: Vec::new(),
                 overlay: None,
                 deferred_history_lines: Vec::new(),
+                deferred_history_images: Vec::new(),
                 has_emitted_history_lines: false,
                 enhanced_keys_supported: false,
                 commit_anim_running: Arc::new(AtomicBool::new(false)),
+                commit_anim_tabs: HashSet::new(),
+                tabs: Tabs::default(),
+                palette_recent: Vec::new(),
                 backtrack: BacktrackState::default(),
                 feedback: codex_feedback::CodexFeedback::new(),
                 pending_update_action: None,
@@ -1639,6 +1957,96 @@ mod tests {
         }
     }
 
+    async fn park_background_tab(
+        app: &mut App,
+    ) -> (TabId, tokio::sync::mpsc::UnboundedReceiver<Op>) {
+        let (widget, _tx, _rx, op_rx) = make_chatwidget_manual_with_sender().await;
+        let tab = app.tabs.allocate();
+        app.tabs.park(
+            tab,
+            ParkedTab {
+                widget,
+                transcript_cells: Vec::new(),
+                unseen_cells: Vec::new(),
+                current_model: app.current_model.clone(),
+            },
+        );
+        (tab, op_rx)
+    }
+
+    #[tokio::test]
+    async fn background_approvals_are_routed_to_their_own_tab() {
+        let (mut app, _app_event_rx, mut active_op_rx) = make_test_app_with_channels().await;
+        let (tab, mut background_op_rx) = park_background_tab(&mut app).await;
+
+        app.handle_background_tab_event(
+            tab,
+            AppEvent::CodexEvent(Event {
+                id: "sub-1".into(),
+                msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: "call-1".into(),
+                    turn_id: "turn-1".into(),
+                    command: vec!["echo".into(), "hi".into()],
+                    cwd: PathBuf::from("/tmp"),
+                    reason: None,
+                    proposed_execpolicy_amendment: None,
+                    parsed_cmd: Vec::new(),
+                }),
+            }),
+        );
+        assert_eq!(app.tabs.activity(tab), TabActivity::NeedsApproval);
+        assert_eq!(app.tabs.activity(app.tabs.active()), TabActivity::Idle);
+
+        app.handle_background_tab_event(
+            tab,
+            AppEvent::CodexOp(Op::ExecApproval {
+                id: "call-1".into(),
+                decision: ReviewDecision::Approved,
+            }),
+        );
+        assert_eq!(app.tabs.activity(tab), TabActivity::Working);
+        assert!(matches!(
+            background_op_rx.try_recv(),
+            Ok(Op::ExecApproval { .. })
+        ));
+        assert!(active_op_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn switching_tabs_swaps_transcripts_and_returns_unseen_cells() {
+        let mut app = make_test_app().await;
+        let first_cell: Arc<dyn HistoryCell> = Arc::new(UserHistoryCell {
+            message: "first tab".to_string(),
+        });
+        app.transcript_cells.push(first_cell);
+        let (tab, _op_rx) = park_background_tab(&mut app).await;
+
+        app.handle_background_tab_event(
+            tab,
+            AppEvent::InsertHistoryCell(Box::new(UserHistoryCell {
+                message: "second tab".to_string(),
+            })),
+        );
+
+        let unseen = app.activate_tab(tab).expect("parked tab");
+        assert_eq!(unseen.len(), 1);
+        assert_eq!(app.tabs.active(), tab);
+        assert_eq!(tab_title(&app.transcript_cells), "second tab");
+
+        let unseen = app.activate_tab(0).expect("first tab was parked");
+        assert!(unseen.is_empty());
+        assert_eq!(tab_title(&app.transcript_cells), "first tab");
+        assert!(app.activate_tab(0).is_none());
+    }
+
+    #[tokio::test]
+    async fn events_for_closed_tabs_are_dropped() {
+        let (mut app, mut app_event_rx, mut op_rx) = make_test_app_with_channels().await;
+        app.handle_background_tab_event(7, AppEvent::CodexOp(Op::Interrupt));
+        assert!(op_rx.try_recv().is_err());
+        assert!(app_event_rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn session_summary_skip_zero_usage() {
         assert!(session_summary(TokenUsage::default(), None).is_none());
@@ -8,7 +8,10 @@ use codex_file_search::FileMatch;
 use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::export::ExportFormat;
 use crate::history_cell::HistoryCell;
+use crate::slash_command::SlashCommand;
+use crate::tabs::TabId;
 
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
@@ -20,12 +23,44 @@ use codex_protocol::openai_models::ReasoningEffort;
 pub(crate) enum AppEvent {
     CodexEvent(Event),
 
+    /// An event sent on behalf of a conversation tab. The app unwraps events
+    /// for the active tab and routes the rest to the parked conversation.
+    Tab {
+        tab: TabId,
+        event: Box<AppEvent>,
+    },
+
     /// Start a new session.
     NewSession,
 
+    /// Open a new conversation in another tab and switch to it.
+    NewTab,
+
+    /// Shut down the active tab's conversation and switch to a neighbour.
+    CloseTab,
+
+    /// Write the active conversation's transcript to a file in `format`.
+    ExportTranscript(ExportFormat),
+
     /// Open the resume picker inside the running TUI session.
     OpenResumePicker,
 
+    /// Open the command palette. The app gathers recent sessions first.
+    OpenCommandPalette,
+
+    /// An entry was chosen in the command palette; `id` is its stable key,
+    /// used to rank recently used entries first next time.
+    CommandPaletteUsed(String),
+
+    /// Run a slash command as if it had been typed in the composer.
+    RunSlashCommand(SlashCommand),
+
+    /// Insert text at the cursor in the composer.
+    InsertInComposer(String),
+
+    /// Resume the recorded session at `path`, replacing the current one.
+    ResumeSession(PathBuf),
+
     /// Request to exit the application gracefully.
     ExitRequest,
 
@@ -184,6 +219,28 @@ pub(crate) enum AppEvent {
 
     /// Launch the external editor after a normal draw has completed.
     LaunchExternalEditor,
+
+    /// Apply an edit from the queue editor to the queued message at `index`.
+    /// The queue may advance while the editor is open, so the edit is
+    /// dropped unless that message still reads `text`.
+    EditQueuedMessage {
+        index: usize,
+        text: String,
+        edit: QueueEdit,
+    },
+}
+
+/// A change the user made in the queued-message editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum QueueEdit {
+    /// Move the message back into the composer.
+    Edit,
+    Remove,
+    /// Flip whether the message waits for the previous turn to succeed.
+    ToggleCondition,
+    Move {
+        to: usize,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -5,6 +5,8 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::BottomPaneView;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::hunk_review::HunkReview;
+use crate::bottom_pane::hunk_review::HunkReviewStep;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
@@ -50,6 +52,9 @@ pub(crate) enum ApprovalRequest {
         reason: Option<String>,
         cwd: PathBuf,
         changes: HashMap<PathBuf, FileChange>,
+        /// The changes can only be approved as a whole; hunk review is not
+        /// offered.
+        whole_patch_only: bool,
     },
     McpElicitation {
         server_name: String,
@@ -69,6 +74,8 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    /// Active hunk-by-hunk review of the current patch request, if any.
+    hunk_review: Option<HunkReview>,
 }
 
 impl ApprovalOverlay {
@@ -83,6 +90,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            hunk_review: None,
         };
         view.set_current(request);
         view
@@ -97,6 +105,7 @@ impl ApprovalOverlay {
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
         self.current_complete = false;
+        self.hunk_review = None;
         let (options, params) = Self::build_options(variant, header, &self.features);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
@@ -115,8 +124,10 @@ impl ApprovalOverlay {
                 exec_options(proposed_execpolicy_amendment.clone(), features),
                 "Would you like to run the following command?".to_string(),
             ),
-            ApprovalVariant::ApplyPatch { .. } => (
-                patch_options(),
+            ApprovalVariant::ApplyPatch {
+                whole_patch_only, ..
+            } => (
+                patch_options(*whole_patch_only),
                 "Would you like to make the following edits?".to_string(),
             ),
             ApprovalVariant::McpElicitation { server_name, .. } => (
@@ -174,6 +185,13 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (
+                    ApprovalVariant::ApplyPatch { cwd, changes, .. },
+                    ApprovalDecision::ReviewHunks,
+                ) => {
+                    self.hunk_review = Some(HunkReview::new(changes, cwd.clone()));
+                    return;
+                }
                 (
                     ApprovalVariant::McpElicitation {
                         server_name,
@@ -207,6 +225,32 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_hunk_review_key(&mut self, key_event: KeyEvent) {
+        let Some(review) = self.hunk_review.as_mut() else {
+            return;
+        };
+        match review.handle_key_event(key_event) {
+            HunkReviewStep::Continue => {}
+            HunkReviewStep::Back => self.hunk_review = None,
+            HunkReviewStep::Finished(decision) => {
+                let summary = review.summary();
+                self.hunk_review = None;
+                let Some(ApprovalVariant::ApplyPatch { id, .. }) = self.current_variant.as_ref()
+                else {
+                    return;
+                };
+                if matches!(decision, ReviewDecision::ApprovedHunks { .. }) {
+                    self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_info_event(summary, None),
+                    )));
+                }
+                self.handle_patch_decision(id, decision);
+                self.current_complete = true;
+                self.advance_queue();
+            }
+        }
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -263,6 +307,10 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.hunk_review.is_some() {
+            self.handle_hunk_review_key(key_event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -298,11 +346,18 @@ impl BottomPaneView for ApprovalOverlay {
                 }
             }
         }
+        self.hunk_review = None;
         self.queue.clear();
         self.done = true;
         CancellationEvent::Handled
     }
 
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        self.hunk_review
+            .as_mut()
+            .is_some_and(|review| review.handle_paste(pasted))
+    }
+
     fn is_complete(&self) -> bool {
         self.done
     }
@@ -318,15 +373,24 @@ impl BottomPaneView for ApprovalOverlay {
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.hunk_review {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.hunk_review {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.hunk_review {
+            Some(review) => review.cursor_pos(area),
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -369,6 +433,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 reason,
                 cwd,
                 changes,
+                whole_patch_only,
             } => {
                 let mut header: Vec<Box<dyn Renderable>> = Vec::new();
                 if let Some(reason) = reason
@@ -380,9 +445,14 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
-                header.push(DiffSummary::new(changes, cwd).into());
+                header.push(DiffSummary::new(changes.clone(), cwd.clone()).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch {
+                        id,
+                        cwd,
+                        changes,
+                        whole_patch_only,
+                    },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -418,6 +488,9 @@ enum ApprovalVariant {
     },
     ApplyPatch {
         id: String,
+        cwd: PathBuf,
+        changes: HashMap<PathBuf, FileChange>,
+        whole_patch_only: bool,
     },
     McpElicitation {
         server_name: String,
@@ -428,6 +501,8 @@ enum ApprovalVariant {
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
+    /// Open the hunk-by-hunk review instead of deciding on the whole patch.
+    ReviewHunks,
     McpElicitation(ElicitationAction),
 }
 
@@ -486,21 +561,28 @@ fn exec_options(
     .collect()
 }
 
-fn patch_options() -> Vec<ApprovalOption> {
-    vec![
-        ApprovalOption {
-            label: "Yes, proceed".to_string(),
-            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+fn patch_options(whole_patch_only: bool) -> Vec<ApprovalOption> {
+    let mut options = vec![ApprovalOption {
+        label: "Yes, proceed".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Approved),
+        display_shortcut: None,
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+    }];
+    if !whole_patch_only {
+        options.push(ApprovalOption {
+            label: "Review each hunk".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
             display_shortcut: None,
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
-        },
-        ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
-            decision: ApprovalDecision::Review(ReviewDecision::Abort),
-            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
-            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
-        },
-    ]
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        });
+    }
+    options.push(ApprovalOption {
+        label: "No, and tell Codex what to do differently".to_string(),
+        decision: ApprovalDecision::Review(ReviewDecision::Abort),
+        display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+        additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+    });
+    options
 }
 
 fn elicitation_options() -> Vec<ApprovalOption> {
@@ -693,6 +775,85 @@ mod tests {
         assert_eq!(rendered, expected);
     }
 
+    #[test]
+    fn hunk_review_submits_partial_patch() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let changes = HashMap::from([(
+            PathBuf::from("/repo/a.txt"),
+            FileChange::Update {
+                unified_diff: "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n one\n-two\n+2\n@@ -9,2 +9,2 @@\n nine\n-ten\n+10\n".to_string(),
+                move_path: None,
+            },
+        )]);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "patch".to_string(),
+                reason: None,
+                cwd: PathBuf::from("/repo"),
+                changes,
+                whole_patch_only: false,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(!view.is_complete(), "one hunk is still undecided");
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        let Some(ReviewDecision::ApprovedHunks { patch, .. }) = decision else {
+            panic!("expected a partial approval, got {decision:?}");
+        };
+        assert!(patch.contains("-two\n+2\n"), "{patch}");
+        assert!(patch.contains(" nine\n ten\n"), "{patch}");
+    }
+
+    #[test]
+    fn whole_patch_only_requests_do_not_offer_hunk_review() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let changes = HashMap::from([(
+            PathBuf::from("/repo/a.txt"),
+            FileChange::Update {
+                unified_diff: "--- a/a.txt\n+++ b/a.txt\n@@ -1,2 +1,2 @@\n one\n-two\n+2\n"
+                    .to_string(),
+                move_path: None,
+            },
+        )]);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "overlay".to_string(),
+                reason: None,
+                cwd: PathBuf::from("/repo"),
+                changes,
+                whole_patch_only: true,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        // Without hunk review, `r` does nothing and `y` approves everything.
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        assert!(!view.is_complete());
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::Approved));
+    }
+
     #[test]
     fn enter_sets_last_selected_index_without_dismissing() {
         let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
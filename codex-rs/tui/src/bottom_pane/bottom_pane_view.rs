@@ -1,4 +1,5 @@
 use crate::bottom_pane::ApprovalRequest;
+use crate::bottom_pane::QueuedMessage;
 use crate::render::renderable::Renderable;
 use crossterm::event::KeyEvent;
 
@@ -34,4 +35,7 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<ApprovalRequest> {
         Some(request)
     }
+
+    /// Called when the queued user messages change while the view is open.
+    fn on_queue_changed(&mut self, _messages: &[QueuedMessage]) {}
 }
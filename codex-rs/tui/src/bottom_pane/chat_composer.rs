@@ -51,9 +51,11 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::textarea::TextArea;
 use crate::bottom_pane::textarea::TextAreaState;
+use crate::bottom_pane::textarea::ViMode;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::keymap;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -487,6 +489,10 @@ impl ChatComposer {
 
     /// Handle a key event coming from the main UI.
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        if let Some(result) = self.handle_vi_key(key_event) {
+            self.sync_popups();
+            return result;
+        }
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
@@ -497,9 +503,54 @@ impl ChatComposer {
         // Update (or hide/show) popup after processing the key.
         self.sync_popups();
 
+        // Like a vi-mode shell prompt, each new message starts in insert mode.
+        if matches!(
+            result.0,
+            InputResult::Submitted(_) | InputResult::Command(_)
+        ) && self.textarea.vi_mode().is_some()
+        {
+            self.textarea.set_vi_enabled(true);
+        }
+
         result
     }
 
+    pub(crate) fn set_vi_mode(&mut self, enabled: bool) {
+        self.textarea.set_vi_enabled(enabled);
+    }
+
+    pub(crate) fn in_vi_insert_mode(&self) -> bool {
+        self.textarea.vi_mode() == Some(ViMode::Insert)
+    }
+
+    /// Vi mode: Esc leaves insert mode (unless a popup wants it), and plain
+    /// characters in normal mode are commands rather than text. Everything
+    /// else (Enter, arrows, control chords) keeps its usual meaning.
+    fn handle_vi_key(&mut self, key_event: KeyEvent) -> Option<(InputResult, bool)> {
+        if !matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat) {
+            return None;
+        }
+        match (self.textarea.vi_mode()?, key_event.code) {
+            (ViMode::Insert, KeyCode::Esc)
+                if key_event.modifiers == KeyModifiers::NONE && !self.popup_active() =>
+            {
+                self.textarea.enter_vi_normal_mode();
+                Some((InputResult::None, true))
+            }
+            (ViMode::Normal, KeyCode::Char(c))
+                if matches!(
+                    key_event.modifiers,
+                    KeyModifiers::NONE | KeyModifiers::SHIFT
+                ) =>
+            {
+                self.footer_mode = reset_mode_after_activity(self.footer_mode);
+                self.textarea.vi_normal_input(c);
+                Some((InputResult::None, true))
+            }
+            _ => None,
+        }
+    }
+
     /// Return true if either the slash-command popup or the file-search popup is active.
     pub(crate) fn popup_active(&self) -> bool {
         !matches!(self.active_popup, ActivePopup::None)
@@ -902,6 +953,10 @@ impl ChatComposer {
         self.skills.as_ref()
     }
 
+    pub(crate) fn custom_prompts(&self) -> &[CustomPrompt] {
+        &self.custom_prompts
+    }
+
     /// Extract a token prefixed with `prefix` under the cursor, if any.
     ///
     /// The returned string **does not** include the prefix.
@@ -1628,6 +1683,8 @@ impl ChatComposer {
             is_task_running: self.is_task_running,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            vi_mode: self.textarea.vi_mode(),
+            keymap: keymap::current(),
         }
     }
 
@@ -2205,6 +2262,41 @@ mod tests {
         assert!(!composer.esc_backtrack_hint);
     }
 
+    #[test]
+    fn vi_mode_esc_enters_normal_mode_and_submit_resets_to_insert() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            true,
+            "Ask Codex to do anything".to_string(),
+            true,
+        );
+        composer.set_vi_mode(true);
+        assert!(composer.in_vi_insert_mode());
+
+        composer.insert_str("fix the bug");
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!composer.in_vi_insert_mode());
+        assert_eq!(composer.footer_props().vi_mode, Some(ViMode::Normal));
+
+        // In normal mode letters are commands: `b` then `D` deletes the last word.
+        for c in ['b', 'D'] {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(composer.current_text(), "fix the ");
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(result, InputResult::Submitted(text) if text == "fix the"));
+        assert!(composer.in_vi_insert_mode());
+    }
+
     #[test]
     fn clear_for_ctrl_c_records_cleared_draft() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
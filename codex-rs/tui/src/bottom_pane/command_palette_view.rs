@@ -0,0 +1,540 @@
+use codex_common::fuzzy_match::fuzzy_match;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::list_selection_view::SelectionAction;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::popup_consts::standard_popup_hint_line;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+/// How many of the most recently used entries get a ranking boost.
+const RECENT_BOOST: usize = 10;
+
+/// `fuzzy_match` rewards matches that start at the first character; names
+/// led by a `/` or `$` sigil get the same reward when the match starts right
+/// after it, so `appr` ranks `/approvals` like `Approvals: …`.
+const SIGIL_PREFIX_BONUS: i32 = 100;
+
+/// Where a palette entry comes from; shown next to its description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PaletteCategory {
+    Command,
+    Prompt,
+    Skill,
+    Session,
+    Model,
+    Setting,
+}
+
+impl PaletteCategory {
+    fn label(self) -> &'static str {
+        match self {
+            PaletteCategory::Command => "command",
+            PaletteCategory::Prompt => "prompt",
+            PaletteCategory::Skill => "skill",
+            PaletteCategory::Session => "session",
+            PaletteCategory::Model => "model",
+            PaletteCategory::Setting => "setting",
+        }
+    }
+}
+
+/// One entry in the command palette.
+pub(crate) struct PaletteItem {
+    /// Stable key used to remember recently used entries across openings.
+    pub id: String,
+    pub category: PaletteCategory,
+    /// The text that is searched and displayed.
+    pub name: String,
+    pub description: Option<String>,
+    pub is_current: bool,
+    pub disabled_reason: Option<String>,
+    pub actions: Vec<SelectionAction>,
+}
+
+/// A fuzzy-searchable list of everything the TUI can do: slash commands,
+/// prompts, skills, recent sessions, models and settings.
+pub(crate) struct CommandPaletteView {
+    items: Vec<PaletteItem>,
+    /// Entry ids, most recently used first.
+    recent: Vec<String>,
+    query: String,
+    /// Indices into `items` that match `query`, best first, with the
+    /// matched character positions for highlighting.
+    filtered: Vec<(usize, Option<Vec<usize>>)>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl CommandPaletteView {
+    pub(crate) fn new(
+        items: Vec<PaletteItem>,
+        recent: Vec<String>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let mut view = Self {
+            items,
+            recent,
+            query: String::new(),
+            filtered: Vec::new(),
+            state: ScrollState::new(),
+            complete: false,
+            app_event_tx,
+        };
+        view.apply_filter();
+        view
+    }
+
+    fn recency_rank(&self, id: &str) -> Option<usize> {
+        self.recent
+            .iter()
+            .take(RECENT_BOOST)
+            .position(|recent| recent == id)
+    }
+
+    /// Rank items against the query. With no query, recently used entries
+    /// come first and the rest keep their original order; otherwise matches
+    /// are ordered by fuzzy score with a bonus for recent use.
+    fn apply_filter(&mut self) {
+        let query = self.query.trim();
+        let mut ranked: Vec<(i32, usize, Option<Vec<usize>>)> = Vec::new();
+        for (idx, item) in self.items.iter().enumerate() {
+            let recency_bonus = self
+                .recency_rank(&item.id)
+                .map_or(0, |rank| (RECENT_BOOST - rank) as i32);
+            if query.is_empty() {
+                ranked.push((-recency_bonus, idx, None));
+                continue;
+            }
+            let Some((indices, score)) = fuzzy_match(&item.name, query) else {
+                continue;
+            };
+            let prefix_bonus = if starts_after_sigil(&item.name, query) {
+                SIGIL_PREFIX_BONUS
+            } else {
+                0
+            };
+            let score = score
+                .saturating_sub(prefix_bonus)
+                .saturating_sub(recency_bonus);
+            ranked.push((score, idx, Some(indices)));
+        }
+        ranked.sort_by_key(|(score, idx, _)| (*score, *idx));
+        self.filtered = ranked
+            .into_iter()
+            .map(|(_, idx, indices)| (idx, indices))
+            .collect();
+
+        let len = self.filtered.len();
+        self.state.selected_idx = (len > 0).then_some(0);
+        self.state.scroll_top = 0;
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.filtered
+            .iter()
+            .enumerate()
+            .filter_map(|(visible_idx, (idx, indices))| {
+                let item = self.items.get(*idx)?;
+                let prefix = if self.state.selected_idx == Some(visible_idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let marker = if item.is_current { " (current)" } else { "" };
+                let category = item.category.label();
+                let description = match &item.description {
+                    Some(description) => format!("{category} · {description}"),
+                    None => category.to_string(),
+                };
+                Some(GenericDisplayRow {
+                    name: format!("{prefix} {}{marker}", item.name),
+                    // Shift past the two-column selection prefix.
+                    match_indices: indices
+                        .as_ref()
+                        .map(|indices| indices.iter().map(|i| i + 2).collect()),
+                    description: Some(description),
+                    disabled_reason: item.disabled_reason.clone(),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    fn move_up(&mut self) {
+        let len = self.filtered.len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_down(&mut self) {
+        let len = self.filtered.len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn accept(&mut self) {
+        let Some(item) = self
+            .state
+            .selected_idx
+            .and_then(|selected| self.filtered.get(selected))
+            .and_then(|(idx, _)| self.items.get(*idx))
+        else {
+            return;
+        };
+        if item.disabled_reason.is_some() {
+            return;
+        }
+        self.app_event_tx
+            .send(AppEvent::CommandPaletteUsed(item.id.clone()));
+        for action in &item.actions {
+            action(&self.app_event_tx);
+        }
+        self.complete = true;
+    }
+
+    #[cfg(test)]
+    fn visible_names(&self) -> Vec<&str> {
+        self.filtered
+            .iter()
+            .map(|(idx, _)| self.items[*idx].name.as_str())
+            .collect()
+    }
+
+    fn rows_width(total_width: u16) -> u16 {
+        total_width.saturating_sub(2)
+    }
+}
+
+fn starts_after_sigil(name: &str, query: &str) -> bool {
+    !query.starts_with(['/', '$'])
+        && name
+            .to_lowercase()
+            .strip_prefix(['/', '$'])
+            .is_some_and(|rest| rest.starts_with(&query.to_lowercase()))
+}
+
+impl BottomPaneView for CommandPaletteView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_down(),
+            KeyEvent {
+                code: KeyCode::Enter,
+                ..
+            } => self.accept(),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                if self.query.pop().is_some() {
+                    self.apply_filter();
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                self.query.push(c);
+                self.apply_filter();
+            }
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        let pasted = pasted.replace(['\r', '\n'], " ");
+        if pasted.is_empty() {
+            return false;
+        }
+        self.query.push_str(&pasted);
+        self.apply_filter();
+        true
+    }
+}
+
+impl Renderable for CommandPaletteView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            Self::rows_width(width).saturating_add(1),
+        );
+        // Title, spacer and search line, plus top and bottom padding and the
+        // footer hint.
+        rows_height.saturating_add(6)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(content_area.width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+        let [title_area, _, search_area, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Max(1),
+            Constraint::Length(1),
+            Constraint::Length(rows_height),
+        ])
+        .areas(content_area.inset(Insets::vh(1, 2)));
+
+        Line::from("Command palette".bold()).render(title_area, buf);
+
+        let query_line = if self.query.is_empty() {
+            Line::from("Type to search commands, prompts, sessions and settings".dim())
+        } else {
+            Line::from(self.query.clone())
+        };
+        query_line.render(search_area, buf);
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                y: list_area.y,
+                width: rows_width.max(1),
+                height: list_area.height,
+            };
+            render_rows(
+                render_area,
+                buf,
+                &rows,
+                &self.state,
+                render_area.height as usize,
+                "no matches",
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            y: footer_area.y,
+            width: footer_area.width.saturating_sub(2),
+            height: footer_area.height,
+        };
+        standard_popup_hint_line().dim().render(hint_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn item(id: &str, category: PaletteCategory, name: &str, description: &str) -> PaletteItem {
+        let text = name.to_string();
+        PaletteItem {
+            id: id.to_string(),
+            category,
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            is_current: false,
+            disabled_reason: None,
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::InsertInComposer(text.clone()));
+            })],
+        }
+    }
+
+    fn make_view(recent: &[&str]) -> (CommandPaletteView, UnboundedReceiver<AppEvent>) {
+        let (tx_raw, rx) = unbounded_channel::<AppEvent>();
+        let items = vec![
+            item(
+                "cmd:model",
+                PaletteCategory::Command,
+                "/model",
+                "choose what model and reasoning effort to use",
+            ),
+            item(
+                "cmd:approvals",
+                PaletteCategory::Command,
+                "/approvals",
+                "choose what Codex can do without approval",
+            ),
+            item(
+                "setting:approvals:auto",
+                PaletteCategory::Setting,
+                "Approvals: Agent",
+                "Codex can read and edit files in the current workspace",
+            ),
+            item(
+                "setting:effort:high",
+                PaletteCategory::Setting,
+                "Reasoning effort: High",
+                "Maximizes reasoning depth for complex problems",
+            ),
+            item(
+                "model:gpt-5.1-codex",
+                PaletteCategory::Model,
+                "Switch model: gpt-5.1-codex",
+                "Optimized for codex.",
+            ),
+        ];
+        let recent = recent.iter().map(ToString::to_string).collect();
+        (
+            CommandPaletteView::new(items, recent, AppEventSender::new(tx_raw)),
+            rx,
+        )
+    }
+
+    fn type_query(view: &mut CommandPaletteView, query: &str) {
+        for c in query.chars() {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn render_lines(view: &CommandPaletteView, width: u16) -> String {
+        let height = view.desired_height(width);
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|row| {
+                (0..area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn empty_query_lists_recent_entries_first() {
+        let (view, _rx) = make_view(&["setting:effort:high", "cmd:approvals"]);
+        assert_eq!(
+            view.visible_names(),
+            vec![
+                "Reasoning effort: High",
+                "/approvals",
+                "/model",
+                "Approvals: Agent",
+                "Switch model: gpt-5.1-codex",
+            ]
+        );
+    }
+
+    #[test]
+    fn query_fuzzy_matches_with_prefixes_first() {
+        let (mut view, _rx) = make_view(&[]);
+        type_query(&mut view, "appr");
+        assert_eq!(view.visible_names(), vec!["/approvals", "Approvals: Agent"]);
+
+        type_query(&mut view, "zz");
+        assert_eq!(view.visible_names(), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn recent_use_breaks_ties_between_matches() {
+        let (mut view, _rx) = make_view(&["setting:approvals:auto"]);
+        type_query(&mut view, "appr");
+        assert_eq!(view.visible_names(), vec!["Approvals: Agent", "/approvals"]);
+    }
+
+    #[test]
+    fn enter_records_use_and_runs_actions() {
+        let (mut view, mut rx) = make_view(&[]);
+        type_query(&mut view, "effort");
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        let AppEvent::CommandPaletteUsed(id) = rx.try_recv().expect("usage event") else {
+            panic!("expected CommandPaletteUsed");
+        };
+        assert_eq!(id, "setting:effort:high");
+        let AppEvent::InsertInComposer(text) = rx.try_recv().expect("action event") else {
+            panic!("expected the item's action to run");
+        };
+        assert_eq!(text, "Reasoning effort: High");
+    }
+
+    #[test]
+    fn disabled_entries_are_not_accepted() {
+        let (mut view, mut rx) = make_view(&[]);
+        view.items[0].disabled_reason = Some("Disabled by policy".to_string());
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(!view.is_complete());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn renders_palette_with_query() {
+        let (mut view, _rx) = make_view(&[]);
+        assert_snapshot!("command_palette_empty", render_lines(&view, 80));
+        type_query(&mut view, "mod");
+        assert_snapshot!("command_palette_filtered", render_lines(&view, 80));
+    }
+}
@@ -1,7 +1,10 @@
+use crate::bottom_pane::textarea::ViMode;
 #[cfg(target_os = "linux")]
 use crate::clipboard_paste::is_probably_wsl;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyBindings;
+use crate::keymap::Keymap;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
 use crate::ui_consts::FOOTER_INDENT_COLS;
@@ -22,6 +25,8 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    pub(crate) vi_mode: Option<ViMode>,
+    pub(crate) keymap: &'static Keymap,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -89,6 +94,7 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
                 props.context_window_percent,
                 props.context_window_used_tokens,
             );
+            prepend_vi_mode(&mut line, props.vi_mode);
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
@@ -106,17 +112,31 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
                 use_shift_enter_hint: props.use_shift_enter_hint,
                 esc_backtrack_hint: props.esc_backtrack_hint,
                 is_wsl,
+                keymap: props.keymap,
             };
             shortcut_overlay_lines(state)
         }
-        FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint)],
-        FooterMode::ContextOnly => vec![context_window_line(
-            props.context_window_percent,
-            props.context_window_used_tokens,
-        )],
+        FooterMode::EscHint => vec![esc_hint_line(props.esc_backtrack_hint, props.keymap)],
+        FooterMode::ContextOnly => {
+            let mut line = context_window_line(
+                props.context_window_percent,
+                props.context_window_used_tokens,
+            );
+            prepend_vi_mode(&mut line, props.vi_mode);
+            vec![line]
+        }
     }
 }
 
+fn prepend_vi_mode(line: &mut Line<'static>, vi_mode: Option<ViMode>) {
+    let label = match vi_mode {
+        Some(ViMode::Normal) => "NORMAL".cyan().bold(),
+        Some(ViMode::Insert) => "INSERT".dim(),
+        None => return,
+    };
+    line.spans.splice(0..0, [label, " · ".dim()]);
+}
+
 #[derive(Clone, Copy, Debug)]
 struct CtrlCReminderState {
     is_task_running: bool,
@@ -127,6 +147,7 @@ struct ShortcutsState {
     use_shift_enter_hint: bool,
     esc_backtrack_hint: bool,
     is_wsl: bool,
+    keymap: &'static Keymap,
 }
 
 fn ctrl_c_reminder_line(state: CtrlCReminderState) -> Line<'static> {
@@ -142,8 +163,10 @@ fn ctrl_c_reminder_line(state: CtrlCReminderState) -> Line<'static> {
     .dim()
 }
 
-fn esc_hint_line(esc_backtrack_hint: bool) -> Line<'static> {
-    let esc = key_hint::plain(KeyCode::Esc);
+fn esc_hint_line(esc_backtrack_hint: bool, keymap: &Keymap) -> Line<'static> {
+    let Some(esc) = keymap.edit_previous.primary() else {
+        return Line::default();
+    };
     if esc_backtrack_hint {
         Line::from(vec![esc.into(), " again to edit previous message".into()]).dim()
     } else {
@@ -286,7 +309,7 @@ impl ShortcutBinding {
 enum DisplayCondition {
     Always,
     WhenShiftEnterHint,
-    WhenNotShiftEnterHint,
+    /// Under WSL with the default paste-image bindings.
     WhenUnderWSL,
 }
 
@@ -295,8 +318,7 @@ impl DisplayCondition {
         match self {
             DisplayCondition::Always => true,
             DisplayCondition::WhenShiftEnterHint => state.use_shift_enter_hint,
-            DisplayCondition::WhenNotShiftEnterHint => !state.use_shift_enter_hint,
-            DisplayCondition::WhenUnderWSL => state.is_wsl,
+            DisplayCondition::WhenUnderWSL => state.is_wsl && !state.keymap.paste_image_customized,
         }
     }
 }
@@ -304,18 +326,27 @@ impl DisplayCondition {
 struct ShortcutDescriptor {
     id: ShortcutId,
     bindings: &'static [ShortcutBinding],
+    /// Keymap action whose first chord is shown when no fixed binding applies.
+    action: Option<fn(&Keymap) -> &KeyBindings>,
     prefix: &'static str,
     label: &'static str,
 }
 
 impl ShortcutDescriptor {
-    fn binding_for(&self, state: ShortcutsState) -> Option<&'static ShortcutBinding> {
-        self.bindings.iter().find(|binding| binding.matches(state))
+    fn binding_for(&self, state: ShortcutsState) -> Option<KeyBinding> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.matches(state))
+            .map(|binding| binding.key)
+            .or_else(|| {
+                self.action
+                    .and_then(|action| action(state.keymap).primary())
+            })
     }
 
     fn overlay_entry(&self, state: ShortcutsState) -> Option<Line<'static>> {
         let binding = self.binding_for(state)?;
-        let mut line = Line::from(vec![self.prefix.into(), binding.key.into()]);
+        let mut line = Line::from(vec![self.prefix.into(), binding.into()]);
         match self.id {
             ShortcutId::EditPrevious => {
                 if state.esc_backtrack_hint {
@@ -323,7 +354,7 @@ impl ShortcutDescriptor {
                 } else {
                     line.extend(vec![
                         " ".into(),
-                        key_hint::plain(KeyCode::Esc).into(),
+                        binding.into(),
                         " to edit previous message".into(),
                     ]);
                 }
@@ -341,21 +372,17 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::plain(KeyCode::Char('/')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " for commands",
     },
     ShortcutDescriptor {
         id: ShortcutId::InsertNewline,
-        bindings: &[
-            ShortcutBinding {
-                key: key_hint::shift(KeyCode::Enter),
-                condition: DisplayCondition::WhenShiftEnterHint,
-            },
-            ShortcutBinding {
-                key: key_hint::ctrl(KeyCode::Char('j')),
-                condition: DisplayCondition::WhenNotShiftEnterHint,
-            },
-        ],
+        bindings: &[ShortcutBinding {
+            key: key_hint::shift(KeyCode::Enter),
+            condition: DisplayCondition::WhenShiftEnterHint,
+        }],
+        action: Some(|keymap| &keymap.newline),
         prefix: "",
         label: " for newline",
     },
@@ -365,41 +392,33 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::plain(KeyCode::Char('@')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " for file paths",
     },
     ShortcutDescriptor {
         id: ShortcutId::PasteImage,
         // Show Ctrl+Alt+V when running under WSL (terminals often intercept plain
-        // Ctrl+V); otherwise fall back to Ctrl+V.
-        bindings: &[
-            ShortcutBinding {
-                key: key_hint::ctrl_alt(KeyCode::Char('v')),
-                condition: DisplayCondition::WhenUnderWSL,
-            },
-            ShortcutBinding {
-                key: key_hint::ctrl(KeyCode::Char('v')),
-                condition: DisplayCondition::Always,
-            },
-        ],
+        // Ctrl+V); otherwise fall back to the first paste-image binding.
+        bindings: &[ShortcutBinding {
+            key: key_hint::ctrl_alt(KeyCode::Char('v')),
+            condition: DisplayCondition::WhenUnderWSL,
+        }],
+        action: Some(|keymap| &keymap.paste_image),
         prefix: "",
         label: " to paste images",
     },
     ShortcutDescriptor {
         id: ShortcutId::ExternalEditor,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('g')),
-            condition: DisplayCondition::Always,
-        }],
+        bindings: &[],
+        action: Some(|keymap| &keymap.external_editor),
         prefix: "",
         label: " to edit in external editor",
     },
     ShortcutDescriptor {
         id: ShortcutId::EditPrevious,
-        bindings: &[ShortcutBinding {
-            key: key_hint::plain(KeyCode::Esc),
-            condition: DisplayCondition::Always,
-        }],
+        bindings: &[],
+        action: Some(|keymap| &keymap.edit_previous),
         prefix: "",
         label: "",
     },
@@ -409,15 +428,14 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
             key: key_hint::ctrl(KeyCode::Char('c')),
             condition: DisplayCondition::Always,
         }],
+        action: None,
         prefix: "",
         label: " to exit",
     },
     ShortcutDescriptor {
         id: ShortcutId::ShowTranscript,
-        bindings: &[ShortcutBinding {
-            key: key_hint::ctrl(KeyCode::Char('t')),
-            condition: DisplayCondition::Always,
-        }],
+        bindings: &[],
+        action: Some(|keymap| &keymap.transcript),
         prefix: "",
         label: " to view transcript",
     },
@@ -426,6 +444,7 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::keymap;
     use insta::assert_snapshot;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
@@ -453,6 +472,8 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -465,6 +486,8 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -477,6 +500,8 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -489,6 +514,8 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -501,6 +528,8 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -513,6 +542,8 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -525,6 +556,8 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                vi_mode: None,
+                keymap: keymap::current(),
             },
         );
 
@@ -537,6 +570,54 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                vi_mode: None,
+                keymap: keymap::current(),
+            },
+        );
+    }
+
+    #[test]
+    fn footer_shortcuts_reflect_custom_keymap() {
+        use codex_core::config::types::KeyChords;
+        use codex_core::config::types::TuiKeymap;
+
+        let keymap = Keymap::from_config(&TuiKeymap {
+            transcript: Some(KeyChords::One("ctrl+o".to_string())),
+            external_editor: Some(KeyChords::One("f4".to_string())),
+            edit_previous: Some(KeyChords::One("alt+up".to_string())),
+            ..Default::default()
+        })
+        .expect("valid keymap");
+        let keymap: &'static Keymap = Box::leak(Box::new(keymap));
+
+        snapshot_footer(
+            "footer_shortcuts_custom_keymap",
+            FooterProps {
+                mode: FooterMode::ShortcutOverlay,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                context_window_percent: None,
+                context_window_used_tokens: None,
+                vi_mode: None,
+                keymap,
+            },
+        );
+    }
+
+    #[test]
+    fn footer_shows_vi_mode() {
+        snapshot_footer(
+            "footer_vi_normal_mode",
+            FooterProps {
+                mode: FooterMode::ShortcutSummary,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                context_window_percent: Some(72),
+                context_window_used_tokens: None,
+                vi_mode: Some(ViMode::Normal),
+                keymap: keymap::current(),
             },
         );
     }
@@ -0,0 +1,928 @@
+//! Hunk-by-hunk review of a proposed patch.
+//!
+//! Opened from the patch approval overlay, it walks every file and hunk of an
+//! `ApplyPatchApprovalRequest` so the user can accept, reject or edit each one.
+//! The outcome is a reduced `apply_patch` body plus a note for the model that
+//! describes what was rejected or changed.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+use codex_core::protocol::ReviewDecision;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::StatefulWidgetRef;
+use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+
+use super::textarea::TextArea;
+use super::textarea::TextAreaState;
+use crate::diff_render::DiffLineType;
+use crate::diff_render::DiffSyntax;
+use crate::diff_render::display_path_for;
+use crate::diff_render::render_hunk_lines;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+
+/// Hunks longer than this are truncated in the bottom pane; the full patch is
+/// still available through the full-screen approval view.
+const MAX_VISIBLE_HUNK_LINES: usize = 24;
+const MAX_EDITOR_HEIGHT: u16 = 16;
+
+/// Result of handling a key press while reviewing hunks.
+pub(crate) enum HunkReviewStep {
+    Continue,
+    /// Leave the review and return to the approval options.
+    Back,
+    /// Every hunk has a decision; submit this to the agent.
+    Finished(ReviewDecision),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum HunkDecision {
+    Pending,
+    Accepted,
+    Rejected,
+    /// Accepted with the user's replacement for the updated side of the hunk.
+    Edited(Vec<String>),
+}
+
+#[derive(Clone, Debug)]
+enum FileKind {
+    Add,
+    Delete,
+    Update { move_path: Option<PathBuf> },
+}
+
+#[derive(Clone, Debug)]
+struct ReviewHunk {
+    old_start: usize,
+    new_start: usize,
+    lines: Vec<(DiffLineType, String)>,
+    decision: HunkDecision,
+}
+
+impl ReviewHunk {
+    fn old_lines(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter(|(kind, _)| *kind != DiffLineType::Insert)
+            .map(|(_, text)| text.clone())
+            .collect()
+    }
+
+    fn new_lines(&self) -> Vec<String> {
+        self.lines
+            .iter()
+            .filter(|(kind, _)| *kind != DiffLineType::Delete)
+            .map(|(_, text)| text.clone())
+            .collect()
+    }
+
+    fn header(&self) -> String {
+        format!(
+            "@@ -{},{} +{},{} @@",
+            self.old_start,
+            self.old_lines().len(),
+            self.new_start,
+            self.new_lines().len()
+        )
+    }
+
+    fn applies(&self) -> bool {
+        matches!(
+            self.decision,
+            HunkDecision::Accepted | HunkDecision::Edited(_)
+        )
+    }
+
+    /// Lines of this hunk in `apply_patch` chunk syntax. Hunks that are not
+    /// applied keep their original lines as context so later chunks are
+    /// located exactly as in the proposed patch.
+    fn patch_lines(&self) -> Vec<String> {
+        match &self.decision {
+            HunkDecision::Accepted => self
+                .lines
+                .iter()
+                .map(|(kind, text)| match kind {
+                    DiffLineType::Context => format!(" {text}"),
+                    DiffLineType::Delete => format!("-{text}"),
+                    DiffLineType::Insert => format!("+{text}"),
+                })
+                .collect(),
+            HunkDecision::Pending | HunkDecision::Rejected => self
+                .old_lines()
+                .into_iter()
+                .map(|text| format!(" {text}"))
+                .collect(),
+            HunkDecision::Edited(new_lines) => {
+                let old_lines = self.old_lines();
+                let prefix = old_lines
+                    .iter()
+                    .zip(new_lines)
+                    .take_while(|(old, new)| old == new)
+                    .count();
+                let suffix = old_lines[prefix..]
+                    .iter()
+                    .rev()
+                    .zip(new_lines[prefix..].iter().rev())
+                    .take_while(|(old, new)| old == new)
+                    .count();
+                let mut out: Vec<String> = old_lines[..prefix]
+                    .iter()
+                    .map(|text| format!(" {text}"))
+                    .collect();
+                out.extend(
+                    old_lines[prefix..old_lines.len() - suffix]
+                        .iter()
+                        .map(|text| format!("-{text}")),
+                );
+                out.extend(
+                    new_lines[prefix..new_lines.len() - suffix]
+                        .iter()
+                        .map(|text| format!("+{text}")),
+                );
+                out.extend(
+                    old_lines[old_lines.len() - suffix..]
+                        .iter()
+                        .map(|text| format!(" {text}")),
+                );
+                out
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ReviewFile {
+    path: PathBuf,
+    kind: FileKind,
+    hunks: Vec<ReviewHunk>,
+}
+
+impl ReviewFile {
+    fn from_change(path: PathBuf, change: &FileChange) -> Self {
+        let (kind, hunks) = match change {
+            FileChange::Add { content } => (
+                FileKind::Add,
+                vec![ReviewHunk {
+                    old_start: 0,
+                    new_start: 1,
+                    lines: content
+                        .lines()
+                        .map(|line| (DiffLineType::Insert, line.to_string()))
+                        .collect(),
+                    decision: HunkDecision::Pending,
+                }],
+            ),
+            FileChange::Delete { content } => (
+                FileKind::Delete,
+                vec![ReviewHunk {
+                    old_start: 1,
+                    new_start: 0,
+                    lines: content
+                        .lines()
+                        .map(|line| (DiffLineType::Delete, line.to_string()))
+                        .collect(),
+                    decision: HunkDecision::Pending,
+                }],
+            ),
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let hunks = diffy::Patch::from_str(unified_diff)
+                    .map(|patch| {
+                        patch
+                            .hunks()
+                            .iter()
+                            .map(|hunk| ReviewHunk {
+                                old_start: hunk.old_range().start(),
+                                new_start: hunk.new_range().start(),
+                                lines: hunk
+                                    .lines()
+                                    .iter()
+                                    .map(|line| match line {
+                                        diffy::Line::Context(text) => (
+                                            DiffLineType::Context,
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                        diffy::Line::Delete(text) => (
+                                            DiffLineType::Delete,
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                        diffy::Line::Insert(text) => (
+                                            DiffLineType::Insert,
+                                            text.trim_end_matches('\n').to_string(),
+                                        ),
+                                    })
+                                    .collect(),
+                                decision: HunkDecision::Pending,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (
+                    FileKind::Update {
+                        move_path: move_path.clone(),
+                    },
+                    hunks,
+                )
+            }
+        };
+        Self { path, kind, hunks }
+    }
+
+    fn patch_section(&self) -> Option<String> {
+        if !self.hunks.iter().any(ReviewHunk::applies) {
+            return None;
+        }
+        let path = self.path.display();
+        let mut out = String::new();
+        match &self.kind {
+            FileKind::Add => {
+                out.push_str(&format!("*** Add File: {path}\n"));
+                for hunk in &self.hunks {
+                    let lines = match &hunk.decision {
+                        HunkDecision::Edited(lines) => lines.clone(),
+                        _ => hunk.new_lines(),
+                    };
+                    for line in lines {
+                        out.push_str(&format!("+{line}\n"));
+                    }
+                }
+            }
+            FileKind::Delete => out.push_str(&format!("*** Delete File: {path}\n")),
+            FileKind::Update { move_path } => {
+                out.push_str(&format!("*** Update File: {path}\n"));
+                if let Some(move_path) = move_path {
+                    out.push_str(&format!("*** Move to: {}\n", move_path.display()));
+                }
+                for hunk in &self.hunks {
+                    let lines = hunk.patch_lines();
+                    if lines.is_empty() {
+                        continue;
+                    }
+                    out.push_str("@@\n");
+                    for line in lines {
+                        out.push_str(&line);
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Inline editor for the updated side of a hunk.
+struct HunkEditor {
+    textarea: TextArea,
+    state: RefCell<TextAreaState>,
+}
+
+pub(crate) struct HunkReview {
+    cwd: PathBuf,
+    files: Vec<ReviewFile>,
+    /// Flattened `(file, hunk)` positions in review order.
+    order: Vec<(usize, usize)>,
+    current: usize,
+    editor: Option<HunkEditor>,
+}
+
+impl HunkReview {
+    pub(crate) fn new(changes: &HashMap<PathBuf, FileChange>, cwd: PathBuf) -> Self {
+        let mut files: Vec<ReviewFile> = changes
+            .iter()
+            .map(|(path, change)| ReviewFile::from_change(path.clone(), change))
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let order = files
+            .iter()
+            .enumerate()
+            .flat_map(|(file_idx, file)| {
+                (0..file.hunks.len()).map(move |hunk_idx| (file_idx, hunk_idx))
+            })
+            .collect();
+        Self {
+            cwd,
+            files,
+            order,
+            current: 0,
+            editor: None,
+        }
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> HunkReviewStep {
+        if key_event.kind == KeyEventKind::Release {
+            return HunkReviewStep::Continue;
+        }
+        if self.editor.is_some() {
+            return self.handle_editor_key(key_event);
+        }
+        if self.order.is_empty() {
+            return HunkReviewStep::Finished(self.decision());
+        }
+        match key_event.code {
+            KeyCode::Char('y') => self.decide(HunkDecision::Accepted),
+            KeyCode::Char('n') => self.decide(HunkDecision::Rejected),
+            KeyCode::Char('a') => self.decide_remaining(HunkDecision::Accepted),
+            KeyCode::Char('d') => self.decide_remaining(HunkDecision::Rejected),
+            KeyCode::Char('e') => {
+                self.open_editor();
+                HunkReviewStep::Continue
+            }
+            KeyCode::Left | KeyCode::Char('p') => {
+                self.current = self.current.saturating_sub(1);
+                HunkReviewStep::Continue
+            }
+            KeyCode::Esc => HunkReviewStep::Back,
+            _ => HunkReviewStep::Continue,
+        }
+    }
+
+    pub(crate) fn handle_paste(&mut self, pasted: String) -> bool {
+        match self.editor.as_mut() {
+            Some(editor) if !pasted.is_empty() => {
+                editor.textarea.insert_str(&pasted);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// One-line summary of the outcome, for the transcript.
+    pub(crate) fn summary(&self) -> String {
+        let hunks = || self.files.iter().flat_map(|file| file.hunks.iter());
+        let total = hunks().count();
+        let edited = hunks()
+            .filter(|hunk| matches!(hunk.decision, HunkDecision::Edited(_)))
+            .count();
+        let applied = hunks().filter(|hunk| hunk.applies()).count();
+        let rejected = total - applied;
+        let mut summary = format!("Applied {applied} of {total} hunks");
+        if edited > 0 {
+            summary.push_str(&format!(", {edited} edited"));
+        }
+        if rejected > 0 {
+            summary.push_str(&format!(", {rejected} rejected"));
+        }
+        summary
+    }
+
+    fn current_hunk(&self) -> Option<(&ReviewFile, &ReviewHunk)> {
+        let (file_idx, hunk_idx) = *self.order.get(self.current)?;
+        let file = &self.files[file_idx];
+        Some((file, &file.hunks[hunk_idx]))
+    }
+
+    fn current_hunk_mut(&mut self) -> Option<&mut ReviewHunk> {
+        let (file_idx, hunk_idx) = *self.order.get(self.current)?;
+        Some(&mut self.files[file_idx].hunks[hunk_idx])
+    }
+
+    fn decide(&mut self, decision: HunkDecision) -> HunkReviewStep {
+        let Some(hunk) = self.current_hunk_mut() else {
+            return HunkReviewStep::Finished(self.decision());
+        };
+        hunk.decision = decision;
+        self.advance()
+    }
+
+    fn decide_remaining(&mut self, decision: HunkDecision) -> HunkReviewStep {
+        let Some(&current) = self.order.get(self.current) else {
+            return HunkReviewStep::Finished(self.decision());
+        };
+        for &(file_idx, hunk_idx) in &self.order[self.current..] {
+            let hunk = &mut self.files[file_idx].hunks[hunk_idx];
+            if (file_idx, hunk_idx) == current || hunk.decision == HunkDecision::Pending {
+                hunk.decision = decision.clone();
+            }
+        }
+        self.current = self.order.len();
+        HunkReviewStep::Finished(self.decision())
+    }
+
+    fn advance(&mut self) -> HunkReviewStep {
+        self.current += 1;
+        if self.current >= self.order.len() {
+            HunkReviewStep::Finished(self.decision())
+        } else {
+            HunkReviewStep::Continue
+        }
+    }
+
+    fn open_editor(&mut self) {
+        let Some((file, hunk)) = self.current_hunk() else {
+            return;
+        };
+        if matches!(file.kind, FileKind::Delete) {
+            return;
+        }
+        let seed = match &hunk.decision {
+            HunkDecision::Edited(lines) => lines.join("\n"),
+            _ => hunk.new_lines().join("\n"),
+        };
+        let mut textarea = TextArea::new();
+        textarea.set_text(&seed);
+        textarea.set_cursor(0);
+        self.editor = Some(HunkEditor {
+            textarea,
+            state: RefCell::new(TextAreaState::default()),
+        });
+    }
+
+    fn handle_editor_key(&mut self, key_event: KeyEvent) -> HunkReviewStep {
+        let Some(editor) = self.editor.as_mut() else {
+            return HunkReviewStep::Continue;
+        };
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.editor = None;
+                HunkReviewStep::Continue
+            }
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => {
+                // An empty editor removes the updated side entirely rather
+                // than leaving a single blank line.
+                let text = editor.textarea.text();
+                let edited: Vec<String> = if text.is_empty() {
+                    Vec::new()
+                } else {
+                    text.split('\n').map(str::to_string).collect()
+                };
+                self.editor = None;
+                let Some(hunk) = self.current_hunk_mut() else {
+                    return HunkReviewStep::Continue;
+                };
+                hunk.decision = if edited == hunk.new_lines() {
+                    HunkDecision::Accepted
+                } else {
+                    HunkDecision::Edited(edited)
+                };
+                self.advance()
+            }
+            other => {
+                editor.textarea.input(other);
+                HunkReviewStep::Continue
+            }
+        }
+    }
+
+    pub(crate) fn decision(&self) -> ReviewDecision {
+        let all_accepted = self
+            .files
+            .iter()
+            .flat_map(|file| file.hunks.iter())
+            .all(|hunk| hunk.decision == HunkDecision::Accepted);
+        if all_accepted {
+            return ReviewDecision::Approved;
+        }
+        ReviewDecision::ApprovedHunks {
+            patch: self.build_patch(),
+            feedback: self.feedback(),
+        }
+    }
+
+    fn build_patch(&self) -> String {
+        let sections: String = self
+            .files
+            .iter()
+            .filter_map(ReviewFile::patch_section)
+            .collect();
+        if sections.is_empty() {
+            String::new()
+        } else {
+            format!("*** Begin Patch\n{sections}*** End Patch\n")
+        }
+    }
+
+    fn feedback(&self) -> String {
+        let total: usize = self.files.iter().map(|file| file.hunks.len()).sum();
+        let mut rejected = Vec::new();
+        let mut edited = Vec::new();
+        for file in &self.files {
+            let path = display_path_for(&file.path, &self.cwd);
+            for hunk in &file.hunks {
+                let location = match file.kind {
+                    FileKind::Add => format!("{path} (new file)"),
+                    FileKind::Delete => format!("{path} (deleted file)"),
+                    FileKind::Update { .. } => format!("{path} {}", hunk.header()),
+                };
+                match &hunk.decision {
+                    HunkDecision::Accepted => {}
+                    HunkDecision::Pending | HunkDecision::Rejected => {
+                        let mut accepted = hunk.clone();
+                        accepted.decision = HunkDecision::Accepted;
+                        rejected.push((location, accepted.patch_lines()));
+                    }
+                    HunkDecision::Edited(lines) => {
+                        let lines = match file.kind {
+                            FileKind::Add => lines.iter().map(|line| format!("+{line}")).collect(),
+                            _ => hunk.patch_lines(),
+                        };
+                        edited.push((location, lines));
+                    }
+                }
+            }
+        }
+        let applied = total - rejected.len();
+        let mut out = format!(
+            "The user reviewed this patch hunk by hunk and applied {applied} of {total} hunks."
+        );
+        for (title, entries) in [
+            ("Rejected hunks (not applied):", rejected),
+            ("Edited hunks (applied with the user's changes):", edited),
+        ] {
+            if entries.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n\n{title}"));
+            for (location, lines) in entries {
+                out.push_str(&format!(
+                    "\n\n{location}\n```diff\n{}\n```",
+                    lines.join("\n")
+                ));
+            }
+        }
+        out
+    }
+
+    fn header_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let Some((file, hunk)) = self.current_hunk() else {
+            return vec![Line::from("No hunks to review.".dim())];
+        };
+        let mut title: Vec<Span<'static>> = vec![
+            if self.editor.is_some() {
+                "Edit hunk ".bold()
+            } else {
+                "Hunk ".bold()
+            },
+            format!("{} of {}", self.current + 1, self.order.len()).bold(),
+            "  ".into(),
+            display_path_for(&file.path, &self.cwd).into(),
+        ];
+        match &file.kind {
+            FileKind::Add => title.push(" (new file)".dim()),
+            FileKind::Delete => title.push(" (deleted)".dim()),
+            FileKind::Update {
+                move_path: Some(move_path),
+            } => title.push(format!(" → {}", display_path_for(move_path, &self.cwd)).into()),
+            FileKind::Update { move_path: None } => {}
+        }
+        let mut lines = vec![Line::from(title), Line::from("")];
+        if self.editor.is_some() {
+            lines.push(Line::from(
+                "The text below replaces the updated side of this hunk.".dim(),
+            ));
+            return lines;
+        }
+
+        let mut diff = render_hunk_lines(
+            &hunk.lines,
+            hunk.old_start,
+            hunk.new_start,
+            width.saturating_sub(2) as usize,
+            DiffSyntax::for_path(&file.path).as_ref(),
+        );
+        if diff.len() > MAX_VISIBLE_HUNK_LINES {
+            let hidden = diff.len() - MAX_VISIBLE_HUNK_LINES;
+            diff.truncate(MAX_VISIBLE_HUNK_LINES);
+            diff.push(Line::from(format!("… +{hidden} lines").dim()));
+        }
+        lines.extend(diff);
+        match &hunk.decision {
+            HunkDecision::Pending => {}
+            HunkDecision::Accepted => {
+                lines.push(Line::from(""));
+                lines.push(Line::from("✔ accepted".green()));
+            }
+            HunkDecision::Rejected => {
+                lines.push(Line::from(""));
+                lines.push(Line::from("✗ rejected".red()));
+            }
+            HunkDecision::Edited(_) => {
+                lines.push(Line::from(""));
+                lines.push(Line::from("✎ edited".cyan()));
+            }
+        }
+        lines
+    }
+
+    fn hint_line(&self) -> Line<'static> {
+        if self.editor.is_some() {
+            return Line::from(vec![
+                "Press ".into(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to save or ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to cancel".into(),
+            ]);
+        }
+        let mut spans: Vec<Span<'static>> = vec![
+            key_hint::plain(KeyCode::Char('y')).into(),
+            " accept  ".into(),
+            key_hint::plain(KeyCode::Char('n')).into(),
+            " reject  ".into(),
+        ];
+        if !matches!(
+            self.current_hunk().map(|(file, _)| &file.kind),
+            Some(FileKind::Delete)
+        ) {
+            spans.push(key_hint::plain(KeyCode::Char('e')).into());
+            spans.push(" edit  ".into());
+        }
+        spans.extend([
+            key_hint::plain(KeyCode::Char('a')).into(),
+            " accept rest  ".into(),
+            key_hint::plain(KeyCode::Char('d')).into(),
+            " reject rest  ".into(),
+            key_hint::plain(KeyCode::Left).into(),
+            " previous  ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " back".into(),
+        ]);
+        Line::from(spans)
+    }
+
+    fn editor_height(&self, width: u16) -> u16 {
+        self.editor
+            .as_ref()
+            .map(|editor| {
+                editor
+                    .textarea
+                    .desired_height(width.saturating_sub(2))
+                    .clamp(1, MAX_EDITOR_HEIGHT)
+            })
+            .unwrap_or(0)
+    }
+
+    fn header(&self, width: u16) -> Paragraph<'static> {
+        Paragraph::new(self.header_lines(width)).wrap(Wrap { trim: false })
+    }
+
+    fn editor_area(&self, area: Rect) -> Rect {
+        let header_height = self.header(area.width).line_count(area.width) as u16 + 1;
+        Rect {
+            x: area.x.saturating_add(2),
+            y: area.y.saturating_add(header_height),
+            width: area.width.saturating_sub(2),
+            height: self
+                .editor_height(area.width)
+                .min(area.height.saturating_sub(header_height)),
+        }
+    }
+}
+
+impl Renderable for HunkReview {
+    fn desired_height(&self, width: u16) -> u16 {
+        let header = self.header(width).line_count(width) as u16;
+        let editor = if self.editor.is_some() {
+            self.editor_height(width) + 1
+        } else {
+            0
+        };
+        header + editor + 2
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        let header = self.header(area.width);
+        let header_height = header.line_count(area.width) as u16;
+        header.render(
+            Rect {
+                height: header_height.min(area.height),
+                ..area
+            },
+            buf,
+        );
+        let mut hint_y = area.y.saturating_add(header_height).saturating_add(1);
+        if let Some(editor) = self.editor.as_ref() {
+            let editor_area = self.editor_area(area);
+            let mut state = editor.state.borrow_mut();
+            StatefulWidgetRef::render_ref(&(&editor.textarea), editor_area, buf, &mut state);
+            hint_y = editor_area.bottom().saturating_add(1);
+        }
+        if hint_y < area.bottom() {
+            Paragraph::new(self.hint_line()).render(
+                Rect {
+                    y: hint_y,
+                    height: 1,
+                    ..area
+                },
+                buf,
+            );
+        }
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let editor = self.editor.as_ref()?;
+        let state = *editor.state.borrow();
+        editor
+            .textarea
+            .cursor_pos_with_state(self.editor_area(area), state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn update(path: &str, unified_diff: &str) -> HashMap<PathBuf, FileChange> {
+        HashMap::from([(
+            PathBuf::from(path),
+            FileChange::Update {
+                unified_diff: unified_diff.to_string(),
+                move_path: None,
+            },
+        )])
+    }
+
+    const TWO_HUNKS: &str = "\
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn one() {
+-    1
++    10
+ }
+@@ -8,3 +8,3 @@
+ fn two() {
+-    2
++    20
+ }
+";
+
+    fn press(review: &mut HunkReview, code: KeyCode) -> HunkReviewStep {
+        review.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn rejected_hunk_becomes_context_and_is_reported() {
+        let mut review = HunkReview::new(&update("/repo/src/lib.rs", TWO_HUNKS), "/repo".into());
+        assert!(matches!(
+            press(&mut review, KeyCode::Char('n')),
+            HunkReviewStep::Continue
+        ));
+        let HunkReviewStep::Finished(decision) = press(&mut review, KeyCode::Char('y')) else {
+            panic!("expected review to finish after the last hunk");
+        };
+        let ReviewDecision::ApprovedHunks { patch, feedback } = decision else {
+            panic!("expected a partial approval, got {decision:?}");
+        };
+        assert_eq!(
+            patch,
+            "\
+*** Begin Patch
+*** Update File: /repo/src/lib.rs
+@@
+ fn one() {
+     1
+ }
+@@
+ fn two() {
+-    2
++    20
+ }
+*** End Patch
+"
+        );
+        assert!(feedback.contains("applied 1 of 2 hunks"), "{feedback}");
+        assert!(
+            feedback.contains("Rejected hunks (not applied):"),
+            "{feedback}"
+        );
+        assert!(feedback.contains("+    10"), "{feedback}");
+        assert_eq!(review.summary(), "Applied 1 of 2 hunks, 1 rejected");
+    }
+
+    #[test]
+    fn edited_hunk_keeps_common_lines_as_context() {
+        let mut review = HunkReview::new(&update("/repo/src/lib.rs", TWO_HUNKS), "/repo".into());
+        press(&mut review, KeyCode::Char('e'));
+        let editor = review.editor.as_mut().expect("editor should be open");
+        assert_eq!(editor.textarea.text(), "fn one() {\n    10\n}");
+        editor.textarea.set_text("fn one() {\n    11\n}");
+        press(&mut review, KeyCode::Enter);
+        let HunkReviewStep::Finished(ReviewDecision::ApprovedHunks { patch, feedback }) =
+            press(&mut review, KeyCode::Char('d'))
+        else {
+            panic!("expected a partial approval");
+        };
+        assert_eq!(
+            patch,
+            "\
+*** Begin Patch
+*** Update File: /repo/src/lib.rs
+@@
+ fn one() {
+-    1
++    11
+ }
+@@
+ fn two() {
+     2
+ }
+*** End Patch
+"
+        );
+        assert!(
+            feedback.contains("Edited hunks (applied with the user's changes):"),
+            "{feedback}"
+        );
+    }
+
+    #[test]
+    fn editing_the_last_hunk_finishes_the_review() {
+        let mut review = HunkReview::new(&update("/repo/src/lib.rs", TWO_HUNKS), "/repo".into());
+        press(&mut review, KeyCode::Char('n'));
+        press(&mut review, KeyCode::Char('e'));
+        let editor = review.editor.as_mut().expect("editor should be open");
+        editor.textarea.set_text("fn two() {\n    22\n}");
+        let HunkReviewStep::Finished(ReviewDecision::ApprovedHunks { patch, .. }) =
+            press(&mut review, KeyCode::Enter)
+        else {
+            panic!("expected saving the last hunk to finish the review");
+        };
+        assert!(patch.contains("+    22"), "{patch}");
+        assert!(matches!(
+            press(&mut review, KeyCode::Char('a')),
+            HunkReviewStep::Finished(_)
+        ));
+    }
+
+    #[test]
+    fn clearing_the_editor_deletes_the_hunk_lines() {
+        let mut review = HunkReview::new(&update("/repo/src/lib.rs", TWO_HUNKS), "/repo".into());
+        press(&mut review, KeyCode::Char('y'));
+        press(&mut review, KeyCode::Char('e'));
+        let editor = review.editor.as_mut().expect("editor should be open");
+        editor.textarea.set_text("");
+        let HunkReviewStep::Finished(ReviewDecision::ApprovedHunks { patch, .. }) =
+            press(&mut review, KeyCode::Enter)
+        else {
+            panic!("expected a partial approval");
+        };
+        assert_eq!(
+            patch,
+            "\
+*** Begin Patch
+*** Update File: /repo/src/lib.rs
+@@
+ fn one() {
+-    1
++    10
+ }
+@@
+-fn two() {
+-    2
+-}
+*** End Patch
+"
+        );
+    }
+
+    #[test]
+    fn accepting_everything_is_a_plain_approval() {
+        let mut review = HunkReview::new(&update("/repo/src/lib.rs", TWO_HUNKS), "/repo".into());
+        let HunkReviewStep::Finished(decision) = press(&mut review, KeyCode::Char('a')) else {
+            panic!("expected review to finish");
+        };
+        assert_eq!(decision, ReviewDecision::Approved);
+    }
+
+    #[test]
+    fn rejecting_everything_sends_empty_patch() {
+        let changes = HashMap::from([(
+            PathBuf::from("/repo/new.txt"),
+            FileChange::Add {
+                content: "hello\n".to_string(),
+            },
+        )]);
+        let mut review = HunkReview::new(&changes, "/repo".into());
+        let HunkReviewStep::Finished(ReviewDecision::ApprovedHunks { patch, .. }) =
+            press(&mut review, KeyCode::Char('n'))
+        else {
+            panic!("expected a partial approval");
+        };
+        assert_eq!(patch, "");
+    }
+}
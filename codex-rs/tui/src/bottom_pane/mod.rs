@@ -24,11 +24,13 @@ pub(crate) use approval_overlay::ApprovalRequest;
 mod bottom_pane_view;
 mod chat_composer;
 mod chat_composer_history;
+mod command_palette_view;
 mod command_popup;
 pub mod custom_prompt_view;
 mod experimental_features_view;
 mod file_search_popup;
 mod footer;
+mod hunk_review;
 mod list_selection_view;
 mod prompt_args;
 mod skill_popup;
@@ -38,6 +40,7 @@ pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 mod paste_burst;
 pub mod popup_consts;
+mod queue_editor_view;
 mod queued_user_messages;
 mod scroll_state;
 mod selection_popup_common;
@@ -56,10 +59,15 @@ pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
+pub(crate) use command_palette_view::CommandPaletteView;
+pub(crate) use command_palette_view::PaletteCategory;
+pub(crate) use command_palette_view::PaletteItem;
 pub(crate) use experimental_features_view::BetaFeatureItem;
 pub(crate) use experimental_features_view::ExperimentalFeaturesView;
 pub(crate) use list_selection_view::SelectionAction;
 pub(crate) use list_selection_view::SelectionItem;
+pub(crate) use queue_editor_view::QueueEditorView;
+pub(crate) use queued_user_messages::QueuedMessage;
 
 /// Pane displayed in the lower half of the chat UI.
 pub(crate) struct BottomPane {
@@ -152,6 +160,10 @@ impl BottomPane {
         self.composer.skills()
     }
 
+    pub(crate) fn custom_prompts(&self) -> &[CustomPrompt] {
+        self.composer.custom_prompts()
+    }
+
     #[cfg(test)]
     pub(crate) fn context_window_percent(&self) -> Option<i64> {
         self.context_window_percent
@@ -192,9 +204,11 @@ impl BottomPane {
             InputResult::None
         } else {
             // If a task is running and a status line is visible, allow Esc to
-            // send an interrupt even while the composer has focus.
+            // send an interrupt even while the composer has focus. In vi insert
+            // mode the first Esc returns to normal mode instead.
             if matches!(key_event.code, crossterm::event::KeyCode::Esc)
                 && self.is_task_running
+                && !self.composer.in_vi_insert_mode()
                 && let Some(status) = &self.status
             {
                 // Send Op::Interrupt
@@ -411,7 +425,18 @@ impl BottomPane {
     }
 
     /// Update the queued messages preview shown above the composer.
-    pub(crate) fn set_queued_user_messages(&mut self, queued: Vec<String>) {
+    pub(crate) fn set_queued_user_messages(&mut self, queued: Vec<QueuedMessage>) {
+        for view in &mut self.view_stack {
+            view.on_queue_changed(&queued);
+        }
+        if self
+            .view_stack
+            .last()
+            .is_some_and(|view| view.is_complete())
+        {
+            self.view_stack.pop();
+            self.on_active_view_complete();
+        }
         self.queued_user_messages.messages = queued;
         self.request_redraw();
     }
@@ -432,6 +457,15 @@ impl BottomPane {
         self.composer.is_empty()
     }
 
+    pub(crate) fn set_composer_vi_mode(&mut self, enabled: bool) {
+        self.composer.set_vi_mode(enabled);
+        self.request_redraw();
+    }
+
+    pub(crate) fn composer_in_vi_insert_mode(&self) -> bool {
+        self.composer.in_vi_insert_mode()
+    }
+
     pub(crate) fn is_task_running(&self) -> bool {
         self.is_task_running
     }
@@ -822,7 +856,7 @@ mod tests {
         });
 
         pane.set_task_running(true);
-        pane.set_queued_user_messages(vec!["Queued follow-up question".to_string()]);
+        pane.set_queued_user_messages(vec!["Queued follow-up question".into()]);
         pane.hide_status_indicator();
 
         let width = 48;
@@ -850,7 +884,7 @@ mod tests {
         });
 
         pane.set_task_running(true);
-        pane.set_queued_user_messages(vec!["Queued follow-up question".to_string()]);
+        pane.set_queued_user_messages(vec!["Queued follow-up question".into()]);
 
         let width = 48;
         let height = pane.desired_height(width);
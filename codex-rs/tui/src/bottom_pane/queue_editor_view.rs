@@ -0,0 +1,444 @@
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event::QueueEdit;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::style::user_message_style;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::queued_user_messages::QueuedMessage;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+/// Lets the user reorder, edit, delete and condition messages queued while a
+/// turn is running. The queue itself lives in the chat widget; edits are sent
+/// as [`AppEvent::EditQueuedMessage`] and applied locally so the list reacts
+/// immediately, and [`BottomPaneView::on_queue_changed`] resynchronizes the
+/// view when the queue advances underneath it.
+pub(crate) struct QueueEditorView {
+    messages: Vec<QueuedMessage>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+    header: Box<dyn Renderable>,
+}
+
+impl QueueEditorView {
+    pub(crate) fn new(messages: Vec<QueuedMessage>, app_event_tx: AppEventSender) -> Self {
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Queued messages".bold()));
+        header.push(Line::from(
+            "Sent one per turn, from the top, once the current turn ends.".dim(),
+        ));
+
+        let mut view = Self {
+            messages,
+            state: ScrollState::new(),
+            complete: false,
+            app_event_tx,
+            header: Box::new(header),
+        };
+        view.clamp_selection();
+        view
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.messages.len();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn build_rows(&self) -> Vec<GenericDisplayRow> {
+        self.messages
+            .iter()
+            .enumerate()
+            .map(|(idx, message)| {
+                let prefix = if self.state.selected_idx == Some(idx) {
+                    '›'
+                } else {
+                    ' '
+                };
+                let first_line = message.text.lines().next().unwrap_or_default();
+                let more = if message.text.lines().nth(1).is_some() {
+                    " …"
+                } else {
+                    ""
+                };
+                GenericDisplayRow {
+                    name: format!("{prefix} {}. {first_line}{more}", idx + 1),
+                    description: message
+                        .only_if_succeeded
+                        .then(|| "only if the turn before it succeeds".to_string()),
+                    wrap_indent: Some(5),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        let len = self.messages.len();
+        if len == 0 {
+            return;
+        }
+        if down {
+            self.state.move_down_wrap(len);
+        } else {
+            self.state.move_up_wrap(len);
+        }
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    /// Send `edit` for the selected message and mirror it locally.
+    fn apply(&mut self, edit: QueueEdit) {
+        let Some(index) = self.state.selected_idx else {
+            return;
+        };
+        let Some(message) = self.messages.get(index) else {
+            return;
+        };
+        self.app_event_tx.send(AppEvent::EditQueuedMessage {
+            index,
+            text: message.text.clone(),
+            edit,
+        });
+        match edit {
+            QueueEdit::Edit => {
+                self.complete = true;
+                return;
+            }
+            QueueEdit::Remove => {
+                self.messages.remove(index);
+            }
+            QueueEdit::ToggleCondition => {
+                let message = &mut self.messages[index];
+                message.only_if_succeeded = !message.only_if_succeeded;
+            }
+            QueueEdit::Move { to } => {
+                let message = self.messages.remove(index);
+                let to = to.min(self.messages.len());
+                self.messages.insert(to, message);
+                self.state.selected_idx = Some(to);
+            }
+        }
+        if self.messages.is_empty() {
+            self.complete = true;
+        }
+        self.clamp_selection();
+    }
+
+    fn move_selected(&mut self, down: bool) {
+        let Some(index) = self.state.selected_idx else {
+            return;
+        };
+        let to = if down {
+            index + 1
+        } else {
+            match index.checked_sub(1) {
+                Some(to) => to,
+                None => return,
+            }
+        };
+        if to < self.messages.len() {
+            self.apply(QueueEdit::Move { to });
+        }
+    }
+
+    fn rows_width(total_width: u16) -> u16 {
+        total_width.saturating_sub(2)
+    }
+}
+
+impl BottomPaneView for QueueEditorView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('K'),
+                ..
+            } => self.move_selected(false),
+            KeyEvent {
+                code: KeyCode::Down,
+                modifiers: KeyModifiers::SHIFT,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('J'),
+                ..
+            } => self.move_selected(true),
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('k'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_selection(false),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('j'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_selection(true),
+            KeyEvent {
+                code: KeyCode::Enter | KeyCode::Char('e'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.apply(QueueEdit::Edit),
+            KeyEvent {
+                code: KeyCode::Delete | KeyCode::Backspace | KeyCode::Char('d'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.apply(QueueEdit::Remove),
+            KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.apply(QueueEdit::ToggleCondition),
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn on_queue_changed(&mut self, messages: &[QueuedMessage]) {
+        self.messages = messages.to_vec();
+        if self.messages.is_empty() {
+            self.complete = true;
+        }
+        self.clamp_selection();
+    }
+}
+
+impl Renderable for QueueEditorView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let header_height = self
+            .header
+            .desired_height(content_area.width.saturating_sub(4));
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(content_area.width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+        let [header_area, _, list_area] = Layout::vertical([
+            Constraint::Max(header_height),
+            Constraint::Max(1),
+            Constraint::Length(rows_height),
+        ])
+        .areas(content_area.inset(Insets::vh(1, 2)));
+
+        self.header.render(header_area, buf);
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                y: list_area.y,
+                width: rows_width.max(1),
+                height: list_area.height,
+            };
+            render_rows(
+                render_area,
+                buf,
+                &rows,
+                &self.state,
+                MAX_POPUP_ROWS,
+                "  No queued messages",
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            y: footer_area.y,
+            width: footer_area.width.saturating_sub(2),
+            height: footer_area.height,
+        };
+        queue_editor_hint_line().dim().render(hint_area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows = self.build_rows();
+        let rows_width = Self::rows_width(width);
+        let rows_height = measure_rows_height(
+            &rows,
+            &self.state,
+            MAX_POPUP_ROWS,
+            rows_width.saturating_add(1),
+        );
+
+        let mut height = self.header.desired_height(width.saturating_sub(4));
+        height = height.saturating_add(rows_height + 3);
+        height.saturating_add(1)
+    }
+}
+
+fn queue_editor_hint_line() -> Line<'static> {
+    Line::from(vec![
+        key_hint::plain(KeyCode::Char('e')).into(),
+        " edit  ".into(),
+        key_hint::plain(KeyCode::Char('d')).into(),
+        " delete  ".into(),
+        key_hint::plain(KeyCode::Char('c')).into(),
+        " only if ok  ".into(),
+        key_hint::shift(KeyCode::Up).into(),
+        "/↓ move  ".into(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " close".into(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn queue() -> Vec<QueuedMessage> {
+        vec![
+            "fix the lint errors".into(),
+            QueuedMessage {
+                text: "now run the tests".to_string(),
+                only_if_succeeded: true,
+            },
+            "summarize the changes\nfor the PR description".into(),
+        ]
+    }
+
+    fn render(view: &QueueEditorView, width: u16) -> String {
+        let height = view.desired_height(width);
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn queue_editor_snapshot() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let view = QueueEditorView::new(queue(), AppEventSender::new(tx));
+        assert_snapshot!(render(&view, 72));
+    }
+
+    #[test]
+    fn moving_a_message_sends_the_edit_and_keeps_it_selected() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QueueEditorView::new(queue(), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::SHIFT));
+
+        assert_eq!(view.state.selected_idx, Some(1));
+        assert_eq!(view.messages[1].text, "fix the lint errors");
+        match rx.try_recv() {
+            Ok(AppEvent::EditQueuedMessage { index, text, edit }) => {
+                assert_eq!(index, 0);
+                assert_eq!(text, "fix the lint errors");
+                assert_eq!(edit, QueueEdit::Move { to: 1 });
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn deleting_the_last_message_closes_the_view() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QueueEditorView::new(vec!["only one".into()], AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::EditQueuedMessage {
+                edit: QueueEdit::Remove,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn queue_changes_resync_the_list() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let mut view = QueueEditorView::new(queue(), AppEventSender::new(tx));
+        view.move_selection(true);
+        view.move_selection(true);
+
+        // The front message was sent when the turn ended.
+        view.on_queue_changed(&queue()[1..]);
+        assert_eq!(view.messages.len(), 2);
+        assert_eq!(view.state.selected_idx, Some(1));
+
+        view.on_queue_changed(&[]);
+        assert!(view.is_complete());
+    }
+}
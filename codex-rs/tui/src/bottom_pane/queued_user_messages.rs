@@ -6,13 +6,31 @@ use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 
 use crate::key_hint;
+use crate::keymap;
 use crate::render::renderable::Renderable;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 
+/// A message waiting for the current turn to finish.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct QueuedMessage {
+    pub text: String,
+    /// Send this message only if the turn before it succeeded.
+    pub only_if_succeeded: bool,
+}
+
+impl From<&str> for QueuedMessage {
+    fn from(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            only_if_succeeded: false,
+        }
+    }
+}
+
 /// Widget that displays a list of user messages queued while a turn is in progress.
 pub(crate) struct QueuedUserMessages {
-    pub messages: Vec<String>,
+    pub messages: Vec<QueuedMessage>,
 }
 
 impl QueuedUserMessages {
@@ -30,10 +48,15 @@ impl QueuedUserMessages {
         let mut lines = vec![];
 
         for message in &self.messages {
+            let initial_indent = if message.only_if_succeeded {
+                Line::from(vec!["  ↳ ".dim(), "if ok ".green().dim()])
+            } else {
+                Line::from("  ↳ ".dim())
+            };
             let wrapped = word_wrap_lines(
-                message.lines().map(|line| line.dim().italic()),
+                message.text.lines().map(|line| line.dim().italic()),
                 RtOptions::new(width as usize)
-                    .initial_indent(Line::from("  ↳ ".dim()))
+                    .initial_indent(initial_indent)
                     .subsequent_indent(Line::from("    ")),
             );
             let len = wrapped.len();
@@ -45,14 +68,15 @@ impl QueuedUserMessages {
             }
         }
 
-        lines.push(
-            Line::from(vec![
-                "    ".into(),
-                key_hint::alt(KeyCode::Up).into(),
-                " edit".into(),
-            ])
-            .dim(),
-        );
+        let mut hint = vec![
+            "    ".into(),
+            key_hint::alt(KeyCode::Up).into(),
+            " edit".into(),
+        ];
+        if let Some(key) = keymap::current().queue.primary() {
+            hint.extend(["   ".into(), key.into(), " manage queue".into()]);
+        }
+        lines.push(Line::from(hint).dim());
 
         Paragraph::new(lines).into()
     }
@@ -87,14 +111,14 @@ mod tests {
     #[test]
     fn desired_height_one_message() {
         let mut queue = QueuedUserMessages::new();
-        queue.messages.push("Hello, world!".to_string());
+        queue.messages.push("Hello, world!".into());
         assert_eq!(queue.desired_height(40), 2);
     }
 
     #[test]
     fn render_one_message() {
         let mut queue = QueuedUserMessages::new();
-        queue.messages.push("Hello, world!".to_string());
+        queue.messages.push("Hello, world!".into());
         let width = 40;
         let height = queue.desired_height(width);
         let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
@@ -105,8 +129,8 @@ mod tests {
     #[test]
     fn render_two_messages() {
         let mut queue = QueuedUserMessages::new();
-        queue.messages.push("Hello, world!".to_string());
-        queue.messages.push("This is another message".to_string());
+        queue.messages.push("Hello, world!".into());
+        queue.messages.push("This is another message".into());
         let width = 40;
         let height = queue.desired_height(width);
         let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
@@ -117,10 +141,10 @@ mod tests {
     #[test]
     fn render_more_than_three_messages() {
         let mut queue = QueuedUserMessages::new();
-        queue.messages.push("Hello, world!".to_string());
-        queue.messages.push("This is another message".to_string());
-        queue.messages.push("This is a third message".to_string());
-        queue.messages.push("This is a fourth message".to_string());
+        queue.messages.push("Hello, world!".into());
+        queue.messages.push("This is another message".into());
+        queue.messages.push("This is a third message".into());
+        queue.messages.push("This is a fourth message".into());
         let width = 40;
         let height = queue.desired_height(width);
         let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
@@ -133,8 +157,8 @@ mod tests {
         let mut queue = QueuedUserMessages::new();
         queue
             .messages
-            .push("This is a longer message that should be wrapped".to_string());
-        queue.messages.push("This is another message".to_string());
+            .push("This is a longer message that should be wrapped".into());
+        queue.messages.push("This is another message".into());
         let width = 40;
         let height = queue.desired_height(width);
         let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
@@ -147,11 +171,26 @@ mod tests {
         let mut queue = QueuedUserMessages::new();
         queue
             .messages
-            .push("This is\na message\nwith many\nlines".to_string());
+            .push("This is\na message\nwith many\nlines".into());
         let width = 40;
         let height = queue.desired_height(width);
         let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
         queue.render(Rect::new(0, 0, width, height), &mut buf);
         assert_snapshot!("render_many_line_message", format!("{buf:?}"));
     }
+
+    #[test]
+    fn render_conditional_message() {
+        let mut queue = QueuedUserMessages::new();
+        queue.messages.push("Fix the failing test".into());
+        queue.messages.push(QueuedMessage {
+            text: "Now run the full suite".to_string(),
+            only_if_succeeded: true,
+        });
+        let width = 40;
+        let height = queue.desired_height(width);
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        queue.render(Rect::new(0, 0, width, height), &mut buf);
+        assert_snapshot!("render_conditional_message", format!("{buf:?}"));
+    }
 }
@@ -1,4 +1,5 @@
 use crate::key_hint::is_altgr;
+use crate::keymap;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
@@ -34,6 +35,24 @@ pub(crate) struct TextArea {
     preferred_col: Option<usize>,
     elements: Vec<TextElement>,
     kill_buffer: String,
+    /// The kill buffer holds a whole line (vi `dd`, `cc`, `S`), which vi
+    /// `p`/`P` put on a line of its own.
+    kill_linewise: bool,
+    vi: Option<ViState>,
+}
+
+/// Editing mode of the textarea when vi mode (`tui.vi_mode`) is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ViMode {
+    Insert,
+    Normal,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ViState {
+    mode: ViMode,
+    /// First key of a two-key command such as `dd`, `cw` or `gg`.
+    pending: Option<char>,
 }
 
 #[derive(Debug, Clone)]
@@ -57,6 +76,8 @@ impl TextArea {
             preferred_col: None,
             elements: Vec::new(),
             kill_buffer: String::new(),
+            kill_linewise: false,
+            vi: None,
         }
     }
 
@@ -67,6 +88,7 @@ impl TextArea {
         self.preferred_col = None;
         self.elements.clear();
         self.kill_buffer.clear();
+        self.kill_linewise = false;
     }
 
     pub fn text(&self) -> &str {
@@ -213,6 +235,10 @@ impl TextArea {
     }
 
     pub fn input(&mut self, event: KeyEvent) {
+        if keymap::current().newline.is_press(event) {
+            self.insert_str("\n");
+            return;
+        }
         match event {
             // Some terminals (or configurations) send Control key chords as
             // C0 control characters without reporting the CONTROL modifier.
@@ -239,7 +265,7 @@ impl TextArea {
                 ..
             } => self.insert_str(&c.to_string()),
             KeyEvent {
-                code: KeyCode::Char('j' | 'm'),
+                code: KeyCode::Char('m'),
                 modifiers: KeyModifiers::CONTROL,
                 ..
             }
@@ -450,6 +476,231 @@ impl TextArea {
         }
     }
 
+    // ####### Vi Mode #######
+
+    /// Enable or disable vi mode. Enabling starts in insert mode so typing
+    /// behaves as usual until Esc is pressed.
+    pub(crate) fn set_vi_enabled(&mut self, enabled: bool) {
+        self.vi = enabled.then_some(ViState {
+            mode: ViMode::Insert,
+            pending: None,
+        });
+    }
+
+    /// The current vi mode, or `None` when vi mode is disabled.
+    pub(crate) fn vi_mode(&self) -> Option<ViMode> {
+        self.vi.map(|vi| vi.mode)
+    }
+
+    fn set_vi_mode(&mut self, mode: ViMode) {
+        if let Some(vi) = self.vi.as_mut() {
+            vi.mode = mode;
+            vi.pending = None;
+        }
+    }
+
+    /// Leave insert mode. Like vi, the cursor steps back onto the last
+    /// inserted character unless it is already at the start of the line.
+    pub(crate) fn enter_vi_normal_mode(&mut self) {
+        if self.cursor_pos > self.beginning_of_current_line() {
+            self.move_cursor_left();
+        }
+        self.set_vi_mode(ViMode::Normal);
+    }
+
+    /// Handle a character typed in vi normal mode. Unknown keys are ignored
+    /// rather than inserted.
+    pub(crate) fn vi_normal_input(&mut self, c: char) {
+        let Some(vi) = self.vi.as_mut() else {
+            return;
+        };
+        if let Some(op) = vi.pending.take() {
+            self.vi_operator(op, c);
+            return;
+        }
+        match c {
+            'h' => {
+                if self.cursor_pos > self.beginning_of_current_line() {
+                    self.move_cursor_left();
+                }
+            }
+            'l' => {
+                let next = self.next_atomic_boundary(self.cursor_pos);
+                if next < self.end_of_current_line() {
+                    self.set_cursor(next);
+                }
+            }
+            'j' => self.move_cursor_down(),
+            'k' => self.move_cursor_up(),
+            'w' => self.set_cursor(self.beginning_of_next_word()),
+            'b' => self.set_cursor(self.beginning_of_previous_word()),
+            'e' => {
+                let end = self.end_of_word_from(self.next_atomic_boundary(self.cursor_pos));
+                self.set_cursor(self.prev_atomic_boundary(end));
+            }
+            '0' => self.set_cursor(self.beginning_of_current_line()),
+            '^' => self.set_cursor(self.first_non_blank_of_current_line()),
+            '$' => self.set_cursor(self.last_char_of_current_line()),
+            'G' => self.set_cursor(self.text.len()),
+            'x' => {
+                if self.cursor_pos < self.end_of_current_line() {
+                    let end = self.next_atomic_boundary(self.cursor_pos);
+                    self.kill_range(self.cursor_pos..end);
+                }
+            }
+            'X' => {
+                if self.cursor_pos > self.beginning_of_current_line() {
+                    let start = self.prev_atomic_boundary(self.cursor_pos);
+                    self.kill_range(start..self.cursor_pos);
+                }
+            }
+            'D' => self.kill_range(self.cursor_pos..self.end_of_current_line()),
+            'C' => {
+                self.kill_range(self.cursor_pos..self.end_of_current_line());
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'S' => self.vi_operator('c', 'c'),
+            'p' => self.vi_put(true),
+            'P' => self.vi_put(false),
+            'i' => self.set_vi_mode(ViMode::Insert),
+            'a' => {
+                if self.cursor_pos < self.end_of_current_line() {
+                    self.move_cursor_right();
+                }
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'I' => {
+                self.set_cursor(self.first_non_blank_of_current_line());
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'A' => {
+                self.set_cursor(self.end_of_current_line());
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'o' => {
+                self.set_cursor(self.end_of_current_line());
+                self.insert_str("\n");
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'O' => {
+                let bol = self.beginning_of_current_line();
+                self.insert_str_at(bol, "\n");
+                self.set_cursor(bol);
+                self.set_vi_mode(ViMode::Insert);
+            }
+            'd' | 'c' | 'g' => vi.pending = Some(c),
+            _ => {}
+        }
+    }
+
+    /// Apply the second key of a two-key command.
+    fn vi_operator(&mut self, op: char, motion: char) {
+        if op == 'g' {
+            if motion == 'g' {
+                self.set_cursor(0);
+            }
+            return;
+        }
+        let bol = self.beginning_of_current_line();
+        let eol = self.end_of_current_line();
+        let linewise = motion == op;
+        let line = self.text[bol..eol].to_string();
+        let range = match motion {
+            'd' if op == 'd' => {
+                // Remove the whole line, including one adjacent newline.
+                if eol < self.text.len() {
+                    bol..eol + 1
+                } else {
+                    bol.saturating_sub(1)..eol
+                }
+            }
+            'c' if op == 'c' => bol..eol,
+            'w' if op == 'c' => self.cursor_pos..self.end_of_next_word(),
+            'w' => self.cursor_pos..self.beginning_of_next_word(),
+            'b' => self.beginning_of_previous_word()..self.cursor_pos,
+            '$' => self.cursor_pos..eol,
+            '0' => bol..self.cursor_pos,
+            _ => return,
+        };
+        self.kill_range(range);
+        if linewise {
+            self.kill_buffer = line;
+            self.kill_linewise = true;
+        }
+        if op == 'c' {
+            self.set_vi_mode(ViMode::Insert);
+        } else if motion == 'd' {
+            self.set_cursor(self.first_non_blank_of_current_line());
+        }
+    }
+
+    /// vi `p` (`after`) and `P`. A line killed by `dd`, `cc` or `S` goes
+    /// below or above the current line; anything else goes after or at the
+    /// cursor.
+    fn vi_put(&mut self, after: bool) {
+        if !self.kill_linewise {
+            if after && self.cursor_pos < self.end_of_current_line() {
+                self.move_cursor_right();
+            }
+            self.yank();
+            return;
+        }
+        let line = self.kill_buffer.clone();
+        let start = if after {
+            let eol = self.end_of_current_line();
+            self.insert_str_at(eol, &format!("\n{line}"));
+            eol + 1
+        } else {
+            let bol = self.beginning_of_current_line();
+            self.insert_str_at(bol, &format!("{line}\n"));
+            bol
+        };
+        self.set_cursor(start);
+        self.set_cursor(self.first_non_blank_of_current_line());
+    }
+
+    /// Start of the next word (vi `w`): skip the rest of the current word,
+    /// then any whitespace.
+    fn beginning_of_next_word(&self) -> usize {
+        let rest = &self.text[self.cursor_pos..];
+        let mut chars = rest.char_indices().peekable();
+        if let Some(&(_, first)) = chars.peek()
+            && !first.is_whitespace()
+        {
+            let is_separator = is_word_separator(first);
+            while let Some(&(_, ch)) = chars.peek() {
+                if ch.is_whitespace() || is_word_separator(ch) != is_separator {
+                    break;
+                }
+                chars.next();
+            }
+        }
+        let offset = chars
+            .find(|&(_, ch)| !ch.is_whitespace())
+            .map(|(idx, _)| idx)
+            .unwrap_or(rest.len());
+        self.adjust_pos_out_of_elements(self.cursor_pos + offset, false)
+    }
+
+    fn first_non_blank_of_current_line(&self) -> usize {
+        let bol = self.beginning_of_current_line();
+        let eol = self.end_of_current_line();
+        self.text[bol..eol]
+            .find(|c: char| !c.is_whitespace())
+            .map(|idx| bol + idx)
+            .unwrap_or(eol)
+    }
+
+    fn last_char_of_current_line(&self) -> usize {
+        let bol = self.beginning_of_current_line();
+        let eol = self.end_of_current_line();
+        if eol > bol {
+            self.prev_atomic_boundary(eol)
+        } else {
+            eol
+        }
+    }
+
     // ####### Input Functions #######
     pub fn delete_backward(&mut self, n: usize) {
         if n == 0 || self.cursor_pos == 0 {
@@ -546,6 +797,7 @@ impl TextArea {
         }
 
         self.kill_buffer = removed;
+        self.kill_linewise = false;
         self.replace_range_raw(range, "");
     }
 
@@ -895,11 +1147,14 @@ impl TextArea {
     }
 
     pub(crate) fn end_of_next_word(&self) -> usize {
-        let Some(first_non_ws) = self.text[self.cursor_pos..].find(|c: char| !c.is_whitespace())
-        else {
+        self.end_of_word_from(self.cursor_pos)
+    }
+
+    fn end_of_word_from(&self, pos: usize) -> usize {
+        let Some(first_non_ws) = self.text[pos..].find(|c: char| !c.is_whitespace()) else {
             return self.text.len();
         };
-        let word_start = self.cursor_pos + first_non_ws;
+        let word_start = pos + first_non_ws;
         let mut iter = self.text[word_start..].char_indices();
         let Some((_, first_ch)) = iter.next() else {
             return word_start;
@@ -2012,4 +2267,114 @@ mod tests {
             }
         }
     }
+
+    fn vi_normal_with(text: &str) -> TextArea {
+        let mut t = ta_with(text);
+        t.set_vi_enabled(true);
+        t.enter_vi_normal_mode();
+        t
+    }
+
+    fn vi_keys(t: &mut TextArea, keys: &str) {
+        for c in keys.chars() {
+            t.vi_normal_input(c);
+        }
+    }
+
+    #[test]
+    fn vi_escape_steps_back_onto_last_character() {
+        let t = vi_normal_with("hello");
+        assert_eq!(t.vi_mode(), Some(ViMode::Normal));
+        assert_eq!(t.cursor(), 4);
+    }
+
+    #[test]
+    fn vi_motions_move_within_the_line() {
+        let mut t = vi_normal_with("  foo bar.baz\nqux");
+        vi_keys(&mut t, "k0");
+        assert_eq!(t.cursor(), 0);
+        vi_keys(&mut t, "^");
+        assert_eq!(t.cursor(), 2);
+        vi_keys(&mut t, "w");
+        assert_eq!(t.cursor(), 6);
+        vi_keys(&mut t, "w");
+        assert_eq!(t.cursor(), 9);
+        vi_keys(&mut t, "e");
+        assert_eq!(t.cursor(), 12);
+        vi_keys(&mut t, "b");
+        assert_eq!(t.cursor(), 10);
+        vi_keys(&mut t, "$");
+        assert_eq!(t.cursor(), 12);
+        vi_keys(&mut t, "l");
+        assert_eq!(t.cursor(), 12);
+        vi_keys(&mut t, "0h");
+        assert_eq!(t.cursor(), 0);
+        vi_keys(&mut t, "G");
+        assert_eq!(t.cursor(), t.text().len());
+        vi_keys(&mut t, "gg");
+        assert_eq!(t.cursor(), 0);
+    }
+
+    #[test]
+    fn vi_edits_and_operators() {
+        let mut t = vi_normal_with("one two\nthree");
+        vi_keys(&mut t, "gg");
+        vi_keys(&mut t, "x");
+        assert_eq!(t.text(), "ne two\nthree");
+        vi_keys(&mut t, "dw");
+        assert_eq!(t.text(), "two\nthree");
+        vi_keys(&mut t, "dd");
+        assert_eq!(t.text(), "three");
+        vi_keys(&mut t, "p");
+        assert_eq!(t.text(), "three\ntwo");
+        assert_eq!(t.cursor(), 6);
+        vi_keys(&mut t, "kP");
+        assert_eq!(t.text(), "two\nthree\ntwo");
+        assert_eq!(t.cursor(), 0);
+        vi_keys(&mut t, "x$p");
+        assert_eq!(t.text(), "wot\nthree\ntwo");
+
+        let mut t = vi_normal_with("alpha beta");
+        vi_keys(&mut t, "0cw");
+        assert_eq!(t.text(), " beta");
+        assert_eq!(t.vi_mode(), Some(ViMode::Insert));
+        t.insert_str("gamma");
+        assert_eq!(t.text(), "gamma beta");
+    }
+
+    #[test]
+    fn vi_insert_commands_switch_modes() {
+        let mut t = vi_normal_with("  text");
+        vi_keys(&mut t, "I");
+        assert_eq!(t.vi_mode(), Some(ViMode::Insert));
+        assert_eq!(t.cursor(), 2);
+
+        t.enter_vi_normal_mode();
+        vi_keys(&mut t, "A");
+        assert_eq!(t.cursor(), t.text().len());
+
+        t.enter_vi_normal_mode();
+        vi_keys(&mut t, "o");
+        assert_eq!(t.text(), "  text\n");
+        assert_eq!(t.cursor(), t.text().len());
+
+        t.enter_vi_normal_mode();
+        vi_keys(&mut t, "kO");
+        assert_eq!(t.text(), "\n  text\n");
+        assert_eq!(t.cursor(), 0);
+    }
+
+    #[test]
+    fn vi_unknown_keys_do_not_insert_text() {
+        let mut t = vi_normal_with("abc");
+        vi_keys(&mut t, "zq!");
+        assert_eq!(t.text(), "abc");
+    }
+
+    #[test]
+    fn newline_follows_keymap_default() {
+        let mut t = ta_with("a");
+        t.input(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL));
+        assert_eq!(t.text(), "a\n");
+    }
 }
@@ -25,6 +25,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -67,6 +68,7 @@ use codex_core::skills::model::SkillMetadata;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::user_input::UserInput;
 use crossterm::event::KeyCode;
@@ -86,14 +88,20 @@ use tokio::task::JoinHandle;
 use tracing::debug;
 
 use crate::app_event::AppEvent;
+use crate::app_event::QueueEdit;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BetaFeatureItem;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
+use crate::bottom_pane::CommandPaletteView;
 use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::PaletteCategory;
+use crate::bottom_pane::PaletteItem;
+use crate::bottom_pane::QueueEditorView;
+use crate::bottom_pane::QueuedMessage;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -105,12 +113,14 @@ use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::export::ExportFormat;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
+use crate::keymap;
 use crate::markdown::append_markdown;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
@@ -118,7 +128,9 @@ use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::resume_picker::RecentSession;
 use crate::slash_command::SlashCommand;
+use crate::slash_command::built_in_slash_commands;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
@@ -139,6 +151,9 @@ use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxAmendment;
+use codex_core::protocol::SandboxAmendmentAppliedEvent;
+use codex_core::protocol::SandboxDenialEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::ModelPreset;
@@ -322,6 +337,8 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    /// Latest spend estimate; only reported when model pricing is configured.
+    cost: Option<CostSummary>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
@@ -341,6 +358,10 @@ pub(crate) struct ChatWidget {
     reasoning_buffer: String,
     // Accumulates full reasoning content for transcript-only recording
     full_reasoning_buffer: String,
+    // Number of turns started in this session, used to label turn diffs.
+    turns_started: usize,
+    // Latest aggregated diff of each turn that changed files, keyed by turn number.
+    turn_diffs: Vec<(usize, String)>,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -354,6 +375,9 @@ pub(crate) struct ChatWidget {
     suppress_session_configured_redraw: bool,
     // User messages queued while a turn is in progress
     queued_user_messages: VecDeque<UserMessage>,
+    // Whether the most recent turn ended with an error; gates queued
+    // messages marked `only_if_succeeded`.
+    last_turn_failed: bool,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
     // Simple review mode flag; used to adjust layout and banners.
@@ -374,6 +398,8 @@ pub(crate) struct ChatWidget {
 struct UserMessage {
     text: String,
     image_paths: Vec<PathBuf>,
+    /// When queued, send only if the turn before it succeeded.
+    only_if_succeeded: bool,
 }
 
 impl From<String> for UserMessage {
@@ -381,6 +407,7 @@ impl From<String> for UserMessage {
         Self {
             text,
             image_paths: Vec::new(),
+            only_if_succeeded: false,
         }
     }
 }
@@ -390,6 +417,7 @@ impl From<&str> for UserMessage {
         Self {
             text: text.to_string(),
             image_paths: Vec::new(),
+            only_if_succeeded: false,
         }
     }
 }
@@ -398,7 +426,11 @@ fn create_initial_user_message(text: String, image_paths: Vec<PathBuf>) -> Optio
     if text.is_empty() && image_paths.is_empty() {
         None
     } else {
-        Some(UserMessage { text, image_paths })
+        Some(UserMessage {
+            text,
+            image_paths,
+            only_if_succeeded: false,
+        })
     }
 }
 
@@ -566,6 +598,8 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.turns_started += 1;
+        self.last_turn_failed = false;
         self.request_redraw();
     }
 
@@ -720,6 +754,7 @@ impl ChatWidget {
     }
 
     fn on_error(&mut self, message: String) {
+        self.last_turn_failed = true;
         self.finalize_turn();
         self.add_to_history(history_cell::new_error_event(message));
         self.request_redraw();
@@ -816,29 +851,33 @@ impl ChatWidget {
 
         // If any messages were queued during the task, restore them into the composer.
         if !self.queued_user_messages.is_empty() {
-            let queued_text = self
-                .queued_user_messages
-                .iter()
-                .map(|m| m.text.clone())
-                .collect::<Vec<_>>()
-                .join("\n");
-            let existing_text = self.bottom_pane.composer_text();
-            let combined = if existing_text.is_empty() {
-                queued_text
-            } else if queued_text.is_empty() {
-                existing_text
-            } else {
-                format!("{queued_text}\n{existing_text}")
-            };
-            self.bottom_pane.set_composer_text(combined);
-            // Clear the queue and update the status indicator list.
-            self.queued_user_messages.clear();
+            let queued = self.queued_user_messages.drain(..).collect();
+            self.restore_to_composer(queued);
+            // Update the status indicator list now that the queue is empty.
             self.refresh_queued_user_messages();
         }
 
         self.request_redraw();
     }
 
+    /// Put `messages` back into the composer, ahead of any draft.
+    fn restore_to_composer(&mut self, messages: Vec<UserMessage>) {
+        let queued_text = messages
+            .into_iter()
+            .map(|m| m.text)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let existing_text = self.bottom_pane.composer_text();
+        let combined = if existing_text.is_empty() {
+            queued_text
+        } else if queued_text.is_empty() {
+            existing_text
+        } else {
+            format!("{queued_text}\n{existing_text}")
+        };
+        self.bottom_pane.set_composer_text(combined);
+    }
+
     fn on_plan_update(&mut self, update: UpdatePlanArgs) {
         self.add_to_history(history_cell::new_plan_update(update));
     }
@@ -1058,6 +1097,17 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        // Each event carries the whole diff for the turn so far.
+        let turn = self.turns_started;
+        match self.turn_diffs.last_mut() {
+            Some((last_turn, diff)) if *last_turn == turn => *diff = unified_diff,
+            _ => self.turn_diffs.push((turn, unified_diff)),
+        }
+    }
+
+    /// Diffs recorded for the turns of this session, oldest first.
+    pub(crate) fn turn_diffs(&self) -> &[(usize, String)] {
+        &self.turn_diffs
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -1066,6 +1116,18 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_sandbox_denial(&mut self, event: SandboxDenialEvent) {
+        self.add_to_history(history_cell::new_sandbox_denial(&event.denials));
+        if !event.suggested_amendments.is_empty() {
+            self.open_sandbox_amendment_popup(event.suggested_amendments);
+        }
+        self.request_redraw();
+    }
+
+    fn on_sandbox_amendment_applied(&mut self, event: SandboxAmendmentAppliedEvent) {
+        self.add_info_message(format!("Sandbox updated: {}", event.amendment), None);
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
         self.bottom_pane.ensure_status_indicator();
@@ -1272,6 +1334,7 @@ impl ChatWidget {
             reason: ev.reason,
             changes: ev.changes.clone(),
             cwd: self.config.cwd.clone(),
+            whole_patch_only: ev.whole_patch_only,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1454,6 +1517,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1469,10 +1533,13 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            turns_started: 0,
+            turn_diffs: Vec::new(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            last_turn_failed: false,
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
@@ -1485,6 +1552,9 @@ impl ChatWidget {
             external_editor_state: ExternalEditorState::Closed,
         };
 
+        widget
+            .bottom_pane
+            .set_composer_vi_mode(widget.config.tui_vi_mode);
         widget.prefetch_rate_limits();
 
         widget
@@ -1541,6 +1611,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1556,10 +1627,13 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
+            turns_started: 0,
+            turn_diffs: Vec::new(),
             current_status_header: String::from("Working"),
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
+            last_turn_failed: false,
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
@@ -1572,6 +1646,9 @@ impl ChatWidget {
             external_editor_state: ExternalEditorState::Closed,
         };
 
+        widget
+            .bottom_pane
+            .set_composer_vi_mode(widget.config.tui_vi_mode);
         widget.prefetch_rate_limits();
 
         widget
@@ -1588,14 +1665,7 @@ impl ChatWidget {
                 self.on_ctrl_c();
                 return;
             }
-            KeyEvent {
-                code: KeyCode::Char(c),
-                modifiers,
-                kind: KeyEventKind::Press,
-                ..
-            } if modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
-                && c.eq_ignore_ascii_case(&'v') =>
-            {
+            e if e.kind == KeyEventKind::Press && keymap::current().paste_image.is_press(e) => {
                 match paste_image_to_temp_png() {
                     Ok((path, info)) => {
                         self.attach_image(
@@ -1621,6 +1691,22 @@ impl ChatWidget {
         }
 
         match key_event {
+            e if e.kind == KeyEventKind::Press
+                && keymap::current().queue.is_press(e)
+                && !self.queued_user_messages.is_empty()
+                && self.bottom_pane.can_launch_external_editor() =>
+            {
+                self.open_queue_editor();
+            }
+            // With text in the composer the chord keeps its editing meaning
+            // (ctrl+k kills to the end of the line).
+            e if e.kind == KeyEventKind::Press
+                && keymap::current().command_palette.is_press(e)
+                && self.bottom_pane.composer_is_empty()
+                && self.bottom_pane.can_launch_external_editor() =>
+            {
+                self.app_event_tx.send(AppEvent::OpenCommandPalette);
+            }
             KeyEvent {
                 code: KeyCode::Up,
                 modifiers: KeyModifiers::ALT,
@@ -1641,6 +1727,7 @@ impl ChatWidget {
                         let user_message = UserMessage {
                             text,
                             image_paths: self.bottom_pane.take_recent_submission_images(),
+                            only_if_succeeded: false,
                         };
                         self.queue_user_message(user_message);
                     }
@@ -1693,7 +1780,7 @@ impl ChatWidget {
         self.bottom_pane.can_launch_external_editor()
     }
 
-    fn dispatch_command(&mut self, cmd: SlashCommand) {
+    pub(crate) fn dispatch_command(&mut self, cmd: SlashCommand) {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
                 "'/{}' is disabled while a task is in progress.",
@@ -1717,6 +1804,15 @@ impl ChatWidget {
             SlashCommand::Resume => {
                 self.app_event_tx.send(AppEvent::OpenResumePicker);
             }
+            SlashCommand::Tab => {
+                self.app_event_tx.send(AppEvent::NewTab);
+            }
+            SlashCommand::TabClose => {
+                self.app_event_tx.send(AppEvent::CloseTab);
+            }
+            SlashCommand::Export => {
+                self.open_export_popup();
+            }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
                 if init_target.exists() {
@@ -1837,6 +1933,7 @@ impl ChatWidget {
                         ]),
                         reason: None,
                         grant_root: Some(PathBuf::from("/tmp")),
+                        whole_patch_only: false,
                     }),
                 }));
             }
@@ -1917,7 +2014,9 @@ impl ChatWidget {
     }
 
     fn submit_user_message(&mut self, user_message: UserMessage) {
-        let UserMessage { text, image_paths } = user_message;
+        let UserMessage {
+            text, image_paths, ..
+        } = user_message;
         if text.is_empty() && image_paths.is_empty() {
             return;
         }
@@ -1946,8 +2045,8 @@ impl ChatWidget {
             items.push(UserInput::Text { text: text.clone() });
         }
 
-        for path in image_paths {
-            items.push(UserInput::LocalImage { path });
+        for path in &image_paths {
+            items.push(UserInput::LocalImage { path: path.clone() });
         }
 
         if let Some(skills) = self.bottom_pane.skills() {
@@ -1979,6 +2078,11 @@ impl ChatWidget {
         if !text.is_empty() {
             self.add_to_history(history_cell::new_user_prompt(text));
         }
+        for path in &image_paths {
+            if let Some(cell) = history_cell::new_image_attachment(path) {
+                self.add_to_history(cell);
+            }
+        }
         self.needs_final_message_separator = false;
     }
 
@@ -2045,6 +2149,9 @@ impl ChatWidget {
             }
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
+                if ev.cost.is_some() {
+                    self.cost = ev.cost;
+                }
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
@@ -2097,6 +2204,8 @@ impl ChatWidget {
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
+            EventMsg::SandboxDenial(ev) => self.on_sandbox_denial(ev),
+            EventMsg::SandboxAmendmentApplied(ev) => self.on_sandbox_amendment_applied(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
@@ -2222,6 +2331,33 @@ impl ChatWidget {
         if self.bottom_pane.is_task_running() {
             return;
         }
+        if self.last_turn_failed {
+            // Follow-ups that depend on the failed turn go back to the
+            // composer instead of being sent.
+            let mut held = Vec::new();
+            while self
+                .queued_user_messages
+                .front()
+                .is_some_and(|m| m.only_if_succeeded)
+            {
+                held.extend(self.queued_user_messages.pop_front());
+            }
+            if !held.is_empty() {
+                let count = held.len();
+                self.restore_to_composer(held);
+                let (noun, verb) = if count == 1 {
+                    ("message", "was")
+                } else {
+                    ("messages", "were")
+                };
+                self.add_info_message(
+                    format!(
+                        "The last turn failed, so {count} queued {noun} marked \"if ok\" {verb} not sent."
+                    ),
+                    Some("They are back in the composer.".to_string()),
+                );
+            }
+        }
         if let Some(user_message) = self.queued_user_messages.pop_front() {
             self.submit_user_message(user_message);
         }
@@ -2231,14 +2367,67 @@ impl ChatWidget {
 
     /// Rebuild and update the queued user messages from the current queue.
     fn refresh_queued_user_messages(&mut self) {
-        let messages: Vec<String> = self
+        let messages: Vec<QueuedMessage> = self
             .queued_user_messages
             .iter()
-            .map(|m| m.text.clone())
+            .map(|m| QueuedMessage {
+                text: m.text.clone(),
+                only_if_succeeded: m.only_if_succeeded,
+            })
             .collect();
         self.bottom_pane.set_queued_user_messages(messages);
     }
 
+    fn open_queue_editor(&mut self) {
+        let messages = self
+            .queued_user_messages
+            .iter()
+            .map(|m| QueuedMessage {
+                text: m.text.clone(),
+                only_if_succeeded: m.only_if_succeeded,
+            })
+            .collect();
+        let view = QueueEditorView::new(messages, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    /// Apply an edit from the queue editor, unless the queue has moved on and
+    /// `index` no longer holds the message the user was looking at.
+    pub(crate) fn edit_queued_message(&mut self, index: usize, text: &str, edit: QueueEdit) {
+        if self
+            .queued_user_messages
+            .get(index)
+            .is_none_or(|m| m.text != text)
+        {
+            self.refresh_queued_user_messages();
+            return;
+        }
+        match edit {
+            QueueEdit::Edit => {
+                if let Some(user_message) = self.queued_user_messages.remove(index) {
+                    self.bottom_pane.set_composer_text(user_message.text);
+                }
+            }
+            QueueEdit::Remove => {
+                self.queued_user_messages.remove(index);
+            }
+            QueueEdit::ToggleCondition => {
+                if let Some(user_message) = self.queued_user_messages.get_mut(index) {
+                    user_message.only_if_succeeded = !user_message.only_if_succeeded;
+                }
+            }
+            QueueEdit::Move { to } => {
+                if let Some(user_message) = self.queued_user_messages.remove(index) {
+                    let to = to.min(self.queued_user_messages.len());
+                    self.queued_user_messages.insert(to, user_message);
+                }
+            }
+        }
+        self.refresh_queued_user_messages();
+        self.request_redraw();
+    }
+
     pub(crate) fn add_diff_in_progress(&mut self) {
         self.request_redraw();
     }
@@ -2260,6 +2449,7 @@ impl ChatWidget {
             &self.model_family,
             total_usage,
             context_usage,
+            self.cost.as_ref(),
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
@@ -2780,52 +2970,7 @@ impl ChatWidget {
                 Ok(()) => None,
                 Err(err) => Some(err.to_string()),
             };
-            let requires_confirmation = preset.id == "full-access"
-                && !self
-                    .config
-                    .notices
-                    .hide_full_access_warning
-                    .unwrap_or(false);
-            let actions: Vec<SelectionAction> = if requires_confirmation {
-                let preset_clone = preset.clone();
-                vec![Box::new(move |tx| {
-                    tx.send(AppEvent::OpenFullAccessConfirmation {
-                        preset: preset_clone.clone(),
-                    });
-                })]
-            } else if preset.id == "auto" {
-                #[cfg(target_os = "windows")]
-                {
-                    if codex_core::get_platform_sandbox().is_none() {
-                        let preset_clone = preset.clone();
-                        vec![Box::new(move |tx| {
-                            tx.send(AppEvent::OpenWindowsSandboxEnablePrompt {
-                                preset: preset_clone.clone(),
-                            });
-                        })]
-                    } else if let Some((sample_paths, extra_count, failed_scan)) =
-                        self.world_writable_warning_details()
-                    {
-                        let preset_clone = preset.clone();
-                        vec![Box::new(move |tx| {
-                            tx.send(AppEvent::OpenWorldWritableWarningConfirmation {
-                                preset: Some(preset_clone.clone()),
-                                sample_paths: sample_paths.clone(),
-                                extra_count,
-                                failed_scan,
-                            });
-                        })]
-                    } else {
-                        Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
-                    }
-                }
-                #[cfg(not(target_os = "windows"))]
-                {
-                    Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
-                }
-            } else {
-                Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
-            };
+            let actions = self.approval_selection_actions(&preset);
             items.push(SelectionItem {
                 name,
                 description,
@@ -2846,6 +2991,90 @@ impl ChatWidget {
         });
     }
 
+    /// What selecting `preset` does: apply it directly, or first open the
+    /// confirmation or sandbox setup prompt it needs.
+    fn approval_selection_actions(&self, preset: &ApprovalPreset) -> Vec<SelectionAction> {
+        let requires_confirmation = preset.id == "full-access"
+            && !self
+                .config
+                .notices
+                .hide_full_access_warning
+                .unwrap_or(false);
+        if requires_confirmation {
+            let preset_clone = preset.clone();
+            vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenFullAccessConfirmation {
+                    preset: preset_clone.clone(),
+                });
+            })]
+        } else if preset.id == "auto" {
+            #[cfg(target_os = "windows")]
+            {
+                if codex_core::get_platform_sandbox().is_none() {
+                    let preset_clone = preset.clone();
+                    vec![Box::new(move |tx| {
+                        tx.send(AppEvent::OpenWindowsSandboxEnablePrompt {
+                            preset: preset_clone.clone(),
+                        });
+                    })]
+                } else if let Some((sample_paths, extra_count, failed_scan)) =
+                    self.world_writable_warning_details()
+                {
+                    let preset_clone = preset.clone();
+                    vec![Box::new(move |tx| {
+                        tx.send(AppEvent::OpenWorldWritableWarningConfirmation {
+                            preset: Some(preset_clone.clone()),
+                            sample_paths: sample_paths.clone(),
+                            extra_count,
+                            failed_scan,
+                        });
+                    })]
+                } else {
+                    Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
+                }
+            }
+            #[cfg(not(target_os = "windows"))]
+            {
+                Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
+            }
+        } else {
+            Self::approval_preset_actions(preset.approval, preset.sandbox.clone())
+        }
+    }
+
+    pub(crate) fn open_export_popup(&mut self) {
+        let items = [
+            ExportFormat::Markdown,
+            ExportFormat::Html,
+            ExportFormat::Json,
+        ]
+        .into_iter()
+        .map(|format| {
+            let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                tx.send(AppEvent::ExportTranscript(format));
+            })];
+            SelectionItem {
+                name: format.label().to_string(),
+                description: Some(format!("save as .{}", format.extension())),
+                actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Export transcript".to_string()),
+            subtitle: Some(format!(
+                "Writes to {}",
+                self.config.codex_home.join("exports").display()
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_experimental_popup(&mut self) {
         let features: Vec<BetaFeatureItem> = FEATURES
             .iter()
@@ -2865,6 +3094,177 @@ impl ChatWidget {
         self.bottom_pane.show_view(Box::new(view));
     }
 
+    /// Open the command palette over every action reachable from the TUI.
+    /// `recent` lists palette entry ids, most recently used first.
+    pub(crate) fn open_command_palette(&mut self, sessions: Vec<RecentSession>, recent: &[String]) {
+        let mut items: Vec<PaletteItem> = Vec::new();
+
+        let skills_enabled = self.bottom_pane.skills().is_some_and(|s| !s.is_empty());
+        for (name, cmd) in built_in_slash_commands() {
+            if cmd == SlashCommand::Skills && !skills_enabled {
+                continue;
+            }
+            items.push(PaletteItem {
+                id: format!("command:{name}"),
+                category: PaletteCategory::Command,
+                name: format!("/{name}"),
+                description: Some(cmd.description().to_string()),
+                is_current: false,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::RunSlashCommand(cmd));
+                })],
+            });
+        }
+
+        for prompt in self.bottom_pane.custom_prompts() {
+            let text = format!("/{PROMPTS_CMD_PREFIX}:{} ", prompt.name);
+            items.push(PaletteItem {
+                id: format!("prompt:{}", prompt.name),
+                category: PaletteCategory::Prompt,
+                name: text.trim_end().to_string(),
+                description: prompt.description.clone(),
+                is_current: false,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::InsertInComposer(text.clone()));
+                })],
+            });
+        }
+
+        for skill in self.bottom_pane.skills().into_iter().flatten() {
+            let text = format!("${} ", skill.name);
+            items.push(PaletteItem {
+                id: format!("skill:{}", skill.name),
+                category: PaletteCategory::Skill,
+                name: text.trim_end().to_string(),
+                description: Some(
+                    skill
+                        .short_description
+                        .clone()
+                        .unwrap_or_else(|| skill.description.clone()),
+                ),
+                is_current: false,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::InsertInComposer(text.clone()));
+                })],
+            });
+        }
+
+        let current_rollout = self.rollout_path();
+        for session in sessions {
+            if current_rollout.as_ref() == Some(&session.path) {
+                continue;
+            }
+            let path = session.path;
+            items.push(PaletteItem {
+                id: format!("session:{}", path.display()),
+                category: PaletteCategory::Session,
+                name: format!("Resume: {}", session.preview),
+                description: Some(session.updated),
+                is_current: false,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::ResumeSession(path.clone()));
+                })],
+            });
+        }
+
+        let current_model = self.model_family.get_model_slug().to_string();
+        let presets = self
+            .models_manager
+            .try_list_models(&self.config)
+            .unwrap_or_default();
+        for preset in &presets {
+            let model_preset = preset.clone();
+            items.push(PaletteItem {
+                id: format!("model:{}", preset.model),
+                category: PaletteCategory::Model,
+                name: format!("Switch model: {}", preset.display_name),
+                description: (!preset.description.is_empty()).then(|| preset.description.clone()),
+                is_current: preset.model == current_model,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenReasoningPopup {
+                        model: model_preset.clone(),
+                    });
+                })],
+            });
+        }
+
+        let current_approval = self.config.approval_policy.value();
+        let current_sandbox = self.config.sandbox_policy.get();
+        for preset in builtin_approval_presets() {
+            items.push(PaletteItem {
+                id: format!("approvals:{}", preset.id),
+                category: PaletteCategory::Setting,
+                name: format!("Approvals: {}", preset.label),
+                description: Some(preset.description.to_string()),
+                is_current: Self::preset_matches_current(
+                    current_approval,
+                    current_sandbox,
+                    &preset,
+                ),
+                disabled_reason: self
+                    .config
+                    .approval_policy
+                    .can_set(&preset.approval)
+                    .err()
+                    .map(|err| err.to_string()),
+                actions: self.approval_selection_actions(&preset),
+            });
+        }
+
+        if let Some(preset) = presets.iter().find(|preset| preset.model == current_model) {
+            let current_effort = self
+                .config
+                .model_reasoning_effort
+                .unwrap_or(preset.default_reasoning_effort);
+            for option in &preset.supported_reasoning_efforts {
+                let label = Self::reasoning_effort_label(option.effort);
+                items.push(PaletteItem {
+                    id: format!("effort:{}", option.effort),
+                    category: PaletteCategory::Setting,
+                    name: format!("Reasoning effort: {label}"),
+                    description: (!option.description.is_empty())
+                        .then(|| option.description.clone()),
+                    is_current: option.effort == current_effort,
+                    disabled_reason: None,
+                    actions: Self::model_selection_actions(
+                        current_model.clone(),
+                        Some(option.effort),
+                    ),
+                });
+            }
+        }
+
+        for spec in FEATURES {
+            let Some(name) = spec.stage.beta_menu_name() else {
+                continue;
+            };
+            let feature = spec.id;
+            let enabled = self.config.features.enabled(feature);
+            let state = if enabled { "on" } else { "off" };
+            items.push(PaletteItem {
+                id: format!("experimental:{}", spec.key),
+                category: PaletteCategory::Setting,
+                name: format!("Experimental: {name} ({state})"),
+                description: spec.stage.beta_menu_description().map(str::to_string),
+                is_current: false,
+                disabled_reason: None,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::UpdateFeatureFlags {
+                        updates: vec![(feature, !enabled)],
+                    });
+                })],
+            });
+        }
+
+        let view = CommandPaletteView::new(items, recent.to_vec(), self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
     fn approval_preset_actions(
         approval: AskForApproval,
         sandbox: SandboxPolicy,
@@ -2936,6 +3336,53 @@ impl ChatWidget {
         None
     }
 
+    /// Offers to apply one of the sandbox changes that would have let a
+    /// denied command through.
+    pub(crate) fn open_sandbox_amendment_popup(&mut self, amendments: Vec<SandboxAmendment>) {
+        let mut items: Vec<SelectionItem> = amendments
+            .into_iter()
+            .map(|amendment| {
+                let name = format!("Yes, {amendment}");
+                let description = match &amendment {
+                    SandboxAmendment::AddWritableRoot { .. } => {
+                        "Saved to this project's writable_roots in config.toml"
+                    }
+                    SandboxAmendment::RunOutsideSandbox { .. } => {
+                        "Saved as an execpolicy rule; matching commands get no sandbox at all, \
+                         file system included"
+                    }
+                };
+                // The "Sandbox updated" note comes back as
+                // `SandboxAmendmentApplied` once core has saved the change.
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::ApplySandboxAmendment {
+                        amendment: amendment.clone(),
+                    }));
+                })];
+                SelectionItem {
+                    name,
+                    description: Some(description.to_string()),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "No, keep the sandbox as it is".to_string(),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Update the sandbox?".to_string()),
+            subtitle: Some("The sandbox likely blocked the last command.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_full_access_confirmation(&mut self, preset: ApprovalPreset) {
         let approval = preset.approval;
         let sandbox = preset.sandbox;
@@ -3298,6 +3745,12 @@ impl ChatWidget {
         self.bottom_pane.composer_is_empty()
     }
 
+    /// True when vi mode is enabled and the composer is in insert mode, where
+    /// Esc switches to normal mode instead of interrupting or backtracking.
+    pub(crate) fn composer_in_vi_insert_mode(&self) -> bool {
+        self.bottom_pane.composer_in_vi_insert_mode()
+    }
+
     /// True when the UI is in the regular composer state with no running task,
     /// no modal overlay (e.g. approvals or status indicator), and no composer popups.
     /// In this state Esc-Esc backtracking is enabled.
@@ -3374,6 +3827,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             })],
@@ -3429,6 +3883,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            test_output: None,
                         },
                     }));
                 })],
@@ -3467,6 +3922,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            test_output: None,
                         },
                     }));
                 })],
@@ -3503,6 +3959,7 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             }),
@@ -3716,6 +4173,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             })],
@@ -36,6 +36,11 @@ use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SandboxAmendment;
+use codex_core::protocol::SandboxAmendmentAppliedEvent;
+use codex_core::protocol::SandboxDenial;
+use codex_core::protocol::SandboxDenialEvent;
+use codex_core::protocol::SandboxDenialKind;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
@@ -43,6 +48,7 @@ use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
@@ -164,6 +170,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            test_output: None,
         }),
     });
 
@@ -183,6 +190,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            test_output: None,
         }),
     });
 
@@ -206,6 +214,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_review_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -217,6 +226,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            test_output: None,
         }),
     });
 
@@ -225,6 +235,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(review_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(97));
@@ -254,6 +265,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_compact_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -263,6 +275,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: None,
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), None);
@@ -293,6 +306,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(token_info),
             rate_limits: None,
+            cost: None,
         }),
     });
 
@@ -378,6 +392,7 @@ async fn make_chatwidget_manual(
         session_header: SessionHeader::new(resolved_model.clone()),
         initial_user_message: None,
         token_info: None,
+        cost: None,
         rate_limit_snapshot: None,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
@@ -393,12 +408,15 @@ async fn make_chatwidget_manual(
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
         full_reasoning_buffer: String::new(),
+        turns_started: 0,
+        turn_diffs: Vec::new(),
         current_status_header: String::from("Working"),
         retry_status_header: None,
         conversation_id: None,
         frame_requester: FrameRequester::test_dummy(),
         show_welcome_banner: true,
         queued_user_messages: VecDeque::new(),
+        last_turn_failed: false,
         suppress_session_configured_redraw: false,
         pending_notification: None,
         is_review_mode: false,
@@ -947,6 +965,7 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            exit_reason: None,
         }),
     });
 }
@@ -1016,6 +1035,133 @@ async fn alt_up_edits_most_recent_queued_message() {
     );
 }
 
+#[tokio::test]
+async fn ctrl_k_opens_command_palette_only_when_composer_is_empty() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+    assert!(matches!(rx.try_recv(), Ok(AppEvent::OpenCommandPalette)));
+
+    // With a draft, ctrl+k keeps killing to the end of the line.
+    chat.set_composer_text("draft".to_string());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('k'), KeyModifiers::CONTROL));
+    assert!(rx.try_recv().is_err());
+    assert_eq!(chat.bottom_pane.composer_text(), "");
+}
+
+#[tokio::test]
+async fn command_palette_searches_commands_and_settings() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.open_command_palette(Vec::new(), &[]);
+    for c in "approvals".chars() {
+        chat.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(popup.contains("/approvals"), "{popup}");
+    assert!(popup.contains("Approvals: Read Only"), "{popup}");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(AppEvent::CommandPaletteUsed(id)) if id == "command:approvals"
+    ));
+    assert!(matches!(
+        rx.try_recv(),
+        Ok(AppEvent::RunSlashCommand(SlashCommand::Approvals))
+    ));
+}
+
+#[tokio::test]
+async fn queued_if_ok_message_is_held_when_turn_fails() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+
+    chat.queued_user_messages.push_back(UserMessage {
+        only_if_succeeded: true,
+        ..UserMessage::from("now run the tests")
+    });
+    chat.queued_user_messages
+        .push_back(UserMessage::from("explain what went wrong"));
+    chat.refresh_queued_user_messages();
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::Error(ErrorEvent {
+            message: "stream disconnected".to_string(),
+            codex_error_info: None,
+        }),
+    });
+
+    // The conditional follow-up goes back to the composer ...
+    assert_eq!(chat.bottom_pane.composer_text(), "now run the tests");
+    let history = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        history
+            .contains("The last turn failed, so 1 queued message marked \"if ok\" was not sent."),
+        "{history}"
+    );
+    // ... and the unconditional one after it is sent.
+    match op_rx.try_recv() {
+        Ok(Op::UserInput { items }) => assert_eq!(
+            items,
+            vec![UserInput::Text {
+                text: "explain what went wrong".to_string()
+            }]
+        ),
+        other => panic!("expected queued message to be sent, got {other:?}"),
+    }
+    assert!(chat.queued_user_messages.is_empty());
+}
+
+#[tokio::test]
+async fn queued_if_ok_message_is_sent_after_successful_turn() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+    chat.queued_user_messages.push_back(UserMessage {
+        only_if_succeeded: true,
+        ..UserMessage::from("now run the tests")
+    });
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        }),
+    });
+
+    assert_matches!(op_rx.try_recv(), Ok(Op::UserInput { .. }));
+    assert!(chat.queued_user_messages.is_empty());
+}
+
+#[tokio::test]
+async fn queue_edits_apply_only_to_the_expected_message() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane.set_task_running(true);
+    for text in ["first", "second", "third"] {
+        chat.queued_user_messages.push_back(UserMessage::from(text));
+    }
+    chat.refresh_queued_user_messages();
+
+    chat.edit_queued_message(2, "third", QueueEdit::Move { to: 0 });
+    chat.edit_queued_message(1, "first", QueueEdit::ToggleCondition);
+    // Stale edit: index 0 no longer holds "second".
+    chat.edit_queued_message(0, "second", QueueEdit::Remove);
+    chat.edit_queued_message(2, "second", QueueEdit::Edit);
+
+    let queue: Vec<(&str, bool)> = chat
+        .queued_user_messages
+        .iter()
+        .map(|m| (m.text.as_str(), m.only_if_succeeded))
+        .collect();
+    assert_eq!(queue, vec![("third", false), ("first", true)]);
+    assert_eq!(chat.bottom_pane.composer_text(), "second");
+}
+
 /// Pressing Up to recall the most recent history entry and immediately queuing
 /// it while a task is running should always enqueue the same text, even when it
 /// is queued repeatedly.
@@ -1202,6 +1348,7 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            exit_reason: None,
         }),
     });
 
@@ -1665,6 +1812,7 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    test_output: None,
                 }
             );
         }
@@ -1888,6 +2036,66 @@ fn render_bottom_popup(chat: &ChatWidget, width: u16) -> String {
     lines.join("\n")
 }
 
+#[tokio::test]
+async fn sandbox_denial_offers_to_add_writable_root() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let amendment = SandboxAmendment::AddWritableRoot {
+        path: PathBuf::from("/srv/cache"),
+    };
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SandboxDenial(SandboxDenialEvent {
+            call_id: "call-1".into(),
+            command: vec!["touch".into(), "/srv/cache/build.lock".into()],
+            cwd: PathBuf::from("/workspace"),
+            denials: vec![SandboxDenial {
+                kind: SandboxDenialKind::FileSystem,
+                target: Some("/srv/cache/build.lock".into()),
+                evidence: "touch: cannot touch '/srv/cache/build.lock': Read-only file system"
+                    .into(),
+            }],
+            suggested_amendments: vec![amendment.clone()],
+        }),
+    });
+
+    let history = lines_to_single_string(&drain_insert_history(&mut rx).concat());
+    assert!(
+        history.contains("Read-only file system"),
+        "expected the evidence in history, got: {history}"
+    );
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains("Yes, add /srv/cache to the writable roots"),
+        "expected the amendment in the popup, got: {popup}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut applied = None;
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::CodexOp(Op::ApplySandboxAmendment { amendment }) => {
+                applied = Some(amendment);
+            }
+            AppEvent::InsertHistoryCell(_) => {
+                panic!("the update is only confirmed once core has applied it")
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(applied, Some(amendment.clone()));
+
+    chat.handle_codex_event(Event {
+        id: "sub-2".into(),
+        msg: EventMsg::SandboxAmendmentApplied(SandboxAmendmentAppliedEvent { amendment }),
+    });
+    let history = lines_to_single_string(&drain_insert_history(&mut rx).concat());
+    assert!(
+        history.contains("Sandbox updated: add /srv/cache to the writable roots"),
+        "expected the confirmation in history, got: {history}"
+    );
+}
+
 #[tokio::test]
 async fn experimental_features_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -1989,6 +2197,8 @@ async fn preset_matching_ignores_extra_writable_roots() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     assert!(
@@ -2486,6 +2696,7 @@ async fn approval_modal_patch_snapshot() -> anyhow::Result<()> {
         changes,
         reason: Some("The model wants to apply changes".into()),
         grant_root: Some(PathBuf::from("/tmp")),
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-patch".into(),
@@ -2598,6 +2809,40 @@ async fn ui_snapshots_small_heights_idle() {
     }
 }
 
+#[tokio::test]
+async fn turn_diffs_keep_latest_diff_per_turn() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    let start_turn = |chat: &mut ChatWidget| {
+        chat.handle_codex_event(Event {
+            id: "task".into(),
+            msg: EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+            }),
+        });
+    };
+    let diff = |chat: &mut ChatWidget, text: &str| {
+        chat.handle_codex_event(Event {
+            id: "task".into(),
+            msg: EventMsg::TurnDiff(TurnDiffEvent {
+                unified_diff: text.to_string(),
+            }),
+        });
+    };
+
+    start_turn(&mut chat);
+    diff(&mut chat, "first");
+    diff(&mut chat, "first, updated");
+    // A turn without edits records nothing.
+    start_turn(&mut chat);
+    start_turn(&mut chat);
+    diff(&mut chat, "third");
+
+    assert_eq!(
+        chat.turn_diffs(),
+        &[(1, "first, updated".to_string()), (3, "third".to_string())]
+    );
+}
+
 // Snapshot test: ChatWidget at very small heights (task running)
 // Validates how status + composer are presented within tight space.
 #[tokio::test]
@@ -2768,6 +3013,7 @@ async fn apply_patch_events_emit_history_cells() {
         changes,
         reason: None,
         grant_root: None,
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -2867,6 +3113,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             changes: proposed_changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
     drain_insert_history(&mut rx);
@@ -2916,6 +3163,7 @@ async fn apply_patch_manual_flow_snapshot() {
             changes: proposed_changes,
             reason: Some("Manual review required".into()),
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
     let history_before_apply = drain_insert_history(&mut rx);
@@ -2967,6 +3215,7 @@ async fn apply_patch_approval_sends_op_with_submission_id() {
         changes,
         reason: None,
         grant_root: None,
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-123".into(),
@@ -3007,6 +3256,7 @@ async fn apply_patch_full_flow_integration_like() {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -3087,6 +3337,7 @@ async fn apply_patch_untrusted_shows_approval_modal() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -3138,6 +3389,7 @@ async fn apply_patch_request_shows_diff_summary() -> anyhow::Result<()> {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -3497,6 +3749,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -12,17 +12,28 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::color::blend;
+use crate::color::is_light;
 use crate::exec_command::relativize_to_home;
+use crate::live_wrap::take_prefix_by_width;
 use crate::render::Insets;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::render::syntax::Language;
+use crate::render::syntax::SyntaxTheme;
+use crate::render::syntax::highlight_line_spans;
+use crate::render::syntax::language_for_path;
+use crate::render::syntax::slice_spans;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
-// Internal representation for diff line rendering
-enum DiffLineType {
+// Representation for diff line rendering
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DiffLineType {
     Insert,
     Delete,
     Context,
@@ -42,13 +53,43 @@ impl DiffSummary {
 impl Renderable for FileChange {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         let mut lines = vec![];
-        render_change(self, &mut lines, area.width as usize);
+        render_change(self, &mut lines, area.width as usize, None);
         Paragraph::new(lines).render(area, buf);
     }
 
     fn desired_height(&self, width: u16) -> u16 {
         let mut lines = vec![];
-        render_change(self, &mut lines, width as usize);
+        render_change(self, &mut lines, width as usize, None);
+        lines.len() as u16
+    }
+}
+
+/// A file change rendered with syntax highlighting for its path.
+struct HighlightedChange {
+    change: FileChange,
+    syntax: Option<DiffSyntax>,
+}
+
+impl Renderable for HighlightedChange {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let mut lines = vec![];
+        render_change(
+            &self.change,
+            &mut lines,
+            area.width as usize,
+            self.syntax.as_ref(),
+        );
+        Paragraph::new(lines).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let mut lines = vec![];
+        render_change(
+            &self.change,
+            &mut lines,
+            width as usize,
+            self.syntax.as_ref(),
+        );
         lines.len() as u16
     }
 }
@@ -66,8 +107,12 @@ impl From<DiffSummary> for Box<dyn Renderable> {
             path.extend(render_line_count_summary(row.added, row.removed));
             rows.push(Box::new(path));
             rows.push(Box::new(RtLine::from("")));
+            let syntax = DiffSyntax::for_path(row.move_path.as_ref().unwrap_or(&row.path));
             rows.push(Box::new(InsetRenderable::new(
-                Box::new(row.change) as Box<dyn Renderable>,
+                Box::new(HighlightedChange {
+                    change: row.change,
+                    syntax,
+                }) as Box<dyn Renderable>,
                 Insets::tlbr(0, 2, 0, 0),
             )));
         }
@@ -186,14 +231,20 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         }
 
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        let syntax = DiffSyntax::for_path(r.move_path.as_ref().unwrap_or(&r.path));
+        render_change(&r.change, &mut lines, wrap_cols - 4, syntax.as_ref());
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+    syntax: Option<&DiffSyntax>,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
@@ -204,6 +255,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     raw,
                     width,
                     line_number_width,
+                    syntax,
                 ));
             }
         }
@@ -216,6 +268,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     raw,
                     width,
                     line_number_width,
+                    syntax,
                 ));
             }
         }
@@ -265,6 +318,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     s,
                                     width,
                                     line_number_width,
+                                    syntax,
                                 ));
                                 new_ln += 1;
                             }
@@ -276,6 +330,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     s,
                                     width,
                                     line_number_width,
+                                    syntax,
                                 ));
                                 old_ln += 1;
                             }
@@ -287,6 +342,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     s,
                                     width,
                                     line_number_width,
+                                    syntax,
                                 ));
                                 old_ln += 1;
                                 new_ln += 1;
@@ -299,6 +355,55 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
     }
 }
 
+/// Render a single hunk with line numbers, starting at `old_start`/`new_start`
+/// in the original and updated file respectively.
+pub(crate) fn render_hunk_lines(
+    lines: &[(DiffLineType, String)],
+    old_start: usize,
+    new_start: usize,
+    width: usize,
+    syntax: Option<&DiffSyntax>,
+) -> Vec<RtLine<'static>> {
+    let old_len = lines
+        .iter()
+        .filter(|(kind, _)| *kind != DiffLineType::Insert)
+        .count();
+    let new_len = lines
+        .iter()
+        .filter(|(kind, _)| *kind != DiffLineType::Delete)
+        .count();
+    let line_number_width = line_number_width((old_start + old_len).max(new_start + new_len));
+    let mut old_ln = old_start;
+    let mut new_ln = new_start;
+    let mut out = Vec::new();
+    for (kind, text) in lines {
+        let line_number = match kind {
+            DiffLineType::Insert => {
+                new_ln += 1;
+                new_ln - 1
+            }
+            DiffLineType::Delete => {
+                old_ln += 1;
+                old_ln - 1
+            }
+            DiffLineType::Context => {
+                old_ln += 1;
+                new_ln += 1;
+                new_ln - 1
+            }
+        };
+        out.extend(push_wrapped_diff_line(
+            line_number,
+            *kind,
+            text,
+            width,
+            line_number_width,
+            syntax,
+        ));
+    }
+    out
+}
+
 pub(crate) fn display_path_for(path: &Path, cwd: &Path) -> String {
     let path_in_same_repo = match (get_git_repo_root(cwd), get_git_repo_root(path)) {
         (Some(cwd_repo), Some(path_repo)) => cwd_repo == path_repo,
@@ -337,9 +442,14 @@ fn push_wrapped_diff_line(
     text: &str,
     width: usize,
     line_number_width: usize,
+    syntax: Option<&DiffSyntax>,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
     let mut remaining_text: &str = text;
+    // Highlighted content for the whole line; each row takes its slice so
+    // tokens that wrap keep their color.
+    let highlighted = syntax.map(|syntax| syntax.content_spans(kind, text));
+    let mut consumed_chars = 0;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
@@ -352,6 +462,7 @@ fn push_wrapped_diff_line(
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
+    let sign_style = syntax.map_or(line_style, |syntax| syntax.sign_style(kind));
     let mut lines: Vec<RtLine<'static>> = Vec::new();
 
     loop {
@@ -366,25 +477,29 @@ fn push_wrapped_diff_line(
             .unwrap_or_else(|| remaining_text.len());
         let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
         remaining_text = rest;
-
-        if first {
-            // Build gutter (right-aligned line number plus spacer) as a dimmed span
-            let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
-            first = false;
+        let chunk_chars = chunk.chars().count();
+        let content: Vec<RtSpan<'static>> = match &highlighted {
+            Some(spans) => slice_spans(spans, consumed_chars, consumed_chars + chunk_chars),
+            None => vec![RtSpan::styled(chunk.to_string(), line_style)],
+        };
+        consumed_chars += chunk_chars;
+
+        // Build gutter (right-aligned line number plus spacer) as a dimmed span;
+        // continuation lines keep a space for the sign column so content aligns.
+        let mut spans = if first {
+            vec![
+                RtSpan::styled(format!("{ln_str:>gutter_width$} "), style_gutter()),
+                RtSpan::styled(sign_char.to_string(), sign_style),
+            ]
         } else {
-            // Continuation lines keep a space for the sign column so content aligns
-            let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
-        }
+            vec![RtSpan::styled(
+                format!("{:gutter_width$}  ", ""),
+                style_gutter(),
+            )]
+        };
+        first = false;
+        spans.extend(content);
+        lines.push(RtLine::from(spans));
         if remaining_text.is_empty() {
             break;
         }
@@ -392,6 +507,402 @@ fn push_wrapped_diff_line(
     lines
 }
 
+/// Syntax highlighting for diff content, chosen from the file extension.
+/// Changed lines are tinted with a background instead of a red/green
+/// foreground so the token colors stay readable.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DiffSyntax {
+    language: &'static Language,
+    theme: SyntaxTheme,
+    insert_bg: Color,
+    delete_bg: Color,
+}
+
+impl DiffSyntax {
+    /// Returns `None` for unknown languages, and when the terminal background
+    /// is unknown (no tint can be picked); those diffs keep plain red/green text.
+    pub(crate) fn for_path(path: impl AsRef<Path>) -> Option<Self> {
+        Some(Self::with_background(
+            language_for_path(path.as_ref())?,
+            default_bg()?,
+        ))
+    }
+
+    fn with_background(language: &'static Language, bg: (u8, u8, u8)) -> Self {
+        let alpha = if is_light(bg) { 0.15 } else { 0.25 };
+        Self {
+            language,
+            theme: SyntaxTheme::for_background(Some(bg)),
+            insert_bg: best_color(blend((0, 200, 0), bg, alpha)),
+            delete_bg: best_color(blend((220, 0, 0), bg, alpha)),
+        }
+    }
+
+    fn line_style(&self, kind: DiffLineType) -> Style {
+        match kind {
+            DiffLineType::Insert => Style::default().bg(self.insert_bg),
+            DiffLineType::Delete => Style::default().bg(self.delete_bg),
+            DiffLineType::Context => style_context(),
+        }
+    }
+
+    fn sign_style(&self, kind: DiffLineType) -> Style {
+        match kind {
+            DiffLineType::Insert => self.line_style(kind).patch(style_add()),
+            DiffLineType::Delete => self.line_style(kind).patch(style_del()),
+            DiffLineType::Context => style_context(),
+        }
+    }
+
+    fn content_spans(&self, kind: DiffLineType, text: &str) -> Vec<RtSpan<'static>> {
+        let base = self.line_style(kind);
+        highlight_line_spans(text, self.language, &self.theme)
+            .into_iter()
+            .map(|span| {
+                let style = base.patch(span.style);
+                span.style(style)
+            })
+            .collect()
+    }
+}
+
+/// How a file changed in a multi-file git diff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DiffFileStatus {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+/// A single `@@` hunk from a unified diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DiffHunk {
+    pub(crate) old_start: usize,
+    pub(crate) new_start: usize,
+    pub(crate) lines: Vec<(DiffLineType, String)>,
+}
+
+/// One file section of a git-style unified diff (`diff --git a/.. b/..`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DiffFile {
+    /// Path after the change (the old path for deletions).
+    pub(crate) path: String,
+    /// Original path, only set for renames.
+    pub(crate) old_path: Option<String>,
+    pub(crate) status: DiffFileStatus,
+    pub(crate) binary: bool,
+    pub(crate) hunks: Vec<DiffHunk>,
+}
+
+impl DiffFile {
+    /// Returns the number of added and removed lines.
+    pub(crate) fn line_counts(&self) -> (usize, usize) {
+        self.hunks.iter().flat_map(|h| h.lines.iter()).fold(
+            (0, 0),
+            |(a, d), (kind, _)| match kind {
+                DiffLineType::Insert => (a + 1, d),
+                DiffLineType::Delete => (a, d + 1),
+                DiffLineType::Context => (a, d),
+            },
+        )
+    }
+}
+
+/// Parse the output of `git diff` (or the turn diff emitted by core) into
+/// per-file sections. Text that does not belong to a `diff --git` section is
+/// ignored, so ANSI-free output is required.
+pub(crate) fn parse_git_diff(diff: &str) -> Vec<DiffFile> {
+    let mut files = Vec::new();
+    let mut current: Option<PendingDiffFile> = None;
+    for line in diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            files.extend(current.take().map(PendingDiffFile::finish));
+            current = Some(PendingDiffFile::from_header(rest));
+            continue;
+        }
+        let Some(file) = current.as_mut() else {
+            continue;
+        };
+        if let Some(header) = line.strip_prefix("@@") {
+            let (old_start, new_start) = parse_hunk_header(header);
+            file.hunks.push(DiffHunk {
+                old_start,
+                new_start,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+        if let Some(hunk) = file.hunks.last_mut() {
+            let entry = if let Some(text) = line.strip_prefix('+') {
+                (DiffLineType::Insert, text)
+            } else if let Some(text) = line.strip_prefix('-') {
+                (DiffLineType::Delete, text)
+            } else if let Some(text) = line.strip_prefix(' ') {
+                (DiffLineType::Context, text)
+            } else if line.is_empty() {
+                (DiffLineType::Context, "")
+            } else {
+                // "\ No newline at end of file" and similar markers.
+                continue;
+            };
+            hunk.lines.push((entry.0, entry.1.to_string()));
+            continue;
+        }
+        if line.starts_with("new file mode") {
+            file.status = Some(DiffFileStatus::Added);
+        } else if line.starts_with("deleted file mode") {
+            file.status = Some(DiffFileStatus::Deleted);
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            file.old = Some(path.to_string());
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            file.new = Some(path.to_string());
+        } else if line.starts_with("Binary files ") {
+            file.binary = true;
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            match strip_diff_path(path, "a/") {
+                Some(path) => file.old = Some(path),
+                None => file.status = Some(DiffFileStatus::Added),
+            }
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            match strip_diff_path(path, "b/") {
+                Some(path) => file.new = Some(path),
+                None => file.status = Some(DiffFileStatus::Deleted),
+            }
+        }
+    }
+    files.extend(current.map(PendingDiffFile::finish));
+    files
+}
+
+struct PendingDiffFile {
+    old: Option<String>,
+    new: Option<String>,
+    status: Option<DiffFileStatus>,
+    binary: bool,
+    hunks: Vec<DiffHunk>,
+}
+
+impl PendingDiffFile {
+    fn from_header(rest: &str) -> Self {
+        let (old, new) = match rest.strip_prefix("a/").and_then(|r| r.split_once(" b/")) {
+            Some((old, new)) => (Some(old.to_string()), Some(new.to_string())),
+            None => (None, Some(rest.to_string())),
+        };
+        Self {
+            old,
+            new,
+            status: None,
+            binary: false,
+            hunks: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> DiffFile {
+        let path = match self.status {
+            Some(DiffFileStatus::Deleted) => self.old.clone().or(self.new.clone()),
+            _ => self.new.clone().or(self.old.clone()),
+        }
+        .unwrap_or_default();
+        let status = match self.status {
+            Some(status) => status,
+            None if self.old.as_ref().is_some_and(|old| *old != path) => DiffFileStatus::Renamed,
+            None => DiffFileStatus::Modified,
+        };
+        DiffFile {
+            old_path: (status == DiffFileStatus::Renamed)
+                .then_some(self.old)
+                .flatten(),
+            path,
+            status,
+            binary: self.binary,
+            hunks: self.hunks,
+        }
+    }
+}
+
+/// Strips the `a/`/`b/` prefix from a `---`/`+++` path; `None` for `/dev/null`.
+fn strip_diff_path(path: &str, prefix: &str) -> Option<String> {
+    let path = path.split('\t').next().unwrap_or(path);
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+/// Parses ` -a,b +c,d @@ ...` into the old and new start lines.
+fn parse_hunk_header(header: &str) -> (usize, usize) {
+    let mut old_start = 0;
+    let mut new_start = 0;
+    for part in header.split_whitespace() {
+        let parse_start = |range: &str| {
+            range
+                .split(',')
+                .next()
+                .and_then(|n| n.parse::<usize>().ok())
+                .unwrap_or(0)
+        };
+        if let Some(range) = part.strip_prefix('-') {
+            old_start = parse_start(range);
+        } else if let Some(range) = part.strip_prefix('+') {
+            new_start = parse_start(range);
+        } else if part == "@@" {
+            break;
+        }
+    }
+    (old_start, new_start)
+}
+
+/// Render hunks as two columns: the original file on the left and the updated
+/// file on the right. A run of deletions is paired row by row with the
+/// insertions that directly follow it; long lines are truncated.
+pub(crate) fn render_side_by_side_hunks(
+    hunks: &[DiffHunk],
+    width: usize,
+    syntax: Option<&DiffSyntax>,
+) -> Vec<RtLine<'static>> {
+    let max_line_number = hunks
+        .iter()
+        .map(|h| {
+            let old_len = h
+                .lines
+                .iter()
+                .filter(|(kind, _)| *kind != DiffLineType::Insert)
+                .count();
+            let new_len = h
+                .lines
+                .iter()
+                .filter(|(kind, _)| *kind != DiffLineType::Delete)
+                .count();
+            (h.old_start + old_len).max(h.new_start + new_len)
+        })
+        .max()
+        .unwrap_or(0);
+    let line_number_width = line_number_width(max_line_number);
+    let half = width.saturating_sub(1) / 2;
+
+    let mut out = Vec::new();
+    for (idx, hunk) in hunks.iter().enumerate() {
+        if idx > 0 {
+            let spacer = format!("{:width$} ", "", width = line_number_width);
+            let mut spans = vec![RtSpan::styled(spacer.clone(), style_gutter()), "⋮".dim()];
+            spans.push(" ".repeat(half.saturating_sub(spacer.len() + 1)).into());
+            spans.push("│".dim());
+            spans.push(RtSpan::styled(spacer, style_gutter()));
+            spans.push("⋮".dim());
+            out.push(RtLine::from(spans));
+        }
+        let mut old_ln = hunk.old_start;
+        let mut new_ln = hunk.new_start;
+        let lines = &hunk.lines;
+        let mut i = 0;
+        while i < lines.len() {
+            if lines[i].0 == DiffLineType::Context {
+                let text = lines[i].1.as_str();
+                out.push(side_by_side_row(
+                    Some((old_ln, DiffLineType::Context, text)),
+                    Some((new_ln, DiffLineType::Context, text)),
+                    half,
+                    line_number_width,
+                    syntax,
+                ));
+                old_ln += 1;
+                new_ln += 1;
+                i += 1;
+                continue;
+            }
+            let deletes_end = lines[i..]
+                .iter()
+                .position(|(kind, _)| *kind != DiffLineType::Delete)
+                .map_or(lines.len(), |p| i + p);
+            let inserts_end = lines[deletes_end..]
+                .iter()
+                .position(|(kind, _)| *kind != DiffLineType::Insert)
+                .map_or(lines.len(), |p| deletes_end + p);
+            let deletes = &lines[i..deletes_end];
+            let inserts = &lines[deletes_end..inserts_end];
+            for row in 0..deletes.len().max(inserts.len()) {
+                let left = deletes.get(row).map(|(kind, text)| {
+                    old_ln += 1;
+                    (old_ln - 1, *kind, text.as_str())
+                });
+                let right = inserts.get(row).map(|(kind, text)| {
+                    new_ln += 1;
+                    (new_ln - 1, *kind, text.as_str())
+                });
+                out.push(side_by_side_row(
+                    left,
+                    right,
+                    half,
+                    line_number_width,
+                    syntax,
+                ));
+            }
+            i = inserts_end;
+        }
+    }
+    out
+}
+
+fn side_by_side_row(
+    left: Option<(usize, DiffLineType, &str)>,
+    right: Option<(usize, DiffLineType, &str)>,
+    half: usize,
+    line_number_width: usize,
+    syntax: Option<&DiffSyntax>,
+) -> RtLine<'static> {
+    let mut spans = side_by_side_cell(left, half, line_number_width, syntax);
+    spans.push("│".dim());
+    spans.extend(side_by_side_cell(right, half, line_number_width, syntax));
+    RtLine::from(spans)
+}
+
+/// One half of a side-by-side row, padded to exactly `half` columns.
+fn side_by_side_cell(
+    cell: Option<(usize, DiffLineType, &str)>,
+    half: usize,
+    line_number_width: usize,
+    syntax: Option<&DiffSyntax>,
+) -> Vec<RtSpan<'static>> {
+    let Some((line_number, kind, text)) = cell else {
+        return vec![" ".repeat(half).into()];
+    };
+    let gutter = format!("{line_number:>line_number_width$} ");
+    let (sign_char, line_style) = match kind {
+        DiffLineType::Insert => ('+', style_add()),
+        DiffLineType::Delete => ('-', style_del()),
+        DiffLineType::Context => (' ', style_context()),
+    };
+    let sign_style = syntax.map_or(line_style, |syntax| syntax.sign_style(kind));
+    let available = half.saturating_sub(gutter.len() + 1);
+    let text = text.replace('\t', "    ");
+    let (chunk, rest, used) = take_prefix_by_width(&text, available);
+    let (chunk, used, truncated) = if rest.is_empty() || available == 0 {
+        (chunk, used, false)
+    } else {
+        // Leave room for an ellipsis so truncation is visible.
+        let (chunk, _, used) = take_prefix_by_width(&text, available - 1);
+        (chunk, used + 1, true)
+    };
+    let mut spans = vec![
+        RtSpan::styled(gutter, style_gutter()),
+        RtSpan::styled(sign_char.to_string(), sign_style),
+    ];
+    match syntax {
+        Some(syntax) => spans.extend(slice_spans(
+            &syntax.content_spans(kind, &text),
+            0,
+            chunk.chars().count(),
+        )),
+        None => spans.push(RtSpan::styled(chunk, line_style)),
+    }
+    if truncated {
+        spans.push(RtSpan::styled("…", line_style));
+    }
+    spans.push(" ".repeat(available.saturating_sub(used)).into());
+    spans
+}
+
 fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
@@ -419,6 +930,7 @@ fn style_del() -> Style {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::render::syntax::TokenKind;
     use insta::assert_snapshot;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
@@ -465,8 +977,14 @@ mod tests {
         let long_line = "this is a very long line that should wrap across multiple terminal columns and continue";
 
         // Call the wrapping function directly so we can precisely control the width
-        let lines =
-            push_wrapped_diff_line(1, DiffLineType::Insert, long_line, 80, line_number_width(1));
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            long_line,
+            80,
+            line_number_width(1),
+            None,
+        );
 
         // Render into a small terminal to capture the visual layout
         snapshot_lines("wrap_behavior_insert", lines, 90, 8);
@@ -662,4 +1180,117 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    const TURN_DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,4 +1,4 @@
+ fn main() {
+-    println!(\"hello\");
+-    println!(\"old\");
++    println!(\"hello, world\");
+ }
+@@ -10,2 +10,3 @@ fn other() {
+ a
++b
+ c
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1,2 @@
++first
++second
+diff --git a/gone.txt b/gone.txt
+deleted file mode 100644
+index 4444444..0000000
+--- a/gone.txt
++++ /dev/null
+@@ -1 +0,0 @@
+-bye
+\\ No newline at end of file
+diff --git a/old_name.rs b/new_name.rs
+similarity index 100%
+rename from old_name.rs
+rename to new_name.rs
+";
+
+    #[test]
+    fn parse_git_diff_splits_files_and_hunks() {
+        let files = parse_git_diff(TURN_DIFF);
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| {
+                (
+                    f.path.as_str(),
+                    f.old_path.as_deref(),
+                    f.status,
+                    f.hunks.len(),
+                    f.line_counts(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("src/lib.rs", None, DiffFileStatus::Modified, 2, (2, 2)),
+                ("notes.txt", None, DiffFileStatus::Added, 1, (2, 0)),
+                ("gone.txt", None, DiffFileStatus::Deleted, 1, (0, 1)),
+                (
+                    "new_name.rs",
+                    Some("old_name.rs"),
+                    DiffFileStatus::Renamed,
+                    0,
+                    (0, 0)
+                ),
+            ]
+        );
+        assert_eq!(files[0].hunks[1].old_start, 10);
+        assert_eq!(files[0].hunks[1].new_start, 10);
+        assert_eq!(
+            files[2].hunks[0].lines,
+            vec![(DiffLineType::Delete, "bye".to_string())]
+        );
+    }
+
+    #[test]
+    fn ui_snapshot_side_by_side_pairs_changed_lines() {
+        let files = parse_git_diff(TURN_DIFF);
+        let lines = render_side_by_side_hunks(&files[0].hunks, 60, None);
+        snapshot_lines_text("side_by_side_pairs_changed_lines", &lines);
+    }
+
+    #[test]
+    fn highlighted_diff_line_wraps_without_losing_token_styles() {
+        let language = language_for_path(Path::new("lib.rs")).expect("rust");
+        let syntax = DiffSyntax::with_background(language, (0, 0, 0));
+        let text = "let value = \"a long string literal\";";
+        let lines = push_wrapped_diff_line(7, DiffLineType::Insert, text, 20, 1, Some(&syntax));
+
+        let rows: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            rows,
+            vec!["7 +let value = \"a lo", "   ng string literal", "   \";"]
+        );
+
+        let keyword = &lines[0].spans[2];
+        assert_eq!(keyword.content, "let");
+        assert_eq!(
+            keyword.style,
+            syntax
+                .line_style(DiffLineType::Insert)
+                .patch(syntax.theme.style(TokenKind::Keyword))
+        );
+        // The string token continues on the wrapped rows with the same style.
+        let string_style = syntax
+            .line_style(DiffLineType::Insert)
+            .patch(syntax.theme.style(TokenKind::String));
+        assert_eq!(lines[1].spans[1].style, string_style);
+        assert_eq!(lines[2].spans[1].style, string_style);
+    }
 }
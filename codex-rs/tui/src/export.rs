@@ -0,0 +1,855 @@
+//! Render a conversation as a shareable Markdown, HTML or JSON document.
+//!
+//! Exports reuse the [`HistoryCell`] transcript rendering so a shared session
+//! reads the way it did in the TUI. Live sessions export the app's transcript
+//! cells directly; recorded sessions are first rebuilt into cells from their
+//! rollout file by [`cells_from_rollout`].
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_core::RolloutRecorder;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::FileChange;
+use codex_core::protocol::RolloutItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::WebSearchAction;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::text::Line;
+use serde_json::json;
+
+use crate::exec_cell::CommandOutput;
+use crate::exec_cell::ExecCell;
+use crate::exec_cell::new_active_exec_command;
+use crate::history_cell;
+use crate::history_cell::AgentMessageCell;
+use crate::history_cell::FinalMessageSeparator;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::PatchHistoryCell;
+use crate::history_cell::ReasoningSummaryCell;
+use crate::history_cell::SessionInfoCell;
+use crate::history_cell::UserHistoryCell;
+use crate::markdown::append_markdown;
+
+/// Column width used when rendering cells for an export.
+const EXPORT_WIDTH: u16 = 100;
+
+/// Output format for an exported transcript.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for documents in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+            ExportFormat::Json => "JSON",
+        }
+    }
+}
+
+/// Session details printed at the top of an export.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ExportHeader {
+    pub(crate) session_id: Option<String>,
+    pub(crate) started_at: Option<String>,
+    pub(crate) cwd: Option<PathBuf>,
+}
+
+/// Render the rollout at `path` in `format`.
+pub async fn export_rollout(path: &Path, format: ExportFormat) -> std::io::Result<String> {
+    let history = RolloutRecorder::get_rollout_history(path).await?;
+    let (header, cells) = cells_from_rollout(&history.get_rollout_items());
+    Ok(render_export(&header, &cells, format))
+}
+
+pub(crate) fn render_export(
+    header: &ExportHeader,
+    cells: &[Arc<dyn HistoryCell>],
+    format: ExportFormat,
+) -> String {
+    let blocks = blocks_from_cells(cells);
+    match format {
+        ExportFormat::Markdown => render_markdown(header, &blocks),
+        ExportFormat::Html => render_html(header, &blocks),
+        ExportFormat::Json => render_json(header, &blocks),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BlockKind {
+    User,
+    Assistant,
+    Reasoning,
+    Command,
+    Patch,
+    Note,
+}
+
+impl BlockKind {
+    fn of(cell: &dyn HistoryCell) -> Self {
+        let any = cell.as_any();
+        if any.is::<UserHistoryCell>() {
+            BlockKind::User
+        } else if any.is::<AgentMessageCell>() {
+            BlockKind::Assistant
+        } else if any.is::<ReasoningSummaryCell>() {
+            BlockKind::Reasoning
+        } else if any.is::<ExecCell>() {
+            BlockKind::Command
+        } else if any.is::<PatchHistoryCell>() {
+            BlockKind::Patch
+        } else {
+            BlockKind::Note
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            BlockKind::User => "user",
+            BlockKind::Assistant => "assistant",
+            BlockKind::Reasoning => "reasoning",
+            BlockKind::Command => "command",
+            BlockKind::Patch => "patch",
+            BlockKind::Note => "note",
+        }
+    }
+}
+
+/// One entry in the exported document: a cell plus any stream continuations.
+struct Block {
+    kind: BlockKind,
+    lines: Vec<Line<'static>>,
+    /// The user's message as typed, for user blocks.
+    source: Option<String>,
+}
+
+impl Block {
+    /// Plain text without the two-column bullet gutter used in the TUI.
+    fn text(&self) -> String {
+        if let Some(source) = &self.source {
+            return source.trim().to_string();
+        }
+        let lines: Vec<String> = self.lines.iter().map(plain_text).collect();
+        let lines = match self.kind {
+            BlockKind::Command | BlockKind::Patch => lines,
+            _ => strip_gutter(lines),
+        };
+        lines.join("\n").trim_matches('\n').to_string()
+    }
+}
+
+fn blocks_from_cells(cells: &[Arc<dyn HistoryCell>]) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    for cell in cells {
+        let any = cell.as_any();
+        // The session header box and turn separators only make sense on screen.
+        if any.is::<SessionInfoCell>() || any.is::<FinalMessageSeparator>() {
+            continue;
+        }
+        let lines = cell.transcript_lines(EXPORT_WIDTH);
+        if lines.iter().all(|line| plain_text(line).is_empty()) {
+            continue;
+        }
+        let kind = BlockKind::of(cell.as_ref());
+        if cell.is_stream_continuation()
+            && let Some(last) = blocks.last_mut()
+            && last.kind == kind
+        {
+            last.lines.extend(lines);
+            continue;
+        }
+        let source = any
+            .downcast_ref::<UserHistoryCell>()
+            .map(|cell| cell.message.clone());
+        blocks.push(Block {
+            kind,
+            lines,
+            source,
+        });
+    }
+    blocks
+}
+
+fn plain_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+/// Drop the two-column `• `/`› ` gutter when every line carries it.
+fn strip_gutter(lines: Vec<String>) -> Vec<String> {
+    const GUTTERS: [&str; 3] = ["• ", "› ", "  "];
+    let has_gutter = lines
+        .iter()
+        .all(|line| line.is_empty() || GUTTERS.iter().any(|g| line.starts_with(g)));
+    if !has_gutter {
+        return lines;
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            GUTTERS
+                .iter()
+                .find_map(|g| line.strip_prefix(g))
+                .map(str::to_string)
+                .unwrap_or(line)
+        })
+        .collect()
+}
+
+fn title(header: &ExportHeader) -> String {
+    match &header.session_id {
+        Some(id) => format!("Codex session {id}"),
+        None => "Codex session".to_string(),
+    }
+}
+
+fn render_markdown(header: &ExportHeader, blocks: &[Block]) -> String {
+    let mut out = format!("# {}\n", title(header));
+    if let Some(started_at) = &header.started_at {
+        let _ = write!(out, "\n- Started: {started_at}");
+    }
+    if let Some(cwd) = &header.cwd {
+        let _ = write!(out, "\n- Directory: `{}`", cwd.display());
+    }
+    if header.started_at.is_some() || header.cwd.is_some() {
+        out.push('\n');
+    }
+
+    let mut in_codex_turn = false;
+    for block in blocks {
+        let text = block.text();
+        match block.kind {
+            BlockKind::User => {
+                in_codex_turn = false;
+                let _ = write!(out, "\n## You\n\n{text}\n");
+                continue;
+            }
+            _ if !in_codex_turn => {
+                in_codex_turn = true;
+                out.push_str("\n## Codex\n");
+            }
+            _ => {}
+        }
+        match block.kind {
+            BlockKind::Assistant | BlockKind::Note | BlockKind::User => {
+                let _ = write!(out, "\n{text}\n");
+            }
+            BlockKind::Reasoning => {
+                out.push('\n');
+                for line in text.lines() {
+                    if line.is_empty() {
+                        out.push_str(">\n");
+                    } else {
+                        let _ = writeln!(out, "> {line}");
+                    }
+                }
+            }
+            BlockKind::Command | BlockKind::Patch => {
+                let fence = fence_for(&text);
+                let _ = write!(out, "\n{fence}text\n{text}\n{fence}\n");
+            }
+        }
+    }
+    out
+}
+
+/// A code fence longer than any backtick run inside `text`.
+fn fence_for(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+const HTML_STYLE: &str = "\
+body { background: #1e1e1e; color: #d4d4d4; font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; margin: 2em auto; max-width: 60em; }
+header { color: #9d9d9d; margin-bottom: 2em; }
+h1 { color: #d4d4d4; font-size: 1.2em; }
+section { margin: 0 0 1em; }
+section.user { background: #2a2a2a; }
+pre { margin: 0; white-space: pre-wrap; }
+.b { font-weight: bold; }
+.d { opacity: 0.6; }
+.i { font-style: italic; }
+.u { text-decoration: underline; }
+.s { text-decoration: line-through; }";
+
+fn render_html(header: &ExportHeader, blocks: &[Block]) -> String {
+    let title = escape_html(&title(header));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\n{HTML_STYLE}\n</style>\n</head>\n<body>\n<header>\n<h1>{title}</h1>\n"
+    );
+    if let Some(started_at) = &header.started_at {
+        let _ = writeln!(out, "<div>Started: {}</div>", escape_html(started_at));
+    }
+    if let Some(cwd) = &header.cwd {
+        let _ = writeln!(
+            out,
+            "<div>Directory: <code>{}</code></div>",
+            escape_html(&cwd.display().to_string())
+        );
+    }
+    out.push_str("</header>\n");
+    for block in blocks {
+        let _ = write!(out, "<section class=\"{}\"><pre>", block.kind.as_str());
+        for (i, line) in block.lines.iter().enumerate() {
+            if i > 0 {
+                out.push('\n');
+            }
+            push_html_line(&mut out, line);
+        }
+        out.push_str("</pre></section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn push_html_line(out: &mut String, line: &Line<'_>) {
+    for span in &line.spans {
+        if span.content.is_empty() {
+            continue;
+        }
+        let style = line.style.patch(span.style);
+        let mut classes = Vec::new();
+        for (modifier, class) in [
+            (Modifier::BOLD, "b"),
+            (Modifier::DIM, "d"),
+            (Modifier::ITALIC, "i"),
+            (Modifier::UNDERLINED, "u"),
+            (Modifier::CROSSED_OUT, "s"),
+        ] {
+            if style.add_modifier.contains(modifier) {
+                classes.push(class);
+            }
+        }
+        let mut css = String::new();
+        if let Some(color) = style.fg.and_then(css_color) {
+            let _ = write!(css, "color: {color};");
+        }
+        if let Some(color) = style.bg.and_then(css_color) {
+            let _ = write!(css, "background: {color};");
+        }
+        let content = escape_html(&span.content);
+        if classes.is_empty() && css.is_empty() {
+            out.push_str(&content);
+            continue;
+        }
+        out.push_str("<span");
+        if !classes.is_empty() {
+            let _ = write!(out, " class=\"{}\"", classes.join(" "));
+        }
+        if !css.is_empty() {
+            let _ = write!(out, " style=\"{css}\"");
+        }
+        let _ = write!(out, ">{content}</span>");
+    }
+}
+
+/// Map terminal colors onto a dark palette for HTML output.
+fn css_color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Reset | Color::Indexed(_) => return None,
+        Color::Rgb(r, g, b) => return Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        Color::Black => "#000000",
+        Color::Red => "#f14c4c",
+        Color::Green => "#23d18b",
+        Color::Yellow => "#f5f543",
+        Color::Blue => "#3b8eea",
+        Color::Magenta => "#d670d6",
+        Color::Cyan => "#29b8db",
+        Color::Gray => "#cccccc",
+        Color::DarkGray => "#767676",
+        Color::LightRed => "#ff7b72",
+        Color::LightGreen => "#7ee787",
+        Color::LightYellow => "#f2cc60",
+        Color::LightBlue => "#79c0ff",
+        Color::LightMagenta => "#d2a8ff",
+        Color::LightCyan => "#56d4dd",
+        Color::White => "#ffffff",
+    };
+    Some(hex.to_string())
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn render_json(header: &ExportHeader, blocks: &[Block]) -> String {
+    let entries: Vec<_> = blocks
+        .iter()
+        .map(|block| json!({ "kind": block.kind.as_str(), "text": block.text() }))
+        .collect();
+    let document = json!({
+        "session_id": header.session_id,
+        "started_at": header.started_at,
+        "cwd": header.cwd,
+        "entries": entries,
+    });
+    let mut out = serde_json::to_string_pretty(&document).unwrap_or_default();
+    out.push('\n');
+    out
+}
+
+/// A cell being rebuilt from a rollout. Commands stay open until their output
+/// item arrives.
+enum RolloutCell {
+    Done(Arc<dyn HistoryCell>),
+    Exec(ExecCell),
+}
+
+/// Rebuild transcript cells from recorded rollout items: messages, reasoning
+/// summaries, commands with their output, patches, plans and searches.
+pub(crate) fn cells_from_rollout(
+    items: &[RolloutItem],
+) -> (ExportHeader, Vec<Arc<dyn HistoryCell>>) {
+    let mut header = ExportHeader::default();
+    let mut cwd = PathBuf::new();
+    let mut cells: Vec<RolloutCell> = Vec::new();
+    let mut open_commands: HashMap<String, usize> = HashMap::new();
+
+    let push = |cells: &mut Vec<RolloutCell>, cell: Arc<dyn HistoryCell>| {
+        cells.push(RolloutCell::Done(cell));
+    };
+
+    for item in items {
+        match item {
+            RolloutItem::SessionMeta(line) => {
+                if header.session_id.is_none() {
+                    header.session_id = Some(line.meta.id.to_string());
+                    header.started_at = Some(line.meta.timestamp.clone());
+                    header.cwd = Some(line.meta.cwd.clone());
+                    cwd = line.meta.cwd.clone();
+                }
+            }
+            RolloutItem::TurnContext(context) => cwd = context.cwd.clone(),
+            RolloutItem::Compacted(_) => {
+                push(
+                    &mut cells,
+                    Arc::new(history_cell::new_info_event(
+                        "Context compacted".to_string(),
+                        None,
+                    )),
+                );
+            }
+            RolloutItem::EventMsg(event) => match event {
+                EventMsg::UserMessage(event) if !event.message.trim().is_empty() => {
+                    push(
+                        &mut cells,
+                        Arc::new(history_cell::new_user_prompt(
+                            event.message.trim().to_string(),
+                        )),
+                    );
+                }
+                EventMsg::AgentMessage(event) => {
+                    let mut lines = Vec::new();
+                    append_markdown(&event.message, None, &mut lines);
+                    push(&mut cells, Arc::new(AgentMessageCell::new(lines, true)));
+                }
+                EventMsg::AgentReasoning(event) => {
+                    let cell = history_cell::new_reasoning_summary_block(event.text.clone());
+                    push(&mut cells, Arc::from(cell));
+                }
+                EventMsg::TurnAborted(_) => {
+                    push(
+                        &mut cells,
+                        Arc::new(history_cell::new_error_event(
+                            "Conversation interrupted".to_string(),
+                        )),
+                    );
+                }
+                _ => {}
+            },
+            RolloutItem::ResponseItem(item) => match item {
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => match tool_call_cell(name, arguments, &cwd) {
+                    Some(ToolCall::Command(command)) => {
+                        open_commands.insert(call_id.clone(), cells.len());
+                        cells.push(RolloutCell::Exec(exec_cell(call_id, command)));
+                    }
+                    Some(ToolCall::Cell(cell)) => push(&mut cells, cell),
+                    None => {}
+                },
+                ResponseItem::LocalShellCall {
+                    call_id,
+                    id,
+                    action: LocalShellAction::Exec(action),
+                    ..
+                } => {
+                    let call_id = call_id.clone().or_else(|| id.clone()).unwrap_or_default();
+                    if let Some(cell) = apply_patch_command_cell(&action.command, &cwd) {
+                        push(&mut cells, cell);
+                    } else {
+                        open_commands.insert(call_id.clone(), cells.len());
+                        cells.push(RolloutCell::Exec(exec_cell(
+                            &call_id,
+                            action.command.clone(),
+                        )));
+                    }
+                }
+                ResponseItem::CustomToolCall { name, input, .. } if name == "apply_patch" => {
+                    if let Some(cell) = patch_cell(input, &cwd) {
+                        push(&mut cells, cell);
+                    }
+                }
+                ResponseItem::FunctionCallOutput { call_id, output } => {
+                    if let Some(index) = open_commands.remove(call_id)
+                        && let Some(RolloutCell::Exec(cell)) = cells.get_mut(index)
+                    {
+                        let (output, duration) = parse_command_output(&output.content);
+                        cell.complete_call(call_id, output, duration);
+                    }
+                }
+                ResponseItem::WebSearchCall {
+                    action: WebSearchAction::Search { query: Some(query) },
+                    ..
+                } => {
+                    push(
+                        &mut cells,
+                        Arc::new(history_cell::new_web_search_call(query.clone())),
+                    );
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let cells = cells
+        .into_iter()
+        .map(|cell| match cell {
+            RolloutCell::Done(cell) => cell,
+            RolloutCell::Exec(cell) => Arc::new(cell) as Arc<dyn HistoryCell>,
+        })
+        .collect();
+    (header, cells)
+}
+
+enum ToolCall {
+    Command(Vec<String>),
+    Cell(Arc<dyn HistoryCell>),
+}
+
+fn tool_call_cell(name: &str, arguments: &str, cwd: &Path) -> Option<ToolCall> {
+    let args: serde_json::Value = serde_json::from_str(arguments).ok()?;
+    let string_arg = |key: &str| args.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    match name {
+        "shell" | "container.exec" | "local_shell" => {
+            let command: Vec<String> = serde_json::from_value(args.get("command")?.clone()).ok()?;
+            match apply_patch_command_cell(&command, cwd) {
+                Some(cell) => Some(ToolCall::Cell(cell)),
+                None => Some(ToolCall::Command(command)),
+            }
+        }
+        "shell_command" => Some(ToolCall::Command(bash_lc(string_arg("command")?))),
+        "exec_command" => Some(ToolCall::Command(bash_lc(string_arg("cmd")?))),
+        "apply_patch" => patch_cell(&string_arg("input")?, cwd).map(ToolCall::Cell),
+        "update_plan" => {
+            let update: UpdatePlanArgs = serde_json::from_value(args).ok()?;
+            Some(ToolCall::Cell(Arc::new(history_cell::new_plan_update(
+                update,
+            ))))
+        }
+        "view_image" => Some(ToolCall::Cell(Arc::new(
+            history_cell::new_view_image_tool_call(PathBuf::from(string_arg("path")?), cwd),
+        ))),
+        // Follow-up input to a running command carries no new information.
+        "write_stdin" => None,
+        other => Some(ToolCall::Cell(Arc::new(history_cell::new_info_event(
+            format!("Called {other}"),
+            None,
+        )))),
+    }
+}
+
+fn bash_lc(script: String) -> Vec<String> {
+    vec!["bash".to_string(), "-lc".to_string(), script]
+}
+
+fn exec_cell(call_id: &str, command: Vec<String>) -> ExecCell {
+    new_active_exec_command(
+        call_id.to_string(),
+        command,
+        Vec::new(),
+        ExecCommandSource::Agent,
+        None,
+        false,
+    )
+}
+
+/// `["apply_patch", "<patch>"]` invoked through the shell tool.
+fn apply_patch_command_cell(command: &[String], cwd: &Path) -> Option<Arc<dyn HistoryCell>> {
+    match command {
+        [program, patch] if program == "apply_patch" || program == "applypatch" => {
+            patch_cell(patch, cwd)
+        }
+        _ => None,
+    }
+}
+
+/// Rebuild file changes from an `apply_patch` body. The rollout does not keep
+/// the original files, so update hunks are diffed against the lines the patch
+/// replaced and their line numbers count from the start of the edited region.
+fn patch_cell(patch: &str, cwd: &Path) -> Option<Arc<dyn HistoryCell>> {
+    let args = codex_apply_patch::parse_patch(patch).ok()?;
+    let mut changes = HashMap::new();
+    for hunk in args.hunks {
+        match hunk {
+            codex_apply_patch::Hunk::AddFile { path, contents } => {
+                changes.insert(path, FileChange::Add { content: contents });
+            }
+            codex_apply_patch::Hunk::DeleteFile { path } => {
+                changes.insert(
+                    path,
+                    FileChange::Delete {
+                        content: String::new(),
+                    },
+                );
+            }
+            codex_apply_patch::Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                let mut old = String::new();
+                let mut new = String::new();
+                for chunk in chunks {
+                    for line in chunk.old_lines {
+                        old.push_str(&line);
+                        old.push('\n');
+                    }
+                    for line in chunk.new_lines {
+                        new.push_str(&line);
+                        new.push('\n');
+                    }
+                }
+                let unified_diff = diffy::create_patch(&old, &new).to_string();
+                changes.insert(
+                    path,
+                    FileChange::Update {
+                        unified_diff,
+                        move_path,
+                    },
+                );
+            }
+        }
+    }
+    Some(Arc::new(history_cell::new_patch_event(changes, cwd)))
+}
+
+/// Recover the exit code, wall time and output from a shell tool result.
+/// Handles both the JSON payload of the `shell` tool and the text sections
+/// produced by `shell_command` and `exec_command`.
+fn parse_command_output(content: &str) -> (CommandOutput, Duration) {
+    #[derive(serde::Deserialize)]
+    struct Metadata {
+        exit_code: i32,
+        duration_seconds: f32,
+    }
+    #[derive(serde::Deserialize)]
+    struct Structured {
+        output: String,
+        metadata: Metadata,
+    }
+
+    if let Ok(Structured { output, metadata }) = serde_json::from_str::<Structured>(content) {
+        return (
+            CommandOutput {
+                exit_code: metadata.exit_code,
+                aggregated_output: output.clone(),
+                formatted_output: output,
+            },
+            Duration::from_secs_f32(metadata.duration_seconds.max(0.0)),
+        );
+    }
+
+    let mut exit_code = 0;
+    let mut duration = Duration::ZERO;
+    let mut output = content;
+    let mut rest = content;
+    while let Some((line, tail)) = rest.split_once('\n') {
+        if line == "Output:" {
+            output = tail;
+            break;
+        }
+        if let Some(code) = line
+            .strip_prefix("Exit code: ")
+            .or_else(|| line.strip_prefix("Process exited with code "))
+        {
+            exit_code = code.trim().parse().unwrap_or(exit_code);
+        } else if let Some(secs) = line
+            .strip_prefix("Wall time: ")
+            .and_then(|s| s.strip_suffix(" seconds"))
+        {
+            duration = secs
+                .parse::<f64>()
+                .map(|secs| Duration::from_secs_f64(secs.max(0.0)))
+                .unwrap_or(duration);
+        }
+        rest = tail;
+    }
+    (
+        CommandOutput {
+            exit_code,
+            aggregated_output: output.to_string(),
+            formatted_output: output.to_string(),
+        },
+        duration,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::AgentReasoningEvent;
+    use codex_core::protocol::SessionMeta;
+    use codex_core::protocol::SessionMetaLine;
+    use codex_core::protocol::UserMessageEvent;
+    use codex_protocol::ConversationId;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn sample_rollout() -> Vec<RolloutItem> {
+        let id =
+            ConversationId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8").expect("valid id");
+        vec![
+            RolloutItem::SessionMeta(SessionMetaLine {
+                meta: SessionMeta {
+                    id,
+                    timestamp: "2025-01-02T03:04:05Z".to_string(),
+                    cwd: PathBuf::from("/work/repo"),
+                    ..Default::default()
+                },
+                git: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "Why does `make test` fail?".to_string(),
+                images: None,
+            })),
+            RolloutItem::EventMsg(EventMsg::AgentReasoning(AgentReasoningEvent {
+                text: "**Checking tests**\n\nRunning the suite first.".to_string(),
+            })),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell_command".to_string(),
+                arguments: r#"{"command":"make test"}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "Exit code: 2\nWall time: 1.5 seconds\nOutput:\nmissing <stdio.h>"
+                        .to_string(),
+                    ..Default::default()
+                },
+            }),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-2".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** Update File: Makefile\n@@\n-CC=gcc\n+CC=clang\n*** End Patch"
+                    .to_string(),
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: "The build now uses **clang**.".to_string(),
+            })),
+        ]
+    }
+
+    fn export(format: ExportFormat) -> String {
+        let (header, cells) = cells_from_rollout(&sample_rollout());
+        render_export(&header, &cells, format)
+    }
+
+    #[test]
+    fn markdown_export_snapshot() {
+        insta::assert_snapshot!(export(ExportFormat::Markdown));
+    }
+
+    #[test]
+    fn html_export_escapes_and_styles_output() {
+        let html = export(ExportFormat::Html);
+        assert!(html.contains("missing &lt;stdio.h&gt;"), "{html}");
+        assert!(html.contains("<section class=\"patch\">"), "{html}");
+        assert!(html.contains(r#"<span class="b">clang</span>"#), "{html}");
+    }
+
+    #[test]
+    fn json_export_lists_entries_in_order() {
+        let value: serde_json::Value =
+            serde_json::from_str(&export(ExportFormat::Json)).expect("valid json");
+        let kinds: Vec<&str> = value["entries"]
+            .as_array()
+            .expect("entries")
+            .iter()
+            .filter_map(|entry| entry["kind"].as_str())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["user", "reasoning", "command", "patch", "assistant"]
+        );
+        assert_eq!(value["entries"][0]["text"], "Why does `make test` fail?");
+        assert_eq!(value["session_id"], "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    }
+
+    #[test]
+    fn parses_structured_and_text_command_output() {
+        let (output, duration) = parse_command_output(
+            r#"{"output":"ok\n","metadata":{"exit_code":0,"duration_seconds":0.5}}"#,
+        );
+        assert_eq!(output.exit_code, 0);
+        assert_eq!(output.formatted_output, "ok\n");
+        assert_eq!(duration, Duration::from_millis(500));
+
+        let (output, _) = parse_command_output(
+            "Chunk ID: 1\nWall time: 0.2500 seconds\nProcess exited with code 1\nOutput:\nboom",
+        );
+        assert_eq!(output.exit_code, 1);
+        assert_eq!(output.formatted_output, "boom");
+    }
+
+    #[test]
+    fn fences_outgrow_backticks_in_the_text() {
+        assert_eq!(fence_for("plain"), "```");
+        assert_eq!(fence_for("has ```` inside"), "`````");
+    }
+}
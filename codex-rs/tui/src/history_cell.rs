@@ -7,6 +7,8 @@ use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::inline_image::ImageSource;
+use crate::inline_image::InlineImage;
 use crate::live_wrap::take_prefix_by_width;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
@@ -31,12 +33,12 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::SandboxDenial;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
-use image::DynamicImage;
 use image::ImageReader;
 use mcp_types::EmbeddedResourceResource;
 use mcp_types::Resource;
@@ -100,6 +102,12 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn is_stream_continuation(&self) -> bool {
         false
     }
+
+    /// Images to draw over blank rows of [`HistoryCell::display_lines`] at
+    /// the same `width`. Empty unless the terminal can show images.
+    fn inline_images(&self, _width: u16) -> Vec<InlineImage> {
+        Vec::new()
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -676,6 +684,18 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedHunks { .. } => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved part".bold(),
+                    " of the request to run ".into(),
+                    snippet,
+                ],
+            )
+        }
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -728,13 +748,39 @@ impl HistoryCell for PatchHistoryCell {
     }
 }
 
+/// Text followed by an image that is drawn inline when the terminal supports
+/// it. The transcript overlay and exports only see the text.
 #[derive(Debug)]
-struct CompletedMcpToolCallWithImageOutput {
-    _image: DynamicImage,
+pub(crate) struct ImageHistoryCell {
+    lines: Vec<Line<'static>>,
+    image: Option<ImageSource>,
+    /// Columns to indent the image by.
+    indent: u16,
 }
-impl HistoryCell for CompletedMcpToolCallWithImageOutput {
-    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
-        vec!["tool result (image output)".into()]
+
+impl ImageHistoryCell {
+    fn placement(&self, width: u16) -> Option<InlineImage> {
+        self.image
+            .as_ref()?
+            .place(self.lines.len(), self.indent, width)
+    }
+}
+
+impl HistoryCell for ImageHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines = self.lines.clone();
+        if let Some(image) = self.placement(width) {
+            lines.extend((0..image.rows).map(|_| Line::from("")));
+        }
+        lines
+    }
+
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn inline_images(&self, width: u16) -> Vec<InlineImage> {
+        self.placement(width).into_iter().collect()
     }
 }
 
@@ -1271,7 +1317,7 @@ pub(crate) fn new_web_search_call(query: String) -> PrefixedWrappedHistoryCell {
 /// TODO(rgwood-dd): Handle images properly even if they're not the first result.
 fn try_new_completed_mcp_tool_call_with_image_output(
     result: &Result<mcp_types::CallToolResult, String>,
-) -> Option<CompletedMcpToolCallWithImageOutput> {
+) -> Option<ImageHistoryCell> {
     match result {
         Ok(mcp_types::CallToolResult { content, .. }) => {
             if let Some(mcp_types::ContentBlock::ImageContent(image)) = content.first() {
@@ -1298,7 +1344,11 @@ fn try_new_completed_mcp_tool_call_with_image_output(
                     }
                 };
 
-                Some(CompletedMcpToolCallWithImageOutput { _image: image })
+                Some(ImageHistoryCell {
+                    lines: vec!["tool result (image output)".into()],
+                    image: ImageSource::from_image(image, 2),
+                    indent: 2,
+                })
             } else {
                 None
             }
@@ -1526,6 +1576,22 @@ pub(crate) fn new_mcp_tools_output(
 
     PlainHistoryCell { lines }
 }
+/// Explains which sandbox restrictions a failed command most likely ran into.
+#[allow(clippy::disallowed_methods)]
+pub(crate) fn new_sandbox_denial(denials: &[SandboxDenial]) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "⚠ ".yellow(),
+            "The sandbox likely blocked this command".yellow(),
+        ]
+        .into(),
+    ];
+    for denial in denials {
+        lines.push(vec!["  └ ".dim(), denial.evidence.clone().dim()].into());
+    }
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
     let mut line = vec!["• ".dim(), message.into()];
     if let Some(hint) = hint {
@@ -1649,7 +1715,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
+pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> ImageHistoryCell {
     let display_path = display_path_for(&path, cwd);
 
     let lines: Vec<Line<'static>> = vec![
@@ -1657,7 +1723,21 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
         vec!["  └ ".dim(), display_path.dim()].into(),
     ];
 
-    PlainHistoryCell { lines }
+    ImageHistoryCell {
+        lines,
+        image: ImageSource::from_path(&cwd.join(path), 4),
+        indent: 4,
+    }
+}
+
+/// An image attached to a prompt, drawn below it. `None` when images cannot
+/// be shown inline; the prompt text already names the attachment.
+pub(crate) fn new_image_attachment(path: &Path) -> Option<ImageHistoryCell> {
+    Some(ImageHistoryCell {
+        lines: Vec::new(),
+        image: Some(ImageSource::from_path(path, 2)?),
+        indent: 2,
+    })
 }
 
 pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<dyn HistoryCell> {
@@ -0,0 +1,483 @@
+//! Inline images in the transcript via terminal graphics protocols.
+//!
+//! History is written straight into the terminal's scrollback, so a cell that
+//! shows an image leaves blank rows in its display lines and reports where
+//! they are through [`HistoryCell::inline_images`]. The escape sequence for
+//! the protocol chosen at startup is built in the background when the cell is
+//! created; once the rows are on screen, `insert_history` moves the cursor
+//! over them and writes it out. Without a protocol, cells reserve no rows and
+//! show only their text.
+//!
+//! [`HistoryCell::inline_images`]: crate::history_cell::HistoryCell::inline_images
+
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use base64::Engine;
+use codex_core::config::types::InlineImages;
+use codex_core::terminal::TerminalInfo;
+use codex_core::terminal::TerminalName;
+use image::DynamicImage;
+use image::ImageFormat;
+use image::imageops::FilterType;
+
+/// Images never take more than this many rows of the transcript.
+const MAX_ROWS: u16 = 20;
+
+/// Cell size in pixels assumed when the terminal does not report one.
+const FALLBACK_CELL_SIZE: (u16, u16) = (10, 20);
+
+/// Largest base64 payload the kitty protocol accepts in one escape.
+const KITTY_CHUNK: usize = 4096;
+
+static PROTOCOL: OnceLock<Option<ImageProtocol>> = OnceLock::new();
+
+/// Terminal graphics protocols Codex can draw with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ImageProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+/// Resolve `tui.inline_images` against the current terminal and install the
+/// result for the rest of the process. Later calls are ignored.
+pub(crate) fn install(setting: InlineImages) {
+    let protocol = protocol_for(setting, &codex_core::terminal::terminal_info());
+    let _ = PROTOCOL.set(protocol);
+}
+
+/// The protocol images are drawn with, or `None` (the default, and always in
+/// tests) to show text only.
+pub(crate) fn protocol() -> Option<ImageProtocol> {
+    PROTOCOL.get().copied().flatten()
+}
+
+fn protocol_for(setting: InlineImages, terminal: &TerminalInfo) -> Option<ImageProtocol> {
+    match setting {
+        InlineImages::Off => None,
+        InlineImages::Kitty => Some(ImageProtocol::Kitty),
+        InlineImages::Iterm2 => Some(ImageProtocol::Iterm2),
+        InlineImages::Sixel => Some(ImageProtocol::Sixel),
+        InlineImages::Auto => {
+            // Multiplexers either swallow graphics escapes or leave the image
+            // behind when the pane scrolls.
+            let term = terminal.term.as_deref().unwrap_or_default();
+            if terminal.multiplexer.is_some() || term.starts_with("screen") {
+                return None;
+            }
+            match terminal.name {
+                TerminalName::Kitty | TerminalName::Ghostty => Some(ImageProtocol::Kitty),
+                TerminalName::Iterm2 | TerminalName::WezTerm => Some(ImageProtocol::Iterm2),
+                TerminalName::Konsole | TerminalName::WindowsTerminal => Some(ImageProtocol::Sixel),
+                _ if term.starts_with("foot") || term.starts_with("mlterm") => {
+                    Some(ImageProtocol::Sixel)
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Size of one terminal cell in pixels.
+fn cell_size() -> (u16, u16) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => (
+            (size.width / size.columns).max(1),
+            (size.height / size.rows).max(1),
+        ),
+        _ => FALLBACK_CELL_SIZE,
+    }
+}
+
+/// Where an image's pixels come from.
+enum Pixels {
+    File(PathBuf),
+    Decoded(DynamicImage),
+}
+
+impl Pixels {
+    fn decode(self) -> image::ImageResult<DynamicImage> {
+        match self {
+            Self::File(path) => image::open(path),
+            Self::Decoded(image) => Ok(image),
+        }
+    }
+}
+
+/// An image a history cell shows, sized for the terminal as it was when the
+/// cell was created. Decoding, scaling and encoding happen once, on a
+/// background thread, so drawing the cell (again on every transcript replay)
+/// only writes out the finished escape sequence.
+#[derive(Clone, Debug)]
+pub(crate) struct ImageSource {
+    cols: u16,
+    rows: u16,
+    /// Filled by the background thread; `None` inside if the image could not
+    /// be drawn.
+    escape: Arc<OnceLock<Option<String>>>,
+}
+
+impl ImageSource {
+    /// The image at `path`, to be indented by `col` columns. `None` when
+    /// images are off, the file is missing or not an image, or there is no
+    /// room for it.
+    pub(crate) fn from_path(path: &Path, col: u16) -> Option<Self> {
+        protocol()?;
+        let dimensions = image::image_dimensions(path).ok()?;
+        Self::prepare(Pixels::File(path.to_path_buf()), dimensions, col)
+    }
+
+    pub(crate) fn from_image(image: DynamicImage, col: u16) -> Option<Self> {
+        let dimensions = (image.width(), image.height());
+        Self::prepare(Pixels::Decoded(image), dimensions, col)
+    }
+
+    fn prepare(pixels: Pixels, dimensions: (u32, u32), col: u16) -> Option<Self> {
+        let protocol = protocol()?;
+        let width = crossterm::terminal::size().map_or(80, |(width, _)| width);
+        let cell = cell_size();
+        let (cols, rows) = fit(dimensions, cell, width.saturating_sub(col + 1), MAX_ROWS)?;
+        let escape = Arc::new(OnceLock::new());
+        let slot = Arc::clone(&escape);
+        std::thread::spawn(move || {
+            let _ = slot.set(build_escape(protocol, pixels, cell, cols, rows));
+        });
+        Some(Self { cols, rows, escape })
+    }
+
+    /// Place the image at display line `line`, indented by `col` columns, in a
+    /// cell rendered `width` columns wide. `None` when the terminal has become
+    /// too narrow for the size the image was prepared at.
+    pub(crate) fn place(&self, line: usize, col: u16, width: u16) -> Option<InlineImage> {
+        (self.cols <= width.saturating_sub(col + 1)).then(|| InlineImage {
+            escape: Arc::clone(&self.escape),
+            line,
+            col,
+            cols: self.cols,
+            rows: self.rows,
+        })
+    }
+}
+
+/// Decode `pixels`, scale them to `cols` by `rows` cells and encode them for
+/// `protocol`.
+fn build_escape(
+    protocol: ImageProtocol,
+    pixels: Pixels,
+    cell: (u16, u16),
+    cols: u16,
+    rows: u16,
+) -> Option<String> {
+    let decoded = match pixels.decode() {
+        Ok(image) => image,
+        Err(err) => {
+            tracing::warn!("failed to decode inline image: {err}");
+            return None;
+        }
+    };
+    let max_width = u32::from(cols) * u32::from(cell.0);
+    let max_height = u32::from(rows) * u32::from(cell.1);
+    let image = if decoded.width() > max_width || decoded.height() > max_height {
+        decoded.resize(max_width, max_height, FilterType::Triangle)
+    } else {
+        decoded
+    };
+    match protocol {
+        ImageProtocol::Kitty => Some(kitty_escape(&encode_png(&image)?, cols, rows)),
+        ImageProtocol::Iterm2 => Some(iterm2_escape(&encode_png(&image)?, cols, rows)),
+        ImageProtocol::Sixel => Some(sixel_escape(&image)),
+    }
+}
+
+/// Columns and rows that show an image of `pixels` without upscaling it and
+/// without exceeding `max_cols` by `max_rows`.
+fn fit(pixels: (u32, u32), cell: (u16, u16), max_cols: u16, max_rows: u16) -> Option<(u16, u16)> {
+    let (width, height) = pixels;
+    if width == 0 || height == 0 || max_cols == 0 || max_rows == 0 {
+        return None;
+    }
+    let natural_cols = f64::from(width) / f64::from(cell.0);
+    let natural_rows = f64::from(height) / f64::from(cell.1);
+    let scale = 1f64
+        .min(f64::from(max_cols) / natural_cols)
+        .min(f64::from(max_rows) / natural_rows);
+    let cols = (natural_cols * scale)
+        .ceil()
+        .clamp(1.0, f64::from(max_cols)) as u16;
+    let rows = (natural_rows * scale)
+        .ceil()
+        .clamp(1.0, f64::from(max_rows)) as u16;
+    Some((cols, rows))
+}
+
+/// An image placed over blank rows of a history cell's display lines.
+#[derive(Clone, Debug)]
+pub(crate) struct InlineImage {
+    escape: Arc<OnceLock<Option<String>>>,
+    /// Index of the first reserved row among the display lines.
+    pub line: usize,
+    /// Column the image starts at.
+    pub col: u16,
+    pub cols: u16,
+    pub rows: u16,
+}
+
+impl InlineImage {
+    /// The same placement with `line` counted from `offset` lines earlier.
+    pub(crate) fn offset(mut self, offset: usize) -> Self {
+        self.line += offset;
+        self
+    }
+
+    /// Whether the background thread has finished with the image. History
+    /// that shows it is held back until then, so the image lands on the rows
+    /// reserved for it.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.escape.get().is_some()
+    }
+
+    /// The escape sequence that draws this image at the cursor, or `None` if
+    /// it is not ready or the image could not be read.
+    pub(crate) fn escape(&self) -> Option<&str> {
+        self.escape.get()?.as_deref()
+    }
+}
+
+fn encode_png(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut png = Vec::new();
+    match image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png) {
+        Ok(()) => Some(png),
+        Err(err) => {
+            tracing::warn!("failed to encode inline image: {err}");
+            None
+        }
+    }
+}
+
+/// Transmit and display a PNG in one go (`a=T`), scaled to `cols` by `rows`
+/// cells. `C=1` leaves the cursor in place and `q=2` silences replies that
+/// would otherwise arrive as input.
+fn kitty_escape(png: &[u8], cols: u16, rows: u16) -> String {
+    let payload = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = payload.as_bytes().chunks(KITTY_CHUNK).collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        let chunk = String::from_utf8_lossy(chunk);
+        if i == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Ga=T,f=100,c={cols},r={rows},C=1,q=2,m={more};{chunk}\x1b\\"
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+    out
+}
+
+/// iTerm2's inline file transfer, also understood by WezTerm.
+fn iterm2_escape(png: &[u8], cols: u16, rows: u16) -> String {
+    let payload = base64::engine::general_purpose::STANDARD.encode(png);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{payload}\x07",
+        png.len()
+    )
+}
+
+/// Encode `image` as sixels using a fixed 6×6×6 color cube. Mostly transparent
+/// pixels are left unpainted.
+fn sixel_escape(image: &DynamicImage) -> String {
+    let image = image.to_rgba8();
+    let (width, height) = (image.width() as usize, image.height() as usize);
+    let level = |v: u8| (u16::from(v) * 5 + 127) / 255;
+    let colors: Vec<Option<u16>> = image
+        .pixels()
+        .map(|p| (p[3] >= 128).then(|| level(p[0]) * 36 + level(p[1]) * 6 + level(p[2])))
+        .collect();
+
+    // P2=1 keeps unpainted pixels transparent.
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for i in 0..216u16 {
+        let (r, g, b) = (i / 36, i / 6 % 6, i % 6);
+        let _ = write!(out, "#{i};2;{};{};{}", r * 20, g * 20, b * 20);
+    }
+    for top in (0..height).step_by(6) {
+        let band = top..(top + 6).min(height);
+        let mut used: Vec<u16> = band
+            .clone()
+            .flat_map(|y| colors[y * width..(y + 1) * width].iter().flatten().copied())
+            .collect();
+        used.sort_unstable();
+        used.dedup();
+        for (n, color) in used.into_iter().enumerate() {
+            if n > 0 {
+                // Carriage return: overlay the next color on the same band.
+                out.push('$');
+            }
+            let _ = write!(out, "#{color}");
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = band
+                    .clone()
+                    .enumerate()
+                    .filter(|(_, y)| colors[y * width + x] == Some(color))
+                    .fold(0u8, |bits, (dy, _)| bits | (1 << dy));
+                let sixel = char::from(63 + bits);
+                run = match run {
+                    Some((c, len)) if c == sixel => Some((c, len + 1)),
+                    Some((c, len)) => {
+                        push_sixel_run(&mut out, c, len);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((c, len)) = run {
+                push_sixel_run(&mut out, c, len);
+            }
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, sixel: char, len: usize) {
+    if len > 3 {
+        let _ = write!(out, "!{len}{sixel}");
+    } else {
+        out.extend(std::iter::repeat_n(sixel, len));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::terminal::Multiplexer;
+    use image::Rgba;
+    use image::RgbaImage;
+    use pretty_assertions::assert_eq;
+
+    fn terminal(name: TerminalName, term: Option<&str>) -> TerminalInfo {
+        TerminalInfo {
+            name,
+            term_program: None,
+            version: None,
+            term: term.map(str::to_string),
+            multiplexer: None,
+        }
+    }
+
+    #[test]
+    fn auto_detects_protocol_from_terminal() {
+        let auto = |info: TerminalInfo| protocol_for(InlineImages::Auto, &info);
+        assert_eq!(
+            auto(terminal(TerminalName::Kitty, None)),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            auto(terminal(TerminalName::Ghostty, None)),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            auto(terminal(TerminalName::Iterm2, None)),
+            Some(ImageProtocol::Iterm2)
+        );
+        assert_eq!(
+            auto(terminal(TerminalName::Unknown, Some("foot"))),
+            Some(ImageProtocol::Sixel)
+        );
+        assert_eq!(auto(terminal(TerminalName::AppleTerminal, None)), None);
+        assert_eq!(
+            auto(terminal(TerminalName::Unknown, Some("xterm-256color"))),
+            None
+        );
+    }
+
+    #[test]
+    fn auto_skips_multiplexers_but_explicit_settings_win() {
+        let mut info = terminal(TerminalName::Kitty, None);
+        info.multiplexer = Some(Multiplexer::Tmux { version: None });
+        assert_eq!(protocol_for(InlineImages::Auto, &info), None);
+        assert_eq!(
+            protocol_for(InlineImages::Kitty, &info),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            protocol_for(InlineImages::Off, &terminal(TerminalName::Kitty, None)),
+            None
+        );
+    }
+
+    #[test]
+    fn fit_keeps_aspect_ratio_within_bounds() {
+        // 1000×500 px at 10×20 px cells is 100×25 cells; width is the limit.
+        assert_eq!(fit((1000, 500), (10, 20), 50, 20), Some((50, 13)));
+        // A tall image is limited by rows instead.
+        assert_eq!(fit((200, 2000), (10, 20), 80, 20), Some((4, 20)));
+        // Small images are never scaled up.
+        assert_eq!(fit((30, 40), (10, 20), 80, 20), Some((3, 2)));
+        assert_eq!(fit((30, 40), (10, 20), 0, 20), None);
+    }
+
+    #[test]
+    fn placements_share_the_prepared_escape() {
+        let source = ImageSource {
+            cols: 10,
+            rows: 4,
+            escape: Arc::new(OnceLock::new()),
+        };
+        let image = source.place(2, 2, 40).expect("image fits");
+        assert!(!image.is_ready());
+        assert_eq!(image.escape(), None);
+
+        let _ = source.escape.set(Some("\x1b_Gq=2;\x1b\\".to_string()));
+        assert!(image.is_ready());
+        assert_eq!(image.escape(), Some("\x1b_Gq=2;\x1b\\"));
+        let again = source.place(0, 2, 40).expect("image fits");
+        assert!(Arc::ptr_eq(&again.escape, &image.escape));
+        // Narrower than the size the image was prepared at.
+        assert!(source.place(2, 2, 12).is_none());
+    }
+
+    #[test]
+    fn kitty_escape_is_chunked() {
+        let png = vec![0u8; 4000];
+        let escape = kitty_escape(&png, 12, 6);
+        let parts: Vec<&str> = escape.split("\x1b\\").filter(|s| !s.is_empty()).collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("\x1b_Ga=T,f=100,c=12,r=6,C=1,q=2,m=1;"));
+        assert!(parts[1].starts_with("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn iterm2_escape_declares_size_in_cells() {
+        let escape = iterm2_escape(b"png", 12, 6);
+        assert_eq!(
+            escape,
+            "\x1b]1337;File=inline=1;size=3;width=12;height=6;preserveAspectRatio=1:cG5n\x07"
+        );
+    }
+
+    #[test]
+    fn sixel_escape_paints_each_color_per_band() {
+        let mut image = RgbaImage::new(2, 2);
+        image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(1, 0, Rgba([255, 0, 0, 255]));
+        image.put_pixel(0, 1, Rgba([0, 0, 255, 255]));
+        let escape = sixel_escape(&DynamicImage::ImageRgba8(image));
+        // Red (register 180) fills the top row, blue (5) the bottom-left
+        // pixel, and the transparent pixel is left alone.
+        let body = escape
+            .rsplit("#215;2;100;100;100")
+            .next()
+            .unwrap_or_default();
+        assert_eq!(body, "#5A?$#180@@-\x1b\\");
+    }
+}
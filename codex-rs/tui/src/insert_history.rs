@@ -2,7 +2,8 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
-use crate::wrapping::word_wrap_lines_borrowed;
+use crate::inline_image::InlineImage;
+use crate::wrapping::word_wrap_line;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
 use crossterm::queue;
@@ -28,6 +29,19 @@ pub fn insert_history_lines<B>(
     terminal: &mut crate::custom_terminal::Terminal<B>,
     lines: Vec<Line>,
 ) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    insert_history_lines_with_images(terminal, lines, &[])
+}
+
+/// Insert `lines` above the viewport, then draw `images` over the rows they
+/// reserve. Images whose rows already scrolled off the screen are skipped.
+pub(crate) fn insert_history_lines_with_images<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    lines: Vec<Line>,
+    images: &[InlineImage],
+) -> io::Result<()>
 where
     B: Backend + Write,
 {
@@ -40,7 +54,13 @@ where
 
     // Pre-wrap lines using word-aware wrapping so terminal scrollback sees the same
     // formatting as the TUI. This avoids character-level hard wrapping by the terminal.
-    let wrapped = word_wrap_lines_borrowed(&lines, area.width.max(1) as usize);
+    // Lines are wrapped one at a time so image positions can be mapped to wrapped rows.
+    let mut wrapped = Vec::new();
+    let mut line_starts = Vec::with_capacity(lines.len());
+    for line in &lines {
+        line_starts.push(wrapped.len());
+        wrapped.extend(word_wrap_line(line, area.width.max(1) as usize));
+    }
     let wrapped_lines = wrapped.len() as u16;
     let cursor_top = if area.bottom() < screen_size.height {
         // If the viewport is not at the bottom of the screen, scroll it down to make room.
@@ -92,6 +112,9 @@ where
     // fetch/restore the cursor position. insert_history_lines should be cursor-position-neutral :)
     queue!(writer, MoveTo(0, cursor_top))?;
 
+    let last_row = cursor_top
+        .saturating_add(wrapped_lines)
+        .min(area.top().saturating_sub(1));
     for line in wrapped {
         queue!(writer, Print("\r\n"))?;
         queue!(
@@ -123,6 +146,22 @@ where
 
     queue!(writer, ResetScrollRegion)?;
 
+    if area.top() > 0 {
+        for image in images {
+            let Some(&start) = line_starts.get(image.line) else {
+                continue;
+            };
+            // Count rows up from the last line written.
+            let from_bottom = usize::from(wrapped_lines).saturating_sub(start + 1);
+            let Some(row) = usize::from(last_row).checked_sub(from_bottom) else {
+                continue;
+            };
+            if let Some(escape) = image.escape() {
+                queue!(writer, MoveTo(image.col, row as u16), Print(escape))?;
+            }
+        }
+    }
+
     // Restore the cursor position to where it was before we started.
     queue!(writer, MoveTo(last_cursor_pos.x, last_cursor_pos.y))?;
 
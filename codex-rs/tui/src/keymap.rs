@@ -0,0 +1,310 @@
+//! User-configurable key bindings from the `[tui.keymap]` config table.
+//!
+//! The keymap is resolved once at startup and installed process-wide so that
+//! key handlers and footer hints deep inside the widget tree can consult it
+//! without threading it through every constructor. Until [`install`] is
+//! called (e.g. in tests) the default bindings are used.
+
+use std::sync::LazyLock;
+use std::sync::OnceLock;
+
+use codex_core::config::types::KeyChords;
+use codex_core::config::types::TuiKeymap;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+
+static INSTALLED: OnceLock<Keymap> = OnceLock::new();
+static DEFAULT: LazyLock<Keymap> = LazyLock::new(Keymap::default);
+
+/// Install the keymap used for the rest of the process. Later calls are
+/// ignored.
+pub(crate) fn install(keymap: Keymap) {
+    let _ = INSTALLED.set(keymap);
+}
+
+/// The active keymap: the installed one, or the defaults.
+pub(crate) fn current() -> &'static Keymap {
+    INSTALLED.get().unwrap_or(&DEFAULT)
+}
+
+/// The chords bound to a single action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct KeyBindings(Vec<KeyBinding>);
+
+impl KeyBindings {
+    pub(crate) fn new(bindings: Vec<KeyBinding>) -> Self {
+        Self(bindings)
+    }
+
+    /// True if `event` is a press (or repeat) of any bound chord. Shifted
+    /// letters are compared by their lowercase form so `ctrl+shift+v`
+    /// matches regardless of how the terminal reports the character.
+    pub(crate) fn is_press(&self, event: KeyEvent) -> bool {
+        let event = normalize_shifted_char(event);
+        self.0.iter().any(|binding| binding.is_press(event))
+    }
+
+    /// The chord shown in hints, if the action is bound at all.
+    pub(crate) fn primary(&self) -> Option<KeyBinding> {
+        self.0.first().copied()
+    }
+
+    pub(crate) fn as_slice(&self) -> &[KeyBinding] {
+        &self.0
+    }
+}
+
+fn normalize_shifted_char(mut event: KeyEvent) -> KeyEvent {
+    if let KeyCode::Char(c) = event.code
+        && c.is_ascii_uppercase()
+        && event.modifiers.contains(KeyModifiers::SHIFT)
+    {
+        event.code = KeyCode::Char(c.to_ascii_lowercase());
+    }
+    event
+}
+
+/// Resolved key bindings for every configurable TUI action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Keymap {
+    pub(crate) transcript: KeyBindings,
+    pub(crate) external_editor: KeyBindings,
+    pub(crate) edit_previous: KeyBindings,
+    pub(crate) newline: KeyBindings,
+    pub(crate) paste_image: KeyBindings,
+    pub(crate) pager_close: KeyBindings,
+    pub(crate) next_tab: KeyBindings,
+    pub(crate) previous_tab: KeyBindings,
+    pub(crate) queue: KeyBindings,
+    pub(crate) command_palette: KeyBindings,
+    /// Whether `paste_image` was set explicitly; the footer only swaps in the
+    /// WSL-friendly `ctrl+alt+v` hint for the default bindings.
+    pub(crate) paste_image_customized: bool,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            transcript: KeyBindings::new(vec![key_hint::ctrl(KeyCode::Char('t'))]),
+            external_editor: KeyBindings::new(vec![key_hint::ctrl(KeyCode::Char('g'))]),
+            edit_previous: KeyBindings::new(vec![key_hint::plain(KeyCode::Esc)]),
+            newline: KeyBindings::new(vec![key_hint::ctrl(KeyCode::Char('j'))]),
+            paste_image: KeyBindings::new(vec![
+                key_hint::ctrl(KeyCode::Char('v')),
+                key_hint::alt(KeyCode::Char('v')),
+                key_hint::ctrl_alt(KeyCode::Char('v')),
+            ]),
+            pager_close: KeyBindings::new(vec![key_hint::plain(KeyCode::Char('q'))]),
+            next_tab: KeyBindings::new(vec![key_hint::alt(KeyCode::Char('.'))]),
+            previous_tab: KeyBindings::new(vec![key_hint::alt(KeyCode::Char(','))]),
+            queue: KeyBindings::new(vec![key_hint::alt(KeyCode::Char('q'))]),
+            command_palette: KeyBindings::new(vec![key_hint::ctrl(KeyCode::Char('k'))]),
+            paste_image_customized: false,
+        }
+    }
+}
+
+impl Keymap {
+    /// Resolve `[tui.keymap]` on top of the defaults. Errors name the
+    /// offending action and chord.
+    pub(crate) fn from_config(config: &TuiKeymap) -> Result<Self, String> {
+        let mut keymap = Self::default();
+        let TuiKeymap {
+            transcript,
+            external_editor,
+            edit_previous,
+            newline,
+            paste_image,
+            pager_close,
+            next_tab,
+            previous_tab,
+            queue,
+            command_palette,
+        } = config;
+        override_action(&mut keymap.transcript, "transcript", transcript)?;
+        override_action(
+            &mut keymap.external_editor,
+            "external_editor",
+            external_editor,
+        )?;
+        override_action(&mut keymap.edit_previous, "edit_previous", edit_previous)?;
+        override_action(&mut keymap.newline, "newline", newline)?;
+        override_action(&mut keymap.paste_image, "paste_image", paste_image)?;
+        override_action(&mut keymap.pager_close, "pager_close", pager_close)?;
+        override_action(&mut keymap.next_tab, "next_tab", next_tab)?;
+        override_action(&mut keymap.previous_tab, "previous_tab", previous_tab)?;
+        override_action(&mut keymap.queue, "queue", queue)?;
+        override_action(
+            &mut keymap.command_palette,
+            "command_palette",
+            command_palette,
+        )?;
+        keymap.paste_image_customized = paste_image.is_some();
+        Ok(keymap)
+    }
+}
+
+fn override_action(
+    bindings: &mut KeyBindings,
+    action: &str,
+    chords: &Option<KeyChords>,
+) -> Result<(), String> {
+    let Some(chords) = chords else {
+        return Ok(());
+    };
+    let parsed = chords
+        .chords()
+        .into_iter()
+        .map(|chord| {
+            parse_chord(chord).map_err(|err| format!("tui.keymap.{action}: {err} in `{chord}`"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    *bindings = KeyBindings::new(parsed);
+    Ok(())
+}
+
+/// Parse a chord such as `ctrl+t`, `alt+enter`, `shift+tab` or `f5`.
+pub(crate) fn parse_chord(chord: &str) -> Result<KeyBinding, String> {
+    let chord = chord.trim().to_ascii_lowercase();
+    // A trailing "+" is the plus key itself (e.g. "ctrl++").
+    let (mods, key) = match chord.strip_suffix("++") {
+        Some(mods) => (mods, "+"),
+        None => match chord.rsplit_once('+') {
+            Some((mods, key)) => (mods, key),
+            None => ("", chord.as_str()),
+        },
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for part in mods.split('+').filter(|part| !part.is_empty()) {
+        modifiers |= match part {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            other => return Err(format!("unknown modifier `{other}`")),
+        };
+    }
+
+    let code = match key {
+        "" => return Err("missing key".to_string()),
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        key => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                _ => match key.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{key}`")),
+                },
+            }
+        }
+    };
+    Ok(KeyBinding::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(
+            parse_chord("ctrl+t"),
+            Ok(key_hint::ctrl(KeyCode::Char('t')))
+        );
+        assert_eq!(
+            parse_chord("Ctrl+Alt+V"),
+            Ok(key_hint::ctrl_alt(KeyCode::Char('v')))
+        );
+        assert_eq!(
+            parse_chord("shift+tab"),
+            Ok(key_hint::shift(KeyCode::BackTab))
+        );
+        assert_eq!(parse_chord("alt+enter"), Ok(key_hint::alt(KeyCode::Enter)));
+        assert_eq!(parse_chord("f5"), Ok(key_hint::plain(KeyCode::F(5))));
+        assert_eq!(
+            parse_chord("ctrl++"),
+            Ok(key_hint::ctrl(KeyCode::Char('+')))
+        );
+        assert_eq!(parse_chord("q"), Ok(key_hint::plain(KeyCode::Char('q'))));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert_eq!(
+            parse_chord("hyper+t"),
+            Err("unknown modifier `hyper`".to_string())
+        );
+        assert_eq!(parse_chord("ctrl+"), Err("missing key".to_string()));
+        assert_eq!(
+            parse_chord("ctrl+enterr"),
+            Err("unknown key `enterr`".to_string())
+        );
+    }
+
+    #[test]
+    fn config_overrides_only_the_listed_actions() {
+        let keymap = Keymap::from_config(&TuiKeymap {
+            transcript: Some(KeyChords::Many(vec!["ctrl+o".into(), "f2".into()])),
+            pager_close: Some(KeyChords::Many(Vec::new())),
+            ..Default::default()
+        })
+        .expect("valid keymap");
+
+        assert!(
+            keymap
+                .transcript
+                .is_press(KeyEvent::new(KeyCode::F(2), KeyModifiers::NONE))
+        );
+        assert!(
+            !keymap
+                .transcript
+                .is_press(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(keymap.pager_close.primary(), None);
+        assert_eq!(keymap.external_editor, Keymap::default().external_editor);
+    }
+
+    #[test]
+    fn config_errors_name_the_action() {
+        let err = Keymap::from_config(&TuiKeymap {
+            newline: Some(KeyChords::One("ctrl+nope".into())),
+            ..Default::default()
+        })
+        .expect_err("invalid chord");
+        assert_eq!(err, "tui.keymap.newline: unknown key `nope` in `ctrl+nope`");
+    }
+
+    #[test]
+    fn shifted_letters_match_lowercase_chords() {
+        let bindings = KeyBindings::new(vec![KeyBinding::new(
+            KeyCode::Char('v'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )]);
+        assert!(bindings.is_press(KeyEvent::new(
+            KeyCode::Char('V'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )));
+    }
+}
@@ -1,4 +1,8 @@
 use crate::render::line_utils::line_to_static;
+use crate::render::syntax::Language;
+use crate::render::syntax::SyntaxTheme;
+use crate::render::syntax::highlight_code_to_lines;
+use crate::render::syntax::language_for_tag;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use pulldown_cmark::CodeBlockKind;
@@ -99,6 +103,9 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    /// Source of a fenced block in a known language, highlighted as a whole
+    /// when the block ends so multi-line comments and strings keep their style.
+    code_block_highlight: Option<(&'static Language, String)>,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -124,6 +131,7 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            code_block_highlight: None,
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -278,6 +286,10 @@ where
             self.push_line(Line::default());
         }
         self.pending_marker_line = false;
+        if let Some((_, source)) = self.code_block_highlight.as_mut() {
+            source.push_str(&text);
+            return;
+        }
         if self.in_code_block && !self.needs_newline {
             let has_content = self
                 .current_line_content
@@ -394,8 +406,12 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
+        self.code_block_highlight = lang
+            .as_deref()
+            .and_then(language_for_tag)
+            .map(|language| (language, String::new()));
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
@@ -409,6 +425,15 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some((language, source)) = self.code_block_highlight.take() {
+            let source = source.strip_suffix('\n').unwrap_or(&source);
+            if !source.is_empty() {
+                let theme = SyntaxTheme::current();
+                for line in highlight_code_to_lines(source, language, &theme) {
+                    self.push_line(line);
+                }
+            }
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
@@ -652,12 +652,40 @@ fn link() {
 }
 
 #[test]
-fn code_block_unhighlighted() {
+fn code_block_highlighted_by_language_tag() {
     let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+    let expected = Text::from_iter([Line::from_iter([
+        "".into(),
+        "fn".magenta(),
+        " ".into(),
+        "main".bold(),
+        "() {}".into(),
+    ])]);
+    assert_eq!(text, expected);
+}
+
+#[test]
+fn code_block_unknown_language_unhighlighted() {
+    let text = render_markdown_text("```klingon\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
 
+#[test]
+fn code_block_highlight_keeps_multiline_string_style() {
+    let text = render_markdown_text("```python\nx = '''a\nb''' # c\n```\n");
+    let expected = Text::from_iter([
+        Line::from_iter(["".into(), "x = ".into(), "'''a".green()]),
+        Line::from_iter([
+            "".into(),
+            "b'''".green(),
+            " ".into(),
+            "# c".dim().italic(),
+        ]),
+    ]);
+    assert_eq!(text, expected);
+}
+
 #[test]
 fn code_block_multiple_lines_root() {
     let md = "```\nfirst\nsecond\n```\n";
@@ -2,10 +2,17 @@ use std::io::Result;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::diff_render::DiffFile;
+use crate::diff_render::DiffFileStatus;
+use crate::diff_render::DiffSyntax;
+use crate::diff_render::parse_git_diff;
+use crate::diff_render::render_hunk_lines;
+use crate::diff_render::render_side_by_side_hunks;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap;
 use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
@@ -14,6 +21,8 @@ use crate::tui;
 use crate::tui::TuiEvent;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -31,6 +40,7 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Diff(DiffOverlay),
 }
 
 impl Overlay {
@@ -49,10 +59,17 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    /// Diff browser over the working tree diff and the per-turn diffs of the
+    /// session (`(turn number, diff)` pairs, oldest first).
+    pub(crate) fn new_diff(working_tree: String, turn_diffs: &[(usize, String)]) -> Self {
+        Self::Diff(DiffOverlay::new(working_tree, turn_diffs))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Diff(o) => o.handle_event(tui, event),
         }
     }
 
@@ -60,6 +77,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Diff(o) => o.is_done(),
         }
     }
 }
@@ -78,11 +96,18 @@ const KEY_CTRL_F: KeyBinding = key_hint::ctrl(KeyCode::Char('f'));
 const KEY_CTRL_D: KeyBinding = key_hint::ctrl(KeyCode::Char('d'));
 const KEY_CTRL_B: KeyBinding = key_hint::ctrl(KeyCode::Char('b'));
 const KEY_CTRL_U: KeyBinding = key_hint::ctrl(KeyCode::Char('u'));
-const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
-const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_LEFT: KeyBinding = key_hint::plain(KeyCode::Left);
+const KEY_RIGHT: KeyBinding = key_hint::plain(KeyCode::Right);
+const KEY_LEFT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char('['));
+const KEY_RIGHT_BRACKET: KeyBinding = key_hint::plain(KeyCode::Char(']'));
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_SHIFT_TAB: KeyBinding = key_hint::shift(KeyCode::BackTab);
+const KEY_V: KeyBinding = key_hint::plain(KeyCode::Char('v'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -96,6 +121,10 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
+        // Skip actions the user unbound in `[tui.keymap]`.
+        if keys.is_empty() {
+            continue;
+        }
         if !first {
             spans.push("   ".into());
         }
@@ -479,8 +508,11 @@ impl TranscriptOverlay {
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
 
-        let mut pairs: Vec<(&[KeyBinding], &str)> =
-            vec![(&[KEY_Q], "to quit"), (&[KEY_ESC], "to edit prev")];
+        let keymap = keymap::current();
+        let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (keymap.pager_close.as_slice(), "to quit"),
+            (keymap.edit_previous.as_slice(), "to edit prev"),
+        ];
         if self.highlight_cell.is_some() {
             pairs.push((&[KEY_ENTER], "to edit message"));
         }
@@ -500,7 +532,10 @@ impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+                e if keymap::current().pager_close.is_press(e)
+                    || KEY_CTRL_C.is_press(e)
+                    || keymap::current().transcript.is_press(e) =>
+                {
                     self.is_done = true;
                     Ok(())
                 }
@@ -542,7 +577,8 @@ impl StaticOverlay {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs: Vec<(&[KeyBinding], &str)> = vec![(&[KEY_Q], "to quit")];
+        let pairs: Vec<(&[KeyBinding], &str)> =
+            vec![(keymap::current().pager_close.as_slice(), "to quit")];
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -559,7 +595,7 @@ impl StaticOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                e if keymap::current().pager_close.is_press(e) || KEY_CTRL_C.is_press(e) => {
                     self.is_done = true;
                     Ok(())
                 }
@@ -579,6 +615,495 @@ impl StaticOverlay {
     }
 }
 
+/// Minimum width of the diff pane before the diff overlay switches to the
+/// side-by-side layout on its own.
+const SIDE_BY_SIDE_MIN_WIDTH: u16 = 120;
+/// Minimum terminal width for showing the file tree next to the diff.
+const FILE_TREE_MIN_WIDTH: u16 = 80;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DiffLayout {
+    /// Side-by-side on wide panes, unified otherwise.
+    Auto,
+    SideBySide,
+    Unified,
+}
+
+/// A file in one diff scope. In the session scope a file can carry one
+/// section per turn that touched it.
+#[derive(Clone, Debug)]
+struct DiffViewerFile {
+    path: String,
+    old_path: Option<String>,
+    status: DiffFileStatus,
+    added: usize,
+    removed: usize,
+    sections: Vec<(Option<String>, DiffFile)>,
+}
+
+impl DiffViewerFile {
+    fn new(label: Option<String>, file: DiffFile) -> Self {
+        let (added, removed) = file.line_counts();
+        Self {
+            path: file.path.clone(),
+            old_path: file.old_path.clone(),
+            status: file.status,
+            added,
+            removed,
+            sections: vec![(label, file)],
+        }
+    }
+
+    fn push_section(&mut self, label: Option<String>, file: DiffFile) {
+        let (added, removed) = file.line_counts();
+        self.added += added;
+        self.removed += removed;
+        // A file created during the session stays "added" unless a later
+        // turn deleted it again.
+        if self.status != DiffFileStatus::Added || file.status == DiffFileStatus::Deleted {
+            self.status = file.status;
+        }
+        if file.old_path.is_some() && self.old_path.is_none() {
+            self.old_path = file.old_path.clone();
+        }
+        self.sections.push((label, file));
+    }
+}
+
+struct DiffScope {
+    label: &'static str,
+    files: Vec<DiffViewerFile>,
+    /// Shown instead of the diff when the scope has no files.
+    empty_message: String,
+}
+
+impl DiffScope {
+    fn new(label: &'static str, files: Vec<DiffViewerFile>, empty_message: String) -> Self {
+        let mut files = files;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            label,
+            files,
+            empty_message,
+        }
+    }
+}
+
+fn status_marker(status: DiffFileStatus) -> Span<'static> {
+    match status {
+        DiffFileStatus::Added => "A".green(),
+        DiffFileStatus::Deleted => "D".red(),
+        DiffFileStatus::Modified => "M".cyan(),
+        DiffFileStatus::Renamed => "R".magenta(),
+    }
+}
+
+/// Renders the selected file of a diff scope, either unified or side-by-side.
+struct DiffFileView {
+    file: Option<DiffViewerFile>,
+    empty_message: String,
+    layout: DiffLayout,
+}
+
+impl DiffFileView {
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let Some(file) = &self.file else {
+            return vec![self.empty_message.clone().italic().into()];
+        };
+        let side_by_side = match self.layout {
+            DiffLayout::Auto => width >= SIDE_BY_SIDE_MIN_WIDTH,
+            DiffLayout::SideBySide => true,
+            DiffLayout::Unified => false,
+        };
+        let mut header = vec![status_marker(file.status), " ".into()];
+        if let Some(old_path) = &file.old_path {
+            header.push(format!("{old_path} → ").dim());
+        }
+        header.push(file.path.clone().bold());
+        header.push(" (".into());
+        header.push(format!("+{}", file.added).green());
+        header.push(" ".into());
+        header.push(format!("-{}", file.removed).red());
+        header.push(")".into());
+        let mut lines: Vec<Line<'static>> = vec![header.into(), "".into()];
+        let width = usize::from(width);
+        let syntax = DiffSyntax::for_path(&file.path);
+        for (idx, (label, section)) in file.sections.iter().enumerate() {
+            if let Some(label) = label {
+                if idx > 0 {
+                    lines.push("".into());
+                }
+                lines.push(Line::from(format!("── {label} ──")).dim().bold());
+            }
+            if section.binary {
+                lines.push("Binary file changed".italic().into());
+            } else if section.hunks.is_empty() {
+                lines.push("No content changes".italic().into());
+            } else if side_by_side {
+                lines.extend(render_side_by_side_hunks(
+                    &section.hunks,
+                    width,
+                    syntax.as_ref(),
+                ));
+            } else {
+                for (hunk_idx, hunk) in section.hunks.iter().enumerate() {
+                    if hunk_idx > 0 {
+                        lines.push("⋮".dim().into());
+                    }
+                    lines.extend(render_hunk_lines(
+                        &hunk.lines,
+                        hunk.old_start,
+                        hunk.new_start,
+                        width,
+                        syntax.as_ref(),
+                    ));
+                }
+            }
+        }
+        lines
+    }
+}
+
+impl Renderable for DiffFileView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(Text::from(self.lines(area.width))).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.lines(width).len().try_into().unwrap_or(u16::MAX)
+    }
+}
+
+/// Full-screen diff browser opened by `/diff`: a file tree on the left and
+/// the selected file on the right, for the working tree, the last turn or the
+/// whole session.
+pub(crate) struct DiffOverlay {
+    scopes: Vec<DiffScope>,
+    scope: usize,
+    selected: usize,
+    layout: DiffLayout,
+    /// Filter typed after `/`; `Some` while jump-to-file is active.
+    jump_query: Option<String>,
+    view: PagerView,
+    last_pane_width: u16,
+    is_done: bool,
+}
+
+impl DiffOverlay {
+    pub(crate) fn new(working_tree: String, turn_diffs: &[(usize, String)]) -> Self {
+        let working_files: Vec<DiffViewerFile> = parse_git_diff(&working_tree)
+            .into_iter()
+            .map(|file| DiffViewerFile::new(None, file))
+            .collect();
+        let working_message = if working_tree.trim().is_empty() || !working_files.is_empty() {
+            "No changes detected.".to_string()
+        } else {
+            working_tree.trim().to_string()
+        };
+
+        let last_turn_files: Vec<DiffViewerFile> = turn_diffs
+            .last()
+            .map(|(_, diff)| {
+                parse_git_diff(diff)
+                    .into_iter()
+                    .map(|file| DiffViewerFile::new(None, file))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut session_files: Vec<DiffViewerFile> = Vec::new();
+        for (turn, diff) in turn_diffs {
+            for file in parse_git_diff(diff) {
+                let label = Some(format!("Turn {turn}"));
+                match session_files.iter_mut().find(|f| f.path == file.path) {
+                    Some(existing) => existing.push_section(label, file),
+                    None => session_files.push(DiffViewerFile::new(label, file)),
+                }
+            }
+        }
+
+        let no_turn_changes = "Codex has not changed any files in this session.".to_string();
+        let scopes = vec![
+            DiffScope::new("working tree", working_files, working_message),
+            DiffScope::new("last turn", last_turn_files, no_turn_changes.clone()),
+            DiffScope::new("session", session_files, no_turn_changes),
+        ];
+        // Start on the last turn when Codex changed something; otherwise the
+        // working tree is the most useful view.
+        let scope = if scopes[1].files.is_empty() { 0 } else { 1 };
+        let mut overlay = Self {
+            scopes,
+            scope,
+            selected: 0,
+            layout: DiffLayout::Auto,
+            jump_query: None,
+            view: PagerView::new(Vec::new(), String::new(), 0),
+            last_pane_width: 0,
+            is_done: false,
+        };
+        overlay.refresh_view();
+        overlay
+    }
+
+    fn current_scope(&self) -> &DiffScope {
+        &self.scopes[self.scope]
+    }
+
+    /// Rebuilds the diff pane after the scope, file or layout changed.
+    fn refresh_view(&mut self) {
+        let scope = &self.scopes[self.scope];
+        let file = scope.files.get(self.selected).cloned();
+        let title = match scope.files.len() {
+            0 => format!("D I F F · {}", scope.label),
+            n => format!("D I F F · {} · {}/{n}", scope.label, self.selected + 1),
+        };
+        let view = DiffFileView {
+            file,
+            empty_message: scope.empty_message.clone(),
+            layout: self.layout,
+        };
+        self.view = PagerView::new(vec![Box::new(CachedRenderable::new(view))], title, 0);
+    }
+
+    fn select_file(&mut self, index: usize) {
+        self.selected = index;
+        self.refresh_view();
+    }
+
+    fn move_selection(&mut self, forward: bool) {
+        let len = self.current_scope().files.len();
+        if len == 0 {
+            return;
+        }
+        let index = if forward {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+        self.select_file(index);
+    }
+
+    fn cycle_scope(&mut self, forward: bool) {
+        let len = self.scopes.len();
+        self.scope = if forward {
+            (self.scope + 1) % len
+        } else {
+            (self.scope + len - 1) % len
+        };
+        self.selected = 0;
+        self.refresh_view();
+    }
+
+    fn toggle_layout(&mut self) {
+        let side_by_side = match self.layout {
+            DiffLayout::Auto => self.last_pane_width >= SIDE_BY_SIDE_MIN_WIDTH,
+            DiffLayout::SideBySide => true,
+            DiffLayout::Unified => false,
+        };
+        self.layout = if side_by_side {
+            DiffLayout::Unified
+        } else {
+            DiffLayout::SideBySide
+        };
+        let scroll_offset = self.view.scroll_offset;
+        self.refresh_view();
+        self.view.scroll_offset = scroll_offset;
+    }
+
+    /// Jumps to the first file whose path contains the query (case-insensitive).
+    fn jump_to_file(&mut self, query: &str) {
+        let query = query.to_lowercase();
+        if query.is_empty() {
+            return;
+        }
+        if let Some(index) = self
+            .current_scope()
+            .files
+            .iter()
+            .position(|f| f.path.to_lowercase().contains(&query))
+        {
+            self.select_file(index);
+        }
+    }
+
+    /// Tree lines grouped by directory, plus the line index of the selection.
+    fn tree_lines(&self) -> (Vec<Line<'static>>, usize) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let mut selected_line = 0;
+        let mut current_dir: Option<&str> = None;
+        for (idx, file) in self.current_scope().files.iter().enumerate() {
+            let (dir, name) = match file.path.rsplit_once('/') {
+                Some((dir, name)) => (Some(dir), name),
+                None => (None, file.path.as_str()),
+            };
+            if dir != current_dir {
+                if let Some(dir) = dir {
+                    lines.push(format!("{dir}/").dim().into());
+                }
+                current_dir = dir;
+            }
+            let indent = if dir.is_some() { "  " } else { "" };
+            let mut spans = vec![
+                indent.into(),
+                status_marker(file.status),
+                " ".into(),
+                name.to_string().into(),
+                " ".into(),
+                format!("+{}", file.added).green(),
+                " ".into(),
+                format!("-{}", file.removed).red(),
+            ];
+            if idx == self.selected {
+                selected_line = lines.len();
+                spans[3] = spans[3].clone().bold();
+                lines.push(Line::from(spans).reversed());
+            } else {
+                lines.push(spans.into());
+            }
+        }
+        (lines, selected_line)
+    }
+
+    fn render_tree(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        if area.height == 0 {
+            return;
+        }
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        format!("/ files ({})", self.current_scope().files.len())
+            .dim()
+            .render_ref(header, buf);
+        let body = Rect::new(
+            area.x,
+            area.y + 1,
+            area.width,
+            area.height.saturating_sub(2),
+        );
+        let (lines, selected_line) = self.tree_lines();
+        let offset = selected_line.saturating_sub(usize::from(body.height).saturating_sub(1));
+        let lines: Vec<Line<'static>> = lines.into_iter().skip(offset).collect();
+        Paragraph::new(Text::from(lines)).render(body, buf);
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        if let Some(query) = &self.jump_query {
+            let spans: Vec<Span<'static>> = vec![
+                " Jump to file: ".dim(),
+                query.clone().into(),
+                "▏".dim(),
+                "   ".into(),
+                Span::from(KEY_ENTER).dim(),
+                " to jump   ".dim(),
+                Span::from(KEY_ESC).dim(),
+                " to cancel".dim(),
+            ];
+            Paragraph::new(Line::from(spans)).render_ref(line2, buf);
+            return;
+        }
+        let pairs: Vec<(&[KeyBinding], &str)> = vec![
+            (&[KEY_LEFT, KEY_RIGHT], "to switch file"),
+            (&[KEY_TAB], "to switch scope"),
+            (&[KEY_V], "to toggle side-by-side"),
+            (&[KEY_SLASH], "to find a file"),
+            (keymap::current().pager_close.as_slice(), "to quit"),
+        ];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        let pane = if area.width >= FILE_TREE_MIN_WIDTH && !self.current_scope().files.is_empty() {
+            let tree_w = (area.width / 4).clamp(24, 40);
+            self.render_tree(Rect::new(top.x, top.y, tree_w, top.height), buf);
+            Rect::new(
+                top.x + tree_w + 1,
+                top.y,
+                top.width.saturating_sub(tree_w + 1),
+                top.height,
+            )
+        } else {
+            top
+        };
+        self.last_pane_width = pane.width;
+        self.view.render(pane, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        if let Some(query) = self.jump_query.as_mut() {
+            match key_event {
+                e if KEY_ESC.is_press(e) => self.jump_query = None,
+                e if KEY_ENTER.is_press(e) => {
+                    let query = self.jump_query.take().unwrap_or_default();
+                    self.jump_to_file(&query);
+                }
+                e if KEY_BACKSPACE.is_press(e) => {
+                    query.pop();
+                }
+                KeyEvent {
+                    code: KeyCode::Char(c),
+                    modifiers,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } if !modifiers.contains(KeyModifiers::CONTROL) => query.push(c),
+                _ => return Ok(()),
+            }
+            tui.frame_requester().schedule_frame();
+            return Ok(());
+        }
+        match key_event {
+            e if keymap::current().pager_close.is_press(e)
+                || KEY_ESC.is_press(e)
+                || KEY_CTRL_C.is_press(e) =>
+            {
+                self.is_done = true;
+                return Ok(());
+            }
+            e if KEY_RIGHT.is_press(e) || KEY_RIGHT_BRACKET.is_press(e) => {
+                self.move_selection(true);
+            }
+            e if KEY_LEFT.is_press(e) || KEY_LEFT_BRACKET.is_press(e) => {
+                self.move_selection(false);
+            }
+            e if KEY_TAB.is_press(e) => self.cycle_scope(true),
+            e if KEY_SHIFT_TAB.is_press(e) => self.cycle_scope(false),
+            e if KEY_V.is_press(e) => self.toggle_layout(),
+            e if KEY_SLASH.is_press(e) => self.jump_query = Some(String::new()),
+            other => return self.view.handle_key_event(tui, other),
+        }
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => self.handle_key_event(tui, key_event),
+            TuiEvent::Paste(text) => {
+                if let Some(query) = self.jump_query.as_mut() {
+                    query.push_str(text.trim());
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
 fn render_offset_content(
     area: Rect,
     buf: &mut Buffer,
@@ -842,6 +1367,128 @@ mod tests {
         assert_snapshot!(term.backend());
     }
 
+    const TURN_ONE_DIFF: &str = "diff --git a/src/app.rs b/src/app.rs
+index 1111111..2222222 100644
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -1,3 +1,3 @@
+ fn run() {
+-    start();
++    start_app();
+ }
+";
+
+    const TURN_TWO_DIFF: &str = "diff --git a/src/app.rs b/src/app.rs
+index 2222222..3333333 100644
+--- a/src/app.rs
++++ b/src/app.rs
+@@ -3,1 +3,2 @@
+ }
++fn stop() {}
+diff --git a/README.md b/README.md
+new file mode 100644
+index 0000000..4444444
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Demo
+";
+
+    fn diff_overlay() -> DiffOverlay {
+        DiffOverlay::new(
+            String::new(),
+            &[
+                (1, TURN_ONE_DIFF.to_string()),
+                (3, TURN_TWO_DIFF.to_string()),
+            ],
+        )
+    }
+
+    #[test]
+    fn diff_overlay_snapshot_last_turn() {
+        let mut overlay = diff_overlay();
+        let mut term = Terminal::new(TestBackend::new(100, 14)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn diff_overlay_side_by_side_snapshot() {
+        let mut overlay = diff_overlay();
+        overlay.cycle_scope(true);
+        overlay.layout = DiffLayout::SideBySide;
+        overlay.select_file(1);
+        let mut term = Terminal::new(TestBackend::new(100, 16)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn diff_overlay_merges_session_turns_per_file() {
+        let mut overlay = diff_overlay();
+        assert_eq!(overlay.current_scope().label, "last turn");
+        overlay.cycle_scope(true);
+        let scope = overlay.current_scope();
+        assert_eq!(scope.label, "session");
+        let files: Vec<(&str, usize, usize, Vec<Option<String>>)> = scope
+            .files
+            .iter()
+            .map(|f| {
+                (
+                    f.path.as_str(),
+                    f.added,
+                    f.removed,
+                    f.sections.iter().map(|(label, _)| label.clone()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                ("README.md", 1, 0, vec![Some("Turn 3".to_string())]),
+                (
+                    "src/app.rs",
+                    2,
+                    1,
+                    vec![Some("Turn 1".to_string()), Some("Turn 3".to_string())]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_overlay_jumps_to_file_and_cycles_scopes() {
+        let mut overlay = diff_overlay();
+        overlay.jump_to_file("APP");
+        assert_eq!(overlay.selected, 1);
+        overlay.jump_to_file("missing");
+        assert_eq!(overlay.selected, 1);
+        overlay.move_selection(true);
+        assert_eq!(overlay.selected, 0);
+
+        // The working tree scope is empty, so it shows the fallback message.
+        overlay.cycle_scope(false);
+        assert_eq!(overlay.current_scope().label, "working tree");
+        assert!(overlay.current_scope().files.is_empty());
+        assert_eq!(
+            overlay.current_scope().empty_message,
+            "No changes detected."
+        );
+    }
+
+    #[test]
+    fn diff_overlay_opens_on_working_tree_without_turn_diffs() {
+        let overlay = DiffOverlay::new("`/diff` — _not inside a git repository_".to_string(), &[]);
+        let scope = overlay.current_scope();
+        assert_eq!(scope.label, "working tree");
+        assert_eq!(
+            scope.empty_message,
+            "`/diff` — _not inside a git repository_"
+        );
+    }
+
     /// Render transcript overlay and return visible line numbers (`line-NN`) in order.
     fn transcript_line_numbers(overlay: &mut TranscriptOverlay, area: Rect) -> Vec<usize> {
         let mut buf = Buffer::empty(area);
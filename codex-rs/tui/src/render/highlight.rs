@@ -8,6 +8,8 @@ use tree_sitter_highlight::HighlightConfiguration;
 use tree_sitter_highlight::HighlightEvent;
 use tree_sitter_highlight::Highlighter;
 
+use super::syntax::TokenKind;
+
 // Ref: https://github.com/tree-sitter/tree-sitter-bash/blob/master/queries/highlights.scm
 #[derive(Copy, Clone)]
 enum BashHighlight {
@@ -55,6 +57,19 @@ impl BashHighlight {
             _ => Style::default(),
         }
     }
+
+    /// Token class used by the themed highlighter in `syntax`.
+    fn token_kind(self) -> TokenKind {
+        match self {
+            Self::Comment => TokenKind::Comment,
+            Self::Constant => TokenKind::Constant,
+            Self::Function => TokenKind::Function,
+            Self::Keyword => TokenKind::Keyword,
+            Self::Number => TokenKind::Number,
+            Self::String => TokenKind::String,
+            Self::Embedded | Self::Operator | Self::Property => TokenKind::Plain,
+        }
+    }
 }
 
 static HIGHLIGHT_CONFIG: OnceLock<HighlightConfiguration> = OnceLock::new();
@@ -105,36 +120,46 @@ fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Sty
     }
 }
 
-/// Convert a bash script into per-line styled content using tree-sitter's
-/// bash highlight query. The highlighter is streamed so multi-line content is
-/// split into `Line`s while preserving style boundaries.
-pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+/// Splits `script` into segments, each tagged with the innermost highlight
+/// covering it. `None` when tree-sitter fails to highlight the script.
+fn bash_segments(script: &str) -> Option<Vec<(Option<BashHighlight>, &str)>> {
     let mut highlighter = Highlighter::new();
-    let iterator =
-        match highlighter.highlight(highlight_config(), script.as_bytes(), None, |_| None) {
-            Ok(iter) => iter,
-            Err(_) => return vec![script.to_string().into()],
-        };
+    let iterator = highlighter
+        .highlight(highlight_config(), script.as_bytes(), None, |_| None)
+        .ok()?;
 
-    let mut lines: Vec<Line<'static>> = vec![Line::from("")];
+    let mut segments = Vec::new();
     let mut highlight_stack: Vec<Highlight> = Vec::new();
-
     for event in iterator {
-        match event {
-            Ok(HighlightEvent::HighlightStart(highlight)) => highlight_stack.push(highlight),
-            Ok(HighlightEvent::HighlightEnd) => {
+        match event.ok()? {
+            HighlightEvent::HighlightStart(highlight) => highlight_stack.push(highlight),
+            HighlightEvent::HighlightEnd => {
                 highlight_stack.pop();
             }
-            Ok(HighlightEvent::Source { start, end }) => {
+            HighlightEvent::Source { start, end } => {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
-                push_segment(&mut lines, &script[start..end], style);
+                let highlight = highlight_stack.last().map(|h| highlight_for(*h));
+                segments.push((highlight, &script[start..end]));
             }
-            Err(_) => return vec![script.to_string().into()],
         }
     }
+    Some(segments)
+}
+
+/// Convert a bash script into per-line styled content using tree-sitter's
+/// bash highlight query. The highlighter is streamed so multi-line content is
+/// split into `Line`s while preserving style boundaries.
+pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+    let Some(segments) = bash_segments(script) else {
+        return vec![script.to_string().into()];
+    };
+
+    let mut lines: Vec<Line<'static>> = vec![Line::from("")];
+    for (highlight, segment) in segments {
+        push_segment(&mut lines, segment, highlight.map(BashHighlight::style));
+    }
 
     if lines.is_empty() {
         vec![Line::from("")]
@@ -143,6 +168,21 @@ pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
     }
 }
 
+/// The same tree-sitter parse as [`highlight_bash_to_lines`], as tokens for
+/// the themed highlighter that renders code blocks and diffs.
+pub(crate) fn bash_tokens(script: &str) -> Option<Vec<(TokenKind, &str)>> {
+    let segments = bash_segments(script)?;
+    Some(
+        segments
+            .into_iter()
+            .map(|(highlight, text)| {
+                let kind = highlight.map_or(TokenKind::Plain, BashHighlight::token_kind);
+                (kind, text)
+            })
+            .collect(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
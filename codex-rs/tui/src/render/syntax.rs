@@ -0,0 +1,960 @@
+//! Lightweight syntax highlighting for fenced code blocks and diffs.
+//!
+//! Shell scripts are parsed with tree-sitter, through the same bash grammar
+//! and highlight query that [`super::highlight`] uses for commands. Every
+//! other language goes through a lexical highlighter: each is described by
+//! its keywords, comment markers and string delimiters, which is enough to
+//! color the common token classes. `tree-sitter-highlight` would need a
+//! grammar crate per language, each compiling a generated C parser into the
+//! binary; the bash one is already there because core parses commands with
+//! it, but the other dozen would add several megabytes and a C build step for
+//! every language for what is a best-effort coloring. Unknown languages are
+//! left unhighlighted by callers.
+
+use std::path::Path;
+
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use super::highlight::bash_tokens;
+use crate::color::is_light;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
+
+/// Lexical description of a language.
+#[derive(Debug)]
+pub(crate) struct Language {
+    pub(crate) name: &'static str,
+    /// Fence info strings that select this language (besides `name`).
+    aliases: &'static [&'static str],
+    extensions: &'static [&'static str],
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    constants: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Python-style `"""` / `'''` strings.
+    triple_quotes: bool,
+    /// Rust-style `'c'` literals, where a lone `'` starts a lifetime instead.
+    char_literals: bool,
+    /// Treat identifiers starting with an uppercase letter as types.
+    capitalized_types: bool,
+}
+
+const C_KEYWORDS: &[&str] = &[
+    "break", "case", "const", "continue", "default", "do", "else", "enum", "extern", "for", "goto",
+    "if", "inline", "register", "return", "sizeof", "static", "struct", "switch", "typedef",
+    "union", "volatile", "while", "#include", "#define", "#ifdef", "#ifndef", "#endif", "#if",
+    "#else", "#pragma",
+];
+const C_TYPES: &[&str] = &[
+    "char", "double", "float", "int", "long", "short", "signed", "unsigned", "void", "bool",
+    "size_t", "int8_t", "int16_t", "int32_t", "int64_t", "uint8_t", "uint16_t", "uint32_t",
+    "uint64_t",
+];
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+const JS_CONSTANTS: &[&str] = &["true", "false", "null", "undefined", "NaN", "Infinity"];
+
+static LANGUAGES: &[Language] = &[
+    Language {
+        name: "rust",
+        aliases: &["rs"],
+        extensions: &["rs"],
+        keywords: &[
+            "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+            "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+            "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+            "type", "unsafe", "use", "where", "while", "yield",
+        ],
+        types: &[
+            "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32",
+            "i64", "i128", "isize", "f32", "f64",
+        ],
+        constants: &["true", "false", "None", "Some", "Ok", "Err"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"'],
+        triple_quotes: false,
+        char_literals: true,
+        capitalized_types: true,
+    },
+    Language {
+        name: "python",
+        aliases: &["py", "python3", "py3"],
+        extensions: &["py", "pyi"],
+        keywords: &[
+            "and", "as", "assert", "async", "await", "break", "case", "class", "continue", "def",
+            "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+            "in", "is", "lambda", "match", "nonlocal", "not", "or", "pass", "raise", "return",
+            "try", "while", "with", "yield",
+        ],
+        types: &[
+            "bool", "bytes", "dict", "float", "int", "list", "object", "set", "str", "tuple",
+        ],
+        constants: &["True", "False", "None", "self", "cls"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes: true,
+        char_literals: false,
+        capitalized_types: true,
+    },
+    Language {
+        name: "javascript",
+        aliases: &["js", "jsx", "node", "mjs", "cjs"],
+        extensions: &["js", "jsx", "mjs", "cjs"],
+        keywords: JS_KEYWORDS,
+        types: &[],
+        constants: JS_CONSTANTS,
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: true,
+    },
+    Language {
+        name: "typescript",
+        aliases: &["ts", "tsx", "mts", "cts"],
+        extensions: &["ts", "tsx", "mts", "cts"],
+        keywords: &[
+            "abstract",
+            "as",
+            "async",
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "declare",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "export",
+            "extends",
+            "finally",
+            "for",
+            "from",
+            "function",
+            "if",
+            "implements",
+            "import",
+            "in",
+            "instanceof",
+            "interface",
+            "keyof",
+            "let",
+            "namespace",
+            "new",
+            "of",
+            "private",
+            "protected",
+            "public",
+            "readonly",
+            "return",
+            "satisfies",
+            "static",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "try",
+            "type",
+            "typeof",
+            "var",
+            "void",
+            "while",
+            "yield",
+        ],
+        types: &[
+            "any", "bigint", "boolean", "never", "number", "object", "string", "symbol", "unknown",
+        ],
+        constants: JS_CONSTANTS,
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: true,
+    },
+    Language {
+        name: "go",
+        aliases: &["golang"],
+        extensions: &["go"],
+        keywords: &[
+            "break",
+            "case",
+            "chan",
+            "const",
+            "continue",
+            "default",
+            "defer",
+            "else",
+            "fallthrough",
+            "for",
+            "func",
+            "go",
+            "goto",
+            "if",
+            "import",
+            "interface",
+            "map",
+            "package",
+            "range",
+            "return",
+            "select",
+            "struct",
+            "switch",
+            "type",
+            "var",
+        ],
+        types: &[
+            "any",
+            "bool",
+            "byte",
+            "complex64",
+            "complex128",
+            "error",
+            "float32",
+            "float64",
+            "int",
+            "int8",
+            "int16",
+            "int32",
+            "int64",
+            "rune",
+            "string",
+            "uint",
+            "uint8",
+            "uint16",
+            "uint32",
+            "uint64",
+            "uintptr",
+        ],
+        constants: &["true", "false", "nil", "iota"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\'', '`'],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "c",
+        aliases: &["h"],
+        extensions: &["c", "h"],
+        keywords: C_KEYWORDS,
+        types: C_TYPES,
+        constants: &["NULL", "true", "false"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "cpp",
+        aliases: &["c++", "cxx", "cc", "hpp"],
+        extensions: &["cpp", "cc", "cxx", "hpp", "hh", "hxx"],
+        keywords: &[
+            "auto",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "constexpr",
+            "continue",
+            "default",
+            "delete",
+            "do",
+            "else",
+            "enum",
+            "explicit",
+            "extern",
+            "for",
+            "friend",
+            "if",
+            "inline",
+            "namespace",
+            "new",
+            "noexcept",
+            "operator",
+            "override",
+            "private",
+            "protected",
+            "public",
+            "return",
+            "sizeof",
+            "static",
+            "struct",
+            "switch",
+            "template",
+            "this",
+            "throw",
+            "try",
+            "typedef",
+            "typename",
+            "union",
+            "using",
+            "virtual",
+            "volatile",
+            "while",
+            "#include",
+            "#define",
+            "#ifdef",
+            "#ifndef",
+            "#endif",
+            "#if",
+            "#else",
+            "#pragma",
+        ],
+        types: C_TYPES,
+        constants: &["nullptr", "NULL", "true", "false"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "java",
+        aliases: &[],
+        extensions: &["java"],
+        keywords: &[
+            "abstract",
+            "assert",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "continue",
+            "default",
+            "do",
+            "else",
+            "enum",
+            "extends",
+            "final",
+            "finally",
+            "for",
+            "if",
+            "implements",
+            "import",
+            "instanceof",
+            "interface",
+            "new",
+            "package",
+            "private",
+            "protected",
+            "public",
+            "record",
+            "return",
+            "static",
+            "super",
+            "switch",
+            "synchronized",
+            "this",
+            "throw",
+            "throws",
+            "try",
+            "var",
+            "void",
+            "while",
+        ],
+        types: &[
+            "boolean", "byte", "char", "double", "float", "int", "long", "short",
+        ],
+        constants: &["true", "false", "null"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: true,
+    },
+    Language {
+        name: "ruby",
+        aliases: &["rb"],
+        extensions: &["rb"],
+        keywords: &[
+            "alias", "and", "begin", "break", "case", "class", "def", "do", "else", "elsif", "end",
+            "ensure", "for", "if", "in", "module", "next", "not", "or", "redo", "rescue", "retry",
+            "return", "self", "super", "then", "unless", "until", "when", "while", "yield",
+            "require",
+        ],
+        types: &[],
+        constants: &["true", "false", "nil"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: true,
+    },
+    Language {
+        name: "bash",
+        aliases: &["sh", "shell", "zsh"],
+        extensions: &["sh", "bash", "zsh"],
+        keywords: &[
+            "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+            "in", "local", "readonly", "return", "select", "then", "until", "while",
+        ],
+        types: &[],
+        constants: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "json",
+        aliases: &["jsonc", "json5"],
+        extensions: &["json", "jsonc", "json5"],
+        keywords: &[],
+        types: &[],
+        constants: &["true", "false", "null"],
+        line_comments: &["//"],
+        block_comment: Some(("/*", "*/")),
+        quotes: &['"'],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "toml",
+        aliases: &[],
+        extensions: &["toml"],
+        keywords: &[],
+        types: &[],
+        constants: &["true", "false"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes: true,
+        char_literals: false,
+        capitalized_types: false,
+    },
+    Language {
+        name: "yaml",
+        aliases: &["yml"],
+        extensions: &["yaml", "yml"],
+        keywords: &[],
+        types: &[],
+        constants: &["true", "false", "null", "yes", "no"],
+        line_comments: &["#"],
+        block_comment: None,
+        quotes: &['"', '\''],
+        triple_quotes: false,
+        char_literals: false,
+        capitalized_types: false,
+    },
+];
+
+/// Looks up a language from a fenced code block info string such as
+/// `rust`, `rust,ignore` or `python title="x.py"`.
+pub(crate) fn language_for_tag(info: &str) -> Option<&'static Language> {
+    let tag = info
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .next()?
+        .trim_start_matches('{')
+        .trim_start_matches('.')
+        .to_ascii_lowercase();
+    if tag.is_empty() {
+        return None;
+    }
+    LANGUAGES
+        .iter()
+        .find(|lang| lang.name == tag || lang.aliases.contains(&tag.as_str()))
+}
+
+/// Looks up a language from a file extension.
+pub(crate) fn language_for_path(path: &Path) -> Option<&'static Language> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|lang| lang.extensions.contains(&ext.as_str()))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    Plain,
+    Keyword,
+    Type,
+    Constant,
+    Function,
+    String,
+    Number,
+    Comment,
+}
+
+/// Splits `text` into classified tokens. Tokens cover the whole input and may
+/// span newlines (block comments, multi-line strings).
+pub(crate) fn tokenize<'a>(lang: &Language, text: &'a str) -> Vec<(TokenKind, &'a str)> {
+    if lang.name == "bash"
+        && let Some(tokens) = bash_tokens(text)
+    {
+        return tokens;
+    }
+
+    let mut tokens: Vec<(TokenKind, &'a str)> = Vec::new();
+    let mut push = |kind: TokenKind, token: &'a str| {
+        if token.is_empty() {
+            return;
+        }
+        match tokens.last_mut() {
+            // Merge adjacent plain text so callers get fewer spans.
+            Some((TokenKind::Plain, last)) if kind == TokenKind::Plain => {
+                let start = last.as_ptr() as usize - text.as_ptr() as usize;
+                *last = &text[start..start + last.len() + token.len()];
+            }
+            _ => tokens.push((kind, token)),
+        }
+    };
+
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        let prev = text[..i].chars().next_back();
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        let len = if let Some(len) = comment_len(lang, rest, prev) {
+            push(TokenKind::Comment, &rest[..len]);
+            len
+        } else if let Some(len) = string_len(lang, rest) {
+            push(TokenKind::String, &rest[..len]);
+            len
+        } else if c.is_ascii_digit() && !prev.is_some_and(is_ident_char) {
+            let len = number_len(rest);
+            push(TokenKind::Number, &rest[..len]);
+            len
+        } else if is_ident_start(c) || (c == '#' && !lang.line_comments.contains(&"#")) {
+            let len = rest[c.len_utf8()..]
+                .find(|ch: char| !is_ident_char(ch))
+                .map_or(rest.len(), |p| p + c.len_utf8());
+            let word = &rest[..len];
+            push(classify_word(lang, word, &rest[len..]), word);
+            len
+        } else {
+            push(TokenKind::Plain, &rest[..c.len_utf8()]);
+            c.len_utf8()
+        };
+        i += len;
+    }
+    tokens
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn comment_len(lang: &Language, rest: &str, prev: Option<char>) -> Option<usize> {
+    if let Some((open, close)) = lang.block_comment
+        && rest.starts_with(open)
+    {
+        let body = &rest[open.len()..];
+        return Some(
+            body.find(close)
+                .map_or(rest.len(), |p| open.len() + p + close.len()),
+        );
+    }
+    let line_comment = lang.line_comments.iter().any(|marker| {
+        // `#` only starts a comment at a word boundary (`$#`, `a#b`, `#[attr]` are not).
+        rest.starts_with(marker) && (*marker != "#" || prev.is_none_or(char::is_whitespace))
+    });
+    line_comment.then(|| rest.find('\n').unwrap_or(rest.len()))
+}
+
+fn string_len(lang: &Language, rest: &str) -> Option<usize> {
+    if lang.triple_quotes {
+        for delim in ["\"\"\"", "'''"] {
+            if let Some(body) = rest.strip_prefix(delim) {
+                return Some(body.find(delim).map_or(rest.len(), |p| p + 2 * delim.len()));
+            }
+        }
+    }
+    let quote = rest.chars().next()?;
+    if lang.char_literals && quote == '\'' {
+        return char_literal_len(rest);
+    }
+    if !lang.quotes.contains(&quote) {
+        return None;
+    }
+    let mut escaped = false;
+    for (idx, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(idx + 1);
+        } else if c == '\n' && quote != '`' {
+            return Some(idx);
+        }
+    }
+    Some(rest.len())
+}
+
+/// `'a'`, `'\n'` or `'\u{1F600}'`; a lone `'` (a lifetime) is not a literal.
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let body = &rest[1..];
+    let inner_len = if let Some(escape) = body.strip_prefix('\\') {
+        1 + escape.find('\'')?
+    } else {
+        body.chars().next()?.len_utf8()
+    };
+    body[inner_len..]
+        .starts_with('\'')
+        .then_some(inner_len + 2)
+        .filter(|len| *len <= 12)
+}
+
+fn number_len(rest: &str) -> usize {
+    let mut len = 0;
+    let bytes = rest.as_bytes();
+    while len < bytes.len() {
+        let b = bytes[len];
+        let is_fraction_dot = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if b.is_ascii_alphanumeric() || b == b'_' || is_fraction_dot {
+            len += 1;
+        } else {
+            break;
+        }
+    }
+    len
+}
+
+fn classify_word(lang: &Language, word: &str, after: &str) -> TokenKind {
+    if lang.keywords.contains(&word) {
+        TokenKind::Keyword
+    } else if lang.constants.contains(&word) {
+        TokenKind::Constant
+    } else if lang.types.contains(&word) {
+        TokenKind::Type
+    } else if after.starts_with('(') || (lang.name == "rust" && after.starts_with('!')) {
+        TokenKind::Function
+    } else if lang.capitalized_types && word.starts_with(|c: char| c.is_uppercase()) {
+        TokenKind::Type
+    } else {
+        TokenKind::Plain
+    }
+}
+
+/// Token styles, picked for the terminal's light or dark background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SyntaxTheme {
+    keyword: Style,
+    type_: Style,
+    constant: Style,
+    function: Style,
+    string: Style,
+    number: Style,
+    comment: Style,
+}
+
+impl SyntaxTheme {
+    /// Theme for the current terminal background.
+    pub(crate) fn current() -> Self {
+        Self::for_background(default_bg())
+    }
+
+    /// Uses a palette tuned for light or dark backgrounds when the terminal
+    /// reports its background color, and plain ANSI colors otherwise.
+    pub(crate) fn for_background(bg: Option<(u8, u8, u8)>) -> Self {
+        let comment = Style::default().add_modifier(Modifier::DIM | Modifier::ITALIC);
+        let Some(bg) = bg else {
+            return Self {
+                keyword: Style::default().fg(Color::Magenta),
+                type_: Style::default().fg(Color::Cyan),
+                constant: Style::default().fg(Color::Cyan),
+                function: Style::default().add_modifier(Modifier::BOLD),
+                string: Style::default().fg(Color::Green),
+                number: Style::default().fg(Color::Cyan),
+                comment,
+            };
+        };
+        let color = |light: (u8, u8, u8), dark: (u8, u8, u8)| {
+            Style::default().fg(best_color(if is_light(bg) { light } else { dark }))
+        };
+        Self {
+            keyword: color((166, 38, 164), (198, 120, 221)),
+            type_: color((193, 132, 1), (229, 192, 123)),
+            constant: color((152, 104, 1), (209, 154, 102)),
+            function: color((64, 120, 242), (97, 175, 239)),
+            string: color((80, 161, 79), (152, 195, 121)),
+            number: color((152, 104, 1), (209, 154, 102)),
+            comment,
+        }
+    }
+
+    pub(crate) fn style(&self, kind: TokenKind) -> Style {
+        match kind {
+            TokenKind::Plain => Style::default(),
+            TokenKind::Keyword => self.keyword,
+            TokenKind::Type => self.type_,
+            TokenKind::Constant => self.constant,
+            TokenKind::Function => self.function,
+            TokenKind::String => self.string,
+            TokenKind::Number => self.number,
+            TokenKind::Comment => self.comment,
+        }
+    }
+}
+
+/// Highlights a code snippet into one `Line` per source line.
+pub(crate) fn highlight_code_to_lines(
+    code: &str,
+    lang: &Language,
+    theme: &SyntaxTheme,
+) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = vec![Line::default()];
+    for (kind, token) in tokenize(lang, code) {
+        for (i, part) in token.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Line::default());
+            }
+            if part.is_empty() {
+                continue;
+            }
+            if let Some(line) = lines.last_mut() {
+                line.spans
+                    .push(Span::styled(part.to_string(), theme.style(kind)));
+            }
+        }
+    }
+    lines
+}
+
+/// Highlights a single line of code into spans.
+pub(crate) fn highlight_line_spans(
+    text: &str,
+    lang: &Language,
+    theme: &SyntaxTheme,
+) -> Vec<Span<'static>> {
+    tokenize(lang, text)
+        .into_iter()
+        .map(|(kind, token)| Span::styled(token.to_string(), theme.style(kind)))
+        .collect()
+}
+
+/// Returns the spans covering characters `start..end` of the concatenated
+/// span contents, preserving each span's style.
+pub(crate) fn slice_spans(spans: &[Span<'static>], start: usize, end: usize) -> Vec<Span<'static>> {
+    let mut out = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let len = span.content.chars().count();
+        let (from, to) = (start.max(offset), end.min(offset + len));
+        if from < to {
+            let text: String = span
+                .content
+                .chars()
+                .skip(from - offset)
+                .take(to - from)
+                .collect();
+            out.push(Span::styled(text, span.style));
+        }
+        offset += len;
+        if offset >= end {
+            break;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Non-plain tokens only, which keeps the expectations readable.
+    fn kinds<'a>(lang: &str, text: &'a str) -> Vec<(TokenKind, &'a str)> {
+        let lang = language_for_tag(lang).expect("known language");
+        tokenize(lang, text)
+            .into_iter()
+            .filter(|(kind, _)| *kind != TokenKind::Plain)
+            .collect()
+    }
+
+    #[test]
+    fn resolves_languages_from_tags_and_paths() {
+        assert_eq!(
+            language_for_tag("rust,ignore").map(|l| l.name),
+            Some("rust")
+        );
+        assert_eq!(
+            language_for_tag("PY title=x").map(|l| l.name),
+            Some("python")
+        );
+        assert_eq!(language_for_tag("tsx").map(|l| l.name), Some("typescript"));
+        assert!(language_for_tag("brainfuck").is_none());
+        assert!(language_for_tag("").is_none());
+        assert_eq!(
+            language_for_path(Path::new("src/main.go")).map(|l| l.name),
+            Some("go")
+        );
+        assert!(language_for_path(Path::new("README")).is_none());
+    }
+
+    #[test]
+    fn tokenizes_rust() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds(
+                "rust",
+                "fn main() { let c = 'x'; let s: &'a str; println!(\"hi {}\", 42); } // done"
+            ),
+            vec![
+                (Keyword, "fn"),
+                (Function, "main"),
+                (Keyword, "let"),
+                (String, "'x'"),
+                (Keyword, "let"),
+                (Type, "str"),
+                (Function, "println"),
+                (String, "\"hi {}\""),
+                (Number, "42"),
+                (Comment, "// done"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenizes_multiline_python_strings_and_comments() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds(
+                "python",
+                "def f(x):\n    \"\"\"Doc\n    more\"\"\"\n    return None  # no"
+            ),
+            vec![
+                (Keyword, "def"),
+                (Function, "f"),
+                (String, "\"\"\"Doc\n    more\"\"\""),
+                (Keyword, "return"),
+                (Constant, "None"),
+                (Comment, "# no"),
+            ]
+        );
+    }
+
+    #[test]
+    fn shell_goes_through_tree_sitter() {
+        use TokenKind::*;
+        let tokens = kinds("sh", "echo $# \"hi\" # count");
+        assert!(tokens.contains(&(Function, "echo")), "{tokens:?}");
+        assert!(tokens.contains(&(String, "\"hi\"")), "{tokens:?}");
+        assert_eq!(tokens.last(), Some(&(Comment, "# count")));
+
+        let lang = language_for_path(Path::new("scripts/build.sh")).expect("sh");
+        let text = "cat <<EOF\nbody\nEOF";
+        assert_eq!(
+            tokenize(lang, text)
+                .into_iter()
+                .map(|(_, token)| token)
+                .collect::<std::string::String>(),
+            text
+        );
+    }
+
+    #[test]
+    fn highlight_code_splits_lines_and_keeps_text() {
+        let lang = language_for_tag("rust").expect("rust");
+        let theme = SyntaxTheme::for_background(None);
+        let lines = highlight_code_to_lines("/* a\nb */ let x = 1;", lang, &theme);
+        let text: Vec<String> = lines
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(text, vec!["/* a", "b */ let x = 1;"]);
+        assert_eq!(lines[1].spans[0].style, theme.style(TokenKind::Comment));
+    }
+
+    #[test]
+    fn theme_depends_on_background() {
+        let light = SyntaxTheme::for_background(Some((255, 255, 255)));
+        let dark = SyntaxTheme::for_background(Some((0, 0, 0)));
+        let ansi = SyntaxTheme::for_background(None);
+        assert_eq!(
+            ansi.style(TokenKind::Keyword),
+            Style::default().fg(Color::Magenta)
+        );
+        assert_eq!(
+            light.style(TokenKind::Comment),
+            dark.style(TokenKind::Comment)
+        );
+        assert_eq!(light.style(TokenKind::Plain), Style::default());
+    }
+
+    #[test]
+    fn slice_spans_keeps_styles_across_boundaries() {
+        let spans = vec![
+            Span::styled("abc", Style::default().fg(Color::Red)),
+            Span::raw("def"),
+        ];
+        let sliced = slice_spans(&spans, 2, 4);
+        assert_eq!(
+            sliced,
+            vec![
+                Span::styled("c", Style::default().fg(Color::Red)),
+                Span::raw("d")
+            ]
+        );
+    }
+}
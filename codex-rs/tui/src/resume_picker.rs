@@ -627,6 +627,56 @@ impl PickerState {
     }
 }
 
+/// A recorded session offered by the command palette.
+#[derive(Debug, Clone)]
+pub(crate) struct RecentSession {
+    pub path: PathBuf,
+    pub preview: String,
+    /// Relative time of the last update, e.g. "5 minutes ago".
+    pub updated: String,
+}
+
+/// Up to `limit` of the most recently updated sessions recorded in `cwd`,
+/// newest first. Only the first page of rollouts is scanned.
+pub(crate) async fn recent_sessions(
+    codex_home: &Path,
+    default_provider: &str,
+    cwd: &Path,
+    limit: usize,
+) -> Vec<RecentSession> {
+    let provider_filter = vec![default_provider.to_string()];
+    let page = match RolloutRecorder::list_conversations(
+        codex_home,
+        PAGE_SIZE,
+        None,
+        INTERACTIVE_SESSION_SOURCES,
+        Some(provider_filter.as_slice()),
+        default_provider,
+    )
+    .await
+    {
+        Ok(page) => page,
+        Err(err) => {
+            tracing::warn!("failed to list recent sessions: {err}");
+            return Vec::new();
+        }
+    };
+    rows_from_items(page.items)
+        .into_iter()
+        .filter(|row| {
+            row.cwd
+                .as_deref()
+                .is_some_and(|row_cwd| paths_match(row_cwd, cwd))
+        })
+        .take(limit)
+        .map(|row| RecentSession {
+            updated: format_updated_label(&row),
+            path: row.path,
+            preview: row.preview,
+        })
+        .collect()
+}
+
 fn rows_from_items(items: Vec<ConversationItem>) -> Vec<Row> {
     items.into_iter().map(|item| head_to_row(&item)).collect()
 }
@@ -21,8 +21,11 @@ pub enum SlashCommand {
     Resume,
     Init,
     Compact,
+    Tab,
+    TabClose,
     // Undo,
     Diff,
+    Export,
     Mention,
     Status,
     Mcp,
@@ -45,9 +48,12 @@ impl SlashCommand {
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
             SlashCommand::Resume => "resume a saved chat",
+            SlashCommand::Tab => "start another chat in a new tab",
+            SlashCommand::TabClose => "end the chat in this tab and close it",
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Export => "save this conversation as Markdown, HTML or JSON",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
@@ -82,6 +88,9 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Export
+            | SlashCommand::Tab
+            | SlashCommand::TabClose
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
@@ -8,6 +8,7 @@ use chrono::Local;
 use codex_common::create_config_summary_entries;
 use codex_core::config::Config;
 use codex_core::models_manager::model_family::ModelFamily;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::NetworkAccess;
 use codex_core::protocol::SandboxPolicy;
 use codex_core::protocol::TokenUsage;
@@ -65,6 +66,7 @@ struct StatusHistoryCell {
     account: Option<StatusAccountDisplay>,
     session_id: Option<String>,
     token_usage: StatusTokenUsageData,
+    cost: Option<CostSummary>,
     rate_limits: StatusRateLimitData,
 }
 
@@ -75,6 +77,7 @@ pub(crate) fn new_status_output(
     model_family: &ModelFamily,
     total_usage: &TokenUsage,
     context_usage: Option<&TokenUsage>,
+    cost: Option<&CostSummary>,
     session_id: &Option<ConversationId>,
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
@@ -88,6 +91,7 @@ pub(crate) fn new_status_output(
         model_family,
         total_usage,
         context_usage,
+        cost,
         session_id,
         rate_limits,
         plan_type,
@@ -106,6 +110,7 @@ impl StatusHistoryCell {
         model_family: &ModelFamily,
         total_usage: &TokenUsage,
         context_usage: Option<&TokenUsage>,
+        cost: Option<&CostSummary>,
         session_id: &Option<ConversationId>,
         rate_limits: Option<&RateLimitSnapshotDisplay>,
         plan_type: Option<PlanType>,
@@ -160,6 +165,7 @@ impl StatusHistoryCell {
             account,
             session_id,
             token_usage,
+            cost: cost.copied(),
             rate_limits,
         }
     }
@@ -182,6 +188,17 @@ impl StatusHistoryCell {
         ]
     }
 
+    fn cost_spans(&self) -> Option<Vec<Span<'static>>> {
+        let cost = self.cost.as_ref()?;
+        Some(vec![
+            Span::from(format!("${:.2}", cost.session_usd)),
+            Span::from(" session").dim(),
+            Span::from(" · ").dim(),
+            Span::from(format!("${:.2}", cost.daily_usd)).dim(),
+            Span::from(" today").dim(),
+        ])
+    }
+
     fn context_window_spans(&self) -> Option<Vec<Span<'static>>> {
         let context = self.token_usage.context_window.as_ref()?;
         let percent = context.percent_remaining;
@@ -345,6 +362,9 @@ impl HistoryCell for StatusHistoryCell {
             push_label(&mut labels, &mut seen, "Session");
         }
         push_label(&mut labels, &mut seen, "Token usage");
+        if self.cost.is_some() {
+            push_label(&mut labels, &mut seen, "Cost");
+        }
         if self.token_usage.context_window.is_some() {
             push_label(&mut labels, &mut seen, "Context window");
         }
@@ -399,6 +419,10 @@ impl HistoryCell for StatusHistoryCell {
             lines.push(formatter.line("Token usage", self.token_usage_spans()));
         }
 
+        if let Some(spans) = self.cost_spans() {
+            lines.push(formatter.line("Cost", spans));
+        }
+
         if let Some(spans) = self.context_window_spans() {
             lines.push(formatter.line("Context window", spans));
         }
@@ -9,6 +9,7 @@ use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::models_manager::model_family::ModelFamily;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_core::protocol::RateLimitWindow;
@@ -97,6 +98,8 @@ async fn status_snapshot_includes_reasoning_details() {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            readable_roots: vec![],
+            deny_read: vec![],
         })
         .expect("set sandbox policy");
 
@@ -140,6 +143,7 @@ async fn status_snapshot_includes_reasoning_details() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -197,6 +201,7 @@ async fn status_snapshot_includes_monthly_limit() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -242,6 +247,7 @@ async fn status_snapshot_shows_unlimited_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -286,6 +292,7 @@ async fn status_snapshot_shows_positive_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -330,6 +337,7 @@ async fn status_snapshot_hides_zero_credits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -372,6 +380,7 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -414,6 +423,7 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         None,
         None,
@@ -471,6 +481,7 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -517,6 +528,58 @@ async fn status_snapshot_shows_missing_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
+        &None,
+        None,
+        None,
+        now,
+        &model_slug,
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    let sanitized = sanitize_directory(rendered_lines).join("\n");
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_snapshot_includes_cost() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage {
+        input_tokens: 500,
+        cached_input_tokens: 0,
+        output_tokens: 250,
+        reasoning_output_tokens: 0,
+        total_tokens: 750,
+    };
+    let cost = CostSummary {
+        last_usd: 0.25,
+        session_usd: 1.5,
+        daily_usd: 12.75,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let model_family = test_model_family(&model_slug, &config);
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        &model_family,
+        &usage,
+        Some(&usage),
+        Some(&cost),
         &None,
         None,
         None,
@@ -581,6 +644,7 @@ async fn status_snapshot_includes_credits_and_limits() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -633,6 +697,7 @@ async fn status_snapshot_shows_empty_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -694,6 +759,7 @@ async fn status_snapshot_shows_stale_limits_message() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -759,6 +825,7 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         &model_family,
         &usage,
         Some(&usage),
+        None,
         &None,
         Some(&rate_display),
         None,
@@ -810,6 +877,7 @@ async fn status_context_window_uses_last_usage() {
         &model_family,
         &total_usage,
         Some(&last_usage),
+        None,
         &None,
         None,
         None,
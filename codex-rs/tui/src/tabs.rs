@@ -0,0 +1,359 @@
+//! Bookkeeping for running several conversations in one TUI session.
+//!
+//! The [`App`](crate::app::App) keeps rendering a single active
+//! [`ChatWidget`]; every other conversation is parked here together with its
+//! transcript. Events from parked conversations keep flowing (their senders
+//! are tagged with a [`TabId`]), so a background agent can keep working while
+//! the user talks to another one. The tab strip summarises what each
+//! conversation is doing so pending approvals are not missed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::chatwidget::ChatWidget;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::UserHistoryCell;
+use crate::keymap::Keymap;
+use crate::text_formatting::truncate_text;
+
+pub(crate) type TabId = usize;
+
+const TITLE_MAX_GRAPHEMES: usize = 24;
+
+/// What a conversation is doing, as shown by its badge in the tab strip.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TabActivity {
+    #[default]
+    Idle,
+    Working,
+    NeedsApproval,
+    /// A turn finished while the user was looking at another tab.
+    Done,
+    /// A turn failed while the user was looking at another tab.
+    Failed,
+}
+
+impl TabActivity {
+    pub(crate) fn on_event(self, msg: &EventMsg, is_active: bool) -> Self {
+        match msg {
+            EventMsg::TaskStarted(_) => TabActivity::Working,
+            EventMsg::ExecApprovalRequest(_)
+            | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::ElicitationRequest(_) => TabActivity::NeedsApproval,
+            EventMsg::TaskComplete(_) if is_active => TabActivity::Idle,
+            EventMsg::TaskComplete(_) => TabActivity::Done,
+            EventMsg::Error(_) if is_active => TabActivity::Idle,
+            EventMsg::Error(_) => TabActivity::Failed,
+            EventMsg::TurnAborted(_) => TabActivity::Idle,
+            _ => self,
+        }
+    }
+
+    /// Answering an approval puts the conversation back to work.
+    pub(crate) fn on_op(self, op: &Op) -> Self {
+        match op {
+            Op::ExecApproval { .. } | Op::PatchApproval { .. } | Op::ResolveElicitation { .. }
+                if self == TabActivity::NeedsApproval =>
+            {
+                TabActivity::Working
+            }
+            _ => self,
+        }
+    }
+
+    /// The user switched to the tab, so finished turns no longer need a badge.
+    pub(crate) fn seen(self) -> Self {
+        match self {
+            TabActivity::Done | TabActivity::Failed => TabActivity::Idle,
+            other => other,
+        }
+    }
+
+    fn badge(self) -> Option<Span<'static>> {
+        match self {
+            TabActivity::Idle => None,
+            TabActivity::Working => Some("•".cyan()),
+            TabActivity::NeedsApproval => Some("!".magenta().bold()),
+            TabActivity::Done => Some("✓".green()),
+            TabActivity::Failed => Some("✗".red()),
+        }
+    }
+}
+
+/// A conversation that is not currently shown.
+pub(crate) struct ParkedTab {
+    pub(crate) widget: ChatWidget,
+    pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// Cells that arrived while the tab was parked; they are written to the
+    /// scrollback when the user switches back.
+    pub(crate) unseen_cells: Vec<Arc<dyn HistoryCell>>,
+    pub(crate) current_model: String,
+}
+
+pub(crate) struct Tabs {
+    active: TabId,
+    /// Tab ids in strip order.
+    order: Vec<TabId>,
+    next_id: TabId,
+    activity: HashMap<TabId, TabActivity>,
+    parked: HashMap<TabId, ParkedTab>,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self {
+            active: 0,
+            order: vec![0],
+            next_id: 1,
+            activity: HashMap::new(),
+            parked: HashMap::new(),
+        }
+    }
+}
+
+impl Tabs {
+    pub(crate) fn active(&self) -> TabId {
+        self.active
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Reserve an id for a new tab, placed right after the active one.
+    pub(crate) fn allocate(&mut self) -> TabId {
+        let id = self.next_id;
+        self.next_id += 1;
+        let index = self
+            .index_of(self.active)
+            .map_or(self.order.len(), |i| i + 1);
+        self.order.insert(index, id);
+        id
+    }
+
+    pub(crate) fn park(&mut self, id: TabId, tab: ParkedTab) {
+        self.parked.insert(id, tab);
+    }
+
+    pub(crate) fn take_parked(&mut self, id: TabId) -> Option<ParkedTab> {
+        self.parked.remove(&id)
+    }
+
+    pub(crate) fn parked_mut(&mut self, id: TabId) -> Option<&mut ParkedTab> {
+        self.parked.get_mut(&id)
+    }
+
+    pub(crate) fn parked_widgets_mut(&mut self) -> impl Iterator<Item = &mut ChatWidget> {
+        self.parked.values_mut().map(|tab| &mut tab.widget)
+    }
+
+    /// Mark `id` as the displayed tab and clear its finished badge.
+    pub(crate) fn set_active(&mut self, id: TabId) {
+        self.active = id;
+        self.update_activity(id, TabActivity::seen);
+    }
+
+    /// Forget a tab entirely, dropping its parked conversation if any.
+    pub(crate) fn remove(&mut self, id: TabId) {
+        self.order.retain(|tab| *tab != id);
+        self.activity.remove(&id);
+        self.parked.remove(&id);
+    }
+
+    /// The tab `offset` positions away from the active one, wrapping around.
+    pub(crate) fn neighbor(&self, offset: isize) -> Option<TabId> {
+        let len = self.order.len() as isize;
+        if len < 2 {
+            return None;
+        }
+        let index = self.index_of(self.active)? as isize;
+        let target = (index + offset).rem_euclid(len) as usize;
+        self.order.get(target).copied()
+    }
+
+    pub(crate) fn activity(&self, id: TabId) -> TabActivity {
+        self.activity.get(&id).copied().unwrap_or_default()
+    }
+
+    pub(crate) fn update_activity(
+        &mut self,
+        id: TabId,
+        f: impl FnOnce(TabActivity) -> TabActivity,
+    ) {
+        let activity = f(self.activity(id));
+        self.activity.insert(id, activity);
+    }
+
+    /// 1-based position of `id` in the strip.
+    pub(crate) fn number(&self, id: TabId) -> usize {
+        self.index_of(id).map_or(0, |i| i + 1)
+    }
+
+    /// Title for `id`; the active tab's transcript lives in the app, so the
+    /// caller passes it in.
+    pub(crate) fn title(&self, id: TabId, active_cells: &[Arc<dyn HistoryCell>]) -> String {
+        if id == self.active {
+            return tab_title(active_cells);
+        }
+        self.parked
+            .get(&id)
+            .map(|tab| tab_title(&tab.transcript_cells))
+            .unwrap_or_else(|| tab_title(&[]))
+    }
+
+    /// One-line strip listing every tab with its status badge.
+    pub(crate) fn strip_line(
+        &self,
+        active_cells: &[Arc<dyn HistoryCell>],
+        keymap: &Keymap,
+    ) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for (index, id) in self.order.iter().copied().enumerate() {
+            if index > 0 {
+                spans.push(" │ ".dim());
+            }
+            let label = format!("{} {}", index + 1, self.title(id, active_cells));
+            if id == self.active {
+                spans.push(label.cyan().bold());
+            } else {
+                spans.push(label.dim());
+            }
+            if let Some(badge) = self.activity(id).badge() {
+                spans.push(" ".into());
+                spans.push(badge);
+            }
+        }
+        if let Some(key) = keymap.next_tab.primary() {
+            spans.push("   ".into());
+            spans.push(Span::from(key).dim());
+            spans.push(" next tab".dim());
+        }
+        Line::from(spans)
+    }
+
+    fn index_of(&self, id: TabId) -> Option<usize> {
+        self.order.iter().position(|tab| *tab == id)
+    }
+}
+
+/// A short title taken from the first user message in the conversation.
+pub(crate) fn tab_title(cells: &[Arc<dyn HistoryCell>]) -> String {
+    cells
+        .iter()
+        .find_map(|cell| cell.as_any().downcast_ref::<UserHistoryCell>())
+        .and_then(|cell| cell.message.lines().find(|line| !line.trim().is_empty()))
+        .map(|line| truncate_text(line.trim(), TITLE_MAX_GRAPHEMES))
+        .unwrap_or_else(|| "new chat".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ErrorEvent;
+    use codex_core::protocol::ReviewDecision;
+    use codex_core::protocol::TaskCompleteEvent;
+    use codex_core::protocol::TaskStartedEvent;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Paragraph;
+
+    use crate::history_cell::AgentMessageCell;
+
+    fn user(message: &str) -> Arc<dyn HistoryCell> {
+        Arc::new(UserHistoryCell {
+            message: message.to_string(),
+        })
+    }
+
+    #[test]
+    fn activity_follows_turn_lifecycle() {
+        let started = EventMsg::TaskStarted(TaskStartedEvent {
+            model_context_window: None,
+        });
+        let complete = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        });
+        let error = EventMsg::Error(ErrorEvent {
+            message: "boom".to_string(),
+            codex_error_info: None,
+        });
+
+        let working = TabActivity::Idle.on_event(&started, false);
+        assert_eq!(working, TabActivity::Working);
+        assert_eq!(working.on_event(&complete, false), TabActivity::Done);
+        assert_eq!(working.on_event(&complete, true), TabActivity::Idle);
+        assert_eq!(working.on_event(&error, false), TabActivity::Failed);
+        assert_eq!(TabActivity::Done.seen(), TabActivity::Idle);
+        assert_eq!(TabActivity::Working.seen(), TabActivity::Working);
+    }
+
+    #[test]
+    fn answering_an_approval_resumes_work() {
+        let approval = Op::ExecApproval {
+            id: "call-1".to_string(),
+            decision: ReviewDecision::Approved,
+        };
+        assert_eq!(
+            TabActivity::NeedsApproval.on_op(&approval),
+            TabActivity::Working
+        );
+        assert_eq!(TabActivity::Idle.on_op(&approval), TabActivity::Idle);
+    }
+
+    #[test]
+    fn new_tabs_open_next_to_the_active_one_and_neighbors_wrap() {
+        let mut tabs = Tabs::default();
+        let second = tabs.allocate();
+        tabs.set_active(second);
+        let third = tabs.allocate();
+        assert_eq!(tabs.order, vec![0, second, third]);
+
+        tabs.set_active(0);
+        let inserted = tabs.allocate();
+        assert_eq!(tabs.order, vec![0, inserted, second, third]);
+
+        assert_eq!(tabs.neighbor(-1), Some(third));
+        assert_eq!(tabs.neighbor(1), Some(inserted));
+
+        tabs.remove(inserted);
+        tabs.remove(second);
+        tabs.remove(third);
+        assert_eq!(tabs.neighbor(1), None);
+    }
+
+    #[test]
+    fn title_uses_first_user_message() {
+        let cells = vec![
+            Arc::new(AgentMessageCell::new(vec![Line::from("hello")], true))
+                as Arc<dyn HistoryCell>,
+            user("\nrefactor the config loader so it can be tested"),
+            user("second message"),
+        ];
+        assert_eq!(tab_title(&cells), "refactor the config l...");
+        assert_eq!(tab_title(&[]), "new chat");
+    }
+
+    #[test]
+    fn tab_strip_snapshot() {
+        let mut tabs = Tabs::default();
+        let second = tabs.allocate();
+        let third = tabs.allocate();
+        tabs.update_activity(second, |_| TabActivity::NeedsApproval);
+        tabs.update_activity(third, |_| TabActivity::Done);
+        tabs.update_activity(0, |_| TabActivity::Working);
+
+        let line = tabs.strip_line(&[user("fix the flaky test")], &Keymap::default());
+        let mut terminal = Terminal::new(TestBackend::new(80, 1)).expect("terminal");
+        terminal
+            .draw(|f| f.render_widget(Paragraph::new(line), f.area()))
+            .expect("draw");
+        insta::assert_snapshot!(terminal.backend());
+    }
+}
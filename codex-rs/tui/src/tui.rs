@@ -10,6 +10,7 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use crossterm::Command;
 use crossterm::SynchronizedUpdate;
@@ -38,6 +39,7 @@ use tokio_stream::Stream;
 pub use self::frame_requester::FrameRequester;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::inline_image::InlineImage;
 use crate::notifications::DesktopNotificationBackend;
 use crate::notifications::NotificationBackendKind;
 use crate::notifications::detect_backend;
@@ -55,6 +57,9 @@ mod job_control;
 /// A type alias for the terminal type used in this application
 pub type Terminal = CustomTerminal<CrosstermBackend<Stdout>>;
 
+/// How often to check whether queued history images have finished encoding.
+const IMAGE_ENCODE_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 pub fn set_modes() -> Result<()> {
     execute!(stdout(), EnableBracketedPaste)?;
 
@@ -238,6 +243,8 @@ pub struct Tui {
     event_broker: Arc<EventBroker>,
     pub(crate) terminal: Terminal,
     pending_history_lines: Vec<Line<'static>>,
+    /// Images to draw over rows of `pending_history_lines`.
+    pending_history_images: Vec<InlineImage>,
     alt_saved_viewport: Option<ratatui::layout::Rect>,
     #[cfg(unix)]
     suspend_context: SuspendContext,
@@ -267,6 +274,7 @@ impl Tui {
             event_broker: Arc::new(EventBroker::new()),
             terminal,
             pending_history_lines: vec![],
+            pending_history_images: vec![],
             alt_saved_viewport: None,
             #[cfg(unix)]
             suspend_context: SuspendContext::new(),
@@ -436,7 +444,16 @@ impl Tui {
         Ok(())
     }
 
-    pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
+    /// Queue `lines` for insertion above the viewport, drawing `images` over
+    /// the rows they reserve. Each image's `line` indexes into `lines`.
+    pub(crate) fn insert_history_lines_with_images(
+        &mut self,
+        lines: Vec<Line<'static>>,
+        images: Vec<InlineImage>,
+    ) {
+        let offset = self.pending_history_lines.len();
+        self.pending_history_images
+            .extend(images.into_iter().map(|image| image.offset(offset)));
         self.pending_history_lines.extend(lines);
         self.frame_requester().schedule_frame();
     }
@@ -487,12 +504,23 @@ impl Tui {
                 terminal.set_viewport_area(area);
             }
 
-            if !self.pending_history_lines.is_empty() {
-                crate::insert_history::insert_history_lines(
+            // History waits for its images to finish encoding so they land on
+            // the rows reserved for them; poll until they do.
+            if !self
+                .pending_history_images
+                .iter()
+                .all(InlineImage::is_ready)
+            {
+                self.frame_requester
+                    .schedule_frame_in(IMAGE_ENCODE_POLL_INTERVAL);
+            } else if !self.pending_history_lines.is_empty() {
+                crate::insert_history::insert_history_lines_with_images(
                     terminal,
                     self.pending_history_lines.clone(),
+                    &self.pending_history_images,
                 )?;
                 self.pending_history_lines.clear();
+                self.pending_history_images.clear();
             }
 
             // Update the y position for suspending so Ctrl-Z can place the cursor correctly.
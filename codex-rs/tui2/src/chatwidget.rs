@@ -23,6 +23,7 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CostSummary;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -134,6 +135,9 @@ use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::SandboxAmendment;
+use codex_core::protocol::SandboxAmendmentAppliedEvent;
+use codex_core::protocol::SandboxDenialEvent;
 use codex_core::protocol::SandboxPolicy;
 use codex_file_search::FileMatch;
 use codex_protocol::openai_models::ModelPreset;
@@ -290,6 +294,8 @@ pub(crate) struct ChatWidget {
     session_header: SessionHeader,
     initial_user_message: Option<UserMessage>,
     token_info: Option<TokenUsageInfo>,
+    /// Latest spend estimate; only reported when model pricing is configured.
+    cost: Option<CostSummary>,
     rate_limit_snapshot: Option<RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
@@ -926,6 +932,18 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_sandbox_denial(&mut self, event: SandboxDenialEvent) {
+        self.add_to_history(history_cell::new_sandbox_denial(&event.denials));
+        if !event.suggested_amendments.is_empty() {
+            self.open_sandbox_amendment_popup(event.suggested_amendments);
+        }
+        self.request_redraw();
+    }
+
+    fn on_sandbox_amendment_applied(&mut self, event: SandboxAmendmentAppliedEvent) {
+        self.add_info_message(format!("Sandbox updated: {}", event.amendment), None);
+    }
+
     fn on_background_event(&mut self, message: String) {
         debug!("BackgroundEvent: {message}");
         self.bottom_pane.ensure_status_indicator();
@@ -1314,6 +1332,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1399,6 +1418,7 @@ impl ChatWidget {
                 initial_images,
             ),
             token_info: None,
+            cost: None,
             rate_limit_snapshot: None,
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
@@ -1662,6 +1682,7 @@ impl ChatWidget {
                         ]),
                         reason: None,
                         grant_root: Some(PathBuf::from("/tmp")),
+                        whole_patch_only: false,
                     }),
                 }));
             }
@@ -1848,6 +1869,9 @@ impl ChatWidget {
             }
             EventMsg::TokenCount(ev) => {
                 self.set_token_info(ev.info);
+                if ev.cost.is_some() {
+                    self.cost = ev.cost;
+                }
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
@@ -1900,6 +1924,8 @@ impl ChatWidget {
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
+            EventMsg::SandboxDenial(ev) => self.on_sandbox_denial(ev),
+            EventMsg::SandboxAmendmentApplied(ev) => self.on_sandbox_amendment_applied(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
@@ -2063,6 +2089,7 @@ impl ChatWidget {
             &self.model_family,
             total_usage,
             context_usage,
+            self.cost.as_ref(),
             &self.conversation_id,
             self.rate_limit_snapshot.as_ref(),
             self.plan_type,
@@ -2706,6 +2733,53 @@ impl ChatWidget {
         None
     }
 
+    /// Offers to apply one of the sandbox changes that would have let a
+    /// denied command through.
+    pub(crate) fn open_sandbox_amendment_popup(&mut self, amendments: Vec<SandboxAmendment>) {
+        let mut items: Vec<SelectionItem> = amendments
+            .into_iter()
+            .map(|amendment| {
+                let name = format!("Yes, {amendment}");
+                let description = match &amendment {
+                    SandboxAmendment::AddWritableRoot { .. } => {
+                        "Saved to this project's writable_roots in config.toml"
+                    }
+                    SandboxAmendment::RunOutsideSandbox { .. } => {
+                        "Saved as an execpolicy rule; matching commands get no sandbox at all, \
+                         file system included"
+                    }
+                };
+                // The "Sandbox updated" note comes back as
+                // `SandboxAmendmentApplied` once core has saved the change.
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::ApplySandboxAmendment {
+                        amendment: amendment.clone(),
+                    }));
+                })];
+                SelectionItem {
+                    name,
+                    description: Some(description.to_string()),
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        items.push(SelectionItem {
+            name: "No, keep the sandbox as it is".to_string(),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Update the sandbox?".to_string()),
+            subtitle: Some("The sandbox likely blocked the last command.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_full_access_confirmation(&mut self, preset: ApprovalPreset) {
         let approval = preset.approval;
         let sandbox = preset.sandbox;
@@ -3165,6 +3239,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             })],
@@ -3220,6 +3295,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            test_output: None,
                         },
                     }));
                 })],
@@ -3258,6 +3334,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            test_output: None,
                         },
                     }));
                 })],
@@ -3294,6 +3371,7 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             }),
@@ -3507,6 +3585,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        test_output: None,
                     },
                 }));
             })],
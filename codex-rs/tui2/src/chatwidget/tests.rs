@@ -162,6 +162,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            test_output: None,
         }),
     });
 
@@ -181,6 +182,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            test_output: None,
         }),
     });
 
@@ -204,6 +206,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_review_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -215,6 +218,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            test_output: None,
         }),
     });
 
@@ -223,6 +227,7 @@ async fn review_restores_context_window_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(review_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(97));
@@ -252,6 +257,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(make_token_info(pre_compact_tokens, context_window)),
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), Some(30));
@@ -261,6 +267,7 @@ async fn token_count_none_resets_context_indicator() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: None,
             rate_limits: None,
+            cost: None,
         }),
     });
     assert_eq!(chat.bottom_pane.context_window_percent(), None);
@@ -291,6 +298,7 @@ async fn context_indicator_shows_used_tokens_when_window_unknown() {
         msg: EventMsg::TokenCount(TokenCountEvent {
             info: Some(token_info),
             rate_limits: None,
+            cost: None,
         }),
     });
 
@@ -376,6 +384,7 @@ async fn make_chatwidget_manual(
         session_header: SessionHeader::new(resolved_model.clone()),
         initial_user_message: None,
         token_info: None,
+        cost: None,
         rate_limit_snapshot: None,
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
@@ -907,6 +916,7 @@ fn end_exec(
             exit_code,
             duration: std::time::Duration::from_millis(5),
             formatted_output: aggregated,
+            exit_reason: None,
         }),
     });
 }
@@ -1162,6 +1172,7 @@ async fn exec_end_without_begin_uses_event_command() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(5),
             formatted_output: "done".to_string(),
+            exit_reason: None,
         }),
     });
 
@@ -1500,6 +1511,7 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    test_output: None,
                 }
             );
         }
@@ -1759,6 +1771,8 @@ async fn preset_matching_ignores_extra_writable_roots() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        readable_roots: vec![],
+        deny_read: vec![],
     };
 
     assert!(
@@ -2144,6 +2158,7 @@ async fn approval_modal_patch_snapshot() {
         changes,
         reason: Some("The model wants to apply changes".into()),
         grant_root: Some(PathBuf::from("/tmp")),
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-approve-patch".into(),
@@ -2424,6 +2439,7 @@ async fn apply_patch_events_emit_history_cells() {
         changes,
         reason: None,
         grant_root: None,
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "s1".into(),
@@ -2523,6 +2539,7 @@ async fn apply_patch_manual_approval_adjusts_header() {
             changes: proposed_changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
     drain_insert_history(&mut rx);
@@ -2572,6 +2589,7 @@ async fn apply_patch_manual_flow_snapshot() {
             changes: proposed_changes,
             reason: Some("Manual review required".into()),
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
     let history_before_apply = drain_insert_history(&mut rx);
@@ -2623,6 +2641,7 @@ async fn apply_patch_approval_sends_op_with_submission_id() {
         changes,
         reason: None,
         grant_root: None,
+        whole_patch_only: false,
     };
     chat.handle_codex_event(Event {
         id: "sub-123".into(),
@@ -2663,6 +2682,7 @@ async fn apply_patch_full_flow_integration_like() {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -2743,6 +2763,7 @@ async fn apply_patch_untrusted_shows_approval_modal() {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -2792,6 +2813,7 @@ async fn apply_patch_request_shows_diff_summary() {
             changes,
             reason: None,
             grant_root: None,
+            whole_patch_only: false,
         }),
     });
 
@@ -3149,6 +3171,7 @@ async fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             exit_code: 0,
             duration: std::time::Duration::from_millis(16000),
             formatted_output: String::new(),
+            exit_reason: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -27,6 +27,7 @@ use codex_core::config::types::McpServerTransportConfig;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::SandboxDenial;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
@@ -559,6 +560,18 @@ pub fn new_approval_decision_cell(
                 ],
             )
         }
+        ApprovedHunks { .. } => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved part".bold(),
+                    " of the request to run ".into(),
+                    snippet,
+                ],
+            )
+        }
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -1409,6 +1422,22 @@ pub(crate) fn new_mcp_tools_output(
 
     PlainHistoryCell { lines }
 }
+/// Explains which sandbox restrictions a failed command most likely ran into.
+#[allow(clippy::disallowed_methods)]
+pub(crate) fn new_sandbox_denial(denials: &[SandboxDenial]) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "⚠ ".yellow(),
+            "The sandbox likely blocked this command".yellow(),
+        ]
+        .into(),
+    ];
+    for denial in denials {
+        lines.push(vec!["  └ ".dim(), denial.evidence.clone().dim()].into());
+    }
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
     let mut line = vec!["• ".dim(), message.into()];
     if let Some(hint) = hint {
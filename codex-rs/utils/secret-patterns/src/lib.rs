@@ -0,0 +1,52 @@
+//! Patterns for credentials that commonly end up in prompts, tool output and
+//! logs, shared by everything in the workspace that scrubs secrets.
+//!
+//! The patterns are plain strings so each crate can compile them with the
+//! regex engine it already uses; they stick to syntax that both `regex` and
+//! `regex-lite` accept.
+
+/// Capture group that, when present in a pattern, limits redaction to that
+/// part of the match (e.g. the value in `KEY=value`).
+pub const SECRET_GROUP: &str = "secret";
+
+/// `(detector name, pattern)` pairs for the built-in secret detectors.
+pub const BUILTIN_SECRET_PATTERNS: &[(&str, &str)] = &[
+    ("openai_api_key", r"\bsk-[A-Za-z0-9_-]{20,}"),
+    (
+        "bearer_token",
+        r"(?i)\bbearer\s+(?P<secret>[A-Za-z0-9._~+/=-]{16,})",
+    ),
+    ("aws_access_key_id", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    (
+        "aws_secret_access_key",
+        r#"(?i)aws_secret_access_key["']?\s*[=:]\s*["']?(?P<secret>[A-Za-z0-9/+=]{40})"#,
+    ),
+    (
+        "github_token",
+        r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})\b",
+    ),
+    (
+        "private_key",
+        r"(?s)-----BEGIN [A-Z ]*PRIVATE KEY-----.*?-----END [A-Z ]*PRIVATE KEY-----",
+    ),
+    (
+        "jwt",
+        r"\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_compile_with_both_regex_engines() {
+        for (name, pattern) in BUILTIN_SECRET_PATTERNS {
+            assert!(regex::Regex::new(pattern).is_ok(), "{name} (regex)");
+            assert!(
+                regex_lite::Regex::new(pattern).is_ok(),
+                "{name} (regex-lite)"
+            );
+        }
+    }
+}
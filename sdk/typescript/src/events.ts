@@ -25,6 +25,8 @@ export type Usage = {
   cached_input_tokens: number;
   /** The number of output tokens used during the turn. */
   output_tokens: number;
+  /** Estimated session spend in USD; present only when model pricing is configured. */
+  cost_usd?: number;
 };
 
 /** Emitted when a turn is completed. Typically right after the assistant's response. */